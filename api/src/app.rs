@@ -6,12 +6,23 @@ use axum::{
     http::StatusCode,
     http::{
         HeaderValue, Method,
-        header::{AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_TYPE},
+        header::{
+            ACCEPT_RANGES, AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE, ETAG,
+            RANGE,
+        },
     },
     middleware::{from_extractor_with_state, from_fn},
 };
 use plannify_driver_api_core::{
-    ServiceConfig, application::create_repositories, domain::common::CoreError,
+    ServiceConfig,
+    application::create_repositories,
+    domain::common::{
+        CoreError,
+        config::{
+            DocumentShareConfig, DocumentVerificationConfig, MailUnsubscribeConfig,
+            PasswordPolicyConfig, WebPushConfig,
+        },
+    },
 };
 use tower::{ServiceBuilder, buffer::BufferLayer, limit::RateLimitLayer};
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
@@ -28,13 +39,18 @@ use crate::{
         authentication::routes::{
             authentication_routes, refresh_cookie_routes, unauthenticated_routes,
         },
-        common::middleware::{auth::AuthRefreshMiddleware, tracing::tracing_middleware},
+        common::middleware::{
+            auth::{AuthRefreshMiddleware, AuthSuspensionMiddleware},
+            tracing::tracing_middleware,
+        },
         config::routes::config_routes,
         driver::routes::{driver_routes, public_driver_routes},
         formating::routes::formating_routes,
-        mail::routes::mail_routes,
+        mail::routes::{mail_routes, public_mail_routes},
+        notification::routes::notification_routes,
+        storage::routes::public_storage_routes,
         update::routes::update_routes,
-        workday::routes::workday_routes,
+        workday::routes::{public_workday_routes, workday_routes},
     },
 };
 
@@ -75,6 +91,33 @@ impl App {
             ServiceConfig {
                 workday_garbage_retention_days: config.common.workday_garbage_retention_days,
                 account_deactivation_days: config.common.account_deactivation_days,
+                password_policy: PasswordPolicyConfig {
+                    min_score: config.check_content.password_min_score,
+                    breached_dataset_dir: config
+                        .check_content
+                        .password_breached_dataset_dir
+                        .clone(),
+                },
+                mail_unsubscribe: MailUnsubscribeConfig {
+                    api_url: config.common.public_api_url.clone(),
+                    secret: config.common.mail_unsubscribe_secret.clone(),
+                },
+                web_push: WebPushConfig {
+                    allow_insecure_endpoints: matches!(config.environment, Environment::Test),
+                    ..config.common.web_push()
+                },
+                sms: config.common.sms(),
+                mail_events: config.common.mail_events(),
+                mail_dkim: config.smtp.dkim(),
+                document_delivery: config.s3.document_delivery(),
+                storage: config.s3.storage(&config.common.public_api_url),
+                document_verification: DocumentVerificationConfig {
+                    api_url: config.common.public_api_url.clone(),
+                },
+                document_share: DocumentShareConfig {
+                    api_url: config.common.public_api_url.clone(),
+                },
+                document_service: config.common.document_service(),
             },
         )
         .await
@@ -108,8 +151,8 @@ impl App {
             ])
             .allow_origin(cors_origins)
             .allow_credentials(true)
-            .allow_headers([AUTHORIZATION, CONTENT_TYPE])
-            .expose_headers([CONTENT_DISPOSITION]);
+            .allow_headers([AUTHORIZATION, CONTENT_TYPE, RANGE])
+            .expose_headers([ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_RANGE, ETAG]);
 
         let auth_validator = AuthValidator::new(&config.clone().jwt);
         state.auth_validator = auth_validator.clone();
@@ -127,8 +170,13 @@ impl App {
             .merge(driver_routes())
             .merge(workday_routes())
             .merge(mail_routes())
-            .route_layer(from_extractor_with_state::<AuthMiddleware, AuthValidator>(
-                auth_validator.clone(),
+            .merge(notification_routes())
+            .route_layer(from_extractor_with_state::<
+                AuthSuspensionMiddleware,
+                AppState,
+            >(state.clone()))
+            .route_layer(from_extractor_with_state::<AuthMiddleware, AppState>(
+                state.clone(),
             ));
 
         let (app_router, mut api) = OpenApiRouter::<AppState>::new()
@@ -137,6 +185,9 @@ impl App {
             .merge(authentication_routes())
             .merge(unauthenticated_routes())
             .merge(public_driver_routes())
+            .merge(public_mail_routes())
+            .merge(public_storage_routes())
+            .merge(public_workday_routes())
             .merge(config_routes())
             .merge(formating_routes())
             .merge(update_routes())
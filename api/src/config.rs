@@ -1,9 +1,17 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 use clap::Parser;
 use clap::ValueEnum;
 use lettre::SmtpTransport;
-use lettre::message::MessageBuilder;
-use lettre::message::header::ContentType;
+use lettre::message::{Mailbox, MessageBuilder};
+use lettre::transport::smtp::PoolConfig;
 use lettre::transport::smtp::authentication::Credentials;
+use plannify_driver_api_core::domain::common::config::{
+    DocumentDeliveryConfig, DocumentDeliveryMode, DocumentRenderer, DocumentServiceConfig,
+    MailDkimConfig, MailEventsConfig, SmsConfig, StorageBackend, StorageConfig,
+    StorageEncryptionConfig, WebPushConfig,
+};
 
 #[derive(Clone, Parser, Debug, Default)]
 #[command(name = "driver-api")]
@@ -73,6 +81,69 @@ pub struct SmtpConfig {
 
     #[arg(long = "smtp-domain", env = "SMTP_DOMAIN", name = "smtp_domain")]
     pub domain: String,
+
+    /// Defaults to 465 with implicit TLS, 587 with STARTTLS and 25 without encryption
+    #[arg(long = "smtp-port", env = "SMTP_PORT", name = "smtp_port")]
+    pub port: Option<u16>,
+
+    #[arg(
+        long = "smtp-tls",
+        env = "SMTP_TLS",
+        default_value = "tls",
+        name = "smtp_tls"
+    )]
+    pub tls: SmtpTlsMode,
+
+    #[arg(
+        long = "smtp-pool-size",
+        env = "SMTP_POOL_SIZE",
+        default_value = "10",
+        name = "smtp_pool_size"
+    )]
+    pub pool_size: u32,
+
+    #[arg(
+        long = "smtp-timeout",
+        env = "SMTP_TIMEOUT",
+        default_value = "60",
+        name = "smtp_timeout"
+    )]
+    pub timeout_secs: u64,
+
+    /// Mails are DKIM signed when both the selector and the private key file are set
+    #[arg(
+        long = "smtp-dkim-selector",
+        env = "SMTP_DKIM_SELECTOR",
+        name = "smtp_dkim_selector"
+    )]
+    pub dkim_selector: Option<String>,
+
+    /// Defaults to the domain of the default sender address
+    #[arg(
+        long = "smtp-dkim-domain",
+        env = "SMTP_DKIM_DOMAIN",
+        name = "smtp_dkim_domain"
+    )]
+    pub dkim_domain: Option<String>,
+
+    /// PKCS#1 PEM RSA private key, or base64 Ed25519 private key
+    #[arg(
+        long = "smtp-dkim-private-key-path",
+        env = "SMTP_DKIM_PRIVATE_KEY_PATH",
+        name = "smtp_dkim_private_key_path"
+    )]
+    pub dkim_private_key_path: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum, Default, PartialEq)]
+pub enum SmtpTlsMode {
+    /// Implicit TLS, usually on port 465
+    #[default]
+    Tls,
+    /// Plain connection upgraded with STARTTLS, usually on port 587
+    Starttls,
+    /// No encryption, for local test servers only
+    None,
 }
 
 impl SmtpConfig {
@@ -80,17 +151,42 @@ impl SmtpConfig {
         MessageBuilder::new()
             .from(self.default_sender.parse().unwrap())
             .reply_to(self.default_sender_reply_to.parse().unwrap())
-            .header(ContentType::TEXT_HTML)
     }
 
     pub fn to_transport(&self) -> SmtpTransport {
         let creds = Credentials::new(self.username.to_owned(), self.password.to_owned());
 
-        SmtpTransport::relay(&self.domain)
-            .unwrap()
+        let builder = match self.tls {
+            SmtpTlsMode::Tls => SmtpTransport::relay(&self.domain).unwrap(),
+            SmtpTlsMode::Starttls => SmtpTransport::starttls_relay(&self.domain).unwrap(),
+            SmtpTlsMode::None => SmtpTransport::builder_dangerous(&self.domain),
+        };
+        let builder = match self.port {
+            Some(port) => builder.port(port),
+            None => builder,
+        };
+
+        builder
             .credentials(creds)
+            .timeout(Some(Duration::from_secs(self.timeout_secs)))
+            .pool_config(PoolConfig::new().max_size(self.pool_size))
             .build()
     }
+
+    pub fn dkim(&self) -> MailDkimConfig {
+        let domain = self.dkim_domain.clone().unwrap_or_else(|| {
+            self.default_sender
+                .parse::<Mailbox>()
+                .map(|mailbox| mailbox.email.domain().to_string())
+                .unwrap_or_default()
+        });
+
+        MailDkimConfig {
+            selector: self.dkim_selector.clone(),
+            domain,
+            private_key_path: self.dkim_private_key_path.clone(),
+        }
+    }
 }
 
 #[derive(Clone, Parser, Debug, Default)]
@@ -153,6 +249,77 @@ pub struct CommonConfig {
     )]
     pub frontend_url: String,
 
+    #[arg(
+        long = "public-api-url",
+        env = "PUBLIC_API_URL",
+        default_value = "https://api.plannify.be",
+        name = "public_api_url"
+    )]
+    pub public_api_url: String,
+
+    #[arg(
+        long = "mail-unsubscribe-secret",
+        env = "MAIL_UNSUBSCRIBE_SECRET",
+        name = "mail_unsubscribe_secret"
+    )]
+    pub mail_unsubscribe_secret: String,
+
+    #[arg(
+        long = "mail-events-webhook-token",
+        env = "MAIL_EVENTS_WEBHOOK_TOKEN",
+        name = "mail_events_webhook_token"
+    )]
+    pub mail_events_webhook_token: Option<String>,
+
+    #[arg(
+        long = "vapid-private-key",
+        env = "VAPID_PRIVATE_KEY",
+        name = "vapid_private_key"
+    )]
+    pub vapid_private_key: Option<String>,
+
+    #[arg(
+        long = "vapid-subject",
+        env = "VAPID_SUBJECT",
+        default_value = "mailto:contact@plannify.be",
+        name = "vapid_subject"
+    )]
+    pub vapid_subject: String,
+
+    #[arg(
+        long = "sms-gateway-url",
+        env = "SMS_GATEWAY_URL",
+        name = "sms_gateway_url"
+    )]
+    pub sms_gateway_url: Option<String>,
+
+    #[arg(
+        long = "sms-gateway-api-key",
+        env = "SMS_GATEWAY_API_KEY",
+        default_value = "",
+        name = "sms_gateway_api_key"
+    )]
+    pub sms_gateway_api_key: String,
+
+    #[arg(
+        long = "sms-sender",
+        env = "SMS_SENDER",
+        default_value = "Plannify",
+        name = "sms_sender"
+    )]
+    pub sms_sender: String,
+
+    #[arg(long = "sms-log-path", env = "SMS_LOG_PATH", name = "sms_log_path")]
+    pub sms_log_path: Option<PathBuf>,
+
+    #[arg(
+        long = "sms-login-enabled",
+        env = "SMS_LOGIN_ENABLED",
+        default_value = "false",
+        name = "sms_login_enabled"
+    )]
+    pub sms_login_enabled: bool,
+
     #[arg(
         long = "pdf-service-endpoint",
         env = "PDF_SERVICE_ENDPOINT",
@@ -161,6 +328,62 @@ pub struct CommonConfig {
     )]
     pub pdf_service_endpoint: String,
 
+    #[arg(
+        long = "pdf-renderer",
+        env = "PDF_RENDERER",
+        default_value = "grpc-with-fallback",
+        name = "pdf_renderer"
+    )]
+    pub pdf_renderer: PdfRendererKind,
+
+    #[arg(
+        long = "pdf-service-connect-timeout-seconds",
+        env = "PDF_SERVICE_CONNECT_TIMEOUT_SECONDS",
+        default_value = "5",
+        name = "pdf_service_connect_timeout_seconds"
+    )]
+    pub pdf_service_connect_timeout_seconds: u64,
+
+    #[arg(
+        long = "pdf-service-request-timeout-seconds",
+        env = "PDF_SERVICE_REQUEST_TIMEOUT_SECONDS",
+        default_value = "30",
+        name = "pdf_service_request_timeout_seconds"
+    )]
+    pub pdf_service_request_timeout_seconds: u64,
+
+    #[arg(
+        long = "pdf-service-max-retries",
+        env = "PDF_SERVICE_MAX_RETRIES",
+        default_value = "2",
+        name = "pdf_service_max_retries"
+    )]
+    pub pdf_service_max_retries: u32,
+
+    #[arg(
+        long = "pdf-service-retry-base-delay-ms",
+        env = "PDF_SERVICE_RETRY_BASE_DELAY_MS",
+        default_value = "200",
+        name = "pdf_service_retry_base_delay_ms"
+    )]
+    pub pdf_service_retry_base_delay_ms: u64,
+
+    #[arg(
+        long = "pdf-service-circuit-failure-threshold",
+        env = "PDF_SERVICE_CIRCUIT_FAILURE_THRESHOLD",
+        default_value = "5",
+        name = "pdf_service_circuit_failure_threshold"
+    )]
+    pub pdf_service_circuit_failure_threshold: u32,
+
+    #[arg(
+        long = "pdf-service-circuit-open-seconds",
+        env = "PDF_SERVICE_CIRCUIT_OPEN_SECONDS",
+        default_value = "30",
+        name = "pdf_service_circuit_open_seconds"
+    )]
+    pub pdf_service_circuit_open_seconds: u64,
+
     #[arg(
         long = "workday-garbage-retention-days",
         env = "WORKDAY_GARBAGE_RETENTION_DAYS",
@@ -186,6 +409,48 @@ pub struct CommonConfig {
     pub account_deactivation_days: i64,
 }
 
+impl CommonConfig {
+    pub fn web_push(&self) -> WebPushConfig {
+        WebPushConfig {
+            vapid_private_key: self.vapid_private_key.clone(),
+            vapid_subject: self.vapid_subject.clone(),
+            allow_insecure_endpoints: false,
+        }
+    }
+
+    pub fn mail_events(&self) -> MailEventsConfig {
+        MailEventsConfig {
+            webhook_token: self.mail_events_webhook_token.clone(),
+        }
+    }
+
+    pub fn document_service(&self) -> DocumentServiceConfig {
+        DocumentServiceConfig {
+            renderer: match self.pdf_renderer {
+                PdfRendererKind::Grpc => DocumentRenderer::Grpc,
+                PdfRendererKind::GrpcWithFallback => DocumentRenderer::GrpcWithFallback,
+                PdfRendererKind::Local => DocumentRenderer::Local,
+            },
+            connect_timeout: Duration::from_secs(self.pdf_service_connect_timeout_seconds),
+            request_timeout: Duration::from_secs(self.pdf_service_request_timeout_seconds),
+            max_retries: self.pdf_service_max_retries,
+            retry_base_delay: Duration::from_millis(self.pdf_service_retry_base_delay_ms),
+            circuit_failure_threshold: self.pdf_service_circuit_failure_threshold,
+            circuit_open_duration: Duration::from_secs(self.pdf_service_circuit_open_seconds),
+        }
+    }
+
+    pub fn sms(&self) -> SmsConfig {
+        SmsConfig {
+            gateway_url: self.sms_gateway_url.clone(),
+            gateway_api_key: self.sms_gateway_api_key.clone(),
+            sender: self.sms_sender.clone(),
+            log_path: self.sms_log_path.clone(),
+            login_enabled: self.sms_login_enabled,
+        }
+    }
+}
+
 #[derive(Clone, Parser, Debug, Default)]
 pub struct CheckContentConfig {
     #[arg(
@@ -194,6 +459,21 @@ pub struct CheckContentConfig {
         value_delimiter = ','
     )]
     pub email_domain_denylist: Vec<String>,
+
+    #[arg(
+        long = "password-min-score",
+        env = "PASSWORD_MIN_SCORE",
+        default_value = "70",
+        name = "password_min_score"
+    )]
+    pub password_min_score: f64,
+
+    #[arg(
+        long = "password-breached-dataset-dir",
+        env = "PASSWORD_BREACHED_DATASET_DIR",
+        name = "password_breached_dataset_dir"
+    )]
+    pub password_breached_dataset_dir: Option<PathBuf>,
 }
 
 #[derive(Clone, Parser, Debug, Default)]
@@ -240,6 +520,56 @@ pub struct S3Config {
         name = "s3_region"
     )]
     pub region: String,
+
+    #[arg(
+        long = "s3-document-delivery",
+        env = "S3_DOCUMENT_DELIVERY",
+        default_value = "stream",
+        name = "s3_document_delivery"
+    )]
+    pub document_delivery: DocumentDelivery,
+
+    #[arg(
+        long = "s3-presigned-url-ttl-seconds",
+        env = "S3_PRESIGNED_URL_TTL_SECONDS",
+        default_value = "300",
+        name = "s3_presigned_url_ttl_seconds"
+    )]
+    pub presigned_url_ttl_seconds: u64,
+
+    #[arg(
+        long = "storage-backend",
+        env = "STORAGE_BACKEND",
+        default_value = "s3",
+        name = "storage_backend"
+    )]
+    pub storage_backend: StorageBackendKind,
+
+    #[arg(
+        long = "storage-local-root-dir",
+        env = "STORAGE_LOCAL_ROOT_DIR",
+        default_value = "storage",
+        name = "storage_local_root_dir"
+    )]
+    pub storage_local_root_dir: PathBuf,
+
+    #[arg(
+        long = "storage-url-secret",
+        env = "STORAGE_URL_SECRET",
+        default_value = "",
+        name = "storage_url_secret"
+    )]
+    pub storage_url_secret: String,
+
+    /// `<id>:<base64 32-byte key>` master keys wrapping the data keys of the drivers, the
+    /// current one first. The objects are stored unencrypted when empty.
+    #[arg(
+        long = "storage-master-keys",
+        env = "STORAGE_MASTER_KEYS",
+        value_delimiter = ',',
+        name = "storage_master_keys"
+    )]
+    pub storage_master_keys: Vec<String>,
 }
 
 impl S3Config {
@@ -256,6 +586,60 @@ impl S3Config {
             .credentials_provider(credentials)
             .endpoint_url(self.endpoint.clone())
     }
+
+    pub fn document_delivery(&self) -> DocumentDeliveryConfig {
+        DocumentDeliveryConfig {
+            mode: match self.document_delivery {
+                DocumentDelivery::Stream => DocumentDeliveryMode::Stream,
+                DocumentDelivery::Redirect => DocumentDeliveryMode::Redirect,
+            },
+            presigned_url_ttl: Duration::from_secs(self.presigned_url_ttl_seconds),
+        }
+    }
+
+    pub fn storage(&self, public_api_url: &str) -> StorageConfig {
+        StorageConfig {
+            backend: match self.storage_backend {
+                StorageBackendKind::S3 => StorageBackend::S3,
+                StorageBackendKind::LocalFs => StorageBackend::LocalFs,
+            },
+            local_root_dir: self.storage_local_root_dir.clone(),
+            api_url: public_api_url.to_string(),
+            url_secret: self.storage_url_secret.clone(),
+            encryption: StorageEncryptionConfig {
+                master_keys: self.storage_master_keys.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum, Default, PartialEq)]
+pub enum PdfRendererKind {
+    /// PDF service only
+    Grpc,
+    /// PDF service, built-in renderer while it is unavailable
+    #[default]
+    GrpcWithFallback,
+    /// Built-in renderer only
+    Local,
+}
+
+#[derive(Clone, Debug, ValueEnum, Default, PartialEq)]
+pub enum StorageBackendKind {
+    /// S3 compatible bucket
+    #[default]
+    S3,
+    /// Local directory
+    LocalFs,
+}
+
+#[derive(Clone, Debug, ValueEnum, Default, PartialEq)]
+pub enum DocumentDelivery {
+    /// Stream the documents through the API
+    #[default]
+    Stream,
+    /// Redirect to a presigned URL of the bucket
+    Redirect,
 }
 
 #[derive(Clone, Debug, ValueEnum, Default, PartialEq)]
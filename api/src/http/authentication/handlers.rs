@@ -1,4 +1,12 @@
-use axum::{Extension, extract::State, http::header::SET_COOKIE, response::AppendHeaders};
+use axum::{
+    Extension,
+    extract::State,
+    http::{
+        HeaderMap,
+        header::{SET_COOKIE, USER_AGENT},
+    },
+    response::AppendHeaders,
+};
 
 type AuthResponse = Result<
     (
@@ -19,16 +27,19 @@ type RefreshResponse = Result<
 use plannify_driver_api_core::domain::{
     driver::{
         entities::{
-            ConfirmPasswordResetRequest, CreateDriverRequest, CreateDriverResponse, DriverRow,
-            LoginDriverRequest, RequestPasswordResetRequest, VerifyDriverAccountRequest,
+            ConfirmEmailChangeRequest, ConfirmMagicLinkRequest, ConfirmPasswordResetRequest,
+            ConfirmSmsLoginRequest, CreateDriverRequest, CreateDriverResponse, DriverRow,
+            LoginDriverRequest, RequestMagicLinkRequest, RequestPasswordResetRequest,
+            RequestSmsLoginRequest, RevertEmailChangeRequest, VerifyDriverAccountRequest,
         },
         port::DriverService,
     },
     mail::port::MailService,
+    notification::{entities::SecurityAlert, port::NotificationService},
 };
 use plannify_driver_api_core::infrastructure::driver::repositories::error::DriverError;
 
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::{
     AppState,
@@ -64,14 +75,20 @@ use crate::{
 )]
 pub async fn signup(
     State(state): State<AppState>,
+    headers: HeaderMap,
     ValidatedJson(request): ValidatedJson<CreateDriverRequest>,
 ) -> AuthResponse {
     let driver = state
         .service
-        .create_driver(request, state.config.check_content.email_domain_denylist)
+        .create_driver(
+            request,
+            state.config.check_content.email_domain_denylist.clone(),
+        )
         .await?;
 
     state.service.send_creation_email(driver.clone()).await?;
+    // Known from the start, so that logging in again from it is not reported
+    alert_new_device(&state, &driver, &headers).await;
 
     let auth_validator = &state.auth_validator;
     let create_tokens_fn = |driver: &DriverRow| -> Result<(String, String), DriverError> {
@@ -127,9 +144,11 @@ pub async fn signup(
 )]
 pub async fn login(
     State(state): State<AppState>,
+    headers: HeaderMap,
     ValidatedJson(request): ValidatedJson<LoginDriverRequest>,
 ) -> AuthResponse {
     let driver = state.service.login_driver(request).await?;
+    alert_new_device(&state, &driver, &headers).await;
 
     let auth_validator = &state.auth_validator;
     let create_tokens_fn = |driver: &DriverRow| -> Result<(String, String), DriverError> {
@@ -240,6 +259,7 @@ pub async fn verify_driver_account(
     responses(
         (status = 200, description = "Driver auth refreshed successfully", body = CreateDriverResponse),
         (status = 401, description = "Invalid token", body = ErrorBody),
+        (status = 403, description = "Driver is suspended", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -247,6 +267,13 @@ pub async fn refresh_token(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> AuthResponse {
+    if let Some(issued_at) = user_identity.issued_at {
+        state
+            .service
+            .check_driver_session(user_identity.user_id, issued_at)
+            .await?;
+    }
+
     let driver = state
         .service
         .get_driver_for_refresh(user_identity.user_id)
@@ -344,8 +371,300 @@ pub async fn confirm_password_reset(
         .await?;
     state
         .service
-        .send_password_change_notification(driver)
+        .send_password_change_notification(driver.clone())
+        .await?;
+
+    if let Err(e) = state
+        .service
+        .send_security_alert(&driver, SecurityAlert::PasswordChanged)
+        .await
+    {
+        warn!("Failed to send password change alert: {:?}", e);
+    }
+
+    Ok(Response::ok(()))
+}
+
+#[tracing::instrument(
+    name = "request_magic_link",
+    skip_all,
+    fields(
+        email = %request.email,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/authentication/magic-link",
+    tag = "authentication",
+    security(),
+    request_body = RequestMagicLinkRequest,
+    responses(
+        (status = 200, description = "Magic link email sent successfully"),
+        (status = 403, description = "Password reset mail preference is disabled", body = ErrorBody),
+        (status = 404, description = "Driver not found", body = ErrorBody),
+        (status = 409, description = "Magic link token already exists", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn request_magic_link(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<RequestMagicLinkRequest>,
+) -> Result<Response<()>, ApiError> {
+    let driver = state.service.request_magic_link(request.email).await?;
+    state.service.send_magic_link_email(driver).await?;
+    Ok(Response::ok(()))
+}
+
+#[tracing::instrument(
+    name = "confirm_magic_link",
+    skip_all,
+    fields(
+        driver_id = %request.driver_id,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/authentication/magic-link/confirm",
+    tag = "authentication",
+    security(),
+    request_body = ConfirmMagicLinkRequest,
+    responses(
+        (status = 200, description = "Driver logged in successfully", body = CreateDriverResponse),
+        (status = 400, description = "Invalid or expired magic link token", body = ErrorBody),
+        (status = 403, description = "Driver is suspended", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn confirm_magic_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<ConfirmMagicLinkRequest>,
+) -> AuthResponse {
+    let driver = state
+        .service
+        .confirm_magic_link(request.driver_id, request.token)
+        .await?;
+    alert_new_device(&state, &driver, &headers).await;
+
+    let auth_validator = &state.auth_validator;
+    let create_tokens_fn = |driver: &DriverRow| -> Result<(String, String), DriverError> {
+        auth_validator.create_tokens(driver).map_err(|e| {
+            error!(
+                "Failed to create tokens for driver {}: {:?}",
+                driver.pk_driver_id, e
+            );
+            DriverError::Internal
+        })
+    };
+
+    let (access_token, access_token_cookie, refresh_token_cookie) = state
+        .service
+        .generate_tokens(
+            driver,
+            create_tokens_fn,
+            state.config.jwt.access_ttl,
+            state.config.jwt.refresh_ttl,
+            state.config.common.frontend_url.as_str(),
+        )
+        .await?;
+
+    let headers = [
+        (SET_COOKIE, access_token_cookie),
+        (SET_COOKIE, refresh_token_cookie),
+    ];
+
+    Ok((
+        AppendHeaders(headers),
+        Response::ok(CreateDriverResponse { access_token }),
+    ))
+}
+
+#[tracing::instrument(
+    name = "request_sms_login",
+    skip_all,
+    fields(
+        email = %request.email,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/authentication/sms-login",
+    tag = "authentication",
+    security(),
+    request_body = RequestSmsLoginRequest,
+    responses(
+        (status = 200, description = "Login code sent by SMS successfully"),
+        (status = 403, description = "SMS login is disabled or the phone number is not verified", body = ErrorBody),
+        (status = 404, description = "Driver not found", body = ErrorBody),
+        (status = 409, description = "Login code already exists", body = ErrorBody),
+        (status = 429, description = "Too many SMS codes sent to the driver", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn request_sms_login(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<RequestSmsLoginRequest>,
+) -> Result<Response<()>, ApiError> {
+    state.service.request_sms_login(request.email).await?;
+    Ok(Response::ok(()))
+}
+
+#[tracing::instrument(
+    name = "confirm_sms_login",
+    skip_all,
+    fields(
+        email = %request.email,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/authentication/sms-login/confirm",
+    tag = "authentication",
+    security(),
+    request_body = ConfirmSmsLoginRequest,
+    responses(
+        (status = 200, description = "Driver logged in successfully", body = CreateDriverResponse),
+        (status = 400, description = "Invalid or expired login code", body = ErrorBody),
+        (status = 403, description = "SMS login is disabled or the driver is suspended", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn confirm_sms_login(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<ConfirmSmsLoginRequest>,
+) -> AuthResponse {
+    let driver = state
+        .service
+        .confirm_sms_login(request.email, request.code)
+        .await?;
+    alert_new_device(&state, &driver, &headers).await;
+
+    let auth_validator = &state.auth_validator;
+    let create_tokens_fn = |driver: &DriverRow| -> Result<(String, String), DriverError> {
+        auth_validator.create_tokens(driver).map_err(|e| {
+            error!(
+                "Failed to create tokens for driver {}: {:?}",
+                driver.pk_driver_id, e
+            );
+            DriverError::Internal
+        })
+    };
+
+    let (access_token, access_token_cookie, refresh_token_cookie) = state
+        .service
+        .generate_tokens(
+            driver,
+            create_tokens_fn,
+            state.config.jwt.access_ttl,
+            state.config.jwt.refresh_ttl,
+            state.config.common.frontend_url.as_str(),
+        )
+        .await?;
+
+    let headers = [
+        (SET_COOKIE, access_token_cookie),
+        (SET_COOKIE, refresh_token_cookie),
+    ];
+
+    Ok((
+        AppendHeaders(headers),
+        Response::ok(CreateDriverResponse { access_token }),
+    ))
+}
+
+#[tracing::instrument(
+    name = "confirm_email_change",
+    skip_all,
+    fields(
+        driver_id = %request.driver_id,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/authentication/email-change/confirm",
+    tag = "authentication",
+    security(),
+    request_body = ConfirmEmailChangeRequest,
+    responses(
+        (status = 200, description = "Email change confirmed successfully", body = CreateDriverResponse),
+        (status = 400, description = "Invalid or expired email change token", body = ErrorBody),
+        (status = 403, description = "Driver is suspended", body = ErrorBody),
+        (status = 409, description = "Email already taken", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn confirm_email_change(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ConfirmEmailChangeRequest>,
+) -> AuthResponse {
+    let driver = state
+        .service
+        .confirm_email_change(request.driver_id, request.token)
+        .await?;
+
+    let auth_validator = &state.auth_validator;
+    let create_tokens_fn = |driver: &DriverRow| -> Result<(String, String), DriverError> {
+        auth_validator.create_tokens(driver).map_err(|e| {
+            error!(
+                "Failed to create tokens for driver {}: {:?}",
+                driver.pk_driver_id, e
+            );
+            DriverError::Internal
+        })
+    };
+
+    let (access_token, access_token_cookie, refresh_token_cookie) = state
+        .service
+        .generate_tokens(
+            driver,
+            create_tokens_fn,
+            state.config.jwt.access_ttl,
+            state.config.jwt.refresh_ttl,
+            state.config.common.frontend_url.as_str(),
+        )
+        .await?;
+
+    let headers = [
+        (SET_COOKIE, access_token_cookie),
+        (SET_COOKIE, refresh_token_cookie),
+    ];
+
+    Ok((
+        AppendHeaders(headers),
+        Response::ok(CreateDriverResponse { access_token }),
+    ))
+}
+
+#[tracing::instrument(
+    name = "revert_email_change",
+    skip_all,
+    fields(
+        driver_id = %request.driver_id,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/authentication/email-change/revert",
+    tag = "authentication",
+    security(),
+    description = "Cancel a pending email change, or restore the previous address of a recently confirmed one. Every session and API token of the driver is revoked.",
+    request_body = RevertEmailChangeRequest,
+    responses(
+        (status = 200, description = "Email change reverted successfully"),
+        (status = 400, description = "Invalid or expired email change token", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn revert_email_change(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<RevertEmailChangeRequest>,
+) -> Result<Response<()>, ApiError> {
+    state
+        .service
+        .revert_email_change(request.driver_id, request.token)
         .await?;
+
     Ok(Response::ok(()))
 }
 
@@ -370,3 +689,30 @@ pub async fn delete_refresh_token(State(state): State<AppState>) -> RefreshRespo
         Response::ok(()),
     ))
 }
+
+/// Warns the driver by SMS when the login comes from an unknown device. Failures are only
+/// logged, they never block the login.
+async fn alert_new_device(state: &AppState, driver: &DriverRow, headers: &HeaderMap) {
+    let user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    match state
+        .service
+        .register_login_device(driver.pk_driver_id, user_agent)
+        .await
+    {
+        Ok(true) => {
+            if let Err(e) = state
+                .service
+                .send_security_alert(driver, SecurityAlert::NewDeviceLogin)
+                .await
+            {
+                warn!("Failed to send new device alert: {:?}", e);
+            }
+        }
+        Ok(false) => {}
+        Err(e) => warn!("Failed to register login device: {:?}", e),
+    }
+}
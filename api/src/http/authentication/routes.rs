@@ -2,10 +2,13 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 use crate::http::{
     authentication::handlers::{
-        __path_confirm_password_reset, __path_delete_refresh_token, __path_login,
-        __path_refresh_token, __path_request_password_reset, __path_signup,
-        __path_verify_driver_account, confirm_password_reset, delete_refresh_token, login,
-        refresh_token, request_password_reset, signup, verify_driver_account,
+        __path_confirm_email_change, __path_confirm_magic_link, __path_confirm_password_reset,
+        __path_confirm_sms_login, __path_delete_refresh_token, __path_login, __path_refresh_token,
+        __path_request_magic_link, __path_request_password_reset, __path_request_sms_login,
+        __path_revert_email_change, __path_signup, __path_verify_driver_account,
+        confirm_email_change, confirm_magic_link, confirm_password_reset, confirm_sms_login,
+        delete_refresh_token, login, refresh_token, request_magic_link, request_password_reset,
+        request_sms_login, revert_email_change, signup, verify_driver_account,
     },
     common::app_state::AppState,
 };
@@ -17,6 +20,12 @@ pub fn authentication_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(verify_driver_account))
         .routes(routes!(request_password_reset))
         .routes(routes!(confirm_password_reset))
+        .routes(routes!(request_magic_link))
+        .routes(routes!(confirm_magic_link))
+        .routes(routes!(request_sms_login))
+        .routes(routes!(confirm_sms_login))
+        .routes(routes!(confirm_email_change))
+        .routes(routes!(revert_email_change))
 }
 
 pub fn refresh_cookie_routes() -> OpenApiRouter<AppState> {
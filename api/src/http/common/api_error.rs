@@ -7,8 +7,9 @@ use plannify_driver_api_core::{
     domain::common::CoreError,
     infrastructure::{
         driver::repositories::error::DriverError, health::repositories::error::HealthError,
-        mail::repositories::error::MailError, storage::repositories::error::StorageError,
-        update::repositories::error::UpdateError, workday::repositories::error::WorkdayError,
+        mail::repositories::error::MailError, notification::repositories::error::NotificationError,
+        storage::repositories::error::StorageError, update::repositories::error::UpdateError,
+        workday::repositories::error::WorkdayError,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -47,7 +48,19 @@ pub enum ApiError {
     },
 
     #[error("Conflict")]
-    Conflict { error_code: String },
+    Conflict {
+        error_code: String,
+        content: Option<Value>,
+    },
+
+    #[error("Precondition failed")]
+    PreconditionFailed { error_code: String },
+
+    #[error("Too many requests")]
+    TooManyRequests { error_code: String },
+
+    #[error("Range not satisfiable")]
+    RangeNotSatisfiable { error_code: String },
 }
 
 impl ApiError {
@@ -61,6 +74,9 @@ impl ApiError {
             ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
             ApiError::BadRequest { .. } => StatusCode::BAD_REQUEST,
             ApiError::Conflict { .. } => StatusCode::CONFLICT,
+            ApiError::PreconditionFailed { .. } => StatusCode::PRECONDITION_FAILED,
+            ApiError::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::RangeNotSatisfiable { .. } => StatusCode::RANGE_NOT_SATISFIABLE,
         }
     }
 }
@@ -114,7 +130,28 @@ impl From<ApiError> for ErrorBody {
                 content,
                 status,
             },
-            ApiError::Conflict { error_code } => ErrorBody {
+            ApiError::Conflict {
+                error_code,
+                content,
+            } => ErrorBody {
+                message,
+                error_code,
+                content,
+                status,
+            },
+            ApiError::PreconditionFailed { error_code } => ErrorBody {
+                message,
+                error_code,
+                content: None,
+                status,
+            },
+            ApiError::TooManyRequests { error_code } => ErrorBody {
+                message,
+                error_code,
+                content: None,
+                status,
+            },
+            ApiError::RangeNotSatisfiable { error_code } => ErrorBody {
                 message,
                 error_code,
                 content: None,
@@ -180,6 +217,7 @@ impl From<DriverError> for ApiError {
             },
             DriverError::DriverAlreadyExists => ApiError::Conflict {
                 error_code: "DRIVER_ALREADY_EXISTS".to_string(),
+                content: None,
             },
             DriverError::EmailDomainDenylisted { domain } => {
                 let mut content = Mapping::new();
@@ -192,6 +230,10 @@ impl From<DriverError> for ApiError {
             DriverError::DriverNotFound => ApiError::NotFound {
                 error_code: "DRIVER_NOT_FOUND".to_string(),
             },
+            DriverError::DriverVersionConflict => ApiError::Conflict {
+                error_code: "DRIVER_VERSION_CONFLICT".to_string(),
+                content: None,
+            },
             DriverError::DriverLimitReached { start_at, end_at } => {
                 let mut content = Mapping::new();
                 content.insert(
@@ -219,7 +261,10 @@ impl From<DriverError> for ApiError {
             } => {
                 let mut content = Mapping::new();
                 if let Some(msg) = message {
-                    content.insert(Value::String("message".to_string()), Value::String(msg));
+                    content.insert(
+                        Value::String("driver_message".to_string()),
+                        Value::String(msg),
+                    );
                 }
                 content.insert(
                     Value::String("start_at".to_string()),
@@ -252,20 +297,98 @@ impl From<DriverError> for ApiError {
             DriverError::EmailSendError => ApiError::InternalServerError,
             DriverError::AccountAlreadyDeactivated => ApiError::Conflict {
                 error_code: "ACCOUNT_ALREADY_DEACTIVATED".to_string(),
+                content: None,
             },
             DriverError::AccountNotDeactivated => ApiError::Conflict {
                 error_code: "ACCOUNT_NOT_DEACTIVATED".to_string(),
+                content: None,
             },
             DriverError::InvalidRefreshToken => ApiError::Unauthorized {
                 error_code: "INVALID_REFRESH_TOKEN".to_string(),
             },
             DriverError::ResetPasswordTokenAlreadyExists => ApiError::Conflict {
                 error_code: "RESET_PASSWORD_TOKEN_ALREADY_EXISTS".to_string(),
+                content: None,
             },
             DriverError::InvalidResetPasswordToken => ApiError::BadRequest {
                 error_code: "INVALID_RESET_PASSWORD_TOKEN".to_string(),
                 content: None,
             },
+            DriverError::PasswordPolicyViolation { violations } => {
+                let violations = violations
+                    .into_iter()
+                    .map(|violation| {
+                        let mut item = Mapping::new();
+                        item.insert(
+                            Value::String("rule".to_string()),
+                            Value::String(violation.rule.as_str().to_string()),
+                        );
+                        item.insert(
+                            Value::String("message".to_string()),
+                            Value::String(violation.message),
+                        );
+                        Value::Mapping(item)
+                    })
+                    .collect();
+                let mut content = Mapping::new();
+                content.insert(
+                    Value::String("violations".to_string()),
+                    Value::Sequence(violations),
+                );
+                ApiError::BadRequest {
+                    error_code: "PASSWORD_POLICY_VIOLATION".to_string(),
+                    content: Some(Value::Mapping(content)),
+                }
+            }
+            DriverError::MagicLinkTokenAlreadyExists => ApiError::Conflict {
+                error_code: "MAGIC_LINK_TOKEN_ALREADY_EXISTS".to_string(),
+                content: None,
+            },
+            DriverError::InvalidMagicLinkToken => ApiError::BadRequest {
+                error_code: "INVALID_MAGIC_LINK_TOKEN".to_string(),
+                content: None,
+            },
+            DriverError::InvalidEmailChangeToken => ApiError::BadRequest {
+                error_code: "INVALID_EMAIL_CHANGE_TOKEN".to_string(),
+                content: None,
+            },
+            DriverError::SessionRevoked => ApiError::Unauthorized {
+                error_code: "SESSION_REVOKED".to_string(),
+            },
+            DriverError::ApiTokenNotFound => ApiError::NotFound {
+                error_code: "API_TOKEN_NOT_FOUND".to_string(),
+            },
+            DriverError::InvalidApiToken => ApiError::Unauthorized {
+                error_code: "INVALID_API_TOKEN".to_string(),
+            },
+            DriverError::PhoneNumberMissing => ApiError::BadRequest {
+                error_code: "PHONE_NUMBER_MISSING".to_string(),
+                content: None,
+            },
+            DriverError::PhoneNumberAlreadyVerified => ApiError::Conflict {
+                error_code: "PHONE_NUMBER_ALREADY_VERIFIED".to_string(),
+                content: None,
+            },
+            DriverError::PhoneNumberNotVerified => ApiError::Forbidden {
+                error_code: "PHONE_NUMBER_NOT_VERIFIED".to_string(),
+                content: None,
+            },
+            DriverError::SmsCodeAlreadyExists => ApiError::Conflict {
+                error_code: "SMS_CODE_ALREADY_EXISTS".to_string(),
+                content: None,
+            },
+            DriverError::SmsRateLimited => ApiError::TooManyRequests {
+                error_code: "SMS_RATE_LIMITED".to_string(),
+            },
+            DriverError::InvalidSmsCode => ApiError::BadRequest {
+                error_code: "INVALID_SMS_CODE".to_string(),
+                content: None,
+            },
+            DriverError::SmsLoginDisabled => ApiError::Forbidden {
+                error_code: "SMS_LOGIN_DISABLED".to_string(),
+                content: None,
+            },
+            DriverError::SmsSendError => ApiError::InternalServerError,
         }
     }
 }
@@ -275,14 +398,24 @@ impl From<WorkdayError> for ApiError {
         match error {
             WorkdayError::DatabaseError => ApiError::InternalServerError,
             WorkdayError::Internal => ApiError::InternalServerError,
+            WorkdayError::DocumentServiceUnavailable => ApiError::ServiceUnavailable {
+                msg: "Document service is unavailable".to_string(),
+                error_code: "DOCUMENT_SERVICE_UNAVAILABLE".to_string(),
+            },
             WorkdayError::WorkdayAlreadyExists => ApiError::Conflict {
                 error_code: "WORKDAY_ALREADY_EXISTS".to_string(),
+                content: None,
             },
             WorkdayError::WorkdayNotFound => ApiError::NotFound {
                 error_code: "WORKDAY_NOT_FOUND".to_string(),
             },
+            WorkdayError::WorkdayVersionConflict { current } => ApiError::Conflict {
+                error_code: "WORKDAY_VERSION_CONFLICT".to_string(),
+                content: serde_yaml::to_value(*current).ok(),
+            },
             WorkdayError::WorkdayGarbageAlreadyExists => ApiError::Conflict {
                 error_code: "WORKDAY_GARBAGE_ALREADY_EXISTS".to_string(),
+                content: None,
             },
             WorkdayError::WorkdayGarbageNotFound => ApiError::NotFound {
                 error_code: "WORKDAY_GARBAGE_NOT_FOUND".to_string(),
@@ -291,6 +424,39 @@ impl From<WorkdayError> for ApiError {
                 error_code: "WORKDAY_DOCUMENT_ALREADY_GENERATED".to_string(),
                 content: None,
             },
+            WorkdayError::WorkdayDocumentNotFound => ApiError::NotFound {
+                error_code: "DOCUMENT_NOT_FOUND".to_string(),
+            },
+            WorkdayError::DocumentShareNotFound => ApiError::NotFound {
+                error_code: "DOCUMENT_SHARE_NOT_FOUND".to_string(),
+            },
+            WorkdayError::DocumentShareExpired => ApiError::Forbidden {
+                error_code: "DOCUMENT_SHARE_EXPIRED".to_string(),
+                content: None,
+            },
+            WorkdayError::DocumentShareRevoked => ApiError::Forbidden {
+                error_code: "DOCUMENT_SHARE_REVOKED".to_string(),
+                content: None,
+            },
+            WorkdayError::DocumentSharePasswordRequired => ApiError::Unauthorized {
+                error_code: "DOCUMENT_SHARE_PASSWORD_REQUIRED".to_string(),
+            },
+            WorkdayError::DocumentSharePasswordInvalid => ApiError::Unauthorized {
+                error_code: "DOCUMENT_SHARE_PASSWORD_INVALID".to_string(),
+            },
+            WorkdayError::DocumentShareRecipientRequired => ApiError::BadRequest {
+                error_code: "DOCUMENT_SHARE_RECIPIENT_REQUIRED".to_string(),
+                content: None,
+            },
+            WorkdayError::DocumentShareRateLimited => ApiError::TooManyRequests {
+                error_code: "DOCUMENT_SHARE_RATE_LIMITED".to_string(),
+            },
+            WorkdayError::DocumentShareLocked => ApiError::TooManyRequests {
+                error_code: "DOCUMENT_SHARE_LOCKED".to_string(),
+            },
+            WorkdayError::RangeNotSatisfiable => ApiError::RangeNotSatisfiable {
+                error_code: "DOCUMENT_RANGE_NOT_SATISFIABLE".to_string(),
+            },
         }
     }
 }
@@ -319,6 +485,56 @@ impl From<MailError> for ApiError {
                 error_code: "MAIL_PREFERENCE_DISABLED".to_string(),
                 content: None,
             },
+            MailError::InvalidUnsubscribeSignature => ApiError::BadRequest {
+                error_code: "INVALID_UNSUBSCRIBE_SIGNATURE".to_string(),
+                content: None,
+            },
+            MailError::EmailUnreachable => ApiError::BadRequest {
+                error_code: "EMAIL_UNREACHABLE".to_string(),
+                content: None,
+            },
+            MailError::InvalidMailEventsToken => ApiError::Unauthorized {
+                error_code: "INVALID_MAIL_EVENTS_TOKEN".to_string(),
+            },
+            MailError::InvalidMailEvents => ApiError::BadRequest {
+                error_code: "INVALID_MAIL_EVENTS".to_string(),
+                content: None,
+            },
+            MailError::MailNotResendable => ApiError::Conflict {
+                error_code: "MAIL_NOT_RESENDABLE".to_string(),
+                content: None,
+            },
+            MailError::MailResendRateLimited => ApiError::TooManyRequests {
+                error_code: "MAIL_RESEND_RATE_LIMITED".to_string(),
+            },
+            MailError::MailNotCancellable => ApiError::Conflict {
+                error_code: "MAIL_NOT_CANCELLABLE".to_string(),
+                content: None,
+            },
+            MailError::MailAttachmentRangeNotSatisfiable => ApiError::RangeNotSatisfiable {
+                error_code: "MAIL_ATTACHMENT_RANGE_NOT_SATISFIABLE".to_string(),
+            },
+        }
+    }
+}
+
+impl From<NotificationError> for ApiError {
+    fn from(error: NotificationError) -> Self {
+        match error {
+            NotificationError::Internal => ApiError::InternalServerError,
+            NotificationError::DatabaseError => ApiError::InternalServerError,
+            NotificationError::PushSubscriptionNotFound => ApiError::NotFound {
+                error_code: "PUSH_SUBSCRIPTION_NOT_FOUND".to_string(),
+            },
+            NotificationError::InvalidPushSubscription => ApiError::BadRequest {
+                error_code: "INVALID_PUSH_SUBSCRIPTION".to_string(),
+                content: None,
+            },
+            NotificationError::NoPushSubscription => ApiError::InternalServerError,
+            NotificationError::PushNotConfigured => ApiError::InternalServerError,
+            NotificationError::CannotSendPushNotification => ApiError::InternalServerError,
+            NotificationError::CannotSendSms => ApiError::InternalServerError,
+            NotificationError::Mail(error) => error.into(),
         }
     }
 }
@@ -341,12 +557,27 @@ impl From<StorageError> for ApiError {
             StorageError::ObjectNotFound => ApiError::NotFound {
                 error_code: "OBJECT_NOT_FOUND".to_string(),
             },
+            StorageError::InvalidKey => ApiError::NotFound {
+                error_code: "OBJECT_NOT_FOUND".to_string(),
+            },
             StorageError::UploadError(_) => ApiError::InternalServerError,
             StorageError::DownloadError => ApiError::InternalServerError,
             StorageError::DeleteError => ApiError::InternalServerError,
             StorageError::PresignedUrlError => ApiError::InternalServerError,
+            StorageError::PresignedUrlInvalid => ApiError::Forbidden {
+                error_code: "PRESIGNED_URL_INVALID".to_string(),
+                content: None,
+            },
             StorageError::ListError => ApiError::InternalServerError,
+            // The driver was purged, the object cannot be read anymore
+            StorageError::DataKeyNotFound => ApiError::NotFound {
+                error_code: "OBJECT_NOT_FOUND".to_string(),
+            },
+            StorageError::EncryptionError => ApiError::InternalServerError,
             StorageError::Internal => ApiError::InternalServerError,
+            StorageError::RangeNotSatisfiable => ApiError::RangeNotSatisfiable {
+                error_code: "RANGE_NOT_SATISFIABLE".to_string(),
+            },
         }
     }
 }
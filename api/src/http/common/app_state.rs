@@ -41,6 +41,8 @@ impl From<DriverRepositories> for AppState {
             repositories.update_cache_repository,
             repositories.document_external_repository,
             repositories.storage_repository,
+            repositories.notification_database_repository,
+            repositories.sms_repository,
             repositories.service_config,
         );
         let config = Config::default();
@@ -0,0 +1,125 @@
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response as AxumResponse},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::ApiError;
+
+/// Strong entity tag derived from `versions`, what a representation is built from: it changes
+/// with any of them, without reading nor serializing the representation itself. The versions are
+/// hashed as JSON, a stable encoding unlike their `Debug` output.
+pub fn entity_tag<V>(versions: &V) -> String
+where
+    V: Serialize,
+{
+    let key = serde_json::to_vec(versions).expect("entity tag versions serialize to JSON");
+    let digest = Sha256::digest(key);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Whether one of the tags of an `If-None-Match` or `If-Match` header value matches `e_tag`.
+/// `weak` compares the opaque tags only, as `If-None-Match` does (RFC 9110 §8.8.3.2).
+fn matches(header: &HeaderValue, e_tag: &str, weak: bool) -> bool {
+    let Ok(header) = header.to_str() else {
+        return false;
+    };
+
+    header.split(',').map(str::trim).any(|tag| {
+        if tag == "*" {
+            return true;
+        }
+        match tag.strip_prefix("W/") {
+            Some(tag) => weak && tag == e_tag,
+            None => tag == e_tag,
+        }
+    })
+}
+
+/// JSON response carrying the `ETag` of its body, or `304 Not Modified` when the client already
+/// holds it.
+pub struct TaggedResponse<T> {
+    data: Option<T>,
+    e_tag: String,
+}
+
+impl<T> TaggedResponse<T>
+where
+    T: Serialize,
+{
+    /// Create a 304 Not Modified response if `headers` hold `e_tag` in `If-None-Match`, to check
+    /// before building the representation
+    pub fn unchanged(headers: &HeaderMap, e_tag: &str) -> Option<Self> {
+        headers
+            .get(header::IF_NONE_MATCH)
+            .is_some_and(|header| matches(header, e_tag, true))
+            .then(|| Self {
+                data: None,
+                e_tag: e_tag.to_string(),
+            })
+    }
+
+    /// Create a 200 OK response tagged with `e_tag`
+    pub fn ok(data: T, e_tag: String) -> Self {
+        Self {
+            data: Some(data),
+            e_tag,
+        }
+    }
+}
+
+impl<T> IntoResponse for TaggedResponse<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> AxumResponse {
+        let headers = [
+            (
+                header::ETAG,
+                HeaderValue::try_from(&self.e_tag)
+                    .expect("hex entity tags are valid header values"),
+            ),
+            // Per-driver data, always revalidated
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("private, no-cache"),
+            ),
+        ];
+
+        let Some(data) = self.data else {
+            return (StatusCode::NOT_MODIFIED, headers).into_response();
+        };
+        let Ok(body) = serde_json::to_vec(&data) else {
+            return ApiError::InternalServerError.into_response();
+        };
+
+        (
+            StatusCode::OK,
+            headers,
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            )],
+            body,
+        )
+            .into_response()
+    }
+}
+
+/// Fails with `412 Precondition Failed` when `If-Match` names none of the tags of `e_tag`, the
+/// tag of the representation about to be modified. Without the header, the modification always
+/// proceeds.
+pub fn check_if_match(headers: &HeaderMap, e_tag: &str) -> Result<(), ApiError> {
+    let Some(if_match) = headers.get(header::IF_MATCH) else {
+        return Ok(());
+    };
+
+    if matches(if_match, e_tag, false) {
+        Ok(())
+    } else {
+        Err(ApiError::PreconditionFailed {
+            error_code: "PRECONDITION_FAILED".to_string(),
+        })
+    }
+}
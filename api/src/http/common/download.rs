@@ -0,0 +1,70 @@
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use plannify_driver_api_core::domain::storage::entities::{ByteRange, DocumentDownload};
+
+/// Single byte range asked by the `Range` header, if any.
+pub fn requested_range(headers: &HeaderMap) -> Option<ByteRange> {
+    headers
+        .get(header::RANGE)
+        .and_then(|range| range.to_str().ok())
+        .and_then(ByteRange::parse)
+}
+
+/// Response delivering a document as an attachment named `file_name`.
+pub fn document_response(
+    download: DocumentDownload,
+    content_type: &'static str,
+    file_name: &str,
+) -> Response {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::try_from(format!("attachment; filename=\"{}\"", file_name))
+            .unwrap_or(HeaderValue::from_static("attachment")),
+    );
+
+    match download {
+        DocumentDownload::Stream(object) => {
+            headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+            headers.insert(
+                header::CONTENT_LENGTH,
+                HeaderValue::from(object.content_length),
+            );
+            if let Some(e_tag) = object.e_tag.and_then(|e| HeaderValue::try_from(e).ok()) {
+                headers.insert(header::ETAG, e_tag);
+            }
+
+            let status = match object
+                .content_range
+                .and_then(|r| HeaderValue::try_from(r).ok())
+            {
+                Some(content_range) => {
+                    headers.insert(header::CONTENT_RANGE, content_range);
+                    StatusCode::PARTIAL_CONTENT
+                }
+                None => StatusCode::OK,
+            };
+
+            (status, headers, Body::from_stream(object.body)).into_response()
+        }
+        // The presigned URL is only valid for a short time, it must not be cached
+        DocumentDownload::Redirect(url) => match HeaderValue::try_from(url) {
+            Ok(location) => (
+                StatusCode::FOUND,
+                [
+                    (header::LOCATION, location),
+                    (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+                ],
+            )
+                .into_response(),
+            Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        },
+        DocumentDownload::Generated(bytes) => {
+            (StatusCode::OK, headers, Body::from(bytes)).into_response()
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::Utc;
-use plannify_driver_api_core::domain::driver::entities::DriverRow;
+use plannify_driver_api_core::domain::driver::entities::{ApiTokenScope, DriverRow};
 use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
 use uuid::Uuid;
 
 use tracing::error;
@@ -11,6 +12,58 @@ use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
 #[derive(Clone, Debug)]
 pub struct UserIdentity {
     pub user_id: Uuid,
+    /// `None` for a browser session (JWT), which has access to everything.
+    /// `Some` for a personal API token, restricted to the listed scopes.
+    pub scopes: Option<Vec<ApiTokenScope>>,
+    /// `iat` claim of the session JWT, `None` for a personal API token.
+    pub issued_at: Option<i64>,
+}
+
+impl UserIdentity {
+    pub fn from_session(user_id: Uuid, issued_at: i64) -> Self {
+        Self {
+            user_id,
+            scopes: None,
+            issued_at: Some(issued_at),
+        }
+    }
+
+    pub fn from_api_token(user_id: Uuid, scopes: Vec<ApiTokenScope>) -> Self {
+        Self {
+            user_id,
+            scopes: Some(scopes),
+            issued_at: None,
+        }
+    }
+
+    /// Declares the scope a handler requires when called with a personal API token.
+    pub fn require_scope(&self, scope: ApiTokenScope) -> Result<(), ApiError> {
+        match &self.scopes {
+            Some(scopes) if !scopes.contains(&scope) => {
+                let mut content = Mapping::new();
+                content.insert(
+                    Value::String("required_scope".to_string()),
+                    Value::String(scope.to_string()),
+                );
+                Err(ApiError::Forbidden {
+                    error_code: "INSUFFICIENT_SCOPE".to_string(),
+                    content: Some(Value::Mapping(content)),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Declares a handler as reserved to browser sessions (account management, API tokens).
+    pub fn require_session(&self) -> Result<(), ApiError> {
+        match self.scopes {
+            Some(_) => Err(ApiError::Forbidden {
+                error_code: "API_TOKEN_NOT_ALLOWED".to_string(),
+                content: None,
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -154,9 +207,7 @@ impl TokenValidator for AuthValidator {
             });
         }
 
-        Ok(UserIdentity {
-            user_id: claims.sub,
-        })
+        Ok(UserIdentity::from_session(claims.sub, claims.iat))
     }
 
     fn validate_refresh_token(&self, token: &str) -> Result<UserIdentity, ApiError> {
@@ -180,8 +231,6 @@ impl TokenValidator for AuthValidator {
             });
         }
 
-        Ok(UserIdentity {
-            user_id: claims.sub,
-        })
+        Ok(UserIdentity::from_session(claims.sub, claims.iat))
     }
 }
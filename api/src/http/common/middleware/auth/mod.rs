@@ -3,23 +3,27 @@ use axum::{
     http::{header, request::Parts},
 };
 use axum_extra::extract::CookieJar;
+use plannify_driver_api_core::domain::{
+    common::constants::API_TOKEN_PREFIX, driver::port::DriverService,
+};
 
 use tracing::error;
 
-use crate::http::common::{api_error::ApiError, middleware::auth::entities::TokenValidator};
+use crate::http::common::{
+    api_error::ApiError,
+    app_state::AppState,
+    middleware::auth::entities::{TokenValidator, UserIdentity},
+};
 pub mod entities;
 
 pub struct AuthMiddleware;
 
-impl<AuthValidator> FromRequestParts<AuthValidator> for AuthMiddleware
-where
-    AuthValidator: Send + Sync + TokenValidator,
-{
+impl FromRequestParts<AppState> for AuthMiddleware {
     type Rejection = ApiError;
 
     async fn from_request_parts(
         parts: &mut Parts,
-        state: &AuthValidator,
+        state: &AppState,
     ) -> Result<Self, Self::Rejection> {
         let cookie_jar = CookieJar::from_request_parts(parts, state)
             .await
@@ -48,7 +52,20 @@ where
                 .to_string()
         };
 
-        let user_identity = state.validate_token(&token)?;
+        let user_identity = if token.starts_with(API_TOKEN_PREFIX) {
+            // personal API token, scopes are checked by each handler
+            let api_token = state.service.authenticate_api_token(token).await?;
+            UserIdentity::from_api_token(api_token.fk_driver_id, api_token.to_scopes())
+        } else {
+            let user_identity = state.auth_validator.validate_token(&token)?;
+            if let Some(issued_at) = user_identity.issued_at {
+                state
+                    .service
+                    .check_driver_session(user_identity.user_id, issued_at)
+                    .await?;
+            }
+            user_identity
+        };
 
         // add auth state to request
         parts.extensions.insert(user_identity);
@@ -95,3 +112,34 @@ where
         Ok(Self)
     }
 }
+
+/// Rejects requests from drivers with an active suspension that denies access to the
+/// restricted space. Must run after `AuthMiddleware`, which provides the `UserIdentity`.
+pub struct AuthSuspensionMiddleware;
+
+impl FromRequestParts<AppState> for AuthSuspensionMiddleware {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let user_identity = parts
+            .extensions
+            .get::<UserIdentity>()
+            .cloned()
+            .ok_or_else(|| {
+                error!("User identity missing from request extensions");
+                ApiError::Unauthorized {
+                    error_code: "UNAUTHORIZED".to_string(),
+                }
+            })?;
+
+        state
+            .service
+            .check_driver_suspension(user_identity.user_id)
+            .await?;
+
+        Ok(Self)
+    }
+}
@@ -1,5 +1,7 @@
 pub mod api_error;
 pub mod app_state;
+pub mod conditional;
+pub mod download;
 pub mod middleware;
 pub mod response;
 pub mod validator;
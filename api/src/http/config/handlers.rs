@@ -12,6 +12,8 @@ pub struct ConfigResponse {
     pub workday_garbage_retention_days: i64,
     pub support_email: String,
     pub account_deactivation_days: i64,
+    /// `applicationServerKey` of the web push subscriptions, absent when push is disabled.
+    pub vapid_public_key: Option<String>,
 }
 
 #[utoipa::path(
@@ -32,5 +34,6 @@ pub async fn get_config(
         workday_garbage_retention_days: state.config.common.workday_garbage_retention_days,
         support_email: state.config.common.support_email.clone(),
         account_deactivation_days: state.config.common.account_deactivation_days,
+        vapid_public_key: state.config.common.web_push().public_key(),
     }))
 }
@@ -1,21 +1,34 @@
-use axum::{Extension, extract::State, http::header::SET_COOKIE, response::AppendHeaders};
+use axum::{
+    Extension,
+    extract::{Path, State},
+    http::{
+        HeaderMap,
+        header::{IF_MATCH, SET_COOKIE},
+    },
+    response::AppendHeaders,
+};
 use plannify_driver_api_core::domain::{
     driver::{
         entities::{
-            CreateDriverResponse, CreateDriverRestPeriodsRequest, DriverRestPeriod, DriverRow,
-            GetDriverLimitationResponse, GetDriverResponse, UpdateDriverRequest,
+            ConfirmPhoneVerificationRequest, CreateDriverApiTokenRequest,
+            CreateDriverApiTokenResponse, CreateDriverResponse, CreateDriverRestPeriodsRequest,
+            DriverApiToken, DriverRestPeriod, DriverRow, GetDriverLimitationResponse,
+            GetDriverResponse, UpdateDriverRequest,
         },
         port::DriverService,
     },
     mail::port::MailService,
+    notification::{entities::SecurityAlert, port::NotificationService},
 };
 use plannify_driver_api_core::infrastructure::driver::repositories::error::DriverError;
-use tracing::error;
+use tracing::{error, warn};
+use uuid::Uuid;
 
 use crate::{
     ApiError, AppState,
     http::common::{
         api_error::ErrorBody,
+        conditional::{TaggedResponse, check_if_match, entity_tag},
         middleware::auth::entities::{TokenValidator, UserIdentity},
         response::Response,
         validator::ValidatedJson,
@@ -49,6 +62,7 @@ type UpdateDriverResponse = Result<
     responses(
         (status = 200, description = "Driver rest periods retrieved successfully", body = Vec<DriverRestPeriod>),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -56,6 +70,8 @@ pub async fn get_all_rest_periods(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<Vec<DriverRestPeriod>>, ApiError> {
+    user_identity.require_session()?;
+
     let rest_periods = state
         .service
         .get_driver_rest_periods(user_identity.user_id)
@@ -87,6 +103,7 @@ pub async fn get_all_rest_periods(
         (status = 201, description = "Driver rest periods set successfully"),
         (status = 400, description = "Invalid rest period", body = ErrorBody),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -95,6 +112,8 @@ pub async fn set_rest_periods(
     Extension(user_identity): Extension<UserIdentity>,
     ValidatedJson(request): ValidatedJson<CreateDriverRestPeriodsRequest>,
 ) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
     let rest_periods_count = request.rest_periods.len();
 
     state
@@ -125,6 +144,7 @@ pub async fn set_rest_periods(
     responses(
         (status = 200, description = "Driver rest periods deleted successfully"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -132,6 +152,8 @@ pub async fn delete_rest_periods(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
     state
         .service
         .delete_driver_rest_periods(user_identity.user_id)
@@ -151,34 +173,68 @@ pub async fn delete_rest_periods(
     ),
     responses(
         (status = 200, description = "Driver information retrieved successfully", body = GetDriverResponse),
+        (status = 304, description = "Driver information unchanged since the `If-None-Match` entity tag"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
 pub async fn get_driver_info(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-) -> Result<Response<GetDriverResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<TaggedResponse<GetDriverResponse>, ApiError> {
+    user_identity.require_session()?;
+
+    let driver_info = driver_info(&state, user_identity.user_id).await?;
+
+    let e_tag = driver_info_tag(&driver_info);
+    if let Some(unchanged) = TaggedResponse::unchanged(&headers, &e_tag) {
+        return Ok(unchanged);
+    }
+
+    Ok(TaggedResponse::ok(driver_info, e_tag))
+}
+
+/// Entity tag of `GET /me`, checked by `PATCH /me`. Every other field of the representation
+/// changes with the version of the profile.
+fn driver_info_tag(driver_info: &GetDriverResponse) -> String {
+    entity_tag(&(
+        driver_info.pk_driver_id,
+        driver_info.version,
+        driver_info.email_unreachable_at,
+    ))
+}
+
+/// The representation of `GET /me`, whose entity tag `PATCH /me` checks.
+async fn driver_info(state: &AppState, driver_id: Uuid) -> Result<GetDriverResponse, ApiError> {
     let driver = state
         .service
-        .get_driver_by_id(user_identity.user_id)
+        .get_driver_by_id(driver_id)
         .await?
         .ok_or(ApiError::from(DriverError::DriverNotFound))?;
 
-    Ok(Response::ok(GetDriverResponse {
+    let email_unreachable_at = state
+        .service
+        .get_email_unreachable_at(driver.email.clone())
+        .await?;
+
+    Ok(GetDriverResponse {
         pk_driver_id: driver.pk_driver_id,
         firstname: driver.firstname,
         lastname: driver.lastname,
         gender: driver.gender,
         email: driver.email,
+        email_unreachable_at,
         phone_number: driver.phone_number,
+        phone_verified_at: driver.phone_verified_at,
         is_searchable: driver.is_searchable,
         allow_request_professional_agreement: driver.allow_request_professional_agreement,
         created_at: driver.created_at,
         verified_at: driver.verified_at,
-        last_login_at: driver.last_login_at,
         deactivated_at: driver.deactivated_at,
-    }))
+        version: driver.version,
+    })
 }
 
 #[tracing::instrument(
@@ -193,7 +249,7 @@ pub async fn get_driver_info(
     patch,
     path = "/me",
     tag = "driver",
-    description = "Update driver personal information. Only provided fields are updated.",
+    description = "Update driver personal information. Only provided fields are updated. A new email address stays pending until confirmed from that address.",
     request_body = UpdateDriverRequest,
     security(
         ("bearer_auth" = [])
@@ -202,39 +258,59 @@ pub async fn get_driver_info(
         (status = 200, description = "Driver information updated successfully", body = CreateDriverResponse),
         (status = 400, description = "Validation error", body = ErrorBody),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 404, description = "Driver not found", body = ErrorBody),
-        (status = 409, description = "Email already taken", body = ErrorBody),
+        (status = 409, description = "Email already taken, or driver information changed since `version`; the current information is then returned in `content`", body = ErrorBody),
+        (status = 412, description = "Driver information changed since the `If-Match` entity tag of `GET /me`", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
 pub async fn update_driver_info(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-    ValidatedJson(request): ValidatedJson<UpdateDriverRequest>,
+    request_headers: HeaderMap,
+    ValidatedJson(mut request): ValidatedJson<UpdateDriverRequest>,
 ) -> UpdateDriverResponse {
-    let previous_email = request.email.clone();
-    let (driver, email_changed, password_changed) = state
+    user_identity.require_session()?;
+
+    if request_headers.contains_key(IF_MATCH) {
+        let current = driver_info(&state, user_identity.user_id).await?;
+        check_if_match(&request_headers, &driver_info_tag(&current))?;
+        // Keeps the check valid until the update is saved
+        request.version.get_or_insert(current.version);
+    }
+
+    let (driver, email_change, password_changed) = match state
         .service
         .update_driver_info(
             user_identity.user_id,
             request,
-            state.config.check_content.email_domain_denylist,
+            state.config.check_content.email_domain_denylist.clone(),
         )
-        .await?;
+        .await
+    {
+        Ok(updated) => updated,
+        Err(DriverError::DriverVersionConflict) => {
+            let current = driver_info(&state, user_identity.user_id).await?;
+            return Err(ApiError::Conflict {
+                error_code: "DRIVER_VERSION_CONFLICT".to_string(),
+                content: serde_yaml::to_value(current).ok(),
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
 
-    tracing::Span::current().record("email_changed", email_changed);
+    tracing::Span::current().record("email_changed", email_change.is_some());
 
-    if email_changed {
+    if let Some(email_change) = email_change {
         state
             .service
-            .send_email_change_verification(driver.clone())
+            .send_email_change_verification(driver.clone(), email_change.clone())
             .await?;
 
-        let mut previous_driver = driver.clone();
-        previous_driver.email = previous_email.unwrap_or_else(|| driver.email.clone());
         state
             .service
-            .send_email_change_notification(previous_driver)
+            .send_email_change_notification(driver.clone(), email_change)
             .await?;
     }
 
@@ -243,6 +319,14 @@ pub async fn update_driver_info(
             .service
             .send_password_change_notification(driver.clone())
             .await?;
+
+        if let Err(e) = state
+            .service
+            .send_security_alert(&driver, SecurityAlert::PasswordChanged)
+            .await
+        {
+            warn!("Failed to send password change alert: {:?}", e);
+        }
     }
 
     let auth_validator = &state.auth_validator;
@@ -278,6 +362,80 @@ pub async fn update_driver_info(
     ))
 }
 
+#[tracing::instrument(
+    name = "request_phone_verification",
+    skip_all,
+    fields(user_id = %user_identity.user_id)
+)]
+#[utoipa::path(
+    post,
+    path = "/me/phone/verification",
+    tag = "driver",
+    description = "Send a one-time verification code by SMS to the phone number of the driver.",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Verification code sent successfully"),
+        (status = 400, description = "No phone number set", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 409, description = "Phone number already verified or code already sent", body = ErrorBody),
+        (status = 429, description = "Too many SMS codes sent to the driver", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn request_phone_verification(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
+    state
+        .service
+        .request_phone_verification(user_identity.user_id)
+        .await?;
+
+    Ok(Response::ok(()))
+}
+
+#[tracing::instrument(
+    name = "confirm_phone_verification",
+    skip_all,
+    fields(user_id = %user_identity.user_id)
+)]
+#[utoipa::path(
+    post,
+    path = "/me/phone/verification/confirm",
+    tag = "driver",
+    description = "Verify the phone number of the driver with the code received by SMS.",
+    request_body = ConfirmPhoneVerificationRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Phone number verified successfully"),
+        (status = 400, description = "Invalid or expired verification code", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn confirm_phone_verification(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    ValidatedJson(request): ValidatedJson<ConfirmPhoneVerificationRequest>,
+) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
+    state
+        .service
+        .confirm_phone_verification(user_identity.user_id, request.code)
+        .await?;
+
+    Ok(Response::ok(()))
+}
+
 #[tracing::instrument(
     name = "deactivate_driver",
     skip_all,
@@ -292,6 +450,7 @@ pub async fn update_driver_info(
     responses(
         (status = 200, description = "Account deactivated successfully"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 409, description = "Account is already deactivated", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
@@ -300,6 +459,8 @@ pub async fn deactivate_driver(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
     let driver = state
         .service
         .deactivate_driver(user_identity.user_id)
@@ -324,6 +485,7 @@ pub async fn deactivate_driver(
     responses(
         (status = 200, description = "Account reactivated successfully"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 409, description = "Account is not deactivated", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
@@ -332,6 +494,8 @@ pub async fn reactivate_driver(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
     let driver = state
         .service
         .reactivate_driver(user_identity.user_id)
@@ -342,6 +506,123 @@ pub async fn reactivate_driver(
     Ok(Response::ok(()))
 }
 
+#[tracing::instrument(
+    name = "get_api_tokens",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        result.count = tracing::field::Empty,
+    )
+)]
+#[utoipa::path(
+    get,
+    path = "/me/tokens",
+    tag = "driver/tokens",
+    description = "List the personal API tokens of the authenticated driver.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "API tokens retrieved successfully", body = Vec<DriverApiToken>),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_api_tokens(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<Vec<DriverApiToken>>, ApiError> {
+    user_identity.require_session()?;
+
+    let api_tokens = state.service.get_api_tokens(user_identity.user_id).await?;
+
+    tracing::Span::current().record("result.count", api_tokens.len());
+
+    Ok(Response::ok(
+        api_tokens.iter().map(|t| t.to_driver_api_token()).collect(),
+    ))
+}
+
+#[tracing::instrument(
+    name = "create_api_token",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        label = %request.label,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/me/tokens",
+    tag = "driver/tokens",
+    description = "Create a personal API token. The token value is only returned in this response.",
+    request_body = CreateDriverApiTokenRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "API token created successfully", body = CreateDriverApiTokenResponse),
+        (status = 400, description = "Validation error", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn create_api_token(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    ValidatedJson(request): ValidatedJson<CreateDriverApiTokenRequest>,
+) -> Result<Response<CreateDriverApiTokenResponse>, ApiError> {
+    user_identity.require_session()?;
+
+    let (token, api_token) = state
+        .service
+        .create_api_token(user_identity.user_id, request)
+        .await?;
+
+    Ok(Response::created(CreateDriverApiTokenResponse {
+        token,
+        api_token: api_token.to_driver_api_token(),
+    }))
+}
+
+#[tracing::instrument(
+    name = "delete_api_token",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        api_token_id = %api_token_id,
+    )
+)]
+#[utoipa::path(
+    delete,
+    path = "/me/tokens/{api_token_id}",
+    tag = "driver/tokens",
+    description = "Revoke a personal API token.",
+    params(
+        ("api_token_id" = Uuid, Path, description = "The ID of the API token to revoke")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "API token revoked successfully"),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 404, description = "API token not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn delete_api_token(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Path(api_token_id): Path<Uuid>,
+) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
+    state
+        .service
+        .delete_api_token(user_identity.user_id, api_token_id)
+        .await?;
+
+    Ok(Response::ok(()))
+}
+
 #[tracing::instrument(name = "get_current_limitation", skip_all)]
 #[utoipa::path(
     get,
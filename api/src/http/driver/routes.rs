@@ -3,10 +3,13 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use crate::{
     AppState,
     http::driver::handlers::{
-        __path_deactivate_driver, __path_delete_rest_periods, __path_get_all_rest_periods,
-        __path_get_current_limitation, __path_get_driver_info, __path_reactivate_driver,
-        __path_set_rest_periods, __path_update_driver_info, deactivate_driver, delete_rest_periods,
-        get_all_rest_periods, get_current_limitation, get_driver_info, reactivate_driver,
+        __path_confirm_phone_verification, __path_create_api_token, __path_deactivate_driver,
+        __path_delete_api_token, __path_delete_rest_periods, __path_get_all_rest_periods,
+        __path_get_api_tokens, __path_get_current_limitation, __path_get_driver_info,
+        __path_reactivate_driver, __path_request_phone_verification, __path_set_rest_periods,
+        __path_update_driver_info, confirm_phone_verification, create_api_token, deactivate_driver,
+        delete_api_token, delete_rest_periods, get_all_rest_periods, get_api_tokens,
+        get_current_limitation, get_driver_info, reactivate_driver, request_phone_verification,
         set_rest_periods, update_driver_info,
     },
 };
@@ -18,8 +21,13 @@ pub fn driver_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(delete_rest_periods))
         .routes(routes!(get_driver_info))
         .routes(routes!(update_driver_info))
+        .routes(routes!(request_phone_verification))
+        .routes(routes!(confirm_phone_verification))
         .routes(routes!(deactivate_driver))
         .routes(routes!(reactivate_driver))
+        .routes(routes!(get_api_tokens))
+        .routes(routes!(create_api_token))
+        .routes(routes!(delete_api_token))
 }
 
 pub fn public_driver_routes() -> OpenApiRouter<AppState> {
@@ -13,6 +13,8 @@ pub struct HealthResponse {
     pub database_status: String,
     pub cache_status: String,
     pub storage_status: String,
+    /// State of the circuit breaker in front of the PDF service: closed, open or half_open
+    pub document_service_status: String,
     pub timestamp: String,
 }
 
@@ -44,6 +46,7 @@ pub async fn health_check(
         database_status: database_status.to_string(),
         cache_status: cache_status.to_string(),
         storage_status: storage_status.to_string(),
+        document_service_status: health_check.document_service.as_str().to_string(),
         timestamp: Utc::now().to_rfc3339(),
     };
 
@@ -1,23 +1,28 @@
 use axum::{
     Extension,
-    body::Body,
     extract::{Path, State},
-    http::{HeaderValue, StatusCode, header},
+    http::HeaderMap,
     response::IntoResponse,
 };
-use plannify_driver_api_core::domain::mail::{
-    entities::{
-        DriverMail, DriverMailPreference, DriverMailType, GetMailsParams,
-        UpdateMailPreferenceRequest,
+use plannify_driver_api_core::domain::{
+    driver::{entities::ApiTokenScope, port::DriverService},
+    mail::{
+        entities::{
+            DriverMail, DriverMailPreference, DriverMailType, GetMailsParams, MailEventsParams,
+            UnsubscribeParams, UpdateMailPreferenceRequest,
+        },
+        port::MailService,
     },
-    port::MailService,
 };
+use plannify_driver_api_core::infrastructure::mail::repositories::error::MailError;
 use uuid::Uuid;
 
 use crate::{
     ApiError, AppState,
     http::common::{
         api_error::ErrorBody,
+        conditional::{TaggedResponse, entity_tag},
+        download::{document_response, requested_range},
         middleware::auth::entities::UserIdentity,
         response::{PaginatedResponse, Response},
         validator::{ValidatedJson, ValidatedQuery},
@@ -43,7 +48,9 @@ use crate::{
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "Mails retrieved successfully", body = PaginatedResponse<DriverMail>),
+        (status = 304, description = "Mails unchanged since the `If-None-Match` entity tag"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -51,7 +58,19 @@ pub async fn get_mails(
     ValidatedQuery(query): ValidatedQuery<GetMailsParams>,
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-) -> Result<Response<PaginatedResponse<DriverMail>>, ApiError> {
+    headers: HeaderMap,
+) -> Result<TaggedResponse<PaginatedResponse<DriverMail>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::MailsRead)?;
+
+    let data_version = state
+        .service
+        .get_driver_data_version(user_identity.user_id)
+        .await?;
+    let e_tag = entity_tag(&("mails", data_version, query.page, query.limit));
+    if let Some(unchanged) = TaggedResponse::unchanged(&headers, &e_tag) {
+        return Ok(unchanged);
+    }
+
     let (mails, total) = state
         .service
         .get_mails(user_identity.user_id, query.page, query.limit)
@@ -59,11 +78,14 @@ pub async fn get_mails(
 
     tracing::Span::current().record("count", mails.len());
 
-    Ok(Response::ok(PaginatedResponse {
-        data: mails,
-        total,
-        page: query.page,
-    }))
+    Ok(TaggedResponse::ok(
+        PaginatedResponse {
+            data: mails,
+            total,
+            page: query.page,
+        },
+        e_tag,
+    ))
 }
 
 #[tracing::instrument(
@@ -83,6 +105,7 @@ pub async fn get_mails(
     responses(
         (status = 200, description = "Mail types retrieved successfully", body = Vec<DriverMailType>),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -90,6 +113,8 @@ pub async fn get_mail_types(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<Vec<DriverMailType>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::MailsRead)?;
+
     let types = state.service.get_mail_types().await?;
 
     tracing::Span::current().record("count", types.len());
@@ -114,6 +139,7 @@ pub async fn get_mail_types(
     responses(
         (status = 200, description = "Mail preferences retrieved successfully", body = Vec<DriverMailPreference>),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -121,6 +147,8 @@ pub async fn get_mail_preferences(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<Vec<DriverMailPreference>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::MailsRead)?;
+
     let preferences = state
         .service
         .get_mail_preferences(user_identity.user_id)
@@ -137,6 +165,7 @@ pub async fn get_mail_preferences(
     fields(
         user_id = %user_identity.user_id,
         mail_type_id = %mail_type_id,
+        channel = ?request.channel,
         is_enabled = %request.is_enabled,
     )
 )]
@@ -144,7 +173,7 @@ pub async fn get_mail_preferences(
     put,
     path = "/mails/preferences/{mail_type_id}",
     tag = "mails/preferences",
-    description = "Update a driver mail preference",
+    description = "Update a driver mail preference, on the email channel unless `channel` is PUSH",
     params(
         ("mail_type_id" = i32, Path, description = "The mail type ID to update the preference for")
     ),
@@ -154,6 +183,7 @@ pub async fn get_mail_preferences(
         (status = 200, description = "Mail preference updated successfully", body = DriverMailPreference),
         (status = 400, description = "Mail preference is not editable", body = ErrorBody),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
         (status = 404, description = "Mail type not found", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
@@ -163,15 +193,87 @@ pub async fn update_mail_preference(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     ValidatedJson(request): ValidatedJson<UpdateMailPreferenceRequest>,
+) -> Result<Response<DriverMailPreference>, ApiError> {
+    user_identity.require_session()?;
+
+    let preference = state
+        .service
+        .update_mail_preference(
+            user_identity.user_id,
+            mail_type_id,
+            request.channel,
+            request.is_enabled,
+        )
+        .await?;
+
+    Ok(Response::ok(preference))
+}
+
+#[tracing::instrument(
+    name = "unsubscribe",
+    skip_all,
+    fields(
+        driver_id = %query.driver_id,
+        mail_type_id = %query.mail_type_id,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/mails/unsubscribe",
+    tag = "mails/preferences",
+    description = "One-click unsubscribe (RFC 8058) from a mail type, using the signed link of the List-Unsubscribe header",
+    params(UnsubscribeParams),
+    security(),
+    responses(
+        (status = 200, description = "Mail preference disabled successfully", body = DriverMailPreference),
+        (status = 400, description = "Invalid signature or mail preference is not editable", body = ErrorBody),
+        (status = 404, description = "Mail type not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn unsubscribe(
+    ValidatedQuery(query): ValidatedQuery<UnsubscribeParams>,
+    State(state): State<AppState>,
 ) -> Result<Response<DriverMailPreference>, ApiError> {
     let preference = state
         .service
-        .update_mail_preference(user_identity.user_id, mail_type_id, request.is_enabled)
+        .unsubscribe(query.driver_id, query.mail_type_id, query.signature)
         .await?;
 
     Ok(Response::ok(preference))
 }
 
+#[tracing::instrument(name = "process_mail_events", skip_all, fields(count = tracing::field::Empty))]
+#[utoipa::path(
+    post,
+    path = "/mails/events",
+    tag = "mails",
+    description = "Webhook of the email provider reporting bounces and complaints, in a generic format (`{\"email\", \"type\": \"hard_bounce\" | \"soft_bounce\" | \"complaint\"}`) or in the Amazon SES, SendGrid, Mailgun or Postmark format",
+    params(MailEventsParams),
+    request_body(content = String, content_type = "application/json"),
+    security(),
+    responses(
+        (status = 200, description = "Mail events recorded successfully"),
+        (status = 400, description = "Unknown mail events format", body = ErrorBody),
+        (status = 401, description = "Invalid webhook token", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn process_mail_events(
+    ValidatedQuery(query): ValidatedQuery<MailEventsParams>,
+    State(state): State<AppState>,
+    body: String,
+) -> Result<Response<()>, ApiError> {
+    // Amazon SNS posts its JSON as text/plain, so the body is parsed whatever its content type
+    let body = serde_json::from_str(&body).map_err(|_| MailError::InvalidMailEvents)?;
+
+    let count = state.service.process_mail_events(query.token, body).await?;
+
+    tracing::Span::current().record("count", count);
+
+    Ok(Response::ok(()))
+}
+
 #[tracing::instrument(
     name = "get_mail",
     skip_all,
@@ -192,6 +294,7 @@ pub async fn update_mail_preference(
     responses(
         (status = 200, description = "Mail retrieved successfully", body = DriverMail),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 404, description = "Mail not found", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
@@ -201,6 +304,8 @@ pub async fn get_mail(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<DriverMail>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::MailsRead)?;
+
     let mail = state
         .service
         .get_mail(user_identity.user_id, mail_id)
@@ -209,6 +314,90 @@ pub async fn get_mail(
     Ok(Response::ok(mail))
 }
 
+#[tracing::instrument(
+    name = "resend_mail",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        mail_id = %mail_id,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/mails/{mail_id}/resend",
+    tag = "mails",
+    description = "Send a mail of the history again, with fresh links for account verification and password reset mails. Available for account verification, password reset, magic link and monthly report mails, once a minute",
+    params(
+        ("mail_id" = Uuid, Path, description = "The mail ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Mail resent successfully", body = DriverMail),
+        (status = 400, description = "Email address is unreachable", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 404, description = "Mail not found", body = ErrorBody),
+        (status = 409, description = "Mail cannot be resent", body = ErrorBody),
+        (status = 429, description = "A mail was resent too recently", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn resend_mail(
+    Path(mail_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<DriverMail>, ApiError> {
+    user_identity.require_session()?;
+
+    let mail = state
+        .service
+        .resend_mail(user_identity.user_id, mail_id)
+        .await?;
+
+    Ok(Response::created(mail))
+}
+
+#[tracing::instrument(
+    name = "cancel_mail",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        mail_id = %mail_id,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/mails/{mail_id}/cancel",
+    tag = "mails",
+    description = "Cancel an account verification, password reset or magic link mail the driver did not ask for, so that its link stops working. Only mails whose link has not expired yet can be cancelled",
+    params(
+        ("mail_id" = Uuid, Path, description = "The mail ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Mail cancelled successfully", body = DriverMail),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 404, description = "Mail not found", body = ErrorBody),
+        (status = 409, description = "Mail cannot be cancelled", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn cancel_mail(
+    Path(mail_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<DriverMail>, ApiError> {
+    user_identity.require_session()?;
+
+    let mail = state
+        .service
+        .cancel_mail(user_identity.user_id, mail_id)
+        .await?;
+
+    Ok(Response::ok(mail))
+}
+
 #[tracing::instrument(
     name = "get_mail_attachment",
     skip_all,
@@ -228,8 +417,12 @@ pub async fn get_mail(
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "File downloaded successfully", body = [u8]),
+        (status = 206, description = "Requested range of the file", body = [u8]),
+        (status = 302, description = "Redirect to a short-lived download URL of the file"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 404, description = "Attachment not found", body = ErrorBody),
+        (status = 416, description = "Range outside the file", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -237,22 +430,22 @@ pub async fn get_mail_attachment(
     Path(attachment_id): Path<Uuid>,
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, ApiError> {
-    let (bytes, file_name) = state
+    user_identity.require_scope(ApiTokenScope::MailsRead)?;
+
+    let (attachment, file_name) = state
         .service
-        .download_mail_attachment(user_identity.user_id, attachment_id)
+        .download_mail_attachment(
+            user_identity.user_id,
+            attachment_id,
+            requested_range(&headers),
+        )
         .await?;
 
-    let mut headers = axum::http::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/octet-stream"),
-    );
-    headers.insert(
-        header::CONTENT_DISPOSITION,
-        HeaderValue::try_from(format!("attachment; filename=\"{}\"", file_name))
-            .unwrap_or(HeaderValue::from_static("attachment")),
-    );
-
-    Ok((StatusCode::OK, (headers, Body::from(bytes.to_vec()))))
+    Ok(document_response(
+        attachment,
+        "application/octet-stream",
+        &file_name,
+    ))
 }
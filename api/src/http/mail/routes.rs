@@ -3,10 +3,12 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use crate::http::{
     common::app_state::AppState,
     mail::handlers::{
-        __path_get_mail, __path_get_mail_attachment, __path_get_mail_preferences,
-        __path_get_mail_types, __path_get_mails, __path_update_mail_preference, get_mail,
-        get_mail_attachment, get_mail_preferences, get_mail_types, get_mails,
-        update_mail_preference,
+        __path_cancel_mail, __path_get_mail, __path_get_mail_attachment,
+        __path_get_mail_preferences, __path_get_mail_types, __path_get_mails,
+        __path_process_mail_events, __path_resend_mail, __path_unsubscribe,
+        __path_update_mail_preference, cancel_mail, get_mail, get_mail_attachment,
+        get_mail_preferences, get_mail_types, get_mails, process_mail_events, resend_mail,
+        unsubscribe, update_mail_preference,
     },
 };
 
@@ -17,5 +19,13 @@ pub fn mail_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(get_mail_preferences))
         .routes(routes!(update_mail_preference))
         .routes(routes!(get_mail))
+        .routes(routes!(resend_mail))
+        .routes(routes!(cancel_mail))
         .routes(routes!(get_mail_attachment))
 }
+
+pub fn public_mail_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(unsubscribe))
+        .routes(routes!(process_mail_events))
+}
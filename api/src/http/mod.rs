@@ -5,5 +5,7 @@ pub mod driver;
 pub mod formating;
 pub mod health;
 pub mod mail;
+pub mod notification;
+pub mod storage;
 pub mod update;
 pub mod workday;
@@ -0,0 +1,134 @@
+use axum::{
+    Extension,
+    extract::{Path, State},
+};
+use plannify_driver_api_core::domain::notification::{
+    entities::{CreatePushSubscriptionRequest, PushSubscription},
+    port::NotificationService,
+};
+use uuid::Uuid;
+
+use crate::{
+    ApiError, AppState,
+    http::common::{
+        api_error::ErrorBody, middleware::auth::entities::UserIdentity, response::Response,
+        validator::ValidatedJson,
+    },
+};
+
+#[tracing::instrument(
+    name = "get_push_subscriptions",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        result.count = tracing::field::Empty,
+    )
+)]
+#[utoipa::path(
+    get,
+    path = "/me/push-subscriptions",
+    tag = "notification/push-subscriptions",
+    description = "List the browsers receiving the push notifications of the authenticated driver.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Push subscriptions retrieved successfully", body = Vec<PushSubscription>),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_push_subscriptions(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<Vec<PushSubscription>>, ApiError> {
+    user_identity.require_session()?;
+
+    let push_subscriptions = state
+        .service
+        .get_push_subscriptions(user_identity.user_id)
+        .await?;
+
+    tracing::Span::current().record("result.count", push_subscriptions.len());
+
+    Ok(Response::ok(
+        push_subscriptions
+            .iter()
+            .map(|s| s.to_push_subscription())
+            .collect(),
+    ))
+}
+
+#[tracing::instrument(
+    name = "create_push_subscription",
+    skip_all,
+    fields(user_id = %user_identity.user_id)
+)]
+#[utoipa::path(
+    post,
+    path = "/me/push-subscriptions",
+    tag = "notification/push-subscriptions",
+    description = "Register the push subscription of a browser, as returned by `PushSubscription.toJSON()`. Registering an endpoint again replaces its keys.",
+    request_body = CreatePushSubscriptionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Push subscription registered successfully", body = PushSubscription),
+        (status = 400, description = "Validation error or invalid subscription keys", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn create_push_subscription(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    ValidatedJson(request): ValidatedJson<CreatePushSubscriptionRequest>,
+) -> Result<Response<PushSubscription>, ApiError> {
+    user_identity.require_session()?;
+
+    let push_subscription = state
+        .service
+        .create_push_subscription(user_identity.user_id, request)
+        .await?;
+
+    Ok(Response::created(push_subscription.to_push_subscription()))
+}
+
+#[tracing::instrument(
+    name = "delete_push_subscription",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        push_subscription_id = %push_subscription_id,
+    )
+)]
+#[utoipa::path(
+    delete,
+    path = "/me/push-subscriptions/{push_subscription_id}",
+    tag = "notification/push-subscriptions",
+    description = "Stop sending push notifications to a browser.",
+    params(
+        ("push_subscription_id" = Uuid, Path, description = "The ID of the push subscription to delete")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Push subscription deleted successfully"),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens are not allowed", body = ErrorBody),
+        (status = 404, description = "Push subscription not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn delete_push_subscription(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Path(push_subscription_id): Path<Uuid>,
+) -> Result<Response<()>, ApiError> {
+    user_identity.require_session()?;
+
+    state
+        .service
+        .delete_push_subscription(user_identity.user_id, push_subscription_id)
+        .await?;
+
+    Ok(Response::ok(()))
+}
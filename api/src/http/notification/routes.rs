@@ -0,0 +1,17 @@
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::http::{
+    common::app_state::AppState,
+    notification::handlers::{
+        __path_create_push_subscription, __path_delete_push_subscription,
+        __path_get_push_subscriptions, create_push_subscription, delete_push_subscription,
+        get_push_subscriptions,
+    },
+};
+
+pub fn notification_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_push_subscriptions))
+        .routes(routes!(create_push_subscription))
+        .routes(routes!(delete_push_subscription))
+}
@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::IntoResponse,
+};
+use plannify_driver_api_core::domain::storage::{
+    entities::{DocumentDownload, PresignedObjectParams},
+    port::StorageService,
+};
+
+use crate::{
+    ApiError, AppState,
+    http::common::{
+        api_error::ErrorBody,
+        download::{document_response, requested_range},
+        validator::ValidatedQuery,
+    },
+};
+
+#[tracing::instrument(name = "get_presigned_object", skip_all, fields(key = %key))]
+#[utoipa::path(
+    get,
+    path = "/storage/{*key}",
+    tag = "storage",
+    description = "Download an object of the local storage backend through a presigned URL",
+    params(
+        ("key" = String, Path, description = "The object key"),
+        PresignedObjectParams
+    ),
+    security(),
+    responses(
+        (status = 200, description = "Object content", body = [u8]),
+        (status = 206, description = "Requested range of the object", body = [u8]),
+        (status = 403, description = "Invalid or expired presigned URL", body = ErrorBody),
+        (status = 404, description = "Object not found", body = ErrorBody),
+        (status = 416, description = "Range outside the object", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_presigned_object(
+    Path(key): Path<String>,
+    ValidatedQuery(query): ValidatedQuery<PresignedObjectParams>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let object = state
+        .service
+        .get_presigned_object(
+            &key,
+            query.expires,
+            &query.signature,
+            requested_range(&headers),
+        )
+        .await?;
+
+    let file_name = key.rsplit('/').next().unwrap_or(&key);
+    let content_type = if file_name.ends_with(".pdf") {
+        "application/pdf"
+    } else {
+        "application/octet-stream"
+    };
+
+    Ok(document_response(
+        DocumentDownload::Stream(object),
+        content_type,
+        file_name,
+    ))
+}
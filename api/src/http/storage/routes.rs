@@ -0,0 +1,10 @@
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    AppState,
+    http::storage::handlers::{__path_get_presigned_object, get_presigned_object},
+};
+
+pub fn public_storage_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new().routes(routes!(get_presigned_object))
+}
@@ -2,6 +2,8 @@ use crate::{
     ApiError, AppState,
     http::common::{
         api_error::ErrorBody,
+        conditional::{TaggedResponse, check_if_match, entity_tag},
+        download::{document_response, requested_range},
         middleware::auth::entities::UserIdentity,
         response::{PaginatedResponse, Response},
         validator::{ValidatedJson, ValidatedQuery},
@@ -9,19 +11,32 @@ use crate::{
 };
 use axum::{
     Extension,
-    body::Body,
     extract::{Path, State},
-    http::{HeaderValue, StatusCode, header},
+    http::{
+        HeaderMap, HeaderValue,
+        header::{AUTHORIZATION, IF_MATCH, USER_AGENT, WWW_AUTHENTICATE},
+    },
     response::IntoResponse,
 };
+use base64::{Engine, engine::general_purpose::STANDARD};
 use chrono::NaiveDate;
-use plannify_driver_api_core::domain::workday::{
-    entities::{
-        CreateWorkdayRequest, GetWorkdayDocumentsByYearResponse, GetWorkdaysByMonthParams,
-        GetWorkdaysByPeriodParams, UpdateWorkdayRequest, Workday, WorkdayGarbage,
+use plannify_driver_api_core::{
+    domain::{
+        driver::{entities::ApiTokenScope, port::DriverService},
+        mail::entities::monthly_report_file_name,
+        workday::{
+            entities::{
+                CreateDocumentShareRequest, CreateDocumentShareResponse, CreateWorkdayRequest,
+                DocumentShare, DocumentShareAccess, GetWorkdayDocumentsByYearResponse,
+                GetWorkdaysByMonthParams, GetWorkdaysByPeriodParams, UpdateWorkdayRequest, Workday,
+                WorkdayDocumentVerification, WorkdayGarbage, yearly_report_file_name,
+            },
+            port::WorkdayService,
+        },
     },
-    port::WorkdayService,
+    infrastructure::workday::repositories::error::WorkdayError,
 };
+use uuid::Uuid;
 
 #[tracing::instrument(
     name = "get_workday_by_date",
@@ -44,7 +59,9 @@ use plannify_driver_api_core::domain::workday::{
     ),
     responses(
         (status = 200, description = "Workday retrieved successfully", body = Workday),
+        (status = 304, description = "Workday unchanged since the `If-None-Match` entity tag"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 404, description = "Workday not found", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
@@ -53,13 +70,22 @@ pub async fn get_workday_by_date(
     Path(date): Path<NaiveDate>,
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-) -> Result<Response<Workday>, ApiError> {
+    headers: HeaderMap,
+) -> Result<TaggedResponse<Workday>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysRead)?;
+
     let workday = state
         .service
         .get_workday_by_date(user_identity.user_id, date)
         .await?;
 
-    Ok(Response::ok(workday))
+    // Tagged from the workday alone, so that `If-Match` on `PUT /workdays` ignores the others
+    let e_tag = entity_tag(&workday);
+    if let Some(unchanged) = TaggedResponse::unchanged(&headers, &e_tag) {
+        return Ok(unchanged);
+    }
+
+    Ok(TaggedResponse::ok(workday, e_tag))
 }
 
 #[tracing::instrument(
@@ -83,7 +109,9 @@ pub async fn get_workday_by_date(
     ),
     responses(
         (status = 200, description = "Monthly workdays retrieved successfully", body = Vec<Workday>),
+        (status = 304, description = "Monthly workdays unchanged since the `If-None-Match` entity tag"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -91,7 +119,19 @@ pub async fn get_all_workdays_month(
     ValidatedQuery(query): ValidatedQuery<GetWorkdaysByMonthParams>,
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-) -> Result<Response<Vec<Workday>>, ApiError> {
+    headers: HeaderMap,
+) -> Result<TaggedResponse<Vec<Workday>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysRead)?;
+
+    let data_version = state
+        .service
+        .get_driver_data_version(user_identity.user_id)
+        .await?;
+    let e_tag = entity_tag(&("workdays/month", data_version, query.month, query.year));
+    if let Some(unchanged) = TaggedResponse::unchanged(&headers, &e_tag) {
+        return Ok(unchanged);
+    }
+
     let workdays = state
         .service
         .get_workdays_by_month(user_identity.user_id, query.month, query.year)
@@ -99,7 +139,7 @@ pub async fn get_all_workdays_month(
 
     tracing::Span::current().record("count", workdays.len());
 
-    Ok(Response::ok(workdays))
+    Ok(TaggedResponse::ok(workdays, e_tag))
 }
 
 #[tracing::instrument(
@@ -126,6 +166,7 @@ pub async fn get_all_workdays_month(
     responses(
         (status = 200, description = "Period workdays retrieved successfully", body = PaginatedResponse<Workday>),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -134,6 +175,8 @@ pub async fn get_all_workdays_period(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<PaginatedResponse<Workday>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysRead)?;
+
     let (workdays, total_count) = state
         .service
         .get_workdays_by_period(
@@ -186,6 +229,8 @@ pub async fn create_workday(
     Extension(user_identity): Extension<UserIdentity>,
     ValidatedJson(request): ValidatedJson<CreateWorkdayRequest>,
 ) -> Result<Response<Workday>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysWrite)?;
+
     let workday = state
         .service
         .create_workday(user_identity.user_id, request)
@@ -216,20 +261,38 @@ pub async fn create_workday(
         (status = 401, description = "Unauthorized", body = ErrorBody),
         (status = 403, description = "A document has already been generated for this month", body = ErrorBody),
         (status = 404, description = "Workday not found", body = ErrorBody),
+        (status = 409, description = "Workday changed since `version`, the current workday is returned in `content`", body = ErrorBody),
+        (status = 412, description = "Workday changed since the `If-Match` entity tag", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
 pub async fn update_workday(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-    ValidatedJson(request): ValidatedJson<UpdateWorkdayRequest>,
-) -> Result<Response<Workday>, ApiError> {
+    headers: HeaderMap,
+    ValidatedJson(mut request): ValidatedJson<UpdateWorkdayRequest>,
+) -> Result<TaggedResponse<Workday>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysWrite)?;
+
+    if headers.contains_key(IF_MATCH) {
+        let current = state
+            .service
+            .get_workday_by_date(user_identity.user_id, request.date)
+            .await?;
+        check_if_match(&headers, &entity_tag(&current))?;
+        // Keeps the check valid until the update is saved
+        request.version.get_or_insert(current.version);
+    }
+
     let workday = state
         .service
         .update_workday(user_identity.user_id, request)
         .await?;
 
-    Ok(Response::ok(workday.to_workday()))
+    let workday = workday.to_workday();
+    let e_tag = entity_tag(&workday);
+
+    Ok(TaggedResponse::ok(workday, e_tag))
 }
 
 #[tracing::instrument(
@@ -264,6 +327,8 @@ pub async fn delete_workday(
     Extension(user_identity): Extension<UserIdentity>,
     Path(date): Path<NaiveDate>,
 ) -> Result<Response<()>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysWrite)?;
+
     state
         .service
         .create_workday_garbage(user_identity.user_id, date)
@@ -290,6 +355,7 @@ pub async fn delete_workday(
     responses(
         (status = 200, description = "Workdays garbage retrieved successfully", body = Vec<WorkdayGarbage>),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -297,6 +363,8 @@ pub async fn get_all_workday_garbage(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
 ) -> Result<Response<Vec<WorkdayGarbage>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysRead)?;
+
     let workdays = state
         .service
         .get_workdays_garbage(user_identity.user_id)
@@ -341,6 +409,8 @@ pub async fn delete_workday_garbage(
     Extension(user_identity): Extension<UserIdentity>,
     Path(date): Path<NaiveDate>,
 ) -> Result<Response<()>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::WorkdaysWrite)?;
+
     state
         .service
         .delete_workday_garbage(user_identity.user_id, date)
@@ -366,14 +436,28 @@ pub async fn delete_workday_garbage(
     ),
     responses(
         (status = 200, description = "Workday document years retrieved successfully", body = Vec<i32>),
+        (status = 304, description = "Years unchanged since the `If-None-Match` entity tag"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
 pub async fn get_workday_documents(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-) -> Result<Response<Vec<i32>>, ApiError> {
+    headers: HeaderMap,
+) -> Result<TaggedResponse<Vec<i32>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::DocumentsRead)?;
+
+    let data_version = state
+        .service
+        .get_driver_data_version(user_identity.user_id)
+        .await?;
+    let e_tag = entity_tag(&("workdays/documents/year", data_version));
+    if let Some(unchanged) = TaggedResponse::unchanged(&headers, &e_tag) {
+        return Ok(unchanged);
+    }
+
     let documents = state
         .service
         .get_workday_documents(user_identity.user_id)
@@ -381,7 +465,7 @@ pub async fn get_workday_documents(
 
     tracing::Span::current().record("count", documents.len());
 
-    Ok(Response::ok(documents))
+    Ok(TaggedResponse::ok(documents, e_tag))
 }
 
 #[tracing::instrument(
@@ -405,7 +489,9 @@ pub async fn get_workday_documents(
     ),
     responses(
         (status = 200, description = "Workday document months retrieved successfully", body = GetWorkdayDocumentsByYearResponse),
+        (status = 304, description = "Months unchanged since the `If-None-Match` entity tag"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -413,7 +499,19 @@ pub async fn get_workday_documents_by_year(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     Path(year): Path<i32>,
-) -> Result<Response<GetWorkdayDocumentsByYearResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<TaggedResponse<GetWorkdayDocumentsByYearResponse>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::DocumentsRead)?;
+
+    let data_version = state
+        .service
+        .get_driver_data_version(user_identity.user_id)
+        .await?;
+    let e_tag = entity_tag(&("workdays/documents", data_version, year));
+    if let Some(unchanged) = TaggedResponse::unchanged(&headers, &e_tag) {
+        return Ok(unchanged);
+    }
+
     let documents = state
         .service
         .get_workday_documents_by_year(user_identity.user_id, year)
@@ -421,7 +519,7 @@ pub async fn get_workday_documents_by_year(
 
     tracing::Span::current().record("count", documents.len());
 
-    Ok(Response::ok(documents))
+    Ok(TaggedResponse::ok(documents, e_tag))
 }
 
 #[tracing::instrument(
@@ -447,8 +545,12 @@ pub async fn get_workday_documents_by_year(
     ),
     responses(
         (status = 200, description = "PDF file", body = [u8]),
+        (status = 206, description = "Requested range of the PDF file", body = [u8]),
+        (status = 302, description = "Redirect to a short-lived download URL of the PDF file"),
         (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
         (status = 404, description = "No document for this month", body = ErrorBody),
+        (status = 416, description = "Range outside the PDF file", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     )
 )]
@@ -456,7 +558,10 @@ pub async fn get_workday_document_by_month(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     Path((year, month)): Path<(i32, i32)>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, ApiError> {
+    user_identity.require_scope(ApiTokenScope::DocumentsRead)?;
+
     if !(1..=12).contains(&month) {
         return Err(ApiError::BadRequest {
             error_code: "INVALID_MONTH".to_string(),
@@ -464,26 +569,358 @@ pub async fn get_workday_document_by_month(
         });
     }
 
-    let pdf = state
+    let document = state
+        .service
+        .download_workday_document_by_month(
+            user_identity.user_id,
+            month,
+            year,
+            requested_range(&headers),
+        )
+        .await?;
+
+    let document = document.ok_or_else(|| ApiError::NotFound {
+        error_code: "DOCUMENT_NOT_FOUND".to_string(),
+    })?;
+
+    Ok(document_response(
+        document,
+        "application/pdf",
+        &monthly_report_file_name(month as u32, year),
+    ))
+}
+
+#[tracing::instrument(
+    name = "get_yearly_workday_document",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        year = %year,
+    )
+)]
+#[utoipa::path(
+    get,
+    path = "/workdays/documents/{year}/annual",
+    tag = "workdays/documents",
+    description = "Download the yearly recap of the workdays as PDF: months worked, time worked, overnight rests and absences",
+    params(
+        ("year" = i32, Path, description = "Year")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "PDF file", body = [u8]),
+        (status = 206, description = "Requested range of the PDF file", body = [u8]),
+        (status = 302, description = "Redirect to a short-lived download URL of the PDF file"),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
+        (status = 404, description = "No workday this year", body = ErrorBody),
+        (status = 416, description = "Range outside the PDF file", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_yearly_workday_document(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Path(year): Path<i32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    user_identity.require_scope(ApiTokenScope::DocumentsRead)?;
+
+    let document = state
         .service
-        .get_workday_document_by_month(user_identity.user_id, month, year)
+        .download_yearly_workday_document(user_identity.user_id, year, requested_range(&headers))
         .await?;
 
-    let pdf = pdf.ok_or_else(|| ApiError::NotFound {
+    let document = document.ok_or_else(|| ApiError::NotFound {
         error_code: "DOCUMENT_NOT_FOUND".to_string(),
     })?;
 
-    let filename = format!("workdays-{}-{:02}.pdf", year, month);
-    let mut headers = axum::http::HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/pdf"),
-    );
-    headers.insert(
-        header::CONTENT_DISPOSITION,
-        HeaderValue::try_from(format!("attachment; filename=\"{}\"", filename))
-            .unwrap_or(HeaderValue::from_static("attachment")),
-    );
-
-    Ok((StatusCode::OK, (headers, Body::from(pdf.to_vec()))))
+    Ok(document_response(
+        document,
+        "application/pdf",
+        &yearly_report_file_name(year),
+    ))
+}
+
+#[tracing::instrument(name = "verify_workday_document", skip_all)]
+#[utoipa::path(
+    get,
+    path = "/documents/verify/{code}",
+    tag = "workdays/documents",
+    description = "Check a monthly workday report against the verification code printed on it: the driver name, month and SHA-256 digest of the PDF file as generated, and when a regenerated document superseded it",
+    params(
+        ("code" = String, Path, description = "Verification code printed on the document")
+    ),
+    security(),
+    responses(
+        (status = 200, description = "Document generated with this code", body = WorkdayDocumentVerification),
+        (status = 404, description = "No document generated with this code", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn verify_workday_document(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Response<WorkdayDocumentVerification>, ApiError> {
+    let verification = state.service.verify_workday_document(&code).await?;
+
+    Ok(Response::ok(verification))
+}
+
+#[tracing::instrument(
+    name = "create_document_share",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        year = %year,
+        month = %month,
+    )
+)]
+#[utoipa::path(
+    post,
+    path = "/workdays/documents/{year}/{month}/share",
+    tag = "workdays/documents",
+    description = "Share the stored monthly workday report through a link expiring after the given number of days, optionally protected by a password and mailed to the recipient",
+    params(
+        ("year" = i32, Path, description = "Year"),
+        ("month" = i32, Path, description = "Month (1-12)")
+    ),
+    request_body = CreateDocumentShareRequest,
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 201, description = "Share link created", body = CreateDocumentShareResponse),
+        (status = 400, description = "Invalid request or no recipient to mail the link to", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens cannot share documents", body = ErrorBody),
+        (status = 404, description = "No stored document for this month", body = ErrorBody),
+        (status = 429, description = "Too many shares or share mails in the last day", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn create_document_share(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Path((year, month)): Path<(i32, i32)>,
+    ValidatedJson(request): ValidatedJson<CreateDocumentShareRequest>,
+) -> Result<Response<CreateDocumentShareResponse>, ApiError> {
+    user_identity.require_session()?;
+
+    if !(1..=12).contains(&month) {
+        return Err(ApiError::BadRequest {
+            error_code: "INVALID_MONTH".to_string(),
+            content: None,
+        });
+    }
+
+    let (url, share) = state
+        .service
+        .create_document_share(user_identity.user_id, month, year, request)
+        .await?;
+
+    Ok(Response::created(CreateDocumentShareResponse {
+        url,
+        share: share.to_document_share(),
+    }))
+}
+
+#[tracing::instrument(
+    name = "get_document_shares",
+    skip_all,
+    fields(user_id = %user_identity.user_id)
+)]
+#[utoipa::path(
+    get,
+    path = "/workdays/documents/shares",
+    tag = "workdays/documents",
+    description = "List the share links of the monthly workday reports, the most recent first",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Share links", body = Vec<DocumentShare>),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_document_shares(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<Vec<DocumentShare>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::DocumentsRead)?;
+
+    let shares = state
+        .service
+        .get_document_shares(user_identity.user_id)
+        .await?;
+
+    Ok(Response::ok(
+        shares.iter().map(|s| s.to_document_share()).collect(),
+    ))
+}
+
+#[tracing::instrument(
+    name = "revoke_document_share",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        share_id = %share_id,
+    )
+)]
+#[utoipa::path(
+    delete,
+    path = "/workdays/documents/shares/{share_id}",
+    tag = "workdays/documents",
+    description = "Revoke a share link, which cannot be opened anymore. Revoking a revoked link changes nothing",
+    params(
+        ("share_id" = Uuid, Path, description = "The ID of the share link to revoke")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Share link revoked", body = DocumentShare),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "API tokens cannot revoke share links", body = ErrorBody),
+        (status = 404, description = "Share link not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn revoke_document_share(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Path(share_id): Path<Uuid>,
+) -> Result<Response<DocumentShare>, ApiError> {
+    user_identity.require_session()?;
+
+    let share = state
+        .service
+        .revoke_document_share(user_identity.user_id, share_id)
+        .await?;
+
+    Ok(Response::ok(share.to_document_share()))
+}
+
+#[tracing::instrument(
+    name = "get_document_share_accesses",
+    skip_all,
+    fields(
+        user_id = %user_identity.user_id,
+        share_id = %share_id,
+    )
+)]
+#[utoipa::path(
+    get,
+    path = "/workdays/documents/shares/{share_id}/accesses",
+    tag = "workdays/documents",
+    description = "List the openings of a share link, the refused ones included, the most recent first",
+    params(
+        ("share_id" = Uuid, Path, description = "The ID of the share link")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Access log of the share link", body = Vec<DocumentShareAccess>),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Missing API token scope", body = ErrorBody),
+        (status = 404, description = "Share link not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_document_share_accesses(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Path(share_id): Path<Uuid>,
+) -> Result<Response<Vec<DocumentShareAccess>>, ApiError> {
+    user_identity.require_scope(ApiTokenScope::DocumentsRead)?;
+
+    let accesses = state
+        .service
+        .get_document_share_accesses(user_identity.user_id, share_id)
+        .await?;
+
+    Ok(Response::ok(
+        accesses
+            .iter()
+            .map(|a| a.to_document_share_access())
+            .collect(),
+    ))
+}
+
+#[tracing::instrument(name = "get_shared_document", skip_all)]
+#[utoipa::path(
+    get,
+    path = "/shared/{token}",
+    tag = "workdays/documents",
+    description = "Download a monthly workday report shared by a driver. The password of a protected link is given through HTTP Basic authentication, the username being ignored",
+    params(
+        ("token" = String, Path, description = "Token of the share link")
+    ),
+    security(),
+    responses(
+        (status = 200, description = "PDF file", body = [u8]),
+        (status = 206, description = "Requested range of the PDF file", body = [u8]),
+        (status = 302, description = "Redirect to a short-lived download URL of the PDF file"),
+        (status = 401, description = "Missing or wrong password", body = ErrorBody),
+        (status = 403, description = "Share link expired or revoked", body = ErrorBody),
+        (status = 404, description = "Share link not found", body = ErrorBody),
+        (status = 416, description = "Range outside the PDF file", body = ErrorBody),
+        (status = 429, description = "Share link locked after too many wrong passwords", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    )
+)]
+pub async fn get_shared_document(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    match state
+        .service
+        .get_shared_document(
+            &token,
+            basic_auth_password(&headers),
+            user_agent,
+            requested_range(&headers),
+        )
+        .await
+    {
+        Ok((download, file_name)) => Ok(document_response(download, "application/pdf", &file_name)),
+        // Lets browsers prompt for the password
+        Err(
+            e @ (WorkdayError::DocumentSharePasswordRequired
+            | WorkdayError::DocumentSharePasswordInvalid),
+        ) => {
+            let mut response = ApiError::from(e).into_response();
+            response.headers_mut().insert(
+                WWW_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"Shared document\", charset=\"UTF-8\""),
+            );
+            Ok(response)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Password of an `Authorization: Basic` header, whatever the username.
+fn basic_auth_password(headers: &HeaderMap) -> Option<String> {
+    let credentials = headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let credentials = String::from_utf8(STANDARD.decode(credentials.trim()).ok()?).ok()?;
+
+    credentials
+        .split_once(':')
+        .map(|(_, password)| password.to_string())
 }
@@ -3,14 +3,19 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use crate::http::{
     common::app_state::AppState,
     workday::handlers::{
-        __path_create_workday, __path_delete_workday, __path_delete_workday_garbage,
-        __path_get_all_workday_garbage, __path_get_all_workdays_month,
-        __path_get_all_workdays_period, __path_get_workday_by_date,
-        __path_get_workday_document_by_month, __path_get_workday_documents,
-        __path_get_workday_documents_by_year, __path_update_workday, create_workday,
-        delete_workday, delete_workday_garbage, get_all_workday_garbage, get_all_workdays_month,
-        get_all_workdays_period, get_workday_by_date, get_workday_document_by_month,
-        get_workday_documents, get_workday_documents_by_year, update_workday,
+        __path_create_document_share, __path_create_workday, __path_delete_workday,
+        __path_delete_workday_garbage, __path_get_all_workday_garbage,
+        __path_get_all_workdays_month, __path_get_all_workdays_period,
+        __path_get_document_share_accesses, __path_get_document_shares, __path_get_shared_document,
+        __path_get_workday_by_date, __path_get_workday_document_by_month,
+        __path_get_workday_documents, __path_get_workday_documents_by_year,
+        __path_get_yearly_workday_document, __path_revoke_document_share, __path_update_workday,
+        __path_verify_workday_document, create_document_share, create_workday, delete_workday,
+        delete_workday_garbage, get_all_workday_garbage, get_all_workdays_month,
+        get_all_workdays_period, get_document_share_accesses, get_document_shares,
+        get_shared_document, get_workday_by_date, get_workday_document_by_month,
+        get_workday_documents, get_workday_documents_by_year, get_yearly_workday_document,
+        revoke_document_share, update_workday, verify_workday_document,
     },
 };
 
@@ -27,4 +32,15 @@ pub fn workday_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(get_workday_documents))
         .routes(routes!(get_workday_documents_by_year))
         .routes(routes!(get_workday_document_by_month))
+        .routes(routes!(get_yearly_workday_document))
+        .routes(routes!(create_document_share))
+        .routes(routes!(get_document_shares))
+        .routes(routes!(revoke_document_share))
+        .routes(routes!(get_document_share_accesses))
+}
+
+pub fn public_workday_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(verify_workday_document))
+        .routes(routes!(get_shared_document))
 }
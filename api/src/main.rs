@@ -1,8 +1,8 @@
 use api::{ApiError, app::App};
 use dotenv::dotenv;
 use opentelemetry::trace::TracerProvider as _;
-use opentelemetry_otlp::{SpanExporter, WithExportConfig};
-use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{metrics::SdkMeterProvider, trace::SdkTracerProvider};
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
 use api::config::Config;
@@ -29,6 +29,18 @@ async fn main() -> Result<(), ApiError> {
 
     let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
 
+    let metric_exporter = MetricExporter::builder()
+        .with_tonic()
+        .with_endpoint(&config.otel.exporter_otlp_endpoint)
+        .build()
+        .expect("failed to build OTLP metric exporter");
+
+    let meter_provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(metric_exporter)
+        .build();
+
+    opentelemetry::global::set_meter_provider(meter_provider.clone());
+
     tracing_subscriber::registry()
         .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
         .with(tracing_subscriber::fmt::layer()) // console logs
@@ -44,10 +56,13 @@ async fn main() -> Result<(), ApiError> {
     let app = App::new(config).await?;
     app.start().await?;
 
-    // flush traces before exit
+    // flush traces and metrics before exit
     provider
         .shutdown()
         .expect("failed to shutdown OpenTelemetry");
+    meter_provider
+        .shutdown()
+        .expect("failed to shutdown OpenTelemetry metrics");
 
     Ok(())
 }
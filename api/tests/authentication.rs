@@ -15,6 +15,13 @@ use uuid::Uuid;
 pub mod context;
 pub mod helpers;
 
+use helpers::mail::set_mail_preference;
+
+/// PasswordReset mail type
+const PASSWORD_RESET_TYPE_ID: i32 = 2;
+/// MagicLink mail type
+const MAGIC_LINK_TYPE_ID: i32 = 8;
+
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
@@ -99,6 +106,43 @@ async fn test_signup_with_banned_email(ctx: &mut context::TestContext) {
     assert_eq!(body2.error_code, "EMAIL_DOMAIN_DENYLISTED");
 }
 
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_signup_with_password_policy_violations(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/signup")
+        .json(&json!({
+            "firstname": "Johnny",
+            "lastname": "Doe",
+            "gender": null,
+            "email": "johnny.doe@mail.be",
+            "password": "johnny12",
+            "language": "en"
+        }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "PASSWORD_POLICY_VIOLATION");
+
+    let content = body.content.expect("Violations should be present");
+    let violations = content
+        .get("violations")
+        .and_then(|v| v.as_sequence())
+        .expect("Violations should be a list");
+    let rules: Vec<&str> = violations
+        .iter()
+        .filter_map(|v| v.get("rule").and_then(|r| r.as_str()))
+        .collect();
+    assert_eq!(rules, vec!["too_weak", "contains_personal_information"]);
+    assert_eq!(
+        violations[1].get("message").and_then(|m| m.as_str()),
+        Some("The password cannot contain your first name, last name or email address.")
+    );
+}
+
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
@@ -595,13 +639,13 @@ async fn test_login_with_suspension_can_access_restricted_space(ctx: &mut contex
 #[tokio::test]
 #[serial]
 async fn test_request_password_reset_success(ctx: &mut context::TestContext) {
-    sqlx::query(
-        "UPDATE drivers SET mail_preferences = mail_preferences | 2 WHERE pk_driver_id = $1",
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        PASSWORD_RESET_TYPE_ID,
+        true,
     )
-    .bind(ctx.authenticated_user_id)
-    .execute(&ctx.repositories.pool)
-    .await
-    .unwrap();
+    .await;
 
     let res = ctx
         .unauthenticated_router
@@ -634,26 +678,26 @@ async fn test_request_password_reset_success(ctx: &mut context::TestContext) {
         "Reset password token should be stored in Redis"
     );
 
-    sqlx::query(
-        "UPDATE drivers SET mail_preferences = mail_preferences & ~2 WHERE pk_driver_id = $1",
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        PASSWORD_RESET_TYPE_ID,
+        false,
     )
-    .bind(ctx.authenticated_user_id)
-    .execute(&ctx.repositories.pool)
-    .await
-    .unwrap();
+    .await;
 }
 
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
 async fn test_request_password_reset_token_already_exists(ctx: &mut context::TestContext) {
-    sqlx::query(
-        "UPDATE drivers SET mail_preferences = mail_preferences | 2 WHERE pk_driver_id = $1",
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        PASSWORD_RESET_TYPE_ID,
+        true,
     )
-    .bind(ctx.authenticated_user_id)
-    .execute(&ctx.repositories.pool)
-    .await
-    .unwrap();
+    .await;
 
     ctx.unauthenticated_router
         .post("/authentication/reset-password")
@@ -671,13 +715,13 @@ async fn test_request_password_reset_token_already_exists(ctx: &mut context::Tes
     let body: ErrorBody = res.json();
     assert_eq!(body.error_code, "RESET_PASSWORD_TOKEN_ALREADY_EXISTS");
 
-    sqlx::query(
-        "UPDATE drivers SET mail_preferences = mail_preferences & ~2 WHERE pk_driver_id = $1",
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        PASSWORD_RESET_TYPE_ID,
+        false,
     )
-    .bind(ctx.authenticated_user_id)
-    .execute(&ctx.repositories.pool)
-    .await
-    .unwrap();
+    .await;
 }
 
 #[test_context(context::TestContext)]
@@ -846,3 +890,373 @@ async fn test_confirm_password_reset_invalid_body(ctx: &mut context::TestContext
     let body: ErrorBody = res.json();
     assert_eq!(body.error_code, "BODY_VALIDATION");
 }
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_authenticated_request_with_suspension(ctx: &mut context::TestContext) {
+    let employee = ctx
+        .repositories
+        .employee_repository
+        .get_first_employee()
+        .await
+        .unwrap()
+        .expect("No employee found in the database");
+
+    let suspension = ctx
+        .repositories
+        .driver_database_repository
+        .create_driver_suspension(DriverSuspensionRow {
+            pk_driver_suspension_id: 0,
+            fk_driver_id: ctx.authenticated_user_id,
+            fk_created_employee_id: employee.pk_employee_id,
+            driver_message: Some("Test suspension".to_string()),
+            title: "Test Suspension".to_string(),
+            description: Some("This is a test suspension".to_string()),
+            start_at: chrono::Utc::now() - chrono::Duration::hours(1),
+            end_at: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+            can_access_restricted_space: false,
+            created_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let res = ctx.authenticated_router.get("/me").await;
+
+    res.assert_status(StatusCode::FORBIDDEN);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DRIVER_SUSPENDED");
+    let content = body.content.expect("Suspension content should be present");
+    assert_eq!(
+        content.get("driver_message").and_then(|v| v.as_str()),
+        Some("Test suspension")
+    );
+    assert!(content.get("start_at").is_some());
+    assert!(content.get("end_at").is_some());
+
+    ctx.repositories
+        .driver_database_repository
+        .delete_driver_suspension(suspension.pk_driver_suspension_id)
+        .await
+        .unwrap();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_authenticated_request_with_suspension_can_access_restricted_space(
+    ctx: &mut context::TestContext,
+) {
+    let employee = ctx
+        .repositories
+        .employee_repository
+        .get_first_employee()
+        .await
+        .unwrap()
+        .expect("No employee found in the database");
+
+    let suspension = ctx
+        .repositories
+        .driver_database_repository
+        .create_driver_suspension(DriverSuspensionRow {
+            pk_driver_suspension_id: 0,
+            fk_driver_id: ctx.authenticated_user_id,
+            fk_created_employee_id: employee.pk_employee_id,
+            driver_message: Some("Test suspension".to_string()),
+            title: "Test Suspension".to_string(),
+            description: Some("This is a test suspension".to_string()),
+            start_at: chrono::Utc::now() - chrono::Duration::hours(1),
+            end_at: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+            can_access_restricted_space: true,
+            created_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let res = ctx.authenticated_router.get("/me").await;
+    res.assert_status(StatusCode::OK);
+
+    ctx.repositories
+        .driver_database_repository
+        .delete_driver_suspension(suspension.pk_driver_suspension_id)
+        .await
+        .unwrap();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_refresh_token_with_suspension(ctx: &mut context::TestContext) {
+    let employee = ctx
+        .repositories
+        .employee_repository
+        .get_first_employee()
+        .await
+        .unwrap()
+        .expect("No employee found in the database");
+
+    let suspension = ctx
+        .repositories
+        .driver_database_repository
+        .create_driver_suspension(DriverSuspensionRow {
+            pk_driver_suspension_id: 0,
+            fk_driver_id: ctx.authenticated_user_id,
+            fk_created_employee_id: employee.pk_employee_id,
+            driver_message: None,
+            title: "Test Suspension".to_string(),
+            description: None,
+            start_at: chrono::Utc::now() - chrono::Duration::hours(1),
+            end_at: None,
+            can_access_restricted_space: false,
+            created_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let refresh_token = helpers::auth::generate_mock_refresh_token(&ctx.authenticated_user_id);
+    let res = ctx
+        .unauthenticated_router
+        .get("/authentication/refresh")
+        .add_header(
+            axum::http::header::COOKIE,
+            format!("refresh_token={}", refresh_token),
+        )
+        .await;
+
+    res.assert_status(StatusCode::FORBIDDEN);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DRIVER_SUSPENDED");
+
+    ctx.repositories
+        .driver_database_repository
+        .delete_driver_suspension(suspension.pk_driver_suspension_id)
+        .await
+        .unwrap();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_request_magic_link_success(ctx: &mut context::TestContext) {
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        MAGIC_LINK_TYPE_ID,
+        true,
+    )
+    .await;
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link")
+        .json(&json!({ "email": "TeST.uSeR@eXaMpLe.Be" }))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+
+    let (redis_key, _) = ctx
+        .repositories
+        .driver_cache_repository
+        .get_key_by_type(ctx.authenticated_user_id, DriverCacheKeyType::MagicLink);
+    let token = ctx
+        .repositories
+        .driver_cache_repository
+        .get_redis(redis_key)
+        .await
+        .unwrap();
+    assert!(
+        token.is_some(),
+        "Magic link token should be stored in Redis"
+    );
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link")
+        .json(&json!({ "email": "test.user@example.be" }))
+        .await;
+
+    res.assert_status(StatusCode::CONFLICT);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAGIC_LINK_TOKEN_ALREADY_EXISTS");
+
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        MAGIC_LINK_TYPE_ID,
+        false,
+    )
+    .await;
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_request_magic_link_driver_not_found(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link")
+        .json(&json!({ "email": "unknown.driver@example.be" }))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DRIVER_NOT_FOUND");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_confirm_magic_link_success(ctx: &mut context::TestContext) {
+    let (redis_key, redis_ttl) = ctx
+        .repositories
+        .driver_cache_repository
+        .get_key_by_type(ctx.authenticated_user_id, DriverCacheKeyType::MagicLink);
+    ctx.repositories
+        .driver_cache_repository
+        .set_redis(redis_key.clone(), "valid-token".to_string(), redis_ttl)
+        .await
+        .unwrap();
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link/confirm")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": "valid-token"
+        }))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+    let body: CreateDriverResponse = res.json();
+    assert!(!body.access_token.is_empty());
+
+    let token_after = ctx
+        .repositories
+        .driver_cache_repository
+        .get_redis(redis_key)
+        .await
+        .unwrap();
+    assert!(
+        token_after.is_none(),
+        "Token should be deleted from Redis after use"
+    );
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link/confirm")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": "valid-token"
+        }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_MAGIC_LINK_TOKEN");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_confirm_magic_link_invalid_token(ctx: &mut context::TestContext) {
+    let (redis_key, redis_ttl) = ctx
+        .repositories
+        .driver_cache_repository
+        .get_key_by_type(ctx.authenticated_user_id, DriverCacheKeyType::MagicLink);
+    ctx.repositories
+        .driver_cache_repository
+        .set_redis(redis_key.clone(), "correct-token".to_string(), redis_ttl)
+        .await
+        .unwrap();
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link/confirm")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": "wrong-token"
+        }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_MAGIC_LINK_TOKEN");
+
+    let token_after = ctx
+        .repositories
+        .driver_cache_repository
+        .get_redis(redis_key.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        token_after.as_deref(),
+        Some("correct-token"),
+        "A failed attempt should keep the pending magic link"
+    );
+
+    ctx.repositories
+        .driver_cache_repository
+        .delete_redis(redis_key)
+        .await
+        .unwrap();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_confirm_magic_link_with_suspension(ctx: &mut context::TestContext) {
+    let employee = ctx
+        .repositories
+        .employee_repository
+        .get_first_employee()
+        .await
+        .unwrap()
+        .expect("No employee found in the database");
+
+    let suspension = ctx
+        .repositories
+        .driver_database_repository
+        .create_driver_suspension(DriverSuspensionRow {
+            pk_driver_suspension_id: 0,
+            fk_driver_id: ctx.authenticated_user_id,
+            fk_created_employee_id: employee.pk_employee_id,
+            driver_message: None,
+            title: "Test Suspension".to_string(),
+            description: None,
+            start_at: chrono::Utc::now() - chrono::Duration::hours(1),
+            end_at: None,
+            can_access_restricted_space: false,
+            created_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    let (redis_key, redis_ttl) = ctx
+        .repositories
+        .driver_cache_repository
+        .get_key_by_type(ctx.authenticated_user_id, DriverCacheKeyType::MagicLink);
+    ctx.repositories
+        .driver_cache_repository
+        .set_redis(redis_key, "valid-token".to_string(), redis_ttl)
+        .await
+        .unwrap();
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/magic-link/confirm")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": "valid-token"
+        }))
+        .await;
+
+    res.assert_status(StatusCode::FORBIDDEN);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DRIVER_SUSPENDED");
+
+    ctx.repositories
+        .driver_database_repository
+        .delete_driver_suspension(suspension.pk_driver_suspension_id)
+        .await
+        .unwrap();
+}
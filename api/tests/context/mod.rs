@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use api::config::{
-    CheckContentConfig, CommonConfig, Config, Environment, JwtConfig, OtelConfig, S3Config,
-    SmtpConfig,
+    CheckContentConfig, CommonConfig, Config, DocumentDelivery, Environment, JwtConfig, OtelConfig,
+    PdfRendererKind, S3Config, SmtpConfig, SmtpTlsMode, StorageBackendKind,
 };
 use api::{App, app::AppBuilder};
 use axum_test::TestServer;
@@ -8,6 +10,10 @@ use plannify_driver_api_core::domain::storage::port::StorageRepository;
 use plannify_driver_api_core::{
     ServiceConfig,
     application::{DriverRepositories, create_repositories},
+    domain::common::config::{
+        DocumentShareConfig, DocumentVerificationConfig, MailUnsubscribeConfig,
+        PasswordPolicyConfig,
+    },
 };
 use test_context::AsyncTestContext;
 use uuid::Uuid;
@@ -44,6 +50,7 @@ pub struct TestContext {
     pub repositories: DriverRepositories,
     pub authenticated_user_id: Uuid,
     pub test_token: String,
+    config: Config,
 }
 
 impl AsyncTestContext for TestContext {
@@ -61,6 +68,13 @@ impl AsyncTestContext for TestContext {
             username: "user".to_string(),
             password: "password".to_string(),
             domain: "localhost".to_string(),
+            port: None,
+            tls: SmtpTlsMode::Tls,
+            pool_size: 10,
+            timeout_secs: 60,
+            dkim_selector: None,
+            dkim_domain: None,
+            dkim_private_key_path: None,
         };
 
         let jwt: JwtConfig = JwtConfig {
@@ -75,7 +89,24 @@ impl AsyncTestContext for TestContext {
             origins: vec!["0.0.0.0/0".to_string()],
             rate_limit_requests: 100,
             frontend_url: "http://localhost:3000".to_string(),
+            public_api_url: "http://localhost:8080".to_string(),
+            mail_unsubscribe_secret: "test-unsubscribe-secret".to_string(),
+            vapid_private_key: None,
+            vapid_subject: "mailto:contact@plannify.be".to_string(),
+            sms_gateway_url: None,
+            sms_gateway_api_key: String::new(),
+            sms_sender: "Plannify".to_string(),
+            sms_log_path: None,
+            sms_login_enabled: true,
+            mail_events_webhook_token: Some("mail-events-token".to_string()),
             pdf_service_endpoint: "http://localhost:4000".to_string(),
+            pdf_renderer: PdfRendererKind::GrpcWithFallback,
+            pdf_service_connect_timeout_seconds: 5,
+            pdf_service_request_timeout_seconds: 30,
+            pdf_service_max_retries: 2,
+            pdf_service_retry_base_delay_ms: 200,
+            pdf_service_circuit_failure_threshold: 5,
+            pdf_service_circuit_open_seconds: 30,
             workday_garbage_retention_days: 30,
             support_email: "contact@plannify.be".to_string(),
             account_deactivation_days: 30,
@@ -88,12 +119,20 @@ impl AsyncTestContext for TestContext {
             secret_key: std::env::var("S3_SECRET_KEY").expect("S3_SECRET_KEY must be set"),
             bucket_name: std::env::var("S3_BUCKET_NAME").unwrap_or_else(|_| "plannify".to_string()),
             region: std::env::var("S3_REGION").unwrap_or_else(|_| "garage".to_string()),
+            document_delivery: DocumentDelivery::Stream,
+            presigned_url_ttl_seconds: 300,
+            storage_backend: StorageBackendKind::S3,
+            storage_local_root_dir: PathBuf::from("storage"),
+            storage_url_secret: "storage-url-secret".to_string(),
+            storage_master_keys: vec![],
         };
 
         let otel_config = OtelConfig::default();
 
         let check_content = CheckContentConfig {
             email_domain_denylist: vec!["example.fr".to_string(), "example.com".to_string()],
+            password_min_score: 70.0,
+            password_breached_dataset_dir: None,
         };
 
         let config = Config {
@@ -124,6 +163,30 @@ impl AsyncTestContext for TestContext {
             ServiceConfig {
                 workday_garbage_retention_days: config.common.workday_garbage_retention_days,
                 account_deactivation_days: config.common.account_deactivation_days,
+                password_policy: PasswordPolicyConfig {
+                    min_score: config.check_content.password_min_score,
+                    breached_dataset_dir: config
+                        .check_content
+                        .password_breached_dataset_dir
+                        .clone(),
+                },
+                mail_unsubscribe: MailUnsubscribeConfig {
+                    api_url: config.common.public_api_url.clone(),
+                    secret: config.common.mail_unsubscribe_secret.clone(),
+                },
+                web_push: config.common.web_push(),
+                sms: config.common.sms(),
+                mail_events: config.common.mail_events(),
+                mail_dkim: config.smtp.dkim(),
+                document_delivery: config.s3.document_delivery(),
+                storage: config.s3.storage(&config.common.public_api_url),
+                document_verification: DocumentVerificationConfig {
+                    api_url: config.common.public_api_url.clone(),
+                },
+                document_share: DocumentShareConfig {
+                    api_url: config.common.public_api_url.clone(),
+                },
+                document_service: config.common.document_service(),
             },
         )
         .await
@@ -181,6 +244,7 @@ impl AsyncTestContext for TestContext {
             repositories,
             authenticated_user_id: fallback_user_id,
             test_token,
+            config,
         }
     }
 
@@ -230,4 +294,22 @@ impl TestContext {
     pub fn test_token(&self) -> &str {
         &self.test_token
     }
+
+    /// Create a new authenticated router on an app built from the test configuration changed
+    /// by `configure`, e.g. to use another storage backend or document delivery mode
+    pub async fn create_authenticated_router_with_config(
+        &self,
+        configure: impl FnOnce(&mut Config),
+    ) -> TestServer {
+        let mut config = self.config.clone();
+        configure(&mut config);
+        let app = App::build(config).await.expect("Failed to build app");
+
+        let mut router = TestServer::new(app.app_router()).unwrap();
+        router.add_header(
+            axum::http::header::AUTHORIZATION,
+            format!("Bearer {}", self.test_token),
+        );
+        router
+    }
 }
@@ -1,15 +1,18 @@
 use api::http::common::api_error::ErrorBody;
 use api::http::common::middleware::auth::entities::AccessClaims;
+use axum_test::TestServer;
 use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
 use plannify_driver_api_core::domain::{
     driver::{
         entities::{
-            CreateDriverResponse, DriverLimitationRow, DriverRestPeriod, EntityType,
-            GetDriverLimitationResponse,
+            ApiTokenScope, CreateDriverApiTokenResponse, CreateDriverResponse, DriverApiToken,
+            DriverEmailChange, DriverLimitationRow, DriverRestPeriod, DriverSmsCode, EntityType,
+            GetDriverLimitationResponse, GetDriverResponse,
         },
         port::{DriverCacheKeyType, DriverCacheRepository, DriverDatabaseRepository},
     },
     employee::port::EmployeeRepository,
+    notification::entities::PushSubscription,
 };
 use reqwest::StatusCode;
 use serde_json::json;
@@ -327,6 +330,122 @@ async fn test_update_driver_info_unauthorized(ctx: &mut context::TestContext) {
     assert_eq!(body.error_code, "UNAUTHORIZED");
 }
 
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_driver_info_not_modified(ctx: &mut context::TestContext) {
+    let res = ctx.authenticated_router.get("/me").await;
+    res.assert_status(StatusCode::OK);
+    let e_tag = res.header(axum::http::header::ETAG);
+
+    let res = ctx
+        .authenticated_router
+        .get("/me")
+        .add_header(axum::http::header::IF_NONE_MATCH, e_tag)
+        .await;
+
+    res.assert_status(StatusCode::NOT_MODIFIED);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_driver_info_stale_if_match(ctx: &mut context::TestContext) {
+    let original = ctx
+        .repositories
+        .driver_database_repository
+        .get_driver_by_id(ctx.authenticated_user_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .add_header(axum::http::header::IF_MATCH, "\"stale\"")
+        .json(&json!({ "firstname": "Other" }))
+        .await;
+
+    res.assert_status(StatusCode::PRECONDITION_FAILED);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "PRECONDITION_FAILED");
+
+    let driver = ctx
+        .repositories
+        .driver_database_repository
+        .get_driver_by_id(ctx.authenticated_user_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(driver.firstname, original.firstname);
+
+    let res = ctx.authenticated_router.get("/me").await;
+    let e_tag = res.header(axum::http::header::ETAG);
+
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .add_header(axum::http::header::IF_MATCH, e_tag)
+        .json(&json!({}))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_driver_info_if_match_survives_login(ctx: &mut context::TestContext) {
+    let res = ctx.authenticated_router.get("/me").await;
+    let e_tag = res.header(axum::http::header::ETAG);
+
+    ctx.repositories
+        .driver_database_repository
+        .update_driver_last_login(ctx.authenticated_user_id, chrono::Utc::now())
+        .await
+        .unwrap();
+
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .add_header(axum::http::header::IF_MATCH, e_tag)
+        .json(&json!({ "firstname": "Other" }))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_driver_info_stale_version(ctx: &mut context::TestContext) {
+    let res = ctx.authenticated_router.get("/me").await;
+    res.assert_status(StatusCode::OK);
+    let original: GetDriverResponse = res.json();
+
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .json(&json!({ "version": original.version }))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+
+    // A second device still editing the first version gets the current profile back
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .json(&json!({ "firstname": "Other", "version": original.version }))
+        .await;
+
+    res.assert_status(StatusCode::CONFLICT);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DRIVER_VERSION_CONFLICT");
+    let current: GetDriverResponse = serde_yaml::from_value(body.content.unwrap()).unwrap();
+    assert_eq!(current.firstname, original.firstname);
+    assert_eq!(current.version, original.version + 1);
+}
+
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
@@ -483,18 +602,45 @@ async fn test_update_driver_info_gender_formatting(ctx: &mut context::TestContex
         .unwrap();
 }
 
+async fn take_email_change(
+    ctx: &context::TestContext,
+    key_type: DriverCacheKeyType,
+) -> Option<DriverEmailChange> {
+    let (redis_key, _) = ctx
+        .repositories
+        .driver_cache_repository
+        .get_key_by_type(ctx.authenticated_user_id, key_type);
+    let cached = ctx
+        .repositories
+        .driver_cache_repository
+        .get_redis(redis_key)
+        .await
+        .unwrap()?;
+    Some(serde_json::from_str(&cached).unwrap())
+}
+
+async fn clear_email_changes(ctx: &context::TestContext) {
+    for key_type in [
+        DriverCacheKeyType::PendingEmailChange,
+        DriverCacheKeyType::ConfirmedEmailChange,
+        DriverCacheKeyType::SessionRevocation,
+    ] {
+        let (redis_key, _) = ctx
+            .repositories
+            .driver_cache_repository
+            .get_key_by_type(ctx.authenticated_user_id, key_type);
+        ctx.repositories
+            .driver_cache_repository
+            .delete_redis(redis_key)
+            .await
+            .unwrap();
+    }
+}
+
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
 async fn test_update_driver_info_email_normalization(ctx: &mut context::TestContext) {
-    let original = ctx
-        .repositories
-        .driver_database_repository
-        .get_driver_by_id(ctx.authenticated_user_id)
-        .await
-        .unwrap()
-        .unwrap();
-
     let res = ctx
         .authenticated_router
         .patch("/me")
@@ -503,24 +649,16 @@ async fn test_update_driver_info_email_normalization(ctx: &mut context::TestCont
 
     res.assert_status(StatusCode::OK);
 
-    let driver = ctx
-        .repositories
-        .driver_database_repository
-        .get_driver_by_id(ctx.authenticated_user_id)
+    let email_change = take_email_change(ctx, DriverCacheKeyType::PendingEmailChange)
         .await
-        .unwrap()
-        .unwrap();
+        .expect("Email change must be pending");
 
     assert_eq!(
-        driver.email, "new.email@example.be",
+        email_change.new_email, "new.email@example.be",
         "Email must be normalized to lowercase"
     );
 
-    ctx.repositories
-        .driver_database_repository
-        .update_driver(original)
-        .await
-        .unwrap();
+    clear_email_changes(ctx).await;
 }
 
 #[test_context(context::TestContext)]
@@ -566,7 +704,7 @@ async fn test_update_driver_info_same_email_stays_verified(ctx: &mut context::Te
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
-async fn test_update_driver_info_email_change_clears_verification(ctx: &mut context::TestContext) {
+async fn test_update_driver_info_email_change_stays_pending(ctx: &mut context::TestContext) {
     let original = ctx
         .repositories
         .driver_database_repository
@@ -575,11 +713,6 @@ async fn test_update_driver_info_email_change_clears_verification(ctx: &mut cont
         .unwrap()
         .unwrap();
 
-    assert!(
-        original.verified_at.is_some(),
-        "Test user must start as verified"
-    );
-
     let res = ctx
         .authenticated_router
         .patch("/me")
@@ -588,9 +721,8 @@ async fn test_update_driver_info_email_change_clears_verification(ctx: &mut cont
 
     res.assert_status(StatusCode::OK);
     let body: CreateDriverResponse = res.json();
-    assert!(!body.access_token.is_empty());
 
-    // DB state: verified_at must be cleared and email normalised
+    // The current address stays in use until the new one is confirmed
     let driver = ctx
         .repositories
         .driver_database_repository
@@ -599,33 +731,26 @@ async fn test_update_driver_info_email_change_clears_verification(ctx: &mut cont
         .unwrap()
         .unwrap();
 
-    assert!(
-        driver.verified_at.is_none(),
-        "verified_at must be cleared after email change"
-    );
-    assert_eq!(driver.email, "changed.email@example.be");
+    assert_eq!(driver.email, original.email);
+    assert!(driver.verified_at.is_some());
 
-    // The new access token must embed verified = false
     let claims = decode_access_token(&body.access_token);
-    assert!(
-        !claims.driver.verified,
-        "New token must carry verified=false after email change"
-    );
-    assert_eq!(claims.driver.email, "changed.email@example.be");
+    assert!(claims.driver.verified);
+    assert_eq!(claims.driver.email, original.email);
 
-    ctx.repositories
-        .driver_database_repository
-        .update_driver(original)
+    let email_change = take_email_change(ctx, DriverCacheKeyType::PendingEmailChange)
         .await
-        .unwrap();
+        .expect("Email change must be pending");
+    assert_eq!(email_change.previous_email, original.email);
+    assert_eq!(email_change.new_email, "changed.email@example.be");
+
+    clear_email_changes(ctx).await;
 }
 
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
-async fn test_update_driver_info_email_change_new_token_is_rejected(
-    ctx: &mut context::TestContext,
-) {
+async fn test_confirm_and_revert_email_change(ctx: &mut context::TestContext) {
     let original = ctx
         .repositories
         .driver_database_repository
@@ -639,22 +764,58 @@ async fn test_update_driver_info_email_change_new_token_is_rejected(
         .patch("/me")
         .json(&json!({ "email": "changed.email@example.be" }))
         .await;
+    res.assert_status(StatusCode::OK);
+
+    let email_change = take_email_change(ctx, DriverCacheKeyType::PendingEmailChange)
+        .await
+        .unwrap();
 
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/email-change/confirm")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": email_change.confirm_token,
+        }))
+        .await;
     res.assert_status(StatusCode::OK);
     let body: CreateDriverResponse = res.json();
+    let claims = decode_access_token(&body.access_token);
+    assert_eq!(claims.driver.email, "changed.email@example.be");
+    assert!(claims.driver.verified);
 
-    // A protected route must reject the new (unverified) token with DRIVER_NOT_VERIFIED
-    let mut router_with_new_token = axum_test::TestServer::new(ctx.app.app_router()).unwrap();
-    router_with_new_token.add_header(
-        axum::http::header::AUTHORIZATION,
-        format!("Bearer {}", body.access_token),
+    assert!(
+        take_email_change(ctx, DriverCacheKeyType::PendingEmailChange)
+            .await
+            .is_none()
     );
 
-    let get_res = router_with_new_token.get("/rest-periods").await;
-    get_res.assert_status(StatusCode::UNAUTHORIZED);
-    let get_body: ErrorBody = get_res.json();
-    assert_eq!(get_body.error_code, "DRIVER_NOT_VERIFIED");
+    // Sessions issued before the revert are rejected afterwards
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/email-change/revert")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": email_change.revert_token,
+        }))
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let driver = ctx
+        .repositories
+        .driver_database_repository
+        .get_driver_by_id(ctx.authenticated_user_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(driver.email, original.email);
 
+    let res = ctx.authenticated_router.get("/rest-periods").await;
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "SESSION_REVOKED");
+
+    clear_email_changes(ctx).await;
     ctx.repositories
         .driver_database_repository
         .update_driver(original)
@@ -662,6 +823,24 @@ async fn test_update_driver_info_email_change_new_token_is_rejected(
         .unwrap();
 }
 
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_confirm_email_change_invalid_token(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/email-change/confirm")
+        .json(&json!({
+            "driver_id": ctx.authenticated_user_id,
+            "token": "not-a-valid-token",
+        }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_EMAIL_CHANGE_TOKEN");
+}
+
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
@@ -1047,6 +1226,37 @@ async fn test_update_driver_info_validation_password_too_short(ctx: &mut context
     assert_eq!(body.error_code, "BODY_VALIDATION");
 }
 
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_driver_info_password_policy_violation(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .json(&json!({ "password": "MyTest.User#2026" }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "PASSWORD_POLICY_VIOLATION");
+
+    let content = body.content.expect("Violations should be present");
+    let violation = &content
+        .get("violations")
+        .and_then(|v| v.as_sequence())
+        .expect("Violations should be a list")[0];
+    assert_eq!(
+        violation.get("rule").and_then(|r| r.as_str()),
+        Some("contains_personal_information")
+    );
+    assert_eq!(
+        violation.get("message").and_then(|m| m.as_str()),
+        Some(
+            "Le mot de passe ne peut pas contenir votre nom, votre prénom ou votre adresse email."
+        )
+    );
+}
+
 #[test_context(context::TestContext)]
 #[tokio::test]
 #[serial]
@@ -1593,3 +1803,369 @@ async fn test_get_current_limitation_with_more_limits(ctx: &mut context::TestCon
         .await
         .unwrap();
 }
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_api_token_lifecycle(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/me/tokens")
+        .json(&json!({
+            "label": "Tachograph reader",
+            "scopes": ["workdays:read"],
+            "expires_in_days": 30
+        }))
+        .await;
+
+    res.assert_status(StatusCode::CREATED);
+    let body: CreateDriverApiTokenResponse = res.json();
+    assert!(body.token.starts_with("pdt_"));
+    assert_eq!(body.api_token.scopes, vec![ApiTokenScope::WorkdaysRead]);
+
+    let mut token_router = TestServer::new(ctx.app.app_router()).unwrap();
+    token_router.add_header(
+        axum::http::header::AUTHORIZATION,
+        format!("Bearer {}", body.token),
+    );
+
+    // Allowed scope
+    let res = token_router
+        .get("/workdays/month")
+        .add_query_param("month", 1)
+        .add_query_param("year", 2026)
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    // Missing scope
+    let res = token_router
+        .post("/workdays")
+        .json(&json!({
+            "date": "2026-03-01",
+            "start_time": "08:00:00",
+            "end_time": "17:00:00",
+            "rest_time": "01:00:00",
+            "overnight_rest": false
+        }))
+        .await;
+    res.assert_status(StatusCode::FORBIDDEN);
+    let error: ErrorBody = res.json();
+    assert_eq!(error.error_code, "INSUFFICIENT_SCOPE");
+
+    // Account management is reserved to sessions
+    let res = token_router.get("/me/tokens").await;
+    res.assert_status(StatusCode::FORBIDDEN);
+    let error: ErrorBody = res.json();
+    assert_eq!(error.error_code, "API_TOKEN_NOT_ALLOWED");
+
+    let res = ctx.authenticated_router.get("/me/tokens").await;
+    res.assert_status(StatusCode::OK);
+    let tokens: Vec<DriverApiToken> = res.json();
+    let created = tokens
+        .iter()
+        .find(|t| t.pk_driver_api_token_id == body.api_token.pk_driver_api_token_id)
+        .expect("Created token should be listed");
+    assert!(created.last_used_at.is_some());
+
+    let res = ctx
+        .authenticated_router
+        .delete(&format!(
+            "/me/tokens/{}",
+            body.api_token.pk_driver_api_token_id
+        ))
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let res = token_router
+        .get("/workdays/month")
+        .add_query_param("month", 1)
+        .add_query_param("year", 2026)
+        .await;
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    let error: ErrorBody = res.json();
+    assert_eq!(error.error_code, "INVALID_API_TOKEN");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_create_api_token_invalid_scope(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/me/tokens")
+        .json(&json!({
+            "label": "Tachograph reader",
+            "scopes": ["drivers:admin"],
+            "expires_in_days": 30
+        }))
+        .await;
+
+    assert!(res.status_code().is_client_error());
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_delete_api_token_not_found(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .delete(&format!("/me/tokens/{}", Uuid::new_v4()))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "API_TOKEN_NOT_FOUND");
+}
+
+// Uncompressed P-256 generator point, a valid browser public key.
+const PUSH_P256DH: &str =
+    "BGsX0fLhLEJH-Lzm5WOkQPJ3A32BLeszoPShOUXYmMKWT-NC4v4af5uO5-tKfA-eFivOM1drMV7Oy7ZAaDe_UfU";
+const PUSH_AUTH: &str = "AAECAwQFBgcICQoLDA0ODw";
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_push_subscription_lifecycle(ctx: &mut context::TestContext) {
+    let endpoint = format!("https://push.example.com/send/{}", Uuid::new_v4());
+
+    let res = ctx
+        .authenticated_router
+        .post("/me/push-subscriptions")
+        .json(&json!({
+            "endpoint": endpoint,
+            "keys": { "p256dh": PUSH_P256DH, "auth": PUSH_AUTH }
+        }))
+        .await;
+
+    res.assert_status(StatusCode::CREATED);
+    let created: PushSubscription = res.json();
+    assert_eq!(created.endpoint, endpoint);
+
+    let res = ctx.authenticated_router.get("/me/push-subscriptions").await;
+    res.assert_status(StatusCode::OK);
+    let subscriptions: Vec<PushSubscription> = res.json();
+    assert!(
+        subscriptions
+            .iter()
+            .any(|s| s.pk_push_subscription_id == created.pk_push_subscription_id)
+    );
+
+    let res = ctx
+        .authenticated_router
+        .delete(&format!(
+            "/me/push-subscriptions/{}",
+            created.pk_push_subscription_id
+        ))
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let res = ctx.authenticated_router.get("/me/push-subscriptions").await;
+    let subscriptions: Vec<PushSubscription> = res.json();
+    assert!(
+        subscriptions
+            .iter()
+            .all(|s| s.pk_push_subscription_id != created.pk_push_subscription_id)
+    );
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_create_push_subscription_invalid_keys(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/me/push-subscriptions")
+        .json(&json!({
+            "endpoint": "https://push.example.com/send/invalid",
+            "keys": { "p256dh": "bm90LWEta2V5", "auth": PUSH_AUTH }
+        }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_PUSH_SUBSCRIPTION");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_create_push_subscription_private_endpoint(ctx: &mut context::TestContext) {
+    for endpoint in [
+        "http://push.example.com/send/insecure",
+        "https://169.254.169.254/latest/meta-data",
+        "https://localhost/push",
+    ] {
+        let res = ctx
+            .authenticated_router
+            .post("/me/push-subscriptions")
+            .json(&json!({
+                "endpoint": endpoint,
+                "keys": { "p256dh": PUSH_P256DH, "auth": PUSH_AUTH }
+            }))
+            .await;
+
+        res.assert_status(StatusCode::BAD_REQUEST);
+        let body: ErrorBody = res.json();
+        assert_eq!(body.error_code, "INVALID_PUSH_SUBSCRIPTION");
+    }
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_delete_push_subscription_not_found(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .delete(&format!("/me/push-subscriptions/{}", Uuid::new_v4()))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "PUSH_SUBSCRIPTION_NOT_FOUND");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_push_subscriptions_unauthorized(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .get("/me/push-subscriptions")
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+async fn take_sms_code(
+    ctx: &context::TestContext,
+    key_type: DriverCacheKeyType,
+) -> Option<DriverSmsCode> {
+    let (redis_key, _) = ctx
+        .repositories
+        .driver_cache_repository
+        .get_key_by_type(ctx.authenticated_user_id, key_type);
+    let cached = ctx
+        .repositories
+        .driver_cache_repository
+        .get_redis(redis_key)
+        .await
+        .unwrap()?;
+    Some(serde_json::from_str(&cached).unwrap())
+}
+
+/// Lets the test send its SMS codes without waiting for the cooldown
+async fn clear_sms_limits(ctx: &context::TestContext) {
+    for key_type in [
+        DriverCacheKeyType::SmsCooldown,
+        DriverCacheKeyType::SmsDailyCount,
+    ] {
+        let (redis_key, _) = ctx
+            .repositories
+            .driver_cache_repository
+            .get_key_by_type(ctx.authenticated_user_id, key_type);
+        ctx.repositories
+            .driver_cache_repository
+            .delete_redis(redis_key)
+            .await
+            .unwrap();
+    }
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_phone_verification_and_sms_login(ctx: &mut context::TestContext) {
+    let original = ctx
+        .repositories
+        .driver_database_repository
+        .get_driver_by_id(ctx.authenticated_user_id)
+        .await
+        .unwrap()
+        .unwrap();
+    clear_sms_limits(ctx).await;
+
+    let res = ctx
+        .authenticated_router
+        .patch("/me")
+        .json(&json!({ "phone_number": "+32470123456" }))
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let res = ctx
+        .authenticated_router
+        .post("/me/phone/verification")
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let sms_code = take_sms_code(ctx, DriverCacheKeyType::PhoneVerification)
+        .await
+        .expect("Verification code must be pending");
+    assert_eq!(sms_code.phone_number, "+32470123456");
+
+    let res = ctx
+        .authenticated_router
+        .post("/me/phone/verification/confirm")
+        .json(&json!({ "code": sms_code.code }))
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let res = ctx.authenticated_router.get("/me").await;
+    res.assert_status(StatusCode::OK);
+    let body: serde_json::Value = res.json();
+    assert!(!body["phone_verified_at"].is_null());
+
+    clear_sms_limits(ctx).await;
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/sms-login")
+        .json(&json!({ "email": original.email }))
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    let sms_code = take_sms_code(ctx, DriverCacheKeyType::SmsLogin)
+        .await
+        .expect("Login code must be pending");
+
+    let res = ctx
+        .unauthenticated_router
+        .post("/authentication/sms-login/confirm")
+        .json(&json!({ "email": original.email, "code": sms_code.code }))
+        .await;
+    res.assert_status(StatusCode::OK);
+    let body: CreateDriverResponse = res.json();
+    assert!(!body.access_token.is_empty());
+
+    ctx.repositories
+        .driver_database_repository
+        .update_driver(original)
+        .await
+        .unwrap();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_confirm_phone_verification_invalid_code(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/me/phone/verification/confirm")
+        .json(&json!({ "code": "123456" }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_SMS_CODE");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_request_phone_verification_unauthorized(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post("/me/phone/verification")
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
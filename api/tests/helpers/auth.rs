@@ -1,4 +1,4 @@
-use api::http::common::middleware::auth::entities::{AccessClaims, DriverClaims};
+use api::http::common::middleware::auth::entities::{AccessClaims, DriverClaims, RefreshClaims};
 use jsonwebtoken::Algorithm;
 use uuid::Uuid;
 
@@ -33,3 +33,26 @@ pub fn generate_mock_token(user_id: &Uuid) -> String {
     )
     .expect("Failed to generate mock token")
 }
+
+/// Generate a refresh token signed with the test secret key
+pub fn generate_mock_refresh_token(user_id: &Uuid) -> String {
+    use chrono::Utc;
+    use jsonwebtoken::{EncodingKey, Header, encode};
+
+    let now = Utc::now().timestamp();
+    let claims = RefreshClaims {
+        sub: *user_id,
+        exp: now + 86400,
+        iat: now,
+    };
+
+    encode(
+        &Header {
+            alg: Algorithm::HS256,
+            ..Default::default()
+        },
+        &claims,
+        &EncodingKey::from_secret("test-secret-key".as_ref()),
+    )
+    .expect("Failed to generate mock refresh token")
+}
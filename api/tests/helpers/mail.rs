@@ -0,0 +1,37 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Set the email preference of a driver for a mail type.
+pub async fn set_mail_preference(
+    pool: &PgPool,
+    driver_id: Uuid,
+    mail_type_id: i32,
+    is_enabled: bool,
+) {
+    sqlx::query(
+        "INSERT INTO driver_notification_preferences (fk_driver_id, fk_mail_type_id, channel, is_enabled)
+         VALUES ($1, $2, 'EMAIL', $3)
+         ON CONFLICT (fk_driver_id, fk_mail_type_id, channel)
+         DO UPDATE SET is_enabled = EXCLUDED.is_enabled, updated_at = NOW()",
+    )
+    .bind(driver_id)
+    .bind(mail_type_id)
+    .bind(is_enabled)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Disable every email preference of a driver, as in the test dataset.
+pub async fn disable_all_mail_preferences(pool: &PgPool, driver_id: Uuid) {
+    sqlx::query(
+        "INSERT INTO driver_notification_preferences (fk_driver_id, fk_mail_type_id, channel, is_enabled)
+         SELECT $1, pk_driver_mail_type_id, 'EMAIL', FALSE FROM driver_mail_types
+         ON CONFLICT (fk_driver_id, fk_mail_type_id, channel)
+         DO UPDATE SET is_enabled = FALSE, updated_at = NOW()",
+    )
+    .bind(driver_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
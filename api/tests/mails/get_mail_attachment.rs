@@ -1,5 +1,5 @@
-use api::http::common::api_error::ErrorBody;
-use axum::http::StatusCode;
+use api::{config::DocumentDelivery, http::common::api_error::ErrorBody};
+use axum::http::{StatusCode, header};
 use serial_test::serial;
 use test_context::test_context;
 
@@ -7,6 +7,8 @@ use crate::context;
 
 // IDs defined in config/test-dataset.sql
 const ATTACHMENT_USER_A: &str = "423e4567-e89b-12d3-a456-426614174000"; // belongs to User A's mail
+// Content seeded in Garage by the test context for the attachment of User A
+const ATTACHMENT_USER_A_CONTENT: &str = "%PDF-1.4 test file: attachment-1.pdf";
 
 #[test_context(context::TestContext)]
 #[tokio::test]
@@ -89,3 +91,55 @@ async fn test_get_mail_attachment_cross_user_isolation(ctx: &mut context::TestCo
     let body: ErrorBody = res.json();
     assert_eq!(body.error_code, "MAIL_ATTACHMENT_NOT_FOUND");
 }
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_mail_attachment_range_returns_partial_content(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .get(&format!("/mails/attachments/{}", ATTACHMENT_USER_A))
+        .add_header(header::RANGE, "bytes=9-")
+        .await;
+
+    res.assert_status(StatusCode::PARTIAL_CONTENT);
+    res.assert_header(
+        header::CONTENT_RANGE,
+        format!(
+            "bytes 9-{}/{}",
+            ATTACHMENT_USER_A_CONTENT.len() - 1,
+            ATTACHMENT_USER_A_CONTENT.len()
+        ),
+    );
+    assert!(res.headers().contains_key(header::ETAG));
+    assert_eq!(
+        res.as_bytes().as_ref(),
+        &ATTACHMENT_USER_A_CONTENT.as_bytes()[9..]
+    );
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_mail_attachment_redirects_to_presigned_url(ctx: &mut context::TestContext) {
+    let router = ctx
+        .create_authenticated_router_with_config(|config| {
+            config.s3.document_delivery = DocumentDelivery::Redirect
+        })
+        .await;
+
+    let res = router
+        .get(&format!("/mails/attachments/{}", ATTACHMENT_USER_A))
+        .await;
+
+    res.assert_status(StatusCode::FOUND);
+
+    let location = res
+        .headers()
+        .get(header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    assert!(location.contains("mails/attachment-1.pdf"));
+    assert!(location.contains("X-Amz-Signature="));
+}
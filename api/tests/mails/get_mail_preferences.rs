@@ -27,19 +27,23 @@ async fn test_get_mail_preferences_success(ctx: &mut context::TestContext) {
     res.assert_status(StatusCode::OK);
 
     let body: Vec<DriverMailPreference> = res.json();
-    assert_eq!(body.len(), 4, "4 preferences must exist (one per type)");
+    assert_eq!(body.len(), 6, "6 preferences must exist (one per type)");
 
-    // mail_preferences = 0 in dataset → all preferences should be disabled
+    // every preference is disabled in dataset → all preferences should be disabled
     assert!(
         body.iter().all(|p| !p.is_enabled),
         "all preferences should be disabled (bitmask = 0)"
     );
 
-    // only MONTHLY_REPORTS (id=4) is editable
+    // only MONTHLY_REPORTS (id=4), WORKDAY_REMINDERS (id=5) and DOCUMENTS_READY (id=6) are editable
     let editable: Vec<&DriverMailPreference> = body.iter().filter(|p| p.is_editable).collect();
-    assert_eq!(editable.len(), 1);
+    assert_eq!(editable.len(), 3);
     assert_eq!(editable[0].mail_type_id, 4);
     assert_eq!(editable[0].label, "MONTHLY_REPORTS");
+    assert_eq!(editable[1].mail_type_id, 5);
+    assert_eq!(editable[1].label, "WORKDAY_REMINDERS");
+    assert_eq!(editable[2].mail_type_id, 6);
+    assert_eq!(editable[2].label, "DOCUMENTS_READY");
 }
 
 #[test_context(context::TestContext)]
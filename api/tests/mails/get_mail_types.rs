@@ -29,8 +29,8 @@ async fn test_get_mail_types_success(ctx: &mut context::TestContext) {
     let body: Vec<DriverMailType> = res.json();
     assert_eq!(
         body.len(),
-        4,
-        "4 types of mail must exist in the test database"
+        6,
+        "6 types of mail must exist in the test database"
     );
 
     assert_eq!(body[0].pk_driver_mail_type_id, 1);
@@ -48,6 +48,14 @@ async fn test_get_mail_types_success(ctx: &mut context::TestContext) {
     assert_eq!(body[3].pk_driver_mail_type_id, 4);
     assert_eq!(body[3].label, "MONTHLY_REPORTS");
     assert!(body[3].is_editable);
+
+    assert_eq!(body[4].pk_driver_mail_type_id, 5);
+    assert_eq!(body[4].label, "WORKDAY_REMINDERS");
+    assert!(body[4].is_editable);
+
+    assert_eq!(body[5].pk_driver_mail_type_id, 6);
+    assert_eq!(body[5].label, "DOCUMENTS_READY");
+    assert!(body[5].is_editable);
 }
 
 #[test_context(context::TestContext)]
@@ -0,0 +1,118 @@
+use api::http::common::api_error::ErrorBody;
+use axum::http::StatusCode;
+use serial_test::serial;
+use test_context::test_context;
+
+use crate::context;
+
+// IDs are hardcoded for test purposes and correspond to entries in the test database
+const MAIL_USER_A_1: &str = "223e4567-e89b-12d3-a456-426614174000"; // SUCCESS, type 1, sans PJ
+const MAIL_USER_A_2: &str = "223e4567-e89b-12d3-a456-426614174001"; // PENDING, type 4, avec PJ
+const MAIL_USER_B: &str = "223e4567-e89b-12d3-a456-426614174002"; // appartient à User B
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_resend_mail_unauthorized(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post(&format!("/mails/{}/resend", MAIL_USER_A_2))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "UNAUTHORIZED");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_resend_mail_not_found(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/mails/00000000-0000-0000-0000-000000000000/resend")
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_NOT_FOUND");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_resend_mail_cross_user_isolation(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post(&format!("/mails/{}/resend", MAIL_USER_B))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_NOT_FOUND");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_resend_mail_monthly_report_without_report_attachment(ctx: &mut context::TestContext) {
+    // The attachment of the dataset is not a monthly report PDF, its period is unknown
+    let res = ctx
+        .authenticated_router
+        .post(&format!("/mails/{}/resend", MAIL_USER_A_2))
+        .await;
+
+    res.assert_status(StatusCode::CONFLICT);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_NOT_RESENDABLE");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_cancel_mail_not_found(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/mails/00000000-0000-0000-0000-000000000000/cancel")
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_NOT_FOUND");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_cancel_mail_with_expired_link(ctx: &mut context::TestContext) {
+    // The verification mail of the dataset is older than its link
+    let res = ctx
+        .authenticated_router
+        .post(&format!("/mails/{}/cancel", MAIL_USER_A_1))
+        .await;
+
+    res.assert_status(StatusCode::CONFLICT);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_NOT_CANCELLABLE");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_cancel_mail_without_link(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post(&format!("/mails/{}/cancel", MAIL_USER_A_2))
+        .await;
+
+    res.assert_status(StatusCode::CONFLICT);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_NOT_CANCELLABLE");
+}
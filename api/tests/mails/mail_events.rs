@@ -0,0 +1,92 @@
+use api::http::common::api_error::ErrorBody;
+use axum::http::StatusCode;
+use plannify_driver_api_core::domain::mail::port::MailDatabaseRepository;
+use serde_json::json;
+use serial_test::serial;
+use test_context::test_context;
+use uuid::Uuid;
+
+use crate::context;
+
+const EVENTS_PATH: &str = "/mails/events?token=mail-events-token";
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_mail_events_invalid_token(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post("/mails/events?token=wrong-token")
+        .json(&json!({ "email": "bounce@example.be", "type": "hard_bounce" }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_MAIL_EVENTS_TOKEN");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_mail_events_unknown_format(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post(EVENTS_PATH)
+        .json(&json!({ "foo": "bar" }))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_MAIL_EVENTS");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_mail_events_hard_bounce_suppresses_address(ctx: &mut context::TestContext) {
+    let email = format!("bounce-{}@example.be", Uuid::new_v4());
+
+    let res = ctx
+        .unauthenticated_router
+        .post(EVENTS_PATH)
+        .json(&json!([{ "email": email, "type": "soft_bounce" }]))
+        .await;
+    res.assert_status(StatusCode::OK);
+    assert!(
+        ctx.repositories
+            .mail_database_repository
+            .get_email_suppression(email.clone())
+            .await
+            .unwrap()
+            .is_none(),
+        "A soft bounce must not suppress the address"
+    );
+
+    // Amazon SNS posts its notifications as text/plain
+    let message = json!({
+        "notificationType": "Bounce",
+        "bounce": {
+            "bounceType": "Permanent",
+            "bouncedRecipients": [{ "emailAddress": email.to_uppercase() }],
+            "timestamp": "2026-03-01T10:00:00.000Z"
+        }
+    });
+    let res = ctx
+        .unauthenticated_router
+        .post(EVENTS_PATH)
+        .text(json!({ "Type": "Notification", "Message": message.to_string() }).to_string())
+        .await;
+    res.assert_status(StatusCode::OK);
+
+    assert!(
+        ctx.repositories
+            .mail_database_repository
+            .get_email_suppression(email)
+            .await
+            .unwrap()
+            .is_some(),
+        "A hard bounce must suppress the address"
+    );
+}
@@ -3,21 +3,24 @@ use serde_json::json;
 use serial_test::serial;
 use test_context::test_context;
 
-use crate::context;
+use crate::{
+    context,
+    helpers::mail::{disable_all_mail_preferences, set_mail_preference},
+};
 
 const BASELINE_EMAIL: &str = "test.user@example.be";
 const BASELINE_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$GvJ0zPtHLrLN0ubKYXtqdw$dAqS9mMzUO55YVmiWPESW60AagJ5px+803z3nuEmH48";
-/// AccountChangement mail type (id=3): bit = 1 << (3 - 1) = 4
-const ACCOUNT_CHANGEMENT_BIT: i32 = 4;
+/// AccountChangement mail type
+const ACCOUNT_CHANGEMENT_TYPE_ID: i32 = 3;
 
 /// Reset the test driver to its test-dataset values.
 ///
 /// This must be called at the start AND end of every test in this module to ensure
-/// that a previous crashed run cannot leave dirty state (email, mail_preferences,
+/// that a previous crashed run cannot leave dirty state (email, mail preferences,
 /// password_hash, verified_at) that would break other tests — notably
 /// `get_mail_preferences::test_get_mail_preferences_success`, which asserts
-/// `mail_preferences = 0` and runs before this module alphabetically (now fixed by
-/// putting `mail_preference_guard` first in mod.rs).
+/// that every preference is disabled and runs before this module alphabetically (now
+/// fixed by putting `mail_preference_guard` first in mod.rs).
 ///
 /// Also removes any phantom driver that may have stolen the baseline email when
 /// the test driver's email was changed away and a signup test ran.
@@ -32,7 +35,6 @@ async fn reset_driver_to_baseline(ctx: &mut context::TestContext) {
     sqlx::query(
         "UPDATE drivers
          SET email           = $1,
-             mail_preferences = 0,
              password_hash   = $2,
              verified_at     = '2026-01-01 00:00:00'
          WHERE pk_driver_id = $3",
@@ -43,28 +45,28 @@ async fn reset_driver_to_baseline(ctx: &mut context::TestContext) {
     .execute(&ctx.repositories.pool)
     .await
     .unwrap();
+
+    disable_all_mail_preferences(&ctx.repositories.pool, ctx.authenticated_user_id).await;
 }
 
 async fn enable_account_changement(ctx: &mut context::TestContext) {
-    sqlx::query(
-        "UPDATE drivers SET mail_preferences = mail_preferences | $1 WHERE pk_driver_id = $2",
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        ACCOUNT_CHANGEMENT_TYPE_ID,
+        true,
     )
-    .bind(ACCOUNT_CHANGEMENT_BIT)
-    .bind(ctx.authenticated_user_id)
-    .execute(&ctx.repositories.pool)
-    .await
-    .unwrap();
+    .await;
 }
 
 async fn disable_account_changement(ctx: &mut context::TestContext) {
-    sqlx::query(
-        "UPDATE drivers SET mail_preferences = mail_preferences & ~$1 WHERE pk_driver_id = $2",
+    set_mail_preference(
+        &ctx.repositories.pool,
+        ctx.authenticated_user_id,
+        ACCOUNT_CHANGEMENT_TYPE_ID,
+        false,
     )
-    .bind(ACCOUNT_CHANGEMENT_BIT)
-    .bind(ctx.authenticated_user_id)
-    .execute(&ctx.repositories.pool)
-    .await
-    .unwrap();
+    .await;
 }
 
 async fn count_mails_by_description(ctx: &context::TestContext, description: &str) -> i64 {
@@ -3,5 +3,8 @@ pub mod get_mail_attachment;
 pub mod get_mail_preferences;
 pub mod get_mail_types;
 pub mod get_mails;
+pub mod mail_actions;
+pub mod mail_events;
 pub mod mail_preference_guard;
+pub mod unsubscribe;
 pub mod update_mail_preference;
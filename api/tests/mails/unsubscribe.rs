@@ -0,0 +1,105 @@
+use api::http::common::api_error::ErrorBody;
+use axum::http::StatusCode;
+use plannify_driver_api_core::domain::{
+    common::config::MailUnsubscribeConfig, mail::entities::DriverMailPreference,
+};
+use serde_json::json;
+use serial_test::serial;
+use test_context::test_context;
+use uuid::Uuid;
+
+use crate::context;
+
+// Type 1 (ACCOUNT_VERIFICATION) : not editable
+// Type 4 (MONTHLY_REPORTS) : editable
+const EDITABLE_TYPE_ID: i32 = 4;
+const NON_EDITABLE_TYPE_ID: i32 = 1;
+
+fn unsubscribe_config() -> MailUnsubscribeConfig {
+    MailUnsubscribeConfig {
+        api_url: "http://localhost:8080".to_string(),
+        secret: "test-unsubscribe-secret".to_string(),
+    }
+}
+
+fn unsubscribe_path(driver_id: Uuid, mail_type_id: i32) -> String {
+    unsubscribe_config()
+        .url(driver_id, mail_type_id)
+        .trim_start_matches("http://localhost:8080")
+        .to_string()
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_unsubscribe_invalid_signature(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post(&format!(
+            "/mails/unsubscribe?driver_id={}&mail_type_id={}&signature=deadbeef",
+            ctx.authenticated_user_id(),
+            EDITABLE_TYPE_ID
+        ))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "INVALID_UNSUBSCRIBE_SIGNATURE");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_unsubscribe_not_editable(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .post(&unsubscribe_path(
+            ctx.authenticated_user_id(),
+            NON_EDITABLE_TYPE_ID,
+        ))
+        .await;
+
+    res.assert_status(StatusCode::BAD_REQUEST);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "MAIL_PREFERENCE_NOT_EDITABLE");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_unsubscribe_success(ctx: &mut context::TestContext) {
+    // Setup : activate the preference first
+    ctx.authenticated_router
+        .put(&format!("/mails/preferences/{}", EDITABLE_TYPE_ID))
+        .json(&json!({ "is_enabled": true }))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let res = ctx
+        .unauthenticated_router
+        .post(&unsubscribe_path(
+            ctx.authenticated_user_id(),
+            EDITABLE_TYPE_ID,
+        ))
+        .text("List-Unsubscribe=One-Click")
+        .await;
+
+    res.assert_status(StatusCode::OK);
+
+    let body: DriverMailPreference = res.json();
+    assert_eq!(body.mail_type_id, EDITABLE_TYPE_ID);
+    assert!(!body.is_enabled);
+
+    // The cached preferences are invalidated
+    let res = ctx.authenticated_router.get("/mails/preferences").await;
+    res.assert_status(StatusCode::OK);
+
+    let preferences: Vec<DriverMailPreference> = res.json();
+    let preference = preferences
+        .iter()
+        .find(|p| p.mail_type_id == EDITABLE_TYPE_ID)
+        .expect("editable preference should be listed");
+    assert!(!preference.is_enabled);
+}
@@ -152,3 +152,36 @@ async fn test_update_mail_preference_invalidates_cache(ctx: &mut context::TestCo
         .await
         .assert_status(StatusCode::OK);
 }
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_mail_preference_push_channel(ctx: &mut context::TestContext) {
+    let res = ctx.authenticated_router.get("/mails/preferences").await;
+    res.assert_status(StatusCode::OK);
+    let before: Vec<DriverMailPreference> = res.json();
+    let pref_before = before
+        .iter()
+        .find(|p| p.mail_type_id == EDITABLE_TYPE_ID)
+        .unwrap();
+
+    let res = ctx
+        .authenticated_router
+        .put(&format!("/mails/preferences/{}", EDITABLE_TYPE_ID))
+        .json(&json!({ "is_enabled": !pref_before.is_push_enabled, "channel": "PUSH" }))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+
+    // Only the push channel changes
+    let body: DriverMailPreference = res.json();
+    assert_eq!(body.is_push_enabled, !pref_before.is_push_enabled);
+    assert_eq!(body.is_enabled, pref_before.is_enabled);
+
+    // Cleanup
+    ctx.authenticated_router
+        .put(&format!("/mails/preferences/{}", EDITABLE_TYPE_ID))
+        .json(&json!({ "is_enabled": pref_before.is_push_enabled, "channel": "PUSH" }))
+        .await
+        .assert_status(StatusCode::OK);
+}
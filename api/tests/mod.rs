@@ -2,4 +2,5 @@ pub mod context;
 pub mod helpers;
 
 pub mod mails;
+pub mod storage;
 pub mod workdays;
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use api::{
+    config::{DocumentDelivery, StorageBackendKind},
+    http::common::api_error::ErrorBody,
+};
+use axum::http::{StatusCode, header};
+use serial_test::serial;
+use test_context::test_context;
+use uuid::Uuid;
+
+use crate::context;
+
+// workday_documents row of (2026, 02) in config/test-dataset.sql
+const DOCUMENT_KEY: &str =
+    "drivers/123e4567-e89b-12d3-a456-426614174000/2026/02/workdays-2026-02.pdf";
+const DOCUMENT_CONTENT: &str = "%PDF-1.4 local file: workdays-2026-02.pdf";
+
+/// Local storage directory holding the (2026, 02) document.
+fn local_storage_dir() -> PathBuf {
+    let root_dir = std::env::temp_dir().join(format!("storage-{}", Uuid::new_v4()));
+    let path = root_dir.join(DOCUMENT_KEY);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, DOCUMENT_CONTENT).unwrap();
+    root_dir
+}
+
+/// Path and query of the presigned URL the document is redirected to.
+async fn presigned_path(router: &axum_test::TestServer) -> String {
+    let res = router.get("/workdays/documents/2026/2").await;
+    res.assert_status(StatusCode::FOUND);
+
+    let location = res
+        .headers()
+        .get(header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    location
+        .strip_prefix("http://localhost:8080")
+        .unwrap_or_else(|| panic!("presigned URL must point to the API, got: {}", location))
+        .to_string()
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_presigned_object_from_local_storage(ctx: &mut context::TestContext) {
+    let root_dir = local_storage_dir();
+    let router = ctx
+        .create_authenticated_router_with_config(|config| {
+            config.s3.storage_backend = StorageBackendKind::LocalFs;
+            config.s3.storage_local_root_dir = root_dir.clone();
+            config.s3.document_delivery = DocumentDelivery::Redirect;
+        })
+        .await;
+
+    let path = presigned_path(&router).await;
+    assert!(path.starts_with(&format!("/storage/{}?expires=", DOCUMENT_KEY)));
+
+    let res = ctx.unauthenticated_router.get(&path).await;
+    // The test context app stores in Garage, it does not serve presigned URLs
+    res.assert_status(StatusCode::NOT_FOUND);
+
+    let res = router.get(&path).await;
+    res.assert_status(StatusCode::OK);
+    res.assert_header(header::CONTENT_TYPE, "application/pdf");
+    assert_eq!(res.as_bytes().as_ref(), DOCUMENT_CONTENT.as_bytes());
+
+    let res = router
+        .get(&path)
+        .add_header(header::RANGE, "bytes=0-7")
+        .await;
+    res.assert_status(StatusCode::PARTIAL_CONTENT);
+    assert_eq!(res.as_bytes().as_ref(), b"%PDF-1.4");
+
+    std::fs::remove_dir_all(root_dir).ok();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_presigned_object_rejects_forged_urls(ctx: &mut context::TestContext) {
+    let root_dir = local_storage_dir();
+    let router = ctx
+        .create_authenticated_router_with_config(|config| {
+            config.s3.storage_backend = StorageBackendKind::LocalFs;
+            config.s3.storage_local_root_dir = root_dir.clone();
+            config.s3.document_delivery = DocumentDelivery::Redirect;
+        })
+        .await;
+
+    let path = presigned_path(&router).await;
+    let (_, query) = path.split_once('?').unwrap();
+
+    // Signature of the document used for another driver's document
+    let res = router
+        .get(&format!(
+            "/storage/drivers/123e4567-e89b-12d3-a456-426614174001/2026/02/workdays-2026-02.pdf?{}",
+            query
+        ))
+        .await;
+    res.assert_status(StatusCode::FORBIDDEN);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "PRESIGNED_URL_INVALID");
+
+    // Expiry pushed back
+    let res = router.get(&path.replace("expires=", "expires=9")).await;
+    res.assert_status(StatusCode::FORBIDDEN);
+
+    std::fs::remove_dir_all(root_dir).ok();
+}
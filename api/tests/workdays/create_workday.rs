@@ -57,6 +57,7 @@ async fn test_create_workday_success(ctx: &mut context::TestContext) {
         end_time: None,
         rest_time: chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
         overnight_rest: false,
+        version: 1,
     };
     verify_workday_content(body, expected_workday);
 
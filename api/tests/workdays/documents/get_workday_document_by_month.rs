@@ -1,5 +1,5 @@
-use api::http::common::api_error::ErrorBody;
-use axum::http::StatusCode;
+use api::{config::DocumentDelivery, http::common::api_error::ErrorBody};
+use axum::http::{StatusCode, header};
 use serial_test::serial;
 use test_context::test_context;
 
@@ -186,3 +186,125 @@ async fn test_get_workday_document_by_month_content_disposition_header(
         disposition
     );
 }
+
+// --- Delivery: streamed from Garage or redirected to a presigned URL ---
+
+// Content seeded in Garage by the test context for (2026, 02)
+const SEEDED_PDF: &str = "%PDF-1.4 test file: workdays-2026-02.pdf";
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_workday_document_by_month_streams_with_length_and_etag(
+    ctx: &mut context::TestContext,
+) {
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/documents/2026/2")
+        .await;
+
+    res.assert_status(StatusCode::OK);
+    res.assert_header(header::CONTENT_LENGTH, SEEDED_PDF.len().to_string());
+    res.assert_header(header::ACCEPT_RANGES, "bytes");
+    assert!(
+        res.headers().contains_key(header::ETAG),
+        "streamed documents must carry the ETag of the stored object"
+    );
+    assert_eq!(res.as_bytes().as_ref(), SEEDED_PDF.as_bytes());
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_workday_document_by_month_range_returns_partial_content(
+    ctx: &mut context::TestContext,
+) {
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/documents/2026/2")
+        .add_header(header::RANGE, "bytes=0-7")
+        .await;
+
+    res.assert_status(StatusCode::PARTIAL_CONTENT);
+    res.assert_header(header::CONTENT_LENGTH, "8");
+    res.assert_header(
+        header::CONTENT_RANGE,
+        format!("bytes 0-7/{}", SEEDED_PDF.len()),
+    );
+    assert_eq!(res.as_bytes().as_ref(), b"%PDF-1.4");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_workday_document_by_month_suffix_range(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/documents/2026/2")
+        .add_header(header::RANGE, "bytes=-4")
+        .await;
+
+    res.assert_status(StatusCode::PARTIAL_CONTENT);
+    assert_eq!(res.as_bytes().as_ref(), b".pdf");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_workday_document_by_month_range_not_satisfiable(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/documents/2026/2")
+        .add_header(header::RANGE, "bytes=100000-")
+        .await;
+
+    res.assert_status(StatusCode::RANGE_NOT_SATISFIABLE);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_RANGE_NOT_SATISFIABLE");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_workday_document_by_month_redirects_to_presigned_url(
+    ctx: &mut context::TestContext,
+) {
+    let router = ctx
+        .create_authenticated_router_with_config(|config| {
+            config.s3.document_delivery = DocumentDelivery::Redirect
+        })
+        .await;
+
+    let res = router.get("/workdays/documents/2026/2").await;
+
+    res.assert_status(StatusCode::FOUND);
+    res.assert_header(header::CACHE_CONTROL, "no-store");
+
+    let location = res
+        .headers()
+        .get(header::LOCATION)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    assert!(
+        location
+            .contains("drivers/123e4567-e89b-12d3-a456-426614174000/2026/02/workdays-2026-02.pdf"),
+        "Location must point to the stored document, got: {}",
+        location
+    );
+    assert!(
+        location.contains("X-Amz-Signature="),
+        "Location must be a presigned URL, got: {}",
+        location
+    );
+
+    // The presigned URL must give the document to a client without credentials
+    let pdf = reqwest::get(&location)
+        .await
+        .expect("presigned URL should be reachable")
+        .bytes()
+        .await
+        .expect("presigned URL should return the document");
+    assert_eq!(pdf.as_ref(), SEEDED_PDF.as_bytes());
+}
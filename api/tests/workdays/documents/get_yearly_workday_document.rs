@@ -0,0 +1,40 @@
+use api::http::common::api_error::ErrorBody;
+use axum::http::StatusCode;
+use serial_test::serial;
+use test_context::test_context;
+
+use crate::context;
+
+// --- Authorization ---
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_yearly_workday_document_unauthorized(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .get("/workdays/documents/2026/annual")
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "UNAUTHORIZED");
+}
+
+// --- Not found ---
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_yearly_workday_document_without_workdays(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/documents/1990/annual")
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_NOT_FOUND");
+}
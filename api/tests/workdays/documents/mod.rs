@@ -1,3 +1,6 @@
 pub mod get_workday_document_by_month;
 pub mod get_workday_document_years;
 pub mod get_workday_documents_by_year;
+pub mod get_yearly_workday_document;
+pub mod share_workday_document;
+pub mod verify_workday_document;
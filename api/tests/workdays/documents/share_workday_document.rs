@@ -0,0 +1,125 @@
+use api::http::common::api_error::ErrorBody;
+use axum::http::{StatusCode, header};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use plannify_driver_api_core::domain::workday::entities::{
+    CreateDocumentShareResponse, DocumentShare, DocumentShareAccess,
+};
+use serde_json::json;
+use serial_test::serial;
+use test_context::test_context;
+
+use crate::context;
+
+/// Path of the share link, which the test routers serve without the public URL.
+fn share_path(url: &str) -> String {
+    let (_, token) = url.rsplit_once("/shared/").expect("Share URL has no token");
+    format!("/shared/{}", token)
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_shared_document_with_password(ctx: &mut context::TestContext) {
+    // (2026, 02): workday_documents row in test-dataset.sql + file uploaded in setup.
+    let res = ctx
+        .authenticated_router
+        .post("/workdays/documents/2026/2/share")
+        .json(&json!({
+            "expires_in_days": 7,
+            "recipient_email": "accounting@employer.be",
+            "password": "employer-2026"
+        }))
+        .await;
+
+    res.assert_status(StatusCode::CREATED);
+    let created: CreateDocumentShareResponse = res.json();
+    assert!(created.share.is_password_protected);
+    assert_eq!((created.share.month, created.share.year), (2, 2026));
+
+    let res = ctx
+        .unauthenticated_router
+        .get(&share_path(&created.url))
+        .await;
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    assert!(res.headers().contains_key(header::WWW_AUTHENTICATE));
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_SHARE_PASSWORD_REQUIRED");
+
+    let res = ctx
+        .unauthenticated_router
+        .get(&share_path(&created.url))
+        .add_header(
+            header::AUTHORIZATION,
+            format!("Basic {}", STANDARD.encode("accountant:employer-2026")),
+        )
+        .await;
+    res.assert_status(StatusCode::OK);
+    assert!(
+        res.headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .contains("application/pdf")
+    );
+
+    let res = ctx
+        .authenticated_router
+        .get(&format!(
+            "/workdays/documents/shares/{}/accesses",
+            created.share.share_id
+        ))
+        .await;
+    res.assert_status(StatusCode::OK);
+    let accesses: Vec<DocumentShareAccess> = res.json();
+    assert_eq!(accesses.len(), 2);
+    assert!(accesses[0].is_granted);
+    assert!(!accesses[1].is_granted);
+
+    let res = ctx
+        .authenticated_router
+        .delete(&format!(
+            "/workdays/documents/shares/{}",
+            created.share.share_id
+        ))
+        .await;
+    res.assert_status(StatusCode::OK);
+    let revoked: DocumentShare = res.json();
+    assert!(revoked.revoked_at.is_some());
+
+    let res = ctx
+        .unauthenticated_router
+        .get(&share_path(&created.url))
+        .await;
+    res.assert_status(StatusCode::FORBIDDEN);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_SHARE_REVOKED");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_share_workday_document_without_stored_document(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .post("/workdays/documents/2035/6/share")
+        .json(&json!({ "expires_in_days": 7 }))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_NOT_FOUND");
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_shared_document_unknown_token(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .get("/shared/unknown-token")
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_SHARE_NOT_FOUND");
+}
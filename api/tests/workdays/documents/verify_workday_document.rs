@@ -0,0 +1,74 @@
+use api::http::common::api_error::ErrorBody;
+use axum::http::StatusCode;
+use plannify_driver_api_core::domain::workday::{
+    entities::{
+        DocumentIntegrity, WorkdayDocumentVerification, document_sha256, new_verification_code,
+    },
+    port::WorkdayDatabaseRepository,
+};
+use serial_test::serial;
+use test_context::test_context;
+
+use crate::context;
+
+const VERIFIED_DOCUMENT_KEY: &str =
+    "drivers/123e4567-e89b-12d3-a456-426614174000/2035/06/workdays-2035-06.pdf";
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_verify_workday_document_without_authentication(ctx: &mut context::TestContext) {
+    let verification_code = new_verification_code();
+    ctx.repositories
+        .workday_database_repository
+        .create_workday_document(
+            ctx.authenticated_user_id,
+            6,
+            2035,
+            VERIFIED_DOCUMENT_KEY.to_string(),
+            "workdays-2035-06.pdf".to_string(),
+            DocumentIntegrity {
+                sha256: document_sha256(b"%PDF-1.4 verified"),
+                verification_code: verification_code.clone(),
+            },
+            false,
+        )
+        .await
+        .expect("Failed to create document");
+
+    let res = ctx
+        .unauthenticated_router
+        .get(&format!(
+            "/documents/verify/{}",
+            verification_code.to_lowercase()
+        ))
+        .await;
+
+    ctx.repositories
+        .workday_database_repository
+        .delete_document_by_s3_path(VERIFIED_DOCUMENT_KEY)
+        .await
+        .ok();
+
+    res.assert_status(StatusCode::OK);
+
+    let body: WorkdayDocumentVerification = res.json();
+    assert_eq!((body.month, body.year), (6, 2035));
+    assert_eq!(body.sha256, document_sha256(b"%PDF-1.4 verified"));
+    assert!(!body.driver_name.is_empty());
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_verify_workday_document_unknown_code(ctx: &mut context::TestContext) {
+    let res = ctx
+        .unauthenticated_router
+        .get(&format!("/documents/verify/{}", new_verification_code()))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "DOCUMENT_NOT_FOUND");
+}
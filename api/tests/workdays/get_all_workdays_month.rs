@@ -1,5 +1,5 @@
 use api::http::common::api_error::ErrorBody;
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use plannify_driver_api_core::domain::workday::entities::Workday;
 use serial_test::serial;
 use test_context::test_context;
@@ -45,6 +45,7 @@ async fn test_get_all_workdays_month_success(ctx: &mut context::TestContext) {
         end_time: chrono::NaiveTime::from_hms_opt(17, 45, 0),
         rest_time: chrono::NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
         overnight_rest: true,
+        version: 1,
     };
     let workday_json1 = &body[0];
     verify_workday_content(*workday_json1, expected_workday1);
@@ -55,6 +56,7 @@ async fn test_get_all_workdays_month_success(ctx: &mut context::TestContext) {
         end_time: None,
         rest_time: chrono::NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
         overnight_rest: false,
+        version: 1,
     };
     let workday_json2 = &body[1];
     verify_workday_content(*workday_json2, expected_workday2);
@@ -200,3 +202,34 @@ async fn test_get_all_workdays_month_cache_invalidation(ctx: &mut context::TestC
         .delete("/workdays/garbage/2026-01-31")
         .await;
 }
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_get_all_workdays_month_not_modified(ctx: &mut context::TestContext) {
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/month?month=1&year=2026")
+        .await;
+
+    res.assert_status(StatusCode::OK);
+    let e_tag = res.header(header::ETAG);
+
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/month?month=1&year=2026")
+        .add_header(header::IF_NONE_MATCH, e_tag.clone())
+        .await;
+
+    res.assert_status(StatusCode::NOT_MODIFIED);
+    assert_eq!(res.header(header::ETAG), e_tag);
+    assert!(res.as_bytes().is_empty());
+
+    let res = ctx
+        .authenticated_router
+        .get("/workdays/month?month=2&year=2026")
+        .add_header(header::IF_NONE_MATCH, e_tag)
+        .await;
+
+    res.assert_status(StatusCode::OK);
+}
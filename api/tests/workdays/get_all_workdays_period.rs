@@ -47,6 +47,7 @@ async fn test_get_all_workdays_period_success(ctx: &mut context::TestContext) {
         end_time: chrono::NaiveTime::from_hms_opt(17, 45, 0),
         rest_time: chrono::NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
         overnight_rest: true,
+        version: 1,
     };
     let workday_json1 = &body.data[0];
     verify_workday_content(*workday_json1, expected_workday1);
@@ -57,6 +58,7 @@ async fn test_get_all_workdays_period_success(ctx: &mut context::TestContext) {
         end_time: None,
         rest_time: chrono::NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
         overnight_rest: false,
+        version: 1,
     };
     let workday_json2 = &body.data[1];
     verify_workday_content(*workday_json2, expected_workday2);
@@ -67,6 +69,7 @@ async fn test_get_all_workdays_period_success(ctx: &mut context::TestContext) {
         end_time: chrono::NaiveTime::from_hms_opt(17, 21, 0),
         rest_time: chrono::NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
         overnight_rest: false,
+        version: 1,
     };
     let workday_json3 = &body.data[2];
     verify_workday_content(*workday_json3, expected_workday3);
@@ -34,6 +34,7 @@ async fn test_get_workday_by_date_success(ctx: &mut context::TestContext) {
         end_time: chrono::NaiveTime::from_hms_opt(17, 45, 0),
         rest_time: chrono::NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
         overnight_rest: true,
+        version: 1,
     };
 
     verify_workday_content(body, expected_workday);
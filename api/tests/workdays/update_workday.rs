@@ -1,5 +1,5 @@
 use api::http::common::api_error::ErrorBody;
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use plannify_driver_api_core::domain::workday::{
     entities::{UpdateWorkdayRequest, Workday},
     port::WorkdayDatabaseRepository,
@@ -59,6 +59,7 @@ async fn test_update_workday_success(ctx: &mut context::TestContext) {
         end_time: None,
         rest_time: chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
         overnight_rest: false,
+        version: 1,
     };
     verify_workday_content(body, expected_workday);
 
@@ -73,6 +74,7 @@ async fn test_update_workday_success(ctx: &mut context::TestContext) {
                 end_time: None,
                 rest_time: chrono::NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
                 overnight_rest: false,
+                version: None,
             },
         )
         .await
@@ -293,8 +295,129 @@ async fn test_update_workday_cache_invalidation(ctx: &mut context::TestContext)
                 end_time: None,
                 rest_time: chrono::NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
                 overnight_rest: false,
+                version: None,
             },
         )
         .await
         .ok();
 }
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_workday_if_match(ctx: &mut context::TestContext) {
+    let res = ctx.authenticated_router.get("/workdays/2026-01-31").await;
+    res.assert_status(StatusCode::OK);
+    let e_tag = res.header(header::ETAG);
+
+    let update = json!({
+        "date": "2026-01-31",
+        "start_time": "08:00:00",
+        "end_time": null,
+        "rest_time": "00:00:00",
+        "overnight_rest": false
+    });
+
+    let res = ctx
+        .authenticated_router
+        .put("/workdays")
+        .add_header(header::IF_MATCH, e_tag.clone())
+        .json(&update)
+        .await;
+
+    res.assert_status(StatusCode::OK);
+    let updated_e_tag = res.header(header::ETAG);
+    assert_ne!(updated_e_tag, e_tag);
+
+    // A second device still holding the first tag must not overwrite the update
+    let res = ctx
+        .authenticated_router
+        .put("/workdays")
+        .add_header(header::IF_MATCH, e_tag)
+        .json(&update)
+        .await;
+
+    res.assert_status(StatusCode::PRECONDITION_FAILED);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "PRECONDITION_FAILED");
+
+    let res = ctx.authenticated_router.get("/workdays/2026-01-31").await;
+    assert_eq!(res.header(header::ETAG), updated_e_tag);
+
+    // Restore original dataset values
+    ctx.repositories
+        .workday_database_repository
+        .update_workday(
+            ctx.authenticated_user_id,
+            UpdateWorkdayRequest {
+                date: chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+                start_time: chrono::NaiveTime::from_hms_opt(7, 40, 0).unwrap(),
+                end_time: None,
+                rest_time: chrono::NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+                overnight_rest: false,
+                version: None,
+            },
+        )
+        .await
+        .unwrap();
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+#[serial]
+async fn test_update_workday_stale_version(ctx: &mut context::TestContext) {
+    let res = ctx.authenticated_router.get("/workdays/2026-01-31").await;
+    res.assert_status(StatusCode::OK);
+    let original: Workday = res.json();
+
+    let update = |start_time: &str| {
+        json!({
+            "date": "2026-01-31",
+            "start_time": start_time,
+            "end_time": null,
+            "rest_time": "00:00:00",
+            "overnight_rest": false,
+            "version": original.version
+        })
+    };
+
+    let res = ctx
+        .authenticated_router
+        .put("/workdays")
+        .json(&update("08:00:00"))
+        .await;
+
+    res.assert_status(StatusCode::OK);
+    let updated: Workday = res.json();
+    assert_eq!(updated.version, original.version + 1);
+
+    // A second device still editing the first version gets the current workday back
+    let res = ctx
+        .authenticated_router
+        .put("/workdays")
+        .json(&update("09:00:00"))
+        .await;
+
+    res.assert_status(StatusCode::CONFLICT);
+    let body: ErrorBody = res.json();
+    assert_eq!(body.error_code, "WORKDAY_VERSION_CONFLICT");
+    let current: Workday = serde_yaml::from_value(body.content.unwrap()).unwrap();
+    assert_eq!(current, updated);
+
+    // Restore original dataset values
+    ctx.repositories
+        .workday_database_repository
+        .update_workday(
+            ctx.authenticated_user_id,
+            UpdateWorkdayRequest {
+                date: chrono::NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+                start_time: chrono::NaiveTime::from_hms_opt(7, 40, 0).unwrap(),
+                end_time: None,
+                rest_time: chrono::NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+                overnight_rest: false,
+                version: None,
+            },
+        )
+        .await
+        .unwrap();
+}
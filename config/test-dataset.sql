@@ -1,6 +1,11 @@
-INSERT INTO "drivers" ("pk_driver_id", "firstname", "lastname", "gender", "email", "password_hash", "phone_number", "is_searchable", "allow_request_professional_agreement", "language", "rest_json", "mail_preferences", "created_at", "verified_at", "last_login_at", "deactivated_at") VALUES
-('123e4567-e89b-12d3-a456-426614174000', 'Test', 'Test', NULL, 'test.user@example.be', '$argon2id$v=19$m=19456,t=2,p=1$GvJ0zPtHLrLN0ubKYXtqdw$dAqS9mMzUO55YVmiWPESW60AagJ5px+803z3nuEmH48', NULL, 't', 'f', 'fr', '[{"end": "00:59:59", "rest": "01:00:00", "start": "00:00:00"}, {"end": "23:59:59", "rest": "01:00:00", "start": "01:00:00"}]', 0, '2026-01-01 00:00:00', '2026-01-01 00:00:00', NULL, NULL),
-('123e4567-e89b-12d3-a456-426614174001', 'Test-bis', 'Test', NULL, 'test-bis.user@example.be', '$argon2id$v=19$m=19456,t=2,p=1$GvJ0zPtHLrLN0ubKYXtqdw$dAqS9mMzUO55YVmiWPESW60AagJ5px+803z3nuEmH48', NULL, 't', 'f', 'fr', NULL, 0, '2026-01-01 00:00:00', '2026-01-01 00:00:00', NULL, NULL);
+INSERT INTO "drivers" ("pk_driver_id", "firstname", "lastname", "gender", "email", "password_hash", "phone_number", "is_searchable", "allow_request_professional_agreement", "language", "rest_json", "created_at", "verified_at", "last_login_at", "deactivated_at") VALUES
+('123e4567-e89b-12d3-a456-426614174000', 'Test', 'Test', NULL, 'test.user@example.be', '$argon2id$v=19$m=19456,t=2,p=1$GvJ0zPtHLrLN0ubKYXtqdw$dAqS9mMzUO55YVmiWPESW60AagJ5px+803z3nuEmH48', NULL, 't', 'f', 'fr', '[{"end": "00:59:59", "rest": "01:00:00", "start": "00:00:00"}, {"end": "23:59:59", "rest": "01:00:00", "start": "01:00:00"}]', '2026-01-01 00:00:00', '2026-01-01 00:00:00', NULL, NULL),
+('123e4567-e89b-12d3-a456-426614174001', 'Test-bis', 'Test', NULL, 'test-bis.user@example.be', '$argon2id$v=19$m=19456,t=2,p=1$GvJ0zPtHLrLN0ubKYXtqdw$dAqS9mMzUO55YVmiWPESW60AagJ5px+803z3nuEmH48', NULL, 't', 'f', 'fr', NULL, '2026-01-01 00:00:00', '2026-01-01 00:00:00', NULL, NULL);
+
+INSERT INTO "driver_notification_preferences" ("fk_driver_id", "fk_mail_type_id", "channel", "frequency", "is_enabled")
+SELECT "driver_id"::uuid, "pk_driver_mail_type_id", 'EMAIL', 'INSTANT', FALSE
+FROM (VALUES ('123e4567-e89b-12d3-a456-426614174000'), ('123e4567-e89b-12d3-a456-426614174001')) AS "test_drivers" ("driver_id")
+CROSS JOIN "driver_mail_types";
 
 INSERT INTO "workdays" ("date", "fk_driver_id", "start_time", "end_time", "rest_time", "overnight_rest") VALUES
 ('2024-01-01', '123e4567-e89b-12d3-a456-426614174000', '08:00:00', '19:00:00', '00:00:00', 't'),
@@ -1,24 +1,35 @@
-use std::sync::Arc;
-
 use lettre::{SmtpTransport, message::MessageBuilder};
 use redis::{Client, aio::ConnectionManager};
 use sqlx::{PgPool, postgres::PgPoolOptions};
-use tera::Tera;
 
 use crate::{
     PostgresHealthRepository, Service, ServiceConfig,
-    domain::common::CoreError,
+    domain::common::{
+        CoreError,
+        config::{DocumentRenderer, StorageBackend},
+    },
     infrastructure::{
-        document::repositories::grpc::GrpcDocumentRepository,
+        document::repositories::{
+            grpc::GrpcDocumentRepository, local::LocalPdfDocumentRepository,
+            renderer::DocumentRendererRepository,
+        },
         driver::repositories::{
             postgres::PostgresDriverRepository, redis::RedisDriverCacheRepository,
         },
         employee::repositories::postgres::PostgresEmployeeRepository,
         mail::repositories::{
             postgres::PostgresMailRepository, redis::RedisMailCacheRepository,
-            smtp::SmtpMailRepository,
+            renderer::MailRenderer, smtp::SmtpMailRepository,
+        },
+        notification::repositories::{
+            postgres::PostgresNotificationRepository, sms::SmsGatewayRepository,
+            web_push::WebPushRepository,
+        },
+        storage::repositories::{
+            backend::StorageBackendRepository, encrypted::EncryptedStorageRepository,
+            local_fs::LocalFsStorageRepository, local_kms::LocalKeyManagementRepository,
+            postgres::PostgresDataKeyRepository, s3::S3StorageRepository,
         },
-        storage::repositories::s3::S3StorageRepository,
         update::repositories::{
             postgres::PostgresUpdateRepository, redis::RedisUpdateCacheRepository,
         },
@@ -30,6 +41,13 @@ use crate::{
 
 use tracing::error;
 
+/// The storage backend, encrypting the objects of each driver with its own data key.
+pub type DriverStorageRepository = EncryptedStorageRepository<
+    StorageBackendRepository,
+    PostgresDataKeyRepository,
+    LocalKeyManagementRepository,
+>;
+
 pub type DriverService = Service<
     PostgresHealthRepository,
     PostgresDriverRepository,
@@ -41,8 +59,10 @@ pub type DriverService = Service<
     RedisMailCacheRepository,
     PostgresUpdateRepository,
     RedisUpdateCacheRepository,
-    GrpcDocumentRepository,
-    S3StorageRepository,
+    DocumentRendererRepository,
+    DriverStorageRepository,
+    PostgresNotificationRepository,
+    SmsGatewayRepository,
 >;
 
 #[derive(Clone)]
@@ -60,8 +80,13 @@ pub struct DriverRepositories {
     pub mail_cache_repository: RedisMailCacheRepository,
     pub update_database_repository: PostgresUpdateRepository,
     pub update_cache_repository: RedisUpdateCacheRepository,
-    pub document_external_repository: GrpcDocumentRepository,
-    pub storage_repository: S3StorageRepository,
+    pub document_external_repository: DocumentRendererRepository,
+    pub storage_repository: DriverStorageRepository,
+    pub data_key_repository: PostgresDataKeyRepository,
+    pub key_management_repository: LocalKeyManagementRepository,
+    pub notification_database_repository: PostgresNotificationRepository,
+    pub web_push_repository: WebPushRepository<PostgresNotificationRepository>,
+    pub sms_repository: SmsGatewayRepository,
     pub service_config: ServiceConfig,
 }
 
@@ -99,10 +124,17 @@ pub async fn create_repositories(
             ))
         })?;
 
-    let tera = match Tera::new("core/templates/mails/**/*.html") {
-        Ok(t) => t,
+    // Anyone could sign the unsubscribe links of any driver with an empty key
+    if service_config.mail_unsubscribe.secret.is_empty() {
+        return Err(CoreError::ServiceUnavailable(
+            "The mail unsubscribe secret must not be empty".to_string(),
+        ));
+    }
+
+    let mail_renderer = match MailRenderer::load("core/templates/mails") {
+        Ok(renderer) => renderer,
         Err(e) => {
-            error!("Templating parsing error: {}", e);
+            error!("Mail templates loading error: {}", e);
             return Err(CoreError::ServiceUnavailable(format!(
                 "Failed to initialize mail templates: {}",
                 e
@@ -110,22 +142,59 @@ pub async fn create_repositories(
         }
     };
 
-    let s3_credentials =
-        aws_sdk_s3::config::Credentials::new(s3_access_key, s3_secret_key, None, None, "Static");
-    let s3_config = aws_sdk_s3::config::Builder::new()
-        .credentials_provider(s3_credentials)
-        .endpoint_url(s3_endpoint)
-        .region(aws_sdk_s3::config::Region::new(s3_region.to_string()))
-        .force_path_style(true)
-        .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
-        .build();
-    let s3_client = aws_sdk_s3::Client::from_conf(s3_config);
+    let mail_dkim = service_config.mail_dkim.signer().inspect_err(|e| {
+        error!("DKIM configuration error: {}", e);
+    })?;
+
+    let storage_repository = match service_config.storage.backend {
+        StorageBackend::S3 => StorageBackendRepository::S3(S3StorageRepository::new(
+            s3_access_key,
+            s3_secret_key,
+            s3_endpoint,
+            s3_region,
+            s3_bucket_name,
+        )),
+        StorageBackend::LocalFs => {
+            let root_dir = &service_config.storage.local_root_dir;
+            std::fs::create_dir_all(root_dir).map_err(|e| {
+                CoreError::ServiceUnavailable(format!(
+                    "Failed to create storage directory {}: {}",
+                    root_dir.display(),
+                    e
+                ))
+            })?;
+            StorageBackendRepository::LocalFs(LocalFsStorageRepository::new(
+                service_config.storage.clone(),
+            ))
+        }
+    };
+
+    if let Some(dataset_dir) = &service_config.password_policy.breached_dataset_dir {
+        // Checked once here rather than failing every password change later on
+        std::fs::File::open(dataset_dir.join("00000.txt")).map_err(|e| {
+            CoreError::ServiceUnavailable(format!(
+                "Failed to read breached password dataset {}: {}",
+                dataset_dir.display(),
+                e
+            ))
+        })?;
+    }
+
+    let key_management_repository =
+        LocalKeyManagementRepository::new(&service_config.storage.encryption).inspect_err(|e| {
+            error!("Storage encryption configuration error: {}", e);
+        })?;
+    let data_key_repository = PostgresDataKeyRepository::new(pg_pool.clone());
 
     let health_repository = PostgresHealthRepository::new(
         pg_pool.clone(),
         redis_manager.clone(),
-        s3_client.clone(),
-        s3_bucket_name.to_string(),
+        storage_repository.clone(),
+    );
+    let storage_repository = EncryptedStorageRepository::new(
+        storage_repository,
+        data_key_repository.clone(),
+        key_management_repository.clone(),
     );
     let driver_database_repository = PostgresDriverRepository::new(pg_pool.clone());
     let driver_cache_repository = RedisDriverCacheRepository::new(redis_manager.clone());
@@ -135,23 +204,44 @@ pub async fn create_repositories(
     let mail_smtp_repository = SmtpMailRepository::new(
         mail_client,
         transport,
-        Arc::new(tera),
-        frontend_url,
+        mail_renderer,
+        frontend_url.clone(),
+        service_config.mail_unsubscribe.clone(),
         is_test_environment,
+        mail_dkim,
     );
     let mail_database_repository = PostgresMailRepository::new(pg_pool.clone());
     let mail_cache_repository = RedisMailCacheRepository::new(redis_manager.clone());
+    let notification_database_repository = PostgresNotificationRepository::new(pg_pool.clone());
+    let web_push_repository = WebPushRepository::new(
+        service_config.web_push.clone(),
+        frontend_url,
+        notification_database_repository.clone(),
+    )
+    .inspect_err(|e| {
+        error!("Web push configuration error: {}", e);
+    })?;
+    let sms_repository = SmsGatewayRepository::new(&service_config.sms);
     let update_database_repository = PostgresUpdateRepository::new(pg_pool.clone());
     let update_cache_repository = RedisUpdateCacheRepository::new(redis_manager.clone());
 
-    let document_external_repository = GrpcDocumentRepository::connect(pdf_service_endpoint)
-        .await
+    let grpc_document_repository = || {
+        GrpcDocumentRepository::connect_lazy(
+            pdf_service_endpoint,
+            service_config.document_service.clone(),
+        )
         .map_err(|e| {
-            CoreError::ServiceUnavailable(format!("Failed to connect to document service: {}", e))
-        })?;
-
-    let storage_repository =
-        S3StorageRepository::new_from_client(s3_client, s3_bucket_name.to_string());
+            CoreError::ServiceUnavailable(format!("Invalid document service endpoint: {}", e))
+        })
+    };
+    let document_external_repository = match service_config.document_service.renderer {
+        DocumentRenderer::Grpc => DocumentRendererRepository::Grpc(grpc_document_repository()?),
+        DocumentRenderer::GrpcWithFallback => DocumentRendererRepository::GrpcWithFallback(
+            grpc_document_repository()?,
+            LocalPdfDocumentRepository,
+        ),
+        DocumentRenderer::Local => DocumentRendererRepository::Local(LocalPdfDocumentRepository),
+    };
 
     Ok(DriverRepositories {
         pool: pg_pool,
@@ -169,6 +259,11 @@ pub async fn create_repositories(
         update_cache_repository,
         document_external_repository,
         storage_repository,
+        data_key_repository,
+        key_management_repository,
+        notification_database_repository,
+        web_push_repository,
+        sms_repository,
         service_config,
     })
 }
@@ -188,6 +283,8 @@ impl From<DriverRepositories> for DriverService {
             val.update_cache_repository,
             val.document_external_repository,
             val.storage_repository,
+            val.notification_database_repository,
+            val.sms_repository,
             val.service_config,
         )
     }
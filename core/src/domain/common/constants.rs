@@ -4,6 +4,10 @@ pub enum EnumDriverMailType {
     PasswordReset,
     AccountChangement,
     MonthlyReports,
+    WorkdayReminders,
+    DocumentsReady,
+    DocumentShared,
+    MagicLink,
 }
 
 impl EnumDriverMailType {
@@ -13,6 +17,27 @@ impl EnumDriverMailType {
             EnumDriverMailType::PasswordReset => 2,
             EnumDriverMailType::AccountChangement => 3,
             EnumDriverMailType::MonthlyReports => 4,
+            EnumDriverMailType::WorkdayReminders => 5,
+            EnumDriverMailType::DocumentsReady => 6,
+            EnumDriverMailType::DocumentShared => 7,
+            EnumDriverMailType::MagicLink => 8,
+        }
+    }
+
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            1 => Some(EnumDriverMailType::AccountVerification),
+            2 => Some(EnumDriverMailType::PasswordReset),
+            3 => Some(EnumDriverMailType::AccountChangement),
+            4 => Some(EnumDriverMailType::MonthlyReports),
+            5 => Some(EnumDriverMailType::WorkdayReminders),
+            6 => Some(EnumDriverMailType::DocumentsReady),
+            7 => Some(EnumDriverMailType::DocumentShared),
+            8 => Some(EnumDriverMailType::MagicLink),
+            _ => None,
         }
     }
 }
+
+/// Prefix of personal API tokens, used to tell them apart from JWTs in the `Authorization` header.
+pub const API_TOKEN_PREFIX: &str = "pdt_";
@@ -4,13 +4,14 @@ use crate::domain::{
     driver::port::{DriverCacheRepository, DriverDatabaseRepository},
     health::port::HealthRepository,
     mail::port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
+    notification::port::{NotificationDatabaseRepository, SmsRepository},
     storage::port::StorageRepository,
     update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
     workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
 };
 
 #[derive(Clone)]
-pub struct Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+pub struct Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -24,6 +25,8 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     pub(crate) health_repository: H,
     pub(crate) driver_database_repository: DD,
@@ -37,12 +40,14 @@ where
     pub(crate) update_cache_repository: UC,
     pub(crate) document_external_repository: DE,
     pub(crate) storage_repository: DS,
+    pub(crate) notification_database_repository: ND,
+    pub(crate) sms_repository: SS,
     pub(crate) config: ServiceConfig,
 }
 
 #[allow(clippy::too_many_arguments)]
-impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
-    Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+    Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -56,6 +61,8 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     pub fn new(
         health_repository: H,
@@ -70,6 +77,8 @@ where
         update_cache_repository: UC,
         document_external_repository: DE,
         storage_repository: DS,
+        notification_database_repository: ND,
+        sms_repository: SS,
         config: ServiceConfig,
     ) -> Self {
         Self {
@@ -85,6 +94,8 @@ where
             update_cache_repository,
             document_external_repository,
             storage_repository,
+            notification_database_repository,
+            sms_repository,
             config,
         }
     }
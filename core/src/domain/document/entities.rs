@@ -0,0 +1,38 @@
+use bytes::Bytes;
+
+/// PDF rendered for a document. The built-in renderer only prints a plain table, its documents
+/// are flagged to be rendered again by the PDF service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedDocument {
+    pub pdf: Bytes,
+    pub is_fallback: bool,
+}
+
+/// Code and verification link the PDF service prints on a stored document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentVerificationCode {
+    pub code: String,
+    pub url: String,
+}
+
+/// State of the circuit breaker guarding the calls to the PDF service.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through.
+    #[default]
+    Closed,
+    /// The service kept failing, calls are rejected without reaching it.
+    Open,
+    /// The open period is over, a trial call decides whether the circuit closes again.
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
@@ -1,13 +1,15 @@
 use std::future::Future;
 
-use bytes::Bytes;
-
 use crate::{
-    domain::workday::entities::Workday,
+    domain::{
+        document::entities::{CircuitState, DocumentVerificationCode, RenderedDocument},
+        workday::entities::{Workday, WorkdayMonthSummary},
+    },
     infrastructure::document::repositories::error::DocumentError,
 };
 
 pub trait DocumentExternalRepository: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
     fn get_workday_documents_by_month(
         &self,
         driver_firstname: String,
@@ -16,7 +18,22 @@ pub trait DocumentExternalRepository: Send + Sync {
         month: i32,
         year: i32,
         workdays: Vec<Workday>,
-    ) -> impl Future<Output = Result<Option<Bytes>, DocumentError>> + Send;
+        verification: Option<DocumentVerificationCode>,
+    ) -> impl Future<Output = Result<Option<RenderedDocument>, DocumentError>> + Send;
+
+    /// Yearly recap from the totals of the months worked, `None` when the service has nothing
+    /// to print.
+    fn get_workday_documents_by_year(
+        &self,
+        driver_firstname: String,
+        driver_lastname: String,
+        language: String,
+        year: i32,
+        months: Vec<WorkdayMonthSummary>,
+    ) -> impl Future<Output = Result<Option<RenderedDocument>, DocumentError>> + Send;
+
+    /// State of the circuit breaker in front of the service, reported by the health check.
+    fn circuit_state(&self) -> CircuitState;
 }
 
 #[derive(Clone, Default)]
@@ -31,7 +48,23 @@ impl DocumentExternalRepository for MockDocumentExternalRepository {
         _month: i32,
         _year: i32,
         _workdays: Vec<Workday>,
-    ) -> Result<Option<bytes::Bytes>, DocumentError> {
+        _verification: Option<DocumentVerificationCode>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        Ok(None)
+    }
+
+    async fn get_workday_documents_by_year(
+        &self,
+        _driver_firstname: String,
+        _driver_lastname: String,
+        _language: String,
+        _year: i32,
+        _months: Vec<WorkdayMonthSummary>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
         Ok(None)
     }
+
+    fn circuit_state(&self) -> CircuitState {
+        CircuitState::Closed
+    }
 }
@@ -25,11 +25,14 @@ pub struct DriverRow {
     pub allow_request_professional_agreement: bool,
     pub language: String,
     pub rest_json: Option<Value>,
-    pub mail_preferences: i32,
     pub created_at: DateTime<Utc>,
     pub verified_at: Option<DateTime<Utc>>,
     pub last_login_at: Option<DateTime<Utc>>,
     pub deactivated_at: Option<DateTime<Utc>>,
+    /// Reset whenever `phone_number` changes.
+    pub phone_verified_at: Option<DateTime<Utc>>,
+    /// Incremented by every update of the profile, `last_login_at` excepted.
+    pub version: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
@@ -60,6 +63,53 @@ impl Display for DriverLanguage {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PasswordPolicyRule {
+    TooWeak,
+    ContainsPersonalInformation,
+    Breached,
+}
+
+impl PasswordPolicyRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PasswordPolicyRule::TooWeak => "too_weak",
+            PasswordPolicyRule::ContainsPersonalInformation => "contains_personal_information",
+            PasswordPolicyRule::Breached => "breached",
+        }
+    }
+
+    pub fn message(&self, language: &DriverLanguage) -> &'static str {
+        match (self, language) {
+            (PasswordPolicyRule::TooWeak, DriverLanguage::FR) => {
+                "Le mot de passe est trop faible. Utilisez une phrase plus longue ou mélangez lettres, chiffres et symboles."
+            }
+            (PasswordPolicyRule::TooWeak, DriverLanguage::EN) => {
+                "The password is too weak. Use a longer phrase or mix letters, numbers and symbols."
+            }
+            (PasswordPolicyRule::ContainsPersonalInformation, DriverLanguage::FR) => {
+                "Le mot de passe ne peut pas contenir votre nom, votre prénom ou votre adresse email."
+            }
+            (PasswordPolicyRule::ContainsPersonalInformation, DriverLanguage::EN) => {
+                "The password cannot contain your first name, last name or email address."
+            }
+            (PasswordPolicyRule::Breached, DriverLanguage::FR) => {
+                "Ce mot de passe apparaît dans une fuite de données connue. Choisissez-en un autre."
+            }
+            (PasswordPolicyRule::Breached, DriverLanguage::EN) => {
+                "This password appears in a known data breach. Please choose another one."
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct PasswordPolicyViolation {
+    pub rule: PasswordPolicyRule,
+    pub message: String,
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateDriverRequest {
     #[validate(length(
@@ -129,13 +179,17 @@ pub struct GetDriverResponse {
     pub lastname: String,
     pub gender: Option<String>,
     pub email: String,
+    /// Set when mails to `email` hard bounced, the driver should change it.
+    pub email_unreachable_at: Option<DateTime<Utc>>,
     pub phone_number: Option<String>,
+    pub phone_verified_at: Option<DateTime<Utc>>,
     pub is_searchable: bool,
     pub allow_request_professional_agreement: bool,
     pub created_at: DateTime<Utc>,
     pub verified_at: Option<DateTime<Utc>>,
-    pub last_login_at: Option<DateTime<Utc>>,
     pub deactivated_at: Option<DateTime<Utc>>,
+    /// To send back in `UpdateDriverRequest::version`.
+    pub version: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, sqlx::Type)]
@@ -276,6 +330,81 @@ pub struct ConfirmPasswordResetRequest {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RequestMagicLinkRequest {
+    #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 255, message = "email cannot be longer than 255 characters"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ConfirmMagicLinkRequest {
+    pub driver_id: Uuid,
+
+    #[validate(length(min = 1, message = "token must be provided"))]
+    pub token: String,
+}
+
+/// One-time code sent by SMS and stored in cache until it is used, it expires or its
+/// attempts run out.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DriverSmsCode {
+    /// Number the code was sent to, changing the number invalidates the code.
+    pub phone_number: String,
+    pub code: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ConfirmPhoneVerificationRequest {
+    #[validate(length(equal = 6, message = "code must contain 6 digits"))]
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RequestSmsLoginRequest {
+    #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 255, message = "email cannot be longer than 255 characters"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ConfirmSmsLoginRequest {
+    #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 255, message = "email cannot be longer than 255 characters"))]
+    pub email: String,
+
+    #[validate(length(equal = 6, message = "code must contain 6 digits"))]
+    pub code: String,
+}
+
+/// Email change stored in cache, first until the new address confirms it, then for the
+/// window during which the previous address can still revert it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DriverEmailChange {
+    pub previous_email: String,
+    pub new_email: String,
+    pub confirm_token: String,
+    pub revert_token: String,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ConfirmEmailChangeRequest {
+    pub driver_id: Uuid,
+
+    #[validate(length(min = 1, message = "token must be provided"))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RevertEmailChangeRequest {
+    pub driver_id: Uuid,
+
+    #[validate(length(min = 1, message = "token must be provided"))]
+    pub token: String,
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateDriverRequest {
     #[validate(length(
@@ -325,4 +454,119 @@ pub struct UpdateDriverRequest {
         message = "language must be 'fr' or 'en'"
     ))]
     pub language: Option<DriverLanguage>,
+
+    /// Version of the profile the update was made from. The update is rejected when the profile
+    /// has changed since, instead of overwriting it.
+    pub version: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+pub enum ApiTokenScope {
+    #[serde(rename = "workdays:read")]
+    WorkdaysRead,
+    #[serde(rename = "workdays:write")]
+    WorkdaysWrite,
+    #[serde(rename = "documents:read")]
+    DocumentsRead,
+    #[serde(rename = "mails:read")]
+    MailsRead,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiTokenScope::WorkdaysRead => "workdays:read",
+            ApiTokenScope::WorkdaysWrite => "workdays:write",
+            ApiTokenScope::DocumentsRead => "documents:read",
+            ApiTokenScope::MailsRead => "mails:read",
+        }
+    }
+}
+
+impl FromStr for ApiTokenScope {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "workdays:read" => Ok(ApiTokenScope::WorkdaysRead),
+            "workdays:write" => Ok(ApiTokenScope::WorkdaysWrite),
+            "documents:read" => Ok(ApiTokenScope::DocumentsRead),
+            "mails:read" => Ok(ApiTokenScope::MailsRead),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for ApiTokenScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct DriverApiTokenRow {
+    pub pk_driver_api_token_id: Uuid,
+    pub fk_driver_id: Uuid,
+    pub label: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DriverApiTokenRow {
+    pub fn to_scopes(&self) -> Vec<ApiTokenScope> {
+        self.scopes
+            .iter()
+            .filter_map(|scope| ApiTokenScope::from_str(scope).ok())
+            .collect()
+    }
+
+    pub fn to_driver_api_token(&self) -> DriverApiToken {
+        DriverApiToken {
+            pk_driver_api_token_id: self.pk_driver_api_token_id,
+            label: self.label.clone(),
+            scopes: self.to_scopes(),
+            expires_at: self.expires_at,
+            last_used_at: self.last_used_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DriverApiToken {
+    pub pk_driver_api_token_id: Uuid,
+    pub label: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateDriverApiTokenRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "label is required and cannot be longer than 100 characters"
+    ))]
+    pub label: String,
+
+    #[validate(length(min = 1, max = 4, message = "between 1 and 4 scopes must be provided"))]
+    pub scopes: Vec<ApiTokenScope>,
+
+    #[validate(range(
+        min = 1,
+        max = 365,
+        message = "expires_in_days must be between 1 and 365"
+    ))]
+    pub expires_in_days: i64,
+}
+
+/// The plain token is only returned once, at creation time.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateDriverApiTokenResponse {
+    pub token: String,
+    pub api_token: DriverApiToken,
 }
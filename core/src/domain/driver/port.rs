@@ -7,8 +7,9 @@ use tracing::error;
 
 use crate::{
     domain::driver::entities::{
-        CreateDriverRequest, CreateDriverRestPeriodRequest, DriverLimitationRow, DriverRestPeriod,
-        DriverRow, DriverSuspensionRow, LoginDriverRequest, UpdateDriverRequest,
+        CreateDriverApiTokenRequest, CreateDriverRequest, CreateDriverRestPeriodRequest,
+        DriverApiTokenRow, DriverEmailChange, DriverLimitationRow, DriverRestPeriod, DriverRow,
+        DriverSuspensionRow, LoginDriverRequest, UpdateDriverRequest,
     },
     infrastructure::driver::repositories::error::DriverError,
 };
@@ -26,6 +27,13 @@ pub trait DriverDatabaseRepository: Send + Sync {
         driver_id: Uuid,
     ) -> impl Future<Output = Result<Option<DriverRow>, DriverError>> + Send;
 
+    /// Counter incremented by the database on every change to the workdays, documents or mails
+    /// of the driver, 0 before the first one.
+    fn get_driver_data_version(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<i64, DriverError>> + Send;
+
     fn get_driver_by_email(
         &self,
         email: String,
@@ -36,11 +44,20 @@ pub trait DriverDatabaseRepository: Send + Sync {
         create_request: CreateDriverRequest,
     ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
 
+    /// Saves `driver` unless the stored driver is no longer at `driver.version`, in which case
+    /// fails with `DriverVersionConflict`. `last_login_at` is left untouched.
     fn update_driver(
         &self,
         driver: DriverRow,
     ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
 
+    /// Records a login without bumping the version of the driver.
+    fn update_driver_last_login(
+        &self,
+        driver_id: Uuid,
+        last_login_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
     fn delete_driver(
         &self,
         driver_id: Uuid,
@@ -91,10 +108,16 @@ pub trait DriverDatabaseRepository: Send + Sync {
         driver_id: Uuid,
     ) -> impl Future<Output = Result<(), DriverError>> + Send;
 
+    /// Active drivers reachable by email or push for the monthly reports.
     fn get_drivers_with_monthly_report_preference(
         &self,
     ) -> impl Future<Output = Result<Vec<DriverRow>, DriverError>> + Send;
 
+    /// Verified and active drivers reachable by email or push for the workday reminders.
+    fn get_drivers_with_workday_reminder_preference(
+        &self,
+    ) -> impl Future<Output = Result<Vec<DriverRow>, DriverError>> + Send;
+
     fn get_drivers_to_delete(
         &self,
     ) -> impl Future<Output = Result<Vec<DriverRow>, DriverError>> + Send;
@@ -103,6 +126,38 @@ pub trait DriverDatabaseRepository: Send + Sync {
         &self,
         driver_id: Uuid,
     ) -> impl Future<Output = Result<Vec<String>, DriverError>> + Send;
+
+    fn create_driver_api_token(
+        &self,
+        api_token: DriverApiTokenRow,
+    ) -> impl Future<Output = Result<DriverApiTokenRow, DriverError>> + Send;
+
+    fn get_driver_api_tokens(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<DriverApiTokenRow>, DriverError>> + Send;
+
+    fn get_driver_api_token_by_hash(
+        &self,
+        token_hash: String,
+    ) -> impl Future<Output = Result<Option<DriverApiTokenRow>, DriverError>> + Send;
+
+    fn update_driver_api_token_last_used(
+        &self,
+        api_token_id: Uuid,
+        last_used_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    fn delete_driver_api_token(
+        &self,
+        driver_id: Uuid,
+        api_token_id: Uuid,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    fn delete_driver_api_tokens(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
 }
 
 pub fn to_title_case(name: String) -> String {
@@ -145,6 +200,12 @@ pub trait DriverService: Send + Sync {
         driver_id: Uuid,
     ) -> impl Future<Output = Result<Option<DriverRow>, DriverError>> + Send;
 
+    /// Changes whenever the workdays, documents or mails of the driver do.
+    fn get_driver_data_version(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<i64, DriverError>> + Send;
+
     fn verify_driver_account(
         &self,
         driver_id: Uuid,
@@ -188,12 +249,43 @@ pub trait DriverService: Send + Sync {
         driver_id: Uuid,
     ) -> impl Future<Output = Result<(), DriverError>> + Send;
 
+    /// A new email address is not applied right away: the returned `DriverEmailChange`
+    /// stays pending until it is confirmed from the new address.
     fn update_driver_info(
         &self,
         driver_id: Uuid,
         update_request: UpdateDriverRequest,
         email_list_deny: Vec<String>,
-    ) -> impl Future<Output = Result<(DriverRow, bool, bool), DriverError>> + Send;
+    ) -> impl Future<Output = Result<(DriverRow, Option<DriverEmailChange>, bool), DriverError>> + Send;
+
+    fn confirm_email_change(
+        &self,
+        driver_id: Uuid,
+        token: String,
+    ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
+
+    /// Cancels a pending email change, or restores the previous address of a change confirmed
+    /// less than `DriverCacheKeyType::ConfirmedEmailChange` ago. Either way, every session and
+    /// API token of the driver is revoked.
+    fn revert_email_change(
+        &self,
+        driver_id: Uuid,
+        token: String,
+    ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
+
+    /// Invalidates every session issued until now, and deletes the driver's API tokens.
+    fn revoke_driver_sessions(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    /// Fails with `DriverError::SessionRevoked` when the session was issued (`iat` claim, in
+    /// seconds) before the driver's sessions were last revoked.
+    fn check_driver_session(
+        &self,
+        driver_id: Uuid,
+        issued_at: i64,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
 
     fn deactivate_driver(
         &self,
@@ -217,9 +309,87 @@ pub trait DriverService: Send + Sync {
         new_password: String,
     ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
 
+    fn request_magic_link(
+        &self,
+        email: String,
+    ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
+
+    /// Consumes the magic link token. A wrong token also discards the pending one,
+    /// so a link can never be guessed in more than one attempt.
+    fn confirm_magic_link(
+        &self,
+        driver_id: Uuid,
+        token: String,
+    ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
+
+    /// Sends a one-time code by SMS to the phone number of the driver.
+    fn request_phone_verification(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    fn confirm_phone_verification(
+        &self,
+        driver_id: Uuid,
+        code: String,
+    ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
+
+    /// Sends a one-time login code by SMS, only to verified phone numbers.
+    fn request_sms_login(
+        &self,
+        email: String,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    /// Each pending code accepts a few wrong attempts before it is discarded.
+    fn confirm_sms_login(
+        &self,
+        email: String,
+        code: String,
+    ) -> impl Future<Output = Result<DriverRow, DriverError>> + Send;
+
+    /// Remembers the device a driver just logged in from, identified by its user agent.
+    /// Returns `true` when the device is new while others were already known, so that the
+    /// very first login after sign up is not reported.
+    fn register_login_device(
+        &self,
+        driver_id: Uuid,
+        user_agent: &str,
+    ) -> impl Future<Output = Result<bool, DriverError>> + Send;
+
     fn get_current_limitation(
         &self,
     ) -> impl Future<Output = Result<Option<DriverLimitationRow>, DriverError>> + Send;
+
+    /// Fails with `DriverError::DriverSuspension` when the driver currently has a suspension
+    /// that denies access to the restricted space. The lookup is cached for a short period
+    /// since it runs on every authenticated request.
+    fn check_driver_suspension(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    /// Returns the plain token alongside the stored row. The plain token is never persisted.
+    fn create_api_token(
+        &self,
+        driver_id: Uuid,
+        create_request: CreateDriverApiTokenRequest,
+    ) -> impl Future<Output = Result<(String, DriverApiTokenRow), DriverError>> + Send;
+
+    fn get_api_tokens(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<DriverApiTokenRow>, DriverError>> + Send;
+
+    fn delete_api_token(
+        &self,
+        driver_id: Uuid,
+        api_token_id: Uuid,
+    ) -> impl Future<Output = Result<(), DriverError>> + Send;
+
+    fn authenticate_api_token(
+        &self,
+        token: String,
+    ) -> impl Future<Output = Result<DriverApiTokenRow, DriverError>> + Send;
 }
 
 #[derive(Clone)]
@@ -227,6 +397,8 @@ pub struct MockDriverDatabaseRepository {
     drivers: Arc<Mutex<Vec<DriverRow>>>,
     limitations: Arc<Mutex<Option<DriverLimitationRow>>>,
     suspensions: Arc<Mutex<Vec<DriverSuspensionRow>>>,
+    api_tokens: Arc<Mutex<Vec<DriverApiTokenRow>>>,
+    document_paths: Arc<Mutex<HashMap<Uuid, Vec<String>>>>,
 }
 
 impl MockDriverDatabaseRepository {
@@ -235,8 +407,20 @@ impl MockDriverDatabaseRepository {
             drivers: Arc::new(Mutex::new(Vec::new())),
             limitations: Arc::new(Mutex::new(None)),
             suspensions: Arc::new(Mutex::new(Vec::new())),
+            api_tokens: Arc::new(Mutex::new(Vec::new())),
+            document_paths: Arc::new(Mutex::new(HashMap::new())),
         }
     }
+
+    /// Records a stored document of the driver, returned by `collect_and_delete_driver_documents`.
+    pub fn add_document(&self, driver_id: Uuid, s3_file_path: &str) {
+        self.document_paths
+            .lock()
+            .unwrap()
+            .entry(driver_id)
+            .or_default()
+            .push(s3_file_path.to_string());
+    }
 }
 
 impl Default for MockDriverDatabaseRepository {
@@ -258,6 +442,10 @@ impl DriverDatabaseRepository for MockDriverDatabaseRepository {
         Ok(driver.cloned())
     }
 
+    async fn get_driver_data_version(&self, _driver_id: Uuid) -> Result<i64, DriverError> {
+        Ok(0)
+    }
+
     async fn get_driver_by_email(&self, email: String) -> Result<DriverRow, DriverError> {
         let drivers = self.drivers.lock().unwrap();
 
@@ -290,11 +478,12 @@ impl DriverDatabaseRepository for MockDriverDatabaseRepository {
             allow_request_professional_agreement: false,
             language: create_request.language.to_string(),
             rest_json: None,
-            mail_preferences: 0,
             created_at: chrono::Utc::now(),
             verified_at: None,
             last_login_at: None,
             deactivated_at: None,
+            phone_verified_at: None,
+            version: 1,
         };
 
         drivers.push(new_driver.clone());
@@ -309,14 +498,34 @@ impl DriverDatabaseRepository for MockDriverDatabaseRepository {
         {
             return Err(DriverError::DriverAlreadyExists);
         }
-        for existing_driver in drivers.iter_mut() {
-            if existing_driver.pk_driver_id == driver.pk_driver_id {
-                *existing_driver = driver.clone();
-                return Ok(driver);
-            }
+        let existing_driver = drivers
+            .iter_mut()
+            .find(|d| d.pk_driver_id == driver.pk_driver_id)
+            .ok_or(DriverError::DriverNotFound)?;
+        if existing_driver.version != driver.version {
+            return Err(DriverError::DriverVersionConflict);
         }
 
-        Err(DriverError::DriverNotFound)
+        *existing_driver = DriverRow {
+            last_login_at: existing_driver.last_login_at,
+            version: driver.version + 1,
+            ..driver
+        };
+        Ok(existing_driver.clone())
+    }
+
+    async fn update_driver_last_login(
+        &self,
+        driver_id: Uuid,
+        last_login_at: DateTime<Utc>,
+    ) -> Result<(), DriverError> {
+        let mut drivers = self.drivers.lock().unwrap();
+        let driver = drivers
+            .iter_mut()
+            .find(|d| d.pk_driver_id == driver_id)
+            .ok_or(DriverError::DriverNotFound)?;
+        driver.last_login_at = Some(last_login_at);
+        Ok(())
     }
 
     async fn delete_driver(&self, driver_id: Uuid) -> Result<(), DriverError> {
@@ -443,13 +652,22 @@ impl DriverDatabaseRepository for MockDriverDatabaseRepository {
     async fn get_drivers_with_monthly_report_preference(
         &self,
     ) -> Result<Vec<DriverRow>, DriverError> {
-        let monthly_report_bit = 1 << (4 - 1);
         let drivers = self.drivers.lock().unwrap();
         let result = drivers
             .iter()
-            .filter(|d| {
-                (d.mail_preferences & monthly_report_bit) != 0 && d.deactivated_at.is_none()
-            })
+            .filter(|d| d.deactivated_at.is_none())
+            .cloned()
+            .collect();
+        Ok(result)
+    }
+
+    async fn get_drivers_with_workday_reminder_preference(
+        &self,
+    ) -> Result<Vec<DriverRow>, DriverError> {
+        let drivers = self.drivers.lock().unwrap();
+        let result = drivers
+            .iter()
+            .filter(|d| d.verified_at.is_some() && d.deactivated_at.is_none())
             .cloned()
             .collect();
         Ok(result)
@@ -468,16 +686,96 @@ impl DriverDatabaseRepository for MockDriverDatabaseRepository {
 
     async fn collect_and_delete_driver_documents(
         &self,
-        _driver_id: Uuid,
+        driver_id: Uuid,
     ) -> Result<Vec<String>, DriverError> {
-        Ok(Vec::new())
+        let mut document_paths = self.document_paths.lock().unwrap();
+        Ok(document_paths.remove(&driver_id).unwrap_or_default())
+    }
+
+    async fn create_driver_api_token(
+        &self,
+        api_token: DriverApiTokenRow,
+    ) -> Result<DriverApiTokenRow, DriverError> {
+        let mut api_tokens = self.api_tokens.lock().unwrap();
+        api_tokens.push(api_token.clone());
+        Ok(api_token)
+    }
+
+    async fn get_driver_api_tokens(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<DriverApiTokenRow>, DriverError> {
+        let api_tokens = self.api_tokens.lock().unwrap();
+        Ok(api_tokens
+            .iter()
+            .filter(|t| t.fk_driver_id == driver_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_driver_api_token_by_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<DriverApiTokenRow>, DriverError> {
+        let api_tokens = self.api_tokens.lock().unwrap();
+        Ok(api_tokens
+            .iter()
+            .find(|t| t.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn update_driver_api_token_last_used(
+        &self,
+        api_token_id: Uuid,
+        last_used_at: DateTime<Utc>,
+    ) -> Result<(), DriverError> {
+        let mut api_tokens = self.api_tokens.lock().unwrap();
+        for api_token in api_tokens.iter_mut() {
+            if api_token.pk_driver_api_token_id == api_token_id {
+                api_token.last_used_at = Some(last_used_at);
+                return Ok(());
+            }
+        }
+        Err(DriverError::ApiTokenNotFound)
+    }
+
+    async fn delete_driver_api_token(
+        &self,
+        driver_id: Uuid,
+        api_token_id: Uuid,
+    ) -> Result<(), DriverError> {
+        let mut api_tokens = self.api_tokens.lock().unwrap();
+        let initial_len = api_tokens.len();
+        api_tokens
+            .retain(|t| !(t.pk_driver_api_token_id == api_token_id && t.fk_driver_id == driver_id));
+        if api_tokens.len() == initial_len {
+            return Err(DriverError::ApiTokenNotFound);
+        }
+        Ok(())
+    }
+
+    async fn delete_driver_api_tokens(&self, driver_id: Uuid) -> Result<(), DriverError> {
+        let mut api_tokens = self.api_tokens.lock().unwrap();
+        api_tokens.retain(|t| t.fk_driver_id != driver_id);
+        Ok(())
     }
 }
 
 pub enum DriverCacheKeyType {
     VerifyEmail,
     ResetPassword,
+    MagicLink,
+    PendingEmailChange,
+    ConfirmedEmailChange,
+    SessionRevocation,
     CurrentLimitation,
+    Suspension,
+    PhoneVerification,
+    SmsLogin,
+    KnownDevices,
+    MailResend,
+    SmsCooldown,
+    SmsDailyCount,
 }
 
 impl DriverCacheKeyType {
@@ -485,7 +783,18 @@ impl DriverCacheKeyType {
         match self {
             DriverCacheKeyType::VerifyEmail => "verify_email",
             DriverCacheKeyType::ResetPassword => "reset_password",
+            DriverCacheKeyType::MagicLink => "magic_link",
+            DriverCacheKeyType::PendingEmailChange => "pending_email_change",
+            DriverCacheKeyType::ConfirmedEmailChange => "confirmed_email_change",
+            DriverCacheKeyType::SessionRevocation => "session_revocation",
             DriverCacheKeyType::CurrentLimitation => "current_limitation",
+            DriverCacheKeyType::Suspension => "suspension",
+            DriverCacheKeyType::PhoneVerification => "phone_verification",
+            DriverCacheKeyType::SmsLogin => "sms_login",
+            DriverCacheKeyType::KnownDevices => "known_devices",
+            DriverCacheKeyType::MailResend => "mail_resend",
+            DriverCacheKeyType::SmsCooldown => "sms_cooldown",
+            DriverCacheKeyType::SmsDailyCount => "sms_daily_count",
         }
     }
 
@@ -493,7 +802,23 @@ impl DriverCacheKeyType {
         match self {
             DriverCacheKeyType::VerifyEmail => 15 * 60,
             DriverCacheKeyType::ResetPassword => 15 * 60,
+            DriverCacheKeyType::MagicLink => 15 * 60,
+            DriverCacheKeyType::PendingEmailChange => 24 * 60 * 60,
+            // Window during which the previous address can still revert a confirmed change
+            DriverCacheKeyType::ConfirmedEmailChange => 3 * 24 * 60 * 60,
+            // Must outlive the refresh tokens issued before the revocation
+            DriverCacheKeyType::SessionRevocation => 365 * 24 * 60 * 60,
             DriverCacheKeyType::CurrentLimitation => 5 * 60,
+            DriverCacheKeyType::Suspension => 60,
+            DriverCacheKeyType::PhoneVerification => 10 * 60,
+            DriverCacheKeyType::SmsLogin => 5 * 60,
+            // Refreshed on every login, a device unused for a year is new again
+            DriverCacheKeyType::KnownDevices => 365 * 24 * 60 * 60,
+            // One resend per driver within the window
+            DriverCacheKeyType::MailResend => 60,
+            // One SMS code per driver within the window
+            DriverCacheKeyType::SmsCooldown => 60,
+            DriverCacheKeyType::SmsDailyCount => 24 * 60 * 60,
         }
     }
 }
@@ -520,6 +845,14 @@ pub trait DriverCacheRepository: Send + Sync {
 
     fn delete_redis(&self, key: String) -> impl Future<Output = Result<(), DriverError>> + Send;
 
+    /// Atomically increments the counter at `key` and returns its new value. The counter expires
+    /// `ttl_seconds` after its first increment.
+    fn increment_redis(
+        &self,
+        key: String,
+        ttl_seconds: u64,
+    ) -> impl Future<Output = Result<i64, DriverError>> + Send;
+
     fn get_key_by_type(&self, driver_id: Uuid, key_type: DriverCacheKeyType) -> (String, u64) {
         (
             self.generate_redis_key(driver_id, key_type.as_str()),
@@ -603,4 +936,17 @@ impl DriverCacheRepository for MockDriverCacheRepository {
         self.cache.lock().unwrap().remove(&key);
         Ok(())
     }
+
+    async fn increment_redis(&self, key: String, ttl_seconds: u64) -> Result<i64, DriverError> {
+        let mut cache = self.cache.lock().unwrap();
+        let now = Utc::now();
+        let (count, expiry) = match cache.get(&key) {
+            Some((value, expiry)) if *expiry > now => {
+                (value.parse::<i64>().unwrap_or(0) + 1, *expiry)
+            }
+            _ => (1, now + chrono::Duration::seconds(ttl_seconds as i64)),
+        };
+        cache.insert(key, (count.to_string(), expiry));
+        Ok(count)
+    }
 }
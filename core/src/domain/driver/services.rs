@@ -1,11 +1,15 @@
 use crate::{
     Service,
     domain::{
+        common::{config::PasswordPolicyConfig, constants::API_TOKEN_PREFIX},
         document::port::DocumentExternalRepository,
         driver::{
             entities::{
-                CreateDriverRequest, CreateDriverRestPeriodRequest, DriverLimitationRow,
-                DriverRestPeriod, DriverRow, LoginDriverRequest, UpdateDriverRequest,
+                CreateDriverApiTokenRequest, CreateDriverRequest, CreateDriverRestPeriodRequest,
+                DriverApiTokenRow, DriverEmailChange, DriverLanguage, DriverLimitationRow,
+                DriverRestPeriod, DriverRow, DriverSmsCode, DriverSuspensionRow,
+                LoginDriverRequest, PasswordPolicyRule, PasswordPolicyViolation,
+                UpdateDriverRequest,
             },
             port::{
                 DriverCacheKeyType, DriverCacheRepository, DriverDatabaseRepository, DriverService,
@@ -14,6 +18,10 @@ use crate::{
         },
         health::port::HealthRepository,
         mail::port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
+        notification::{
+            entities::SmsMessage,
+            port::{NotificationDatabaseRepository, SmsRepository},
+        },
         storage::port::StorageRepository,
         update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
         workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
@@ -23,14 +31,40 @@ use crate::{
 use argon2::{
     Algorithm, Argon2, Params, Version,
     password_hash::PasswordHash,
-    password_hash::{PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+    password_hash::{
+        PasswordHasher, PasswordVerifier, SaltString,
+        rand_core::{OsRng, RngCore},
+    },
 };
+use constant_time_eq::constant_time_eq;
 use serde_json;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::{path::Path, str::FromStr};
 use tracing::error;
 use uuid::Uuid;
 
-impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS> DriverService
-    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+/// `last_used_at` is only written when the stored value is older than this,
+/// to avoid a database write on every request made with the same token.
+const API_TOKEN_LAST_USED_PRECISION_SECONDS: i64 = 60;
+
+/// Name and email parts shorter than this are not looked for in passwords.
+const PASSWORD_PERSONAL_INFORMATION_MIN_LENGTH: usize = 3;
+
+/// Length of the SHA-1 prefix naming a range file of the breached password dataset.
+const BREACHED_PASSWORD_PREFIX_LENGTH: usize = 5;
+
+/// Wrong codes accepted before a pending SMS code is discarded.
+const SMS_CODE_MAX_ATTEMPTS: i64 = 5;
+
+/// SMS codes sent per driver within `DriverCacheKeyType::SmsDailyCount`.
+const SMS_CODES_PER_DAY: i64 = 5;
+
+/// Devices remembered per driver to detect logins from a new one.
+const KNOWN_DEVICES_MAX: usize = 20;
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> DriverService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -44,6 +78,8 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     #[tracing::instrument(
         name = "driver_service.create_driver",
@@ -101,6 +137,18 @@ where
 
         tracing::Span::current().record("email_in_denylist", false);
 
+        check_password_policy(
+            &create_request.password,
+            &[
+                &create_request.firstname,
+                &create_request.lastname,
+                email_local_part(&create_request.email),
+            ],
+            &create_request.language,
+            &self.config.password_policy,
+        )
+        .await?;
+
         let salt = SaltString::generate(&mut OsRng);
         let params = Params::new(19 * 1024, 2, 1, None).map_err(|e| {
             error!("Failed to create Argon2 params for password hashing: {}", e);
@@ -168,15 +216,7 @@ where
             .get_current_driver_suspension(driver.pk_driver_id)
             .await?;
 
-        if let Some(suspension_info) = suspension
-            && !suspension_info.can_access_restricted_space
-        {
-            return Err(DriverError::DriverSuspension {
-                message: suspension_info.driver_message,
-                start_at: suspension_info.start_at.to_rfc3339(),
-                end_at: suspension_info.end_at.map(|dt| dt.to_rfc3339()),
-            });
-        }
+        ensure_not_suspended(suspension)?;
 
         Ok(driver)
     }
@@ -197,6 +237,19 @@ where
         Ok(driver)
     }
 
+    #[tracing::instrument(
+        name = "driver_service.get_driver_data_version",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+        )
+    )]
+    async fn get_driver_data_version(&self, driver_id: Uuid) -> Result<i64, DriverError> {
+        self.driver_database_repository
+            .get_driver_data_version(driver_id)
+            .await
+    }
+
     #[tracing::instrument(
         name = "driver_service.verify_driver_account",
         skip(self),
@@ -239,6 +292,13 @@ where
         Ok(driver)
     }
 
+    #[tracing::instrument(
+        name = "driver_service.get_driver_for_refresh",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+        )
+    )]
     async fn get_driver_for_refresh(&self, driver_id: Uuid) -> Result<DriverRow, DriverError> {
         let driver = self
             .driver_database_repository
@@ -246,12 +306,14 @@ where
             .await?
             .ok_or(DriverError::InvalidRefreshToken)?;
 
+        self.check_driver_suspension(driver.pk_driver_id).await?;
+
         Ok(driver)
     }
 
     async fn generate_tokens<F>(
         &self,
-        mut driver: DriverRow,
+        driver: DriverRow,
         create_tokens: F,
         access_ttl: u64,
         refresh_ttl: u64,
@@ -289,9 +351,8 @@ where
             refresh_token, domain, refresh_ttl
         );
 
-        driver.last_login_at = Some(chrono::Utc::now());
         self.driver_database_repository
-            .update_driver(driver)
+            .update_driver_last_login(driver.pk_driver_id, chrono::Utc::now())
             .await?;
 
         Ok((access_token, access_token_cookie, refresh_token_cookie))
@@ -445,14 +506,23 @@ where
         driver_id: Uuid,
         update_request: UpdateDriverRequest,
         email_list_deny: Vec<String>,
-    ) -> Result<(DriverRow, bool, bool), DriverError> {
+    ) -> Result<(DriverRow, Option<DriverEmailChange>, bool), DriverError> {
         let mut driver = self
             .driver_database_repository
             .get_driver_by_id(driver_id)
             .await?
             .ok_or(DriverError::DriverNotFound)?;
 
-        let mut email_changed = false;
+        // Edits made from an outdated profile are rejected; `update_driver` also rejects the
+        // profile if it changes while this update is being prepared
+        if update_request
+            .version
+            .is_some_and(|version| version != driver.version)
+        {
+            return Err(DriverError::DriverVersionConflict);
+        }
+
+        let mut new_email_pending = None;
         let mut password_changed = false;
 
         if let Some(firstname) = update_request.firstname {
@@ -484,13 +554,38 @@ where
                     });
                 }
                 tracing::Span::current().record("email_in_denylist", false);
-                driver.email = new_email;
-                driver.verified_at = None;
-                email_changed = true;
+
+                match self
+                    .driver_database_repository
+                    .get_driver_by_email(new_email.clone())
+                    .await
+                {
+                    Ok(_) => return Err(DriverError::DriverAlreadyExists),
+                    Err(DriverError::DriverNotFound) => {}
+                    Err(e) => return Err(e),
+                }
+
+                new_email_pending = Some(new_email);
             }
         }
 
         if let Some(password) = update_request.password {
+            let language = match &update_request.language {
+                Some(language) => language.clone(),
+                None => DriverLanguage::from_str(&driver.language).unwrap_or(DriverLanguage::FR),
+            };
+            check_password_policy(
+                &password,
+                &[
+                    &driver.firstname,
+                    &driver.lastname,
+                    email_local_part(&driver.email),
+                ],
+                &language,
+                &self.config.password_policy,
+            )
+            .await?;
+
             let salt = SaltString::generate(&mut OsRng);
             let params = Params::new(19 * 1024, 2, 1, None).map_err(|e| {
                 error!("Failed to create Argon2 params for password hashing: {}", e);
@@ -507,24 +602,225 @@ where
             password_changed = true;
         }
 
-        match update_request.phone_number {
-            Some(Some(phone_number)) => driver.phone_number = Some(phone_number),
-            Some(None) => driver.phone_number = None,
-            None => {}
+        if let Some(phone_number) = update_request.phone_number
+            && phone_number != driver.phone_number
+        {
+            driver.phone_number = phone_number;
+            driver.phone_verified_at = None;
         }
 
         if let Some(language) = update_request.language {
             driver.language = language.to_string();
         }
 
-        tracing::Span::current().record("email_changed", email_changed);
+        tracing::Span::current().record("email_changed", new_email_pending.is_some());
 
         let updated_driver = self
             .driver_database_repository
             .update_driver(driver)
             .await?;
 
-        Ok((updated_driver, email_changed, password_changed))
+        let email_change = match new_email_pending {
+            Some(new_email) => {
+                let email_change = DriverEmailChange {
+                    previous_email: updated_driver.email.clone(),
+                    new_email,
+                    confirm_token: self
+                        .driver_cache_repository
+                        .generate_random_value(100)
+                        .await?,
+                    revert_token: self
+                        .driver_cache_repository
+                        .generate_random_value(100)
+                        .await?,
+                    confirmed_at: None,
+                };
+                self.store_email_change(
+                    driver_id,
+                    DriverCacheKeyType::PendingEmailChange,
+                    &email_change,
+                )
+                .await?;
+                Some(email_change)
+            }
+            None => None,
+        };
+
+        Ok((updated_driver, email_change, password_changed))
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.confirm_email_change",
+        skip(self, token),
+        fields(driver_id = %driver_id)
+    )]
+    async fn confirm_email_change(
+        &self,
+        driver_id: Uuid,
+        token: String,
+    ) -> Result<DriverRow, DriverError> {
+        let mut email_change = self
+            .get_email_change(driver_id, DriverCacheKeyType::PendingEmailChange)
+            .await?
+            .filter(|email_change| email_change.confirm_token == token)
+            .ok_or(DriverError::InvalidEmailChangeToken)?;
+
+        let mut driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await?
+            .ok_or(DriverError::InvalidEmailChangeToken)?;
+
+        let suspension = self
+            .driver_database_repository
+            .get_current_driver_suspension(driver.pk_driver_id)
+            .await?;
+
+        ensure_not_suspended(suspension)?;
+
+        // The address may have been taken since the change was requested
+        match self
+            .driver_database_repository
+            .get_driver_by_email(email_change.new_email.clone())
+            .await
+        {
+            Ok(_) => return Err(DriverError::DriverAlreadyExists),
+            Err(DriverError::DriverNotFound) => {}
+            Err(e) => return Err(e),
+        }
+
+        // The link proves the driver owns the new address
+        driver.email = email_change.new_email.clone();
+        driver.verified_at = Some(chrono::Utc::now());
+
+        let updated_driver = self
+            .driver_database_repository
+            .update_driver(driver)
+            .await?;
+
+        let (pending_key, _) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::PendingEmailChange);
+        self.driver_cache_repository
+            .delete_redis(pending_key)
+            .await?;
+
+        email_change.confirmed_at = Some(chrono::Utc::now());
+        self.store_email_change(
+            driver_id,
+            DriverCacheKeyType::ConfirmedEmailChange,
+            &email_change,
+        )
+        .await?;
+
+        Ok(updated_driver)
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.revert_email_change",
+        skip(self, token),
+        fields(
+            driver_id = %driver_id,
+            reverted_confirmed_change = tracing::field::Empty,
+        )
+    )]
+    async fn revert_email_change(
+        &self,
+        driver_id: Uuid,
+        token: String,
+    ) -> Result<DriverRow, DriverError> {
+        let mut driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await?
+            .ok_or(DriverError::InvalidEmailChangeToken)?;
+
+        let pending = self
+            .get_email_change(driver_id, DriverCacheKeyType::PendingEmailChange)
+            .await?
+            .filter(|email_change| email_change.revert_token == token);
+
+        if pending.is_some() {
+            tracing::Span::current().record("reverted_confirmed_change", false);
+
+            let (pending_key, _) = self
+                .driver_cache_repository
+                .get_key_by_type(driver_id, DriverCacheKeyType::PendingEmailChange);
+            self.driver_cache_repository
+                .delete_redis(pending_key)
+                .await?;
+        } else {
+            let email_change = self
+                .get_email_change(driver_id, DriverCacheKeyType::ConfirmedEmailChange)
+                .await?
+                .filter(|email_change| email_change.revert_token == token)
+                .ok_or(DriverError::InvalidEmailChangeToken)?;
+
+            tracing::Span::current().record("reverted_confirmed_change", true);
+
+            driver.email = email_change.previous_email;
+            driver = self
+                .driver_database_repository
+                .update_driver(driver)
+                .await?;
+
+            let (confirmed_key, _) = self
+                .driver_cache_repository
+                .get_key_by_type(driver_id, DriverCacheKeyType::ConfirmedEmailChange);
+            self.driver_cache_repository
+                .delete_redis(confirmed_key)
+                .await?;
+        }
+
+        self.revoke_driver_sessions(driver_id).await?;
+
+        Ok(driver)
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.revoke_driver_sessions",
+        skip(self),
+        fields(driver_id = %driver_id)
+    )]
+    async fn revoke_driver_sessions(&self, driver_id: Uuid) -> Result<(), DriverError> {
+        let (key, ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::SessionRevocation);
+        self.driver_cache_repository
+            .set_redis(key, chrono::Utc::now().timestamp().to_string(), ttl)
+            .await?;
+
+        self.driver_database_repository
+            .delete_driver_api_tokens(driver_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.check_driver_session",
+        skip(self),
+        fields(driver_id = %driver_id)
+    )]
+    async fn check_driver_session(
+        &self,
+        driver_id: Uuid,
+        issued_at: i64,
+    ) -> Result<(), DriverError> {
+        let (key, _) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::SessionRevocation);
+
+        let revoked_at = self
+            .driver_cache_repository
+            .get_redis(key)
+            .await?
+            .and_then(|value| value.parse::<i64>().ok());
+
+        // `iat` only has a one-second precision, so a session issued during the second of the
+        // revocation is revoked as well.
+        match revoked_at {
+            Some(revoked_at) if issued_at <= revoked_at => Err(DriverError::SessionRevoked),
+            _ => Ok(()),
+        }
     }
 
     #[tracing::instrument(
@@ -634,6 +930,18 @@ where
             .await?
             .ok_or(DriverError::InvalidResetPasswordToken)?;
 
+        check_password_policy(
+            &new_password,
+            &[
+                &driver.firstname,
+                &driver.lastname,
+                email_local_part(&driver.email),
+            ],
+            &DriverLanguage::from_str(&driver.language).unwrap_or(DriverLanguage::FR),
+            &self.config.password_policy,
+        )
+        .await?;
+
         let salt = SaltString::generate(&mut OsRng);
         let params = Params::new(19 * 1024, 2, 1, None).map_err(|e| {
             error!("Failed to create Argon2 params for password hashing: {}", e);
@@ -658,6 +966,225 @@ where
         Ok(updated_driver)
     }
 
+    #[tracing::instrument(
+        name = "driver_service.request_magic_link",
+        skip(self),
+        fields(email = %email)
+    )]
+    async fn request_magic_link(&self, email: String) -> Result<DriverRow, DriverError> {
+        let email = to_email_case(email);
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_email(email)
+            .await?;
+
+        let (redis_key, _) = self
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::MagicLink);
+        let existing_token = self.driver_cache_repository.get_redis(redis_key).await?;
+
+        if existing_token.is_some() {
+            return Err(DriverError::MagicLinkTokenAlreadyExists);
+        }
+
+        Ok(driver)
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.confirm_magic_link",
+        skip(self, token),
+        fields(driver_id = %driver_id)
+    )]
+    async fn confirm_magic_link(
+        &self,
+        driver_id: Uuid,
+        token: String,
+    ) -> Result<DriverRow, DriverError> {
+        let (redis_key, _) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::MagicLink);
+        let stored_token = self
+            .driver_cache_repository
+            .get_redis(redis_key.clone())
+            .await?;
+
+        // A wrong token leaves the link pending, so that it cannot be cancelled by guessing
+        if stored_token != Some(token) {
+            return Err(DriverError::InvalidMagicLinkToken);
+        }
+
+        self.driver_cache_repository.delete_redis(redis_key).await?;
+
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await?
+            .ok_or(DriverError::InvalidMagicLinkToken)?;
+
+        let suspension = self
+            .driver_database_repository
+            .get_current_driver_suspension(driver.pk_driver_id)
+            .await?;
+
+        ensure_not_suspended(suspension)?;
+
+        Ok(driver)
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.request_phone_verification",
+        skip(self),
+        fields(driver_id = %driver_id)
+    )]
+    async fn request_phone_verification(&self, driver_id: Uuid) -> Result<(), DriverError> {
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await?
+            .ok_or(DriverError::DriverNotFound)?;
+
+        if driver.phone_number.is_none() {
+            return Err(DriverError::PhoneNumberMissing);
+        }
+        if driver.phone_verified_at.is_some() {
+            return Err(DriverError::PhoneNumberAlreadyVerified);
+        }
+
+        self.send_sms_code(&driver, DriverCacheKeyType::PhoneVerification, |code| {
+            SmsMessage::PhoneVerificationCode { code }
+        })
+        .await
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.confirm_phone_verification",
+        skip(self, code),
+        fields(driver_id = %driver_id)
+    )]
+    async fn confirm_phone_verification(
+        &self,
+        driver_id: Uuid,
+        code: String,
+    ) -> Result<DriverRow, DriverError> {
+        let mut driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await?
+            .ok_or(DriverError::DriverNotFound)?;
+
+        self.check_sms_code(&driver, DriverCacheKeyType::PhoneVerification, &code)
+            .await?;
+
+        driver.phone_verified_at = Some(chrono::Utc::now());
+        self.driver_database_repository.update_driver(driver).await
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.request_sms_login",
+        skip(self),
+        fields(email = %email)
+    )]
+    async fn request_sms_login(&self, email: String) -> Result<(), DriverError> {
+        if !self.config.sms.login_enabled {
+            return Err(DriverError::SmsLoginDisabled);
+        }
+
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_email(to_email_case(email))
+            .await?;
+
+        if driver.phone_number.is_none() || driver.phone_verified_at.is_none() {
+            return Err(DriverError::PhoneNumberNotVerified);
+        }
+
+        self.send_sms_code(&driver, DriverCacheKeyType::SmsLogin, |code| {
+            SmsMessage::LoginCode { code }
+        })
+        .await
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.confirm_sms_login",
+        skip(self, code),
+        fields(email = %email)
+    )]
+    async fn confirm_sms_login(
+        &self,
+        email: String,
+        code: String,
+    ) -> Result<DriverRow, DriverError> {
+        if !self.config.sms.login_enabled {
+            return Err(DriverError::SmsLoginDisabled);
+        }
+
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_email(to_email_case(email))
+            .await
+            .map_err(|_| DriverError::InvalidSmsCode)?;
+
+        if driver.phone_verified_at.is_none() {
+            return Err(DriverError::InvalidSmsCode);
+        }
+
+        self.check_sms_code(&driver, DriverCacheKeyType::SmsLogin, &code)
+            .await?;
+
+        let suspension = self
+            .driver_database_repository
+            .get_current_driver_suspension(driver.pk_driver_id)
+            .await?;
+
+        ensure_not_suspended(suspension)?;
+
+        Ok(driver)
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.register_login_device",
+        skip(self, user_agent),
+        fields(driver_id = %driver_id)
+    )]
+    async fn register_login_device(
+        &self,
+        driver_id: Uuid,
+        user_agent: &str,
+    ) -> Result<bool, DriverError> {
+        let (key, ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::KnownDevices);
+
+        let mut known_devices: Vec<String> =
+            match self.driver_cache_repository.get_redis(key.clone()).await? {
+                Some(cached) => serde_json::from_str(&cached).map_err(|e| {
+                    error!("Failed to deserialize known devices from cache: {}", e);
+                    DriverError::Internal
+                })?,
+                None => Vec::new(),
+            };
+
+        let device = hex::encode(Sha256::digest(user_agent.as_bytes()));
+        let is_new = !known_devices.is_empty() && !known_devices.contains(&device);
+
+        // Most recently used last, the oldest devices are forgotten first
+        known_devices.retain(|d| *d != device);
+        known_devices.push(device);
+        if known_devices.len() > KNOWN_DEVICES_MAX {
+            known_devices.drain(..known_devices.len() - KNOWN_DEVICES_MAX);
+        }
+
+        let serialized = serde_json::to_string(&known_devices).map_err(|e| {
+            error!("Failed to serialize known devices for cache: {}", e);
+            DriverError::Internal
+        })?;
+        self.driver_cache_repository
+            .set_redis(key, serialized, ttl)
+            .await?;
+
+        Ok(is_new)
+    }
+
     #[tracing::instrument(name = "driver_service.get_current_limitation", skip(self))]
     async fn get_current_limitation(&self) -> Result<Option<DriverLimitationRow>, DriverError> {
         let (key, ttl) = self
@@ -694,4 +1221,480 @@ where
 
         Ok(limitation)
     }
+
+    #[tracing::instrument(
+        name = "driver_service.check_driver_suspension",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+            cache_hit = tracing::field::Empty,
+        )
+    )]
+    async fn check_driver_suspension(&self, driver_id: Uuid) -> Result<(), DriverError> {
+        let (key, ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::Suspension);
+
+        if let Some(cached) = self.driver_cache_repository.get_redis(key.clone()).await? {
+            tracing::Span::current().record("cache_hit", true);
+            let suspension: Option<DriverSuspensionRow> =
+                serde_json::from_str(&cached).map_err(|e| {
+                    error!("Failed to deserialize driver suspension from cache: {}", e);
+                    DriverError::Internal
+                })?;
+            return ensure_not_suspended(suspension);
+        }
+
+        tracing::Span::current().record("cache_hit", false);
+
+        let suspension = self
+            .driver_database_repository
+            .get_current_driver_suspension(driver_id)
+            .await?;
+
+        let serialized = serde_json::to_string(&suspension).map_err(|e| {
+            error!("Failed to serialize driver suspension for cache: {}", e);
+            DriverError::Internal
+        })?;
+
+        self.driver_cache_repository
+            .set_redis(key, serialized, ttl)
+            .await?;
+
+        ensure_not_suspended(suspension)
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.create_api_token",
+        skip(self, create_request),
+        fields(
+            driver_id = %driver_id,
+            label = %create_request.label,
+        )
+    )]
+    async fn create_api_token(
+        &self,
+        driver_id: Uuid,
+        create_request: CreateDriverApiTokenRequest,
+    ) -> Result<(String, DriverApiTokenRow), DriverError> {
+        let random_value = self
+            .driver_cache_repository
+            .generate_random_value(40)
+            .await?;
+        let token = format!("{}{}", API_TOKEN_PREFIX, random_value);
+
+        let mut scopes: Vec<String> = create_request
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect();
+        scopes.sort();
+        scopes.dedup();
+
+        let now = chrono::Utc::now();
+        let api_token = self
+            .driver_database_repository
+            .create_driver_api_token(DriverApiTokenRow {
+                pk_driver_api_token_id: Uuid::new_v4(),
+                fk_driver_id: driver_id,
+                label: create_request.label,
+                token_hash: hash_api_token(&token),
+                scopes,
+                expires_at: now + chrono::Duration::days(create_request.expires_in_days),
+                last_used_at: None,
+                created_at: now,
+            })
+            .await?;
+
+        Ok((token, api_token))
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.get_api_tokens",
+        skip(self),
+        fields(driver_id = %driver_id)
+    )]
+    async fn get_api_tokens(&self, driver_id: Uuid) -> Result<Vec<DriverApiTokenRow>, DriverError> {
+        self.driver_database_repository
+            .get_driver_api_tokens(driver_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.delete_api_token",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+            api_token_id = %api_token_id,
+        )
+    )]
+    async fn delete_api_token(
+        &self,
+        driver_id: Uuid,
+        api_token_id: Uuid,
+    ) -> Result<(), DriverError> {
+        self.driver_database_repository
+            .delete_driver_api_token(driver_id, api_token_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "driver_service.authenticate_api_token",
+        skip(self, token),
+        fields(driver_id = tracing::field::Empty)
+    )]
+    async fn authenticate_api_token(
+        &self,
+        token: String,
+    ) -> Result<DriverApiTokenRow, DriverError> {
+        if !token.starts_with(API_TOKEN_PREFIX) {
+            return Err(DriverError::InvalidApiToken);
+        }
+
+        let mut api_token = self
+            .driver_database_repository
+            .get_driver_api_token_by_hash(hash_api_token(&token))
+            .await?
+            .ok_or(DriverError::InvalidApiToken)?;
+
+        tracing::Span::current().record("driver_id", api_token.fk_driver_id.to_string());
+
+        let now = chrono::Utc::now();
+        if api_token.expires_at <= now {
+            return Err(DriverError::InvalidApiToken);
+        }
+
+        let is_stale = api_token.last_used_at.is_none_or(|last_used_at| {
+            now - last_used_at > chrono::Duration::seconds(API_TOKEN_LAST_USED_PRECISION_SECONDS)
+        });
+        if is_stale {
+            self.driver_database_repository
+                .update_driver_api_token_last_used(api_token.pk_driver_api_token_id, now)
+                .await?;
+            api_token.last_used_at = Some(now);
+        }
+
+        Ok(api_token)
+    }
+}
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+    Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+where
+    H: HealthRepository,
+    DD: DriverDatabaseRepository,
+    DC: DriverCacheRepository,
+    WD: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    MS: MailSmtpRepository,
+    MD: MailDatabaseRepository,
+    MC: MailCacheRepository,
+    UD: UpdateDatabaseRepository,
+    UC: UpdateCacheRepository,
+    DE: DocumentExternalRepository,
+    DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
+{
+    async fn get_email_change(
+        &self,
+        driver_id: Uuid,
+        key_type: DriverCacheKeyType,
+    ) -> Result<Option<DriverEmailChange>, DriverError> {
+        let (key, _) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, key_type);
+
+        self.driver_cache_repository
+            .get_redis(key)
+            .await?
+            .map(|cached| {
+                serde_json::from_str(&cached).map_err(|e| {
+                    error!("Failed to deserialize email change from cache: {}", e);
+                    DriverError::Internal
+                })
+            })
+            .transpose()
+    }
+
+    async fn store_email_change(
+        &self,
+        driver_id: Uuid,
+        key_type: DriverCacheKeyType,
+        email_change: &DriverEmailChange,
+    ) -> Result<(), DriverError> {
+        let (key, ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, key_type);
+
+        let serialized = serde_json::to_string(email_change).map_err(|e| {
+            error!("Failed to serialize email change for cache: {}", e);
+            DriverError::Internal
+        })?;
+
+        self.driver_cache_repository
+            .set_redis(key, serialized, ttl)
+            .await
+    }
+
+    /// Fails with `DriverError::SmsCodeAlreadyExists` while a previous code is still pending, and
+    /// with `DriverError::SmsRateLimited` when the driver got a code less than
+    /// `DriverCacheKeyType::SmsCooldown` ago or `SMS_CODES_PER_DAY` codes already.
+    async fn send_sms_code<F>(
+        &self,
+        driver: &DriverRow,
+        key_type: DriverCacheKeyType,
+        message: F,
+    ) -> Result<(), DriverError>
+    where
+        F: FnOnce(String) -> SmsMessage + Send,
+    {
+        let phone_number = driver
+            .phone_number
+            .clone()
+            .ok_or(DriverError::PhoneNumberMissing)?;
+
+        let (key, ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, key_type);
+
+        if self
+            .driver_cache_repository
+            .get_redis(key.clone())
+            .await?
+            .is_some()
+        {
+            return Err(DriverError::SmsCodeAlreadyExists);
+        }
+
+        let (cooldown_key, cooldown_ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::SmsCooldown);
+        if self
+            .driver_cache_repository
+            .get_redis(cooldown_key.clone())
+            .await?
+            .is_some()
+        {
+            return Err(DriverError::SmsRateLimited);
+        }
+        let (daily_key, daily_ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::SmsDailyCount);
+        if self
+            .driver_cache_repository
+            .increment_redis(daily_key, daily_ttl)
+            .await?
+            > SMS_CODES_PER_DAY
+        {
+            return Err(DriverError::SmsRateLimited);
+        }
+        self.driver_cache_repository
+            .set_redis(cooldown_key, String::new(), cooldown_ttl)
+            .await?;
+
+        self.driver_cache_repository
+            .delete_redis(sms_code_attempts_key(&key))
+            .await?;
+        let sms_code = DriverSmsCode {
+            phone_number: phone_number.clone(),
+            code: generate_sms_code(),
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(ttl as i64),
+        };
+        let serialized = serde_json::to_string(&sms_code).map_err(|e| {
+            error!("Failed to serialize SMS code for cache: {}", e);
+            DriverError::Internal
+        })?;
+        self.driver_cache_repository
+            .set_redis(key.clone(), serialized, ttl)
+            .await?;
+
+        if let Err(e) = self
+            .sms_repository
+            .send_sms(
+                &phone_number,
+                &message(sms_code.code).text(&driver.language),
+            )
+            .await
+        {
+            error!("Failed to send SMS code: {:?}", e);
+            self.driver_cache_repository.delete_redis(key).await?;
+            return Err(DriverError::SmsSendError);
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the pending code when it matches, and counts a failed attempt otherwise.
+    async fn check_sms_code(
+        &self,
+        driver: &DriverRow,
+        key_type: DriverCacheKeyType,
+        code: &str,
+    ) -> Result<(), DriverError> {
+        let (key, _) = self
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, key_type);
+
+        let Some(cached) = self.driver_cache_repository.get_redis(key.clone()).await? else {
+            return Err(DriverError::InvalidSmsCode);
+        };
+        let sms_code: DriverSmsCode = serde_json::from_str(&cached).map_err(|e| {
+            error!("Failed to deserialize SMS code from cache: {}", e);
+            DriverError::Internal
+        })?;
+
+        if driver.phone_number.as_deref() != Some(sms_code.phone_number.as_str()) {
+            self.driver_cache_repository.delete_redis(key).await?;
+            return Err(DriverError::InvalidSmsCode);
+        }
+
+        // Counted before comparing, so that parallel guesses each use up an attempt
+        let remaining_ttl = (sms_code.expires_at - chrono::Utc::now()).num_seconds();
+        let attempts = self
+            .driver_cache_repository
+            .increment_redis(sms_code_attempts_key(&key), remaining_ttl.max(1) as u64)
+            .await?;
+        if attempts > SMS_CODE_MAX_ATTEMPTS {
+            self.driver_cache_repository.delete_redis(key).await?;
+            return Err(DriverError::InvalidSmsCode);
+        }
+
+        if constant_time_eq(sms_code.code.as_bytes(), code.as_bytes()) {
+            self.driver_cache_repository
+                .delete_redis(sms_code_attempts_key(&key))
+                .await?;
+            self.driver_cache_repository.delete_redis(key).await?;
+            return Ok(());
+        }
+
+        if attempts == SMS_CODE_MAX_ATTEMPTS {
+            self.driver_cache_repository.delete_redis(key).await?;
+        }
+
+        Err(DriverError::InvalidSmsCode)
+    }
+}
+
+/// Failed attempts on the pending SMS code stored at `key`, kept apart from the code so that they
+/// are counted atomically.
+fn sms_code_attempts_key(key: &str) -> String {
+    format!("{}:attempts", key)
+}
+
+/// Six random digits, drawn without modulo bias.
+fn generate_sms_code() -> String {
+    const RANGE: u32 = 1_000_000;
+    let zone = u32::MAX - u32::MAX % RANGE;
+    loop {
+        let value = OsRng.next_u32();
+        if value < zone {
+            return format!("{:06}", value % RANGE);
+        }
+    }
+}
+
+/// API tokens are long random values, so a plain SHA-256 is enough to store them
+/// (unlike passwords, they don't need a slow hash).
+fn hash_api_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// A cached suspension may have ended (or not started yet) since it was stored,
+/// so the period is checked again against the current time.
+fn ensure_not_suspended(suspension: Option<DriverSuspensionRow>) -> Result<(), DriverError> {
+    let now = chrono::Utc::now();
+    match suspension {
+        Some(suspension)
+            if !suspension.can_access_restricted_space
+                && suspension.start_at <= now
+                && suspension.end_at.is_none_or(|end_at| end_at >= now) =>
+        {
+            Err(DriverError::DriverSuspension {
+                message: suspension.driver_message,
+                start_at: suspension.start_at.to_rfc3339(),
+                end_at: suspension.end_at.map(|dt| dt.to_rfc3339()),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks a new password against the configured policy and reports every failing rule,
+/// localized in the driver's language.
+async fn check_password_policy(
+    password: &str,
+    personal_information: &[&str],
+    language: &DriverLanguage,
+    policy: &PasswordPolicyConfig,
+) -> Result<(), DriverError> {
+    let mut rules = Vec::new();
+
+    let score = passwords::scorer::score(&passwords::analyzer::analyze(password));
+    if score < policy.min_score {
+        rules.push(PasswordPolicyRule::TooWeak);
+    }
+
+    let lowercase_password = password.to_lowercase();
+    let contains_personal_information = personal_information
+        .iter()
+        .flat_map(|information| {
+            information
+                .to_lowercase()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|part| part.chars().count() >= PASSWORD_PERSONAL_INFORMATION_MIN_LENGTH)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .any(|part| lowercase_password.contains(&part));
+    if contains_personal_information {
+        rules.push(PasswordPolicyRule::ContainsPersonalInformation);
+    }
+
+    if let Some(dataset_dir) = &policy.breached_dataset_dir
+        && is_password_breached(password, dataset_dir).await?
+    {
+        rules.push(PasswordPolicyRule::Breached);
+    }
+
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    Err(DriverError::PasswordPolicyViolation {
+        violations: rules
+            .into_iter()
+            .map(|rule| PasswordPolicyViolation {
+                rule,
+                message: rule.message(language).to_string(),
+            })
+            .collect(),
+    })
+}
+
+/// k-anonymity lookup: only the range file matching the first characters of the SHA-1
+/// is read, so the dataset can be mirrored with the same layout as the public range API.
+/// The mirror holds a file for every prefix: a missing one fails instead of accepting the
+/// password unchecked.
+async fn is_password_breached(password: &str, dataset_dir: &Path) -> Result<bool, DriverError> {
+    let digest = hex::encode_upper(Sha1::digest(password.as_bytes()));
+    let (prefix, suffix) = digest.split_at(BREACHED_PASSWORD_PREFIX_LENGTH);
+
+    let range = tokio::fs::read_to_string(dataset_dir.join(format!("{}.txt", prefix)))
+        .await
+        .map_err(|e| {
+            error!("Failed to read breached password range {}: {}", prefix, e);
+            DriverError::Internal
+        })?;
+
+    Ok(range.lines().any(|line| match line.trim().split_once(':') {
+        Some((line_suffix, count)) => {
+            line_suffix.eq_ignore_ascii_case(suffix) && count.parse::<u64>().is_ok_and(|c| c > 0)
+        }
+        None => false,
+    }))
+}
+
+fn email_local_part(email: &str) -> &str {
+    email.split('@').next().unwrap_or(email)
 }
@@ -1,10 +1,16 @@
-use crate::infrastructure::health::repositories::error::HealthError;
+use crate::{
+    domain::document::entities::CircuitState,
+    infrastructure::health::repositories::error::HealthError,
+};
 
 #[derive(Clone)]
 pub struct IsHealthy {
     pub database: bool,
     pub cache: bool,
     pub storage: bool,
+    /// The API keeps serving everything but the document generation while the circuit is
+    /// open, so it does not make the service unhealthy.
+    pub document_service: CircuitState,
 }
 
 impl IsHealthy {
@@ -13,6 +19,7 @@ impl IsHealthy {
             database,
             cache,
             storage,
+            document_service: CircuitState::Closed,
         }
     }
 
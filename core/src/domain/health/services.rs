@@ -8,6 +8,7 @@ use crate::{
             port::{HealthRepository, HealthService},
         },
         mail::port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
+        notification::port::{NotificationDatabaseRepository, SmsRepository},
         storage::port::StorageRepository,
         update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
         workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
@@ -15,8 +16,8 @@ use crate::{
     infrastructure::health::repositories::error::HealthError,
 };
 
-impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS> HealthService
-    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> HealthService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -30,8 +31,12 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     async fn check_health(&self) -> Result<IsHealthy, HealthError> {
-        self.health_repository.ping().await.to_result()
+        let mut health = self.health_repository.ping().await;
+        health.document_service = self.document_external_repository.circuit_state();
+        health.to_result()
     }
 }
@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::prelude::FromRow;
+use tracing::info;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
@@ -13,6 +15,8 @@ pub struct DriverMailTypeRow {
     pub label: String,
     pub index: i32,
     pub is_editable: bool,
+    /// Whether the mail type is sent to drivers who never changed their preference for it.
+    pub default_is_enabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
@@ -38,6 +42,8 @@ pub enum MailStatus {
     PENDING,
     SUCCESS,
     FAILED,
+    /// Withdrawn by the driver, the link it carried no longer working.
+    CANCELLED,
 }
 
 impl FromStr for MailStatus {
@@ -47,6 +53,7 @@ impl FromStr for MailStatus {
             "PENDING" => Ok(MailStatus::PENDING),
             "SUCCESS" => Ok(MailStatus::SUCCESS),
             "FAILED" => Ok(MailStatus::FAILED),
+            "CANCELLED" => Ok(MailStatus::CANCELLED),
             _ => Err(()),
         }
     }
@@ -64,6 +71,8 @@ pub struct DriverMailRow {
     pub content: Option<String>,
     pub created_at: DateTime<Utc>,
     pub sent_at: Option<DateTime<Utc>>,
+    /// The mail this one is a resend of, chaining the resends of a mail in the history.
+    pub fk_resent_from_mail_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -82,6 +91,11 @@ pub struct DriverMailAttachment {
     pub created_at: DateTime<Utc>,
 }
 
+/// Name of the monthly report PDF attached to its mail, such as `workdays-2026-09.pdf`.
+pub fn monthly_report_file_name(month: u32, year: i32) -> String {
+    format!("workdays-{}-{:02}.pdf", year, month)
+}
+
 impl DriverMailAttachmentRow {
     pub fn to_driver_mail_attachment(&self) -> DriverMailAttachment {
         DriverMailAttachment {
@@ -90,6 +104,16 @@ impl DriverMailAttachmentRow {
             created_at: self.created_at,
         }
     }
+
+    /// The `(month, year)` of a monthly report attachment, read back from its file name.
+    pub fn monthly_report_period(&self) -> Option<(u32, i32)> {
+        let period = self
+            .file_name
+            .strip_prefix("workdays-")?
+            .strip_suffix(".pdf")?;
+        let (year, month) = period.split_once('-')?;
+        Some((month.parse().ok()?, year.parse().ok()?))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
@@ -102,6 +126,7 @@ pub struct DriverMail {
     pub content: Option<String>,
     pub created_at: DateTime<Utc>,
     pub sent_at: Option<DateTime<Utc>>,
+    pub resent_from_mail_id: Option<Uuid>,
     pub attachments: Vec<DriverMailAttachment>,
 }
 
@@ -120,6 +145,7 @@ impl DriverMailRow {
             content: self.content.clone(),
             created_at: self.created_at,
             sent_at: self.sent_at,
+            resent_from_mail_id: self.fk_resent_from_mail_id,
             attachments,
         }
     }
@@ -130,17 +156,63 @@ pub struct DriverMailPreference {
     pub mail_type_id: i32,
     pub label: String,
     pub is_editable: bool,
+    /// Whether the mail type is sent by email.
     pub is_enabled: bool,
+    /// Whether the mail type is sent as a push notification.
+    pub is_push_enabled: bool,
+}
+
+#[derive(
+    Debug, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq, Clone, Copy, sqlx::Type,
+)]
+#[sqlx(type_name = "notification_channel")]
+pub enum NotificationChannel {
+    #[default]
+    EMAIL,
+    PUSH,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "notification_frequency")]
+pub enum NotificationFrequency {
+    INSTANT,
+    DIGEST,
+}
+
+/// Preference of a driver for one mail type on one channel. Missing rows fall back to the
+/// `default_is_enabled` of the mail type.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct DriverNotificationPreferenceRow {
+    pub fk_driver_id: Uuid,
+    pub fk_mail_type_id: i32,
+    pub channel: NotificationChannel,
+    pub frequency: NotificationFrequency,
+    pub is_enabled: bool,
+    pub updated_at: DateTime<Utc>,
 }
 
 impl DriverMailTypeRow {
-    pub fn to_mail_preference(&self, mail_preferences_bitmask: i32) -> DriverMailPreference {
-        let bit = 1 << (self.pk_driver_mail_type_id - 1);
+    pub fn is_enabled_for(
+        &self,
+        preferences: &[DriverNotificationPreferenceRow],
+        channel: NotificationChannel,
+    ) -> bool {
+        preferences
+            .iter()
+            .find(|p| p.fk_mail_type_id == self.pk_driver_mail_type_id && p.channel == channel)
+            .map_or(self.default_is_enabled, |p| p.is_enabled)
+    }
+
+    pub fn to_mail_preference(
+        &self,
+        preferences: &[DriverNotificationPreferenceRow],
+    ) -> DriverMailPreference {
         DriverMailPreference {
             mail_type_id: self.pk_driver_mail_type_id,
             label: self.label.clone(),
             is_editable: self.is_editable,
-            is_enabled: (mail_preferences_bitmask & bit) != 0,
+            is_enabled: self.is_enabled_for(preferences, NotificationChannel::EMAIL),
+            is_push_enabled: self.is_enabled_for(preferences, NotificationChannel::PUSH),
         }
     }
 }
@@ -155,7 +227,411 @@ pub struct GetMailsParams {
     pub limit: u32,
 }
 
+#[derive(Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnsubscribeParams {
+    pub driver_id: Uuid,
+
+    pub mail_type_id: i32,
+
+    #[validate(length(min = 1, message = "signature is required"))]
+    pub signature: String,
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateMailPreferenceRequest {
     pub is_enabled: bool,
+
+    /// Channel the preference applies to, email when omitted.
+    #[serde(default)]
+    pub channel: NotificationChannel,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "mail_event_type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MailEventType {
+    HardBounce,
+    SoftBounce,
+    Complaint,
+}
+
+/// Bounce or complaint reported by the email provider about an address we sent to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailEvent {
+    pub email: String,
+    pub event_type: MailEventType,
+    /// Format the event was received in, e.g. `ses` or `sendgrid`.
+    pub provider: String,
+    pub detail: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct MailEventRow {
+    pub pk_mail_event_id: Uuid,
+    /// Latest mail sent to the address before the event, when there is one.
+    pub fk_driver_mail_id: Option<Uuid>,
+    pub fk_driver_id: Option<Uuid>,
+    pub fk_mail_type_id: Option<i32>,
+    pub email: String,
+    pub event_type: MailEventType,
+    pub provider: String,
+    pub detail: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Address no mail is sent to anymore, after a hard bounce or a complaint about a shared document.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct EmailSuppressionRow {
+    pub email: String,
+    pub fk_mail_event_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MailEventsParams {
+    #[validate(length(min = 1, message = "token is required"))]
+    pub token: String,
+}
+
+impl MailEvent {
+    /// Reads the bounces and complaints of a provider webhook, in the generic format
+    /// (`{"email", "type", "detail"?, "occurred_at"?}`, or an array of them) or in the
+    /// Amazon SES (direct or through SNS), SendGrid, Mailgun or Postmark format. Other events
+    /// such as deliveries or opens are skipped. `None` when the body is in no known format.
+    pub fn from_webhook(body: &Value) -> Option<Vec<MailEvent>> {
+        match body {
+            Value::Array(items) => items.iter().try_fold(Vec::new(), |mut events, item| {
+                let parsed = if item.get("event").is_some() {
+                    Self::from_sendgrid(item)?
+                } else {
+                    Self::from_generic(item)?
+                };
+                events.extend(parsed);
+                Some(events)
+            }),
+            Value::Object(object) => {
+                if object.get("Type").and_then(Value::as_str) == Some("Notification") {
+                    let message = object.get("Message")?.as_str()?;
+                    Self::from_ses(&serde_json::from_str(message).ok()?)
+                } else if object.get("Type").and_then(Value::as_str)
+                    == Some("SubscriptionConfirmation")
+                {
+                    info!(
+                        subscribe_url = body["SubscribeURL"].as_str().unwrap_or_default(),
+                        "SNS subscription of the mail events webhook must be confirmed"
+                    );
+                    Some(Vec::new())
+                } else if object.contains_key("notificationType")
+                    || object.contains_key("eventType")
+                {
+                    Self::from_ses(body)
+                } else if let Some(event_data) = object.get("event-data") {
+                    Self::from_mailgun(event_data)
+                } else if object.contains_key("RecordType") {
+                    Self::from_postmark(body)
+                } else if object.contains_key("event") {
+                    Self::from_sendgrid(body)
+                } else {
+                    Self::from_generic(body)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn new(
+        email: &str,
+        event_type: MailEventType,
+        provider: &str,
+        detail: Option<&str>,
+        occurred_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            email: email.trim().to_lowercase(),
+            event_type,
+            provider: provider.to_string(),
+            detail: detail.filter(|d| !d.is_empty()).map(str::to_string),
+            occurred_at: occurred_at.unwrap_or_else(Utc::now),
+        }
+    }
+
+    fn from_generic(body: &Value) -> Option<Vec<MailEvent>> {
+        let event_type = match body.get("type")?.as_str()? {
+            "hard_bounce" => MailEventType::HardBounce,
+            "soft_bounce" => MailEventType::SoftBounce,
+            "complaint" => MailEventType::Complaint,
+            _ => return None,
+        };
+
+        Some(vec![Self::new(
+            body.get("email")?.as_str()?,
+            event_type,
+            "generic",
+            body["detail"].as_str(),
+            parse_rfc3339(&body["occurred_at"]),
+        )])
+    }
+
+    fn from_ses(body: &Value) -> Option<Vec<MailEvent>> {
+        let notification_type = body
+            .get("notificationType")
+            .or_else(|| body.get("eventType"))?
+            .as_str()?;
+
+        let (recipients, event_type, detail_key, details) = match notification_type {
+            "Bounce" => {
+                let bounce = body.get("bounce")?;
+                let event_type = if bounce["bounceType"].as_str() == Some("Permanent") {
+                    MailEventType::HardBounce
+                } else {
+                    MailEventType::SoftBounce
+                };
+                (
+                    &bounce["bouncedRecipients"],
+                    event_type,
+                    "diagnosticCode",
+                    bounce,
+                )
+            }
+            "Complaint" => {
+                let complaint = body.get("complaint")?;
+                (
+                    &complaint["complainedRecipients"],
+                    MailEventType::Complaint,
+                    "complaintFeedbackType",
+                    complaint,
+                )
+            }
+            _ => return Some(Vec::new()),
+        };
+
+        let occurred_at = parse_rfc3339(&details["timestamp"]);
+        recipients
+            .as_array()?
+            .iter()
+            .map(|recipient| {
+                Some(Self::new(
+                    recipient.get("emailAddress")?.as_str()?,
+                    event_type,
+                    "ses",
+                    recipient[detail_key]
+                        .as_str()
+                        .or_else(|| details[detail_key].as_str()),
+                    occurred_at,
+                ))
+            })
+            .collect()
+    }
+
+    fn from_sendgrid(body: &Value) -> Option<Vec<MailEvent>> {
+        let event_type = match (body.get("event")?.as_str()?, body["type"].as_str()) {
+            ("bounce", Some("blocked")) => MailEventType::SoftBounce,
+            ("bounce", _) => MailEventType::HardBounce,
+            ("blocked", _) => MailEventType::SoftBounce,
+            ("spamreport", _) => MailEventType::Complaint,
+            _ => return Some(Vec::new()),
+        };
+
+        Some(vec![Self::new(
+            body.get("email")?.as_str()?,
+            event_type,
+            "sendgrid",
+            body["reason"].as_str(),
+            body["timestamp"]
+                .as_i64()
+                .and_then(|t| DateTime::from_timestamp(t, 0)),
+        )])
+    }
+
+    fn from_mailgun(event_data: &Value) -> Option<Vec<MailEvent>> {
+        let event_type = match (
+            event_data.get("event")?.as_str()?,
+            event_data["severity"].as_str(),
+        ) {
+            ("failed", Some("permanent")) => MailEventType::HardBounce,
+            ("failed", _) => MailEventType::SoftBounce,
+            ("complained", _) => MailEventType::Complaint,
+            _ => return Some(Vec::new()),
+        };
+
+        let delivery_status = &event_data["delivery-status"];
+        Some(vec![Self::new(
+            event_data.get("recipient")?.as_str()?,
+            event_type,
+            "mailgun",
+            delivery_status["description"]
+                .as_str()
+                .filter(|d| !d.is_empty())
+                .or_else(|| delivery_status["message"].as_str()),
+            event_data["timestamp"]
+                .as_f64()
+                .and_then(|t| DateTime::from_timestamp_millis((t * 1000.0) as i64)),
+        )])
+    }
+
+    fn from_postmark(body: &Value) -> Option<Vec<MailEvent>> {
+        let event_type = match (body.get("RecordType")?.as_str()?, body["Type"].as_str()) {
+            ("SpamComplaint", _) | ("Bounce", Some("SpamComplaint")) => MailEventType::Complaint,
+            ("Bounce", Some("HardBounce" | "BadEmailAddress")) => MailEventType::HardBounce,
+            ("Bounce", _) => MailEventType::SoftBounce,
+            _ => return Some(Vec::new()),
+        };
+
+        Some(vec![Self::new(
+            body.get("Email")?.as_str()?,
+            event_type,
+            "postmark",
+            body["Description"].as_str(),
+            parse_rfc3339(&body["BouncedAt"]),
+        )])
+    }
+}
+
+fn parse_rfc3339(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{DriverMailAttachmentRow, MailEvent, MailEventType, monthly_report_file_name};
+
+    #[test]
+    fn test_monthly_report_period_from_file_name() {
+        let mut attachment = DriverMailAttachmentRow {
+            pk_driver_mail_attachment_id: Uuid::new_v4(),
+            fk_driver_mail_id: Uuid::new_v4(),
+            file_name: monthly_report_file_name(9, 2026),
+            s3_file_path: "drivers/1/mails/monthly-2026-09.pdf".to_string(),
+            created_at: Utc::now(),
+        };
+        assert_eq!(attachment.file_name, "workdays-2026-09.pdf");
+        assert_eq!(attachment.monthly_report_period(), Some((9, 2026)));
+
+        attachment.file_name = "invoice.pdf".to_string();
+        assert_eq!(attachment.monthly_report_period(), None);
+    }
+
+    #[test]
+    fn test_generic_events() {
+        let events = MailEvent::from_webhook(&json!([
+            { "email": "John.Doe@Example.be", "type": "hard_bounce", "detail": "550 5.1.1" },
+            { "email": "jane@example.be", "type": "complaint", "occurred_at": "2026-03-01T10:00:00Z" },
+        ]))
+        .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].email, "john.doe@example.be");
+        assert_eq!(events[0].event_type, MailEventType::HardBounce);
+        assert_eq!(events[0].detail.as_deref(), Some("550 5.1.1"));
+        assert_eq!(events[1].event_type, MailEventType::Complaint);
+        assert_eq!(
+            events[1].occurred_at.to_rfc3339(),
+            "2026-03-01T10:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_ses_events_through_sns() {
+        let message = json!({
+            "notificationType": "Bounce",
+            "bounce": {
+                "bounceType": "Permanent",
+                "bouncedRecipients": [
+                    { "emailAddress": "john@example.be", "diagnosticCode": "smtp; 550 user unknown" }
+                ],
+                "timestamp": "2026-03-01T10:00:00.000Z"
+            }
+        });
+        let events = MailEvent::from_webhook(&json!({
+            "Type": "Notification",
+            "Message": message.to_string(),
+        }))
+        .unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].provider, "ses");
+        assert_eq!(events[0].event_type, MailEventType::HardBounce);
+        assert_eq!(events[0].detail.as_deref(), Some("smtp; 550 user unknown"));
+
+        let events = MailEvent::from_webhook(&json!({
+            "eventType": "Complaint",
+            "complaint": { "complainedRecipients": [{ "emailAddress": "john@example.be" }] }
+        }))
+        .unwrap();
+        assert_eq!(events[0].event_type, MailEventType::Complaint);
+
+        let events = MailEvent::from_webhook(&json!({
+            "Type": "SubscriptionConfirmation",
+            "SubscribeURL": "https://sns.example.com/confirm",
+        }))
+        .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_sendgrid_events() {
+        let events = MailEvent::from_webhook(&json!([
+            { "email": "john@example.be", "event": "bounce", "type": "bounce", "timestamp": 1772359200 },
+            { "email": "john@example.be", "event": "bounce", "type": "blocked" },
+            { "email": "john@example.be", "event": "spamreport" },
+            { "email": "john@example.be", "event": "delivered" },
+        ]))
+        .unwrap();
+
+        let event_types: Vec<MailEventType> = events.iter().map(|e| e.event_type).collect();
+        assert_eq!(
+            event_types,
+            vec![
+                MailEventType::HardBounce,
+                MailEventType::SoftBounce,
+                MailEventType::Complaint
+            ]
+        );
+        assert_eq!(events[0].occurred_at.timestamp(), 1772359200);
+    }
+
+    #[test]
+    fn test_mailgun_and_postmark_events() {
+        let events = MailEvent::from_webhook(&json!({
+            "signature": {},
+            "event-data": {
+                "event": "failed",
+                "severity": "permanent",
+                "recipient": "john@example.be",
+                "timestamp": 1772359200.5,
+                "delivery-status": { "description": "", "message": "No such mailbox" }
+            }
+        }))
+        .unwrap();
+        assert_eq!(events[0].provider, "mailgun");
+        assert_eq!(events[0].event_type, MailEventType::HardBounce);
+        assert_eq!(events[0].detail.as_deref(), Some("No such mailbox"));
+
+        let events = MailEvent::from_webhook(&json!({
+            "RecordType": "Bounce",
+            "Type": "SoftBounce",
+            "Email": "john@example.be",
+            "BouncedAt": "2026-03-01T10:00:00Z",
+            "Description": "Mailbox full"
+        }))
+        .unwrap();
+        assert_eq!(events[0].provider, "postmark");
+        assert_eq!(events[0].event_type, MailEventType::SoftBounce);
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        assert!(MailEvent::from_webhook(&json!({ "foo": "bar" })).is_none());
+        assert!(MailEvent::from_webhook(&json!("bounce")).is_none());
+    }
 }
@@ -5,22 +5,27 @@ use std::{
 };
 
 use bytes::Bytes;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use uuid::Uuid;
 
 use crate::{
     domain::{
         common::constants::EnumDriverMailType,
-        driver::entities::DriverRow,
+        driver::entities::{DriverEmailChange, DriverRow},
         mail::entities::{
             DriverMail, DriverMailAttachmentRow, DriverMailPreference, DriverMailRow,
-            DriverMailType, DriverMailTypeRow, MailStatus,
+            DriverMailType, DriverMailTypeRow, DriverNotificationPreferenceRow,
+            EmailSuppressionRow, MailEvent, MailEventRow, MailEventType, MailStatus,
+            NotificationChannel, NotificationFrequency,
         },
+        storage::entities::{ByteRange, DocumentDownload},
+        workday::entities::DocumentShareRow,
     },
     infrastructure::mail::repositories::error::MailError,
 };
 
 pub trait MailSmtpRepository: Send + Sync {
+    /// Sends `body` as a plain-text mail, outside of any template.
     fn send_email(&self, to: String, subject: String, body: String) -> Result<(), MailError>;
 
     fn send_driver_creation_email(
@@ -30,11 +35,11 @@ pub trait MailSmtpRepository: Send + Sync {
         verify_ttl: u64,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
-    fn send_driver_verification_email(
+    fn send_driver_email_change_confirmation_email(
         &self,
         driver: DriverRow,
-        verify_value: String,
-        verify_ttl: u64,
+        confirm_value: String,
+        confirm_ttl: u64,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
     fn send_driver_deactivation_email(
@@ -54,9 +59,19 @@ pub trait MailSmtpRepository: Send + Sync {
         reset_ttl: u64,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
+    fn send_driver_magic_link_email(
+        &self,
+        driver: DriverRow,
+        magic_link_value: String,
+        magic_link_ttl: u64,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
+
     fn send_driver_email_change_email(
         &self,
         driver: DriverRow,
+        new_email: String,
+        revert_value: String,
+        revert_ttl: u64,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
     fn send_driver_password_change_email(
@@ -72,6 +87,28 @@ pub trait MailSmtpRepository: Send + Sync {
         pdf_bytes: Bytes,
         file_name: String,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
+
+    /// Lists the working days the driver has not filled in, `missing_dates` being sorted.
+    fn send_driver_workday_reminder_email(
+        &self,
+        driver: DriverRow,
+        missing_dates: Vec<NaiveDate>,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
+
+    /// Lists the monthly workday documents just generated, as `(month, year)`.
+    fn send_driver_documents_ready_email(
+        &self,
+        driver: DriverRow,
+        documents: Vec<(u32, i32)>,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
+
+    /// Sends a share link to the address of `driver`, set to the recipient of the share.
+    fn send_driver_document_shared_email(
+        &self,
+        driver: DriverRow,
+        share: DocumentShareRow,
+        share_url: String,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
 }
 
 pub trait MailDatabaseRepository: Send + Sync {
@@ -83,6 +120,13 @@ pub trait MailDatabaseRepository: Send + Sync {
         content: Option<String>,
     ) -> impl Future<Output = Result<DriverMailRow, MailError>> + Send;
 
+    /// Records a pending copy of `original` to the current address of the driver, linked to it.
+    fn create_resent_mail(
+        &self,
+        driver: DriverRow,
+        original: DriverMailRow,
+    ) -> impl Future<Output = Result<DriverMailRow, MailError>> + Send;
+
     fn update_mail_status(
         &self,
         mail_id: Uuid,
@@ -106,16 +150,27 @@ pub trait MailDatabaseRepository: Send + Sync {
         mail_type_id: i32,
     ) -> impl Future<Output = Result<DriverMailTypeRow, MailError>> + Send;
 
-    fn get_driver_mail_preferences(
+    fn get_driver_notification_preferences(
         &self,
         driver_id: Uuid,
-    ) -> impl Future<Output = Result<i32, MailError>> + Send;
+    ) -> impl Future<Output = Result<Vec<DriverNotificationPreferenceRow>, MailError>> + Send;
 
-    fn update_driver_mail_preferences(
+    /// Whether the mail type is enabled for the driver on the channel, falling back to the
+    /// default of the mail type when the driver never changed it.
+    fn is_notification_enabled(
         &self,
         driver_id: Uuid,
-        mail_preferences: i32,
-    ) -> impl Future<Output = Result<i32, MailError>> + Send;
+        mail_type: EnumDriverMailType,
+        channel: NotificationChannel,
+    ) -> impl Future<Output = Result<bool, MailError>> + Send;
+
+    fn upsert_driver_notification_preference(
+        &self,
+        driver_id: Uuid,
+        mail_type_id: i32,
+        channel: NotificationChannel,
+        is_enabled: bool,
+    ) -> impl Future<Output = Result<DriverNotificationPreferenceRow, MailError>> + Send;
 
     fn get_mail_attachments(
         &self,
@@ -139,6 +194,21 @@ pub trait MailDatabaseRepository: Send + Sync {
         year: i32,
     ) -> impl Future<Output = Result<bool, MailError>> + Send;
 
+    /// Whether a workday reminder reached the driver since `since`; failed attempts do not count.
+    fn has_workday_reminder_since(
+        &self,
+        driver_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, MailError>> + Send;
+
+    /// Mails of this type created for the driver since `since`, whatever their status.
+    fn count_mails_since(
+        &self,
+        driver_id: Uuid,
+        mail_type: EnumDriverMailType,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<i64, MailError>> + Send;
+
     fn has_document_at_path(
         &self,
         s3_file_path: &str,
@@ -155,6 +225,31 @@ pub trait MailDatabaseRepository: Send + Sync {
         mail_id: Uuid,
         document_id: Uuid,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
+
+    /// Attaches the documents of a mail to another one, the files themselves being shared.
+    fn copy_mail_attachments(
+        &self,
+        from_mail_id: Uuid,
+        to_mail_id: Uuid,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
+
+    /// Records the event against the latest mail sent to its address before it occurred,
+    /// marking that mail as failed on a hard bounce.
+    fn create_mail_event(
+        &self,
+        event: MailEvent,
+    ) -> impl Future<Output = Result<MailEventRow, MailError>> + Send;
+
+    fn suppress_email(
+        &self,
+        email: String,
+        mail_event_id: Uuid,
+    ) -> impl Future<Output = Result<EmailSuppressionRow, MailError>> + Send;
+
+    fn get_email_suppression(
+        &self,
+        email: String,
+    ) -> impl Future<Output = Result<Option<EmailSuppressionRow>, MailError>> + Send;
 }
 
 pub trait MailService: Send + Sync {
@@ -163,9 +258,11 @@ pub trait MailService: Send + Sync {
         driver: DriverRow,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
+    /// Sends the confirmation link of a pending email change to the new address.
     fn send_email_change_verification(
         &self,
         driver: DriverRow,
+        email_change: DriverEmailChange,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
     fn send_deactivation_email(
@@ -183,9 +280,16 @@ pub trait MailService: Send + Sync {
         driver: DriverRow,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
+    fn send_magic_link_email(
+        &self,
+        driver: DriverRow,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
+
+    /// Warns the previous address of an email change, with a link to revert it.
     fn send_email_change_notification(
         &self,
         driver: DriverRow,
+        email_change: DriverEmailChange,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
     fn send_password_change_notification(
@@ -193,6 +297,14 @@ pub trait MailService: Send + Sync {
         driver: DriverRow,
     ) -> impl Future<Output = Result<(), MailError>> + Send;
 
+    /// Mails a share link of a document to the recipient of the share.
+    fn send_document_share_email(
+        &self,
+        driver: DriverRow,
+        share: DocumentShareRow,
+        share_url: String,
+    ) -> impl Future<Output = Result<(), MailError>> + Send;
+
     fn get_mails(
         &self,
         driver_id: Uuid,
@@ -212,9 +324,19 @@ pub trait MailService: Send + Sync {
         &self,
         driver_id: Uuid,
         mail_type_id: i32,
+        channel: NotificationChannel,
         is_enabled: bool,
     ) -> impl Future<Output = Result<DriverMailPreference, MailError>> + Send;
 
+    /// Switches a mail type off on every channel from a signed one-click unsubscribe link, without
+    /// authentication.
+    fn unsubscribe(
+        &self,
+        driver_id: Uuid,
+        mail_type_id: i32,
+        signature: String,
+    ) -> impl Future<Output = Result<DriverMailPreference, MailError>> + Send;
+
     fn get_mail(
         &self,
         driver_id: Uuid,
@@ -225,7 +347,39 @@ pub trait MailService: Send + Sync {
         &self,
         driver_id: Uuid,
         attachment_id: Uuid,
-    ) -> impl Future<Output = Result<(bytes::Bytes, String), MailError>> + Send;
+        range: Option<ByteRange>,
+    ) -> impl Future<Output = Result<(DocumentDownload, String), MailError>> + Send;
+
+    /// Sends a mail of the history again, with fresh tokens for verification and password reset
+    /// mails and the stored attachments for monthly reports. The new mail links to the original.
+    fn resend_mail(
+        &self,
+        driver_id: Uuid,
+        mail_id: Uuid,
+    ) -> impl Future<Output = Result<DriverMail, MailError>> + Send;
+
+    /// Revokes the link of a verification, password reset or magic link mail that has not
+    /// expired yet, for a mail the driver did not ask for.
+    fn cancel_mail(
+        &self,
+        driver_id: Uuid,
+        mail_id: Uuid,
+    ) -> impl Future<Output = Result<DriverMail, MailError>> + Send;
+
+    /// Handles a bounce and complaint webhook of the email provider. Hard bounces stop any
+    /// further mail to the address, complaints switch off the mail types a driver can edit.
+    /// Returns the number of events recorded.
+    fn process_mail_events(
+        &self,
+        token: String,
+        body: serde_json::Value,
+    ) -> impl Future<Output = Result<usize, MailError>> + Send;
+
+    /// When the address hard bounced, `None` while it is still reachable.
+    fn get_email_unreachable_at(
+        &self,
+        email: String,
+    ) -> impl Future<Output = Result<Option<DateTime<Utc>>, MailError>> + Send;
 }
 
 pub struct MockMailSmtpRepository;
@@ -256,11 +410,11 @@ impl MailSmtpRepository for MockMailSmtpRepository {
         Ok(())
     }
 
-    async fn send_driver_verification_email(
+    async fn send_driver_email_change_confirmation_email(
         &self,
         _driver: DriverRow,
-        _verify_value: String,
-        _verify_ttl: u64,
+        _confirm_value: String,
+        _confirm_ttl: u64,
     ) -> Result<(), MailError> {
         Ok(())
     }
@@ -282,7 +436,22 @@ impl MailSmtpRepository for MockMailSmtpRepository {
         Ok(())
     }
 
-    async fn send_driver_email_change_email(&self, _driver: DriverRow) -> Result<(), MailError> {
+    async fn send_driver_magic_link_email(
+        &self,
+        _driver: DriverRow,
+        _magic_link_value: String,
+        _magic_link_ttl: u64,
+    ) -> Result<(), MailError> {
+        Ok(())
+    }
+
+    async fn send_driver_email_change_email(
+        &self,
+        _driver: DriverRow,
+        _new_email: String,
+        _revert_value: String,
+        _revert_ttl: u64,
+    ) -> Result<(), MailError> {
         Ok(())
     }
 
@@ -300,16 +469,71 @@ impl MailSmtpRepository for MockMailSmtpRepository {
     ) -> Result<(), MailError> {
         Ok(())
     }
+
+    async fn send_driver_workday_reminder_email(
+        &self,
+        _driver: DriverRow,
+        _missing_dates: Vec<NaiveDate>,
+    ) -> Result<(), MailError> {
+        Ok(())
+    }
+
+    async fn send_driver_documents_ready_email(
+        &self,
+        _driver: DriverRow,
+        _documents: Vec<(u32, i32)>,
+    ) -> Result<(), MailError> {
+        Ok(())
+    }
+
+    async fn send_driver_document_shared_email(
+        &self,
+        _driver: DriverRow,
+        _share: DocumentShareRow,
+        _share_url: String,
+    ) -> Result<(), MailError> {
+        Ok(())
+    }
 }
 
 pub struct MockMailDatabaseRepository {
     mails: Arc<Mutex<Vec<DriverMailRow>>>,
+    mail_types: Arc<Mutex<Vec<DriverMailTypeRow>>>,
+    notification_preferences: Arc<Mutex<Vec<DriverNotificationPreferenceRow>>>,
+    mail_events: Arc<Mutex<Vec<MailEventRow>>>,
+    email_suppressions: Arc<Mutex<Vec<EmailSuppressionRow>>>,
 }
 
 impl MockMailDatabaseRepository {
     pub fn new() -> Self {
+        // Same rows as `driver_mail_types`
+        let mail_types = [
+            ("ACCOUNT_VERIFICATION", false),
+            ("PASSWORD_RESET", false),
+            ("ACCOUNT_CHANGEMENT", false),
+            ("MONTHLY_REPORTS", true),
+            ("WORKDAY_REMINDERS", true),
+            ("DOCUMENTS_READY", true),
+            ("DOCUMENT_SHARED", false),
+            ("MAGIC_LINK", false),
+        ]
+        .into_iter()
+        .zip(1..)
+        .map(|((label, is_editable), id)| DriverMailTypeRow {
+            pk_driver_mail_type_id: id,
+            label: label.to_string(),
+            index: id,
+            is_editable,
+            default_is_enabled: true,
+        })
+        .collect();
+
         Self {
             mails: Arc::new(Mutex::new(Vec::new())),
+            mail_types: Arc::new(Mutex::new(mail_types)),
+            notification_preferences: Arc::new(Mutex::new(Vec::new())),
+            mail_events: Arc::new(Mutex::new(Vec::new())),
+            email_suppressions: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -339,6 +563,28 @@ impl MailDatabaseRepository for MockMailDatabaseRepository {
             status: MailStatus::PENDING,
             created_at: Utc::now(),
             sent_at: None,
+            fk_resent_from_mail_id: None,
+        };
+
+        let mut mails = self.mails.lock().unwrap();
+        mails.push(mail.clone());
+
+        Ok(mail)
+    }
+
+    async fn create_resent_mail(
+        &self,
+        driver: DriverRow,
+        original: DriverMailRow,
+    ) -> Result<DriverMailRow, MailError> {
+        let mail = DriverMailRow {
+            pk_driver_mail_id: Uuid::new_v4(),
+            email_used: driver.email.clone(),
+            status: MailStatus::PENDING,
+            created_at: Utc::now(),
+            sent_at: None,
+            fk_resent_from_mail_id: Some(original.pk_driver_mail_id),
+            ..original
         };
 
         let mut mails = self.mails.lock().unwrap();
@@ -353,23 +599,11 @@ impl MailDatabaseRepository for MockMailDatabaseRepository {
         status: MailStatus,
         sent_at: Option<DateTime<Utc>>,
     ) -> Result<DriverMailRow, MailError> {
-        let mails = self.mails.lock().unwrap();
-        for mail in mails.iter() {
-            if mail.pk_driver_mail_id == mail_id {
-                let updated_mail = DriverMailRow {
-                    pk_driver_mail_id: mail.pk_driver_mail_id,
-                    fk_driver_id: mail.fk_driver_id,
-                    fk_employee_id: mail.fk_employee_id,
-                    fk_mail_type_id: mail.fk_mail_type_id,
-                    email_used: mail.email_used.clone(),
-                    description: mail.description.clone(),
-                    content: mail.content.clone(),
-                    status: status.clone(),
-                    created_at: mail.created_at,
-                    sent_at,
-                };
-                return Ok(updated_mail);
-            }
+        let mut mails = self.mails.lock().unwrap();
+        if let Some(mail) = mails.iter_mut().find(|m| m.pk_driver_mail_id == mail_id) {
+            mail.status = status;
+            mail.sent_at = sent_at;
+            return Ok(mail.clone());
         }
 
         Err(MailError::MailNotFound)
@@ -377,34 +611,96 @@ impl MailDatabaseRepository for MockMailDatabaseRepository {
 
     async fn get_mails(
         &self,
-        _driver_id: Uuid,
-        _page: u32,
-        _limit: u32,
+        driver_id: Uuid,
+        page: u32,
+        limit: u32,
     ) -> Result<(Vec<DriverMailRow>, u32), MailError> {
-        Ok((Vec::new(), 0))
+        let mails = self.mails.lock().unwrap();
+        let filtered: Vec<DriverMailRow> = mails
+            .iter()
+            .filter(|m| m.fk_driver_id == driver_id)
+            .cloned()
+            .collect();
+
+        let total_count = filtered.len() as u32;
+        let paginated = filtered
+            .into_iter()
+            .skip(((page - 1) * limit) as usize)
+            .take(limit as usize)
+            .collect();
+
+        Ok((paginated, total_count))
     }
 
     async fn get_mail_types(&self) -> Result<Vec<DriverMailTypeRow>, MailError> {
-        Ok(Vec::new())
+        Ok(self.mail_types.lock().unwrap().clone())
     }
 
-    async fn get_mail_type_by_id(
+    async fn get_mail_type_by_id(&self, mail_type_id: i32) -> Result<DriverMailTypeRow, MailError> {
+        self.mail_types
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|t| t.pk_driver_mail_type_id == mail_type_id)
+            .cloned()
+            .ok_or(MailError::MailTypeNotFound)
+    }
+
+    async fn get_driver_notification_preferences(
         &self,
-        _mail_type_id: i32,
-    ) -> Result<DriverMailTypeRow, MailError> {
-        Err(MailError::MailTypeNotFound)
+        driver_id: Uuid,
+    ) -> Result<Vec<DriverNotificationPreferenceRow>, MailError> {
+        let preferences = self.notification_preferences.lock().unwrap();
+        Ok(preferences
+            .iter()
+            .filter(|p| p.fk_driver_id == driver_id)
+            .cloned()
+            .collect())
     }
 
-    async fn get_driver_mail_preferences(&self, _driver_id: Uuid) -> Result<i32, MailError> {
-        Ok(0)
+    async fn is_notification_enabled(
+        &self,
+        driver_id: Uuid,
+        mail_type: EnumDriverMailType,
+        channel: NotificationChannel,
+    ) -> Result<bool, MailError> {
+        let mail_type = self.get_mail_type_by_id(mail_type.as_id()).await?;
+        let preferences = self.notification_preferences.lock().unwrap();
+        let preferences: Vec<_> = preferences
+            .iter()
+            .filter(|p| p.fk_driver_id == driver_id)
+            .cloned()
+            .collect();
+        Ok(mail_type.is_enabled_for(&preferences, channel))
     }
 
-    async fn update_driver_mail_preferences(
+    async fn upsert_driver_notification_preference(
         &self,
-        _driver_id: Uuid,
-        mail_preferences: i32,
-    ) -> Result<i32, MailError> {
-        Ok(mail_preferences)
+        driver_id: Uuid,
+        mail_type_id: i32,
+        channel: NotificationChannel,
+        is_enabled: bool,
+    ) -> Result<DriverNotificationPreferenceRow, MailError> {
+        let mut preferences = self.notification_preferences.lock().unwrap();
+        if let Some(preference) = preferences.iter_mut().find(|p| {
+            p.fk_driver_id == driver_id && p.fk_mail_type_id == mail_type_id && p.channel == channel
+        }) {
+            preference.is_enabled = is_enabled;
+            preference.updated_at = Utc::now();
+            return Ok(preference.clone());
+        }
+
+        let preference = DriverNotificationPreferenceRow {
+            fk_driver_id: driver_id,
+            fk_mail_type_id: mail_type_id,
+            channel,
+            frequency: NotificationFrequency::INSTANT,
+            is_enabled,
+            updated_at: Utc::now(),
+        };
+        preferences.push(preference.clone());
+
+        Ok(preference)
     }
 
     async fn get_mail_attachments(
@@ -442,6 +738,38 @@ impl MailDatabaseRepository for MockMailDatabaseRepository {
         Ok(found)
     }
 
+    async fn has_workday_reminder_since(
+        &self,
+        driver_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<bool, MailError> {
+        let mails = self.mails.lock().unwrap();
+        let found = mails.iter().any(|m| {
+            m.fk_driver_id == driver_id
+                && m.fk_mail_type_id == EnumDriverMailType::WorkdayReminders.as_id()
+                && m.created_at >= since
+                && m.status == MailStatus::SUCCESS
+        });
+        Ok(found)
+    }
+
+    async fn count_mails_since(
+        &self,
+        driver_id: Uuid,
+        mail_type: EnumDriverMailType,
+        since: DateTime<Utc>,
+    ) -> Result<i64, MailError> {
+        let mails = self.mails.lock().unwrap();
+        Ok(mails
+            .iter()
+            .filter(|m| {
+                m.fk_driver_id == driver_id
+                    && m.fk_mail_type_id == mail_type.as_id()
+                    && m.created_at >= since
+            })
+            .count() as i64)
+    }
+
     async fn has_document_at_path(&self, _s3_file_path: &str) -> Result<bool, MailError> {
         Ok(false)
     }
@@ -461,6 +789,86 @@ impl MailDatabaseRepository for MockMailDatabaseRepository {
     ) -> Result<(), MailError> {
         Ok(())
     }
+
+    async fn copy_mail_attachments(
+        &self,
+        _from_mail_id: Uuid,
+        _to_mail_id: Uuid,
+    ) -> Result<(), MailError> {
+        Ok(())
+    }
+
+    async fn create_mail_event(&self, event: MailEvent) -> Result<MailEventRow, MailError> {
+        let mut mails = self.mails.lock().unwrap();
+        let mail = mails
+            .iter_mut()
+            .filter(|m| {
+                m.email_used.to_lowercase() == event.email && m.created_at <= event.occurred_at
+            })
+            .max_by_key(|m| m.created_at);
+
+        let (mail_id, driver_id, mail_type_id) = match mail {
+            Some(mail) => {
+                if event.event_type == MailEventType::HardBounce {
+                    mail.status = MailStatus::FAILED;
+                }
+                (
+                    Some(mail.pk_driver_mail_id),
+                    Some(mail.fk_driver_id),
+                    Some(mail.fk_mail_type_id),
+                )
+            }
+            None => (None, None, None),
+        };
+
+        let row = MailEventRow {
+            pk_mail_event_id: Uuid::new_v4(),
+            fk_driver_mail_id: mail_id,
+            fk_driver_id: driver_id,
+            fk_mail_type_id: mail_type_id,
+            email: event.email,
+            event_type: event.event_type,
+            provider: event.provider,
+            detail: event.detail,
+            occurred_at: event.occurred_at,
+            created_at: Utc::now(),
+        };
+        self.mail_events.lock().unwrap().push(row.clone());
+        Ok(row)
+    }
+
+    async fn suppress_email(
+        &self,
+        email: String,
+        mail_event_id: Uuid,
+    ) -> Result<EmailSuppressionRow, MailError> {
+        let mut email_suppressions = self.email_suppressions.lock().unwrap();
+        if let Some(suppression) = email_suppressions.iter().find(|s| s.email == email) {
+            return Ok(suppression.clone());
+        }
+
+        let suppression = EmailSuppressionRow {
+            email,
+            fk_mail_event_id: mail_event_id,
+            created_at: Utc::now(),
+        };
+        email_suppressions.push(suppression.clone());
+        Ok(suppression)
+    }
+
+    async fn get_email_suppression(
+        &self,
+        email: String,
+    ) -> Result<Option<EmailSuppressionRow>, MailError> {
+        let email = email.to_lowercase();
+        Ok(self
+            .email_suppressions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|s| s.email == email)
+            .cloned())
+    }
 }
 
 // ── Mail cache ────────────────────────────────────────────────────────────────
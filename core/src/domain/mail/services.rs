@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
 
 use crate::{
     Service,
@@ -9,25 +10,57 @@ use crate::{
         common::constants::EnumDriverMailType,
         document::port::DocumentExternalRepository,
         driver::{
-            entities::DriverRow,
+            entities::{DriverEmailChange, DriverRow},
             port::{DriverCacheKeyType, DriverCacheRepository, DriverDatabaseRepository},
         },
         health::port::HealthRepository,
         mail::{
-            entities::{DriverMail, DriverMailPreference, DriverMailType, MailStatus},
+            entities::{
+                DriverMail, DriverMailAttachmentRow, DriverMailPreference, DriverMailRow,
+                DriverMailType, MailEvent, MailEventType, MailStatus, NotificationChannel,
+            },
             port::{MailCacheRepository, MailDatabaseRepository, MailService, MailSmtpRepository},
         },
-        storage::port::StorageRepository,
+        notification::port::{NotificationDatabaseRepository, SmsRepository},
+        storage::{
+            entities::{ByteRange, DocumentDownload},
+            port::StorageRepository,
+        },
         update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
-        workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
+        workday::{
+            entities::DocumentShareRow,
+            port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
+        },
     },
     infrastructure::{
         mail::repositories::error::MailError, storage::repositories::error::StorageError,
     },
 };
 
-impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS> MailService
-    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+/// The cache key of the token behind the link of a mail, for the mails that carry one.
+fn mail_token_key_type(mail: &DriverMailRow) -> Option<DriverCacheKeyType> {
+    match EnumDriverMailType::from_id(mail.fk_mail_type_id)? {
+        EnumDriverMailType::AccountVerification => Some(DriverCacheKeyType::VerifyEmail),
+        EnumDriverMailType::PasswordReset => Some(DriverCacheKeyType::ResetPassword),
+        EnumDriverMailType::MagicLink => Some(DriverCacheKeyType::MagicLink),
+        _ => None,
+    }
+}
+
+/// A mail of the history that can be sent again.
+enum MailResend {
+    AccountVerification,
+    PasswordReset,
+    MagicLink,
+    MonthlyReport {
+        month: u32,
+        year: i32,
+        attachment: DriverMailAttachmentRow,
+    },
+}
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> MailService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -41,6 +74,8 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     #[tracing::instrument(
         name = "mail_service.send_creation_email",
@@ -50,16 +85,23 @@ where
         )
     )]
     async fn send_creation_email(&self, driver: DriverRow) -> Result<(), MailError> {
-        let bitmask = self
+        let is_enabled = self
             .mail_database_repository
-            .get_driver_mail_preferences(driver.pk_driver_id)
+            .is_notification_enabled(
+                driver.pk_driver_id,
+                EnumDriverMailType::AccountVerification,
+                NotificationChannel::EMAIL,
+            )
             .await?;
 
-        let bit = 1 << (EnumDriverMailType::AccountVerification.as_id() - 1);
-        if bitmask & bit == 0 {
+        if !is_enabled {
             return Err(MailError::MailPreferenceDisabled);
         }
 
+        if self.is_email_unreachable(&driver.email).await? {
+            return Ok(());
+        }
+
         let verify_value = self
             .driver_cache_repository
             .generate_random_value(100)
@@ -117,6 +159,10 @@ where
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_deactivation_email(&self, driver: DriverRow) -> Result<(), MailError> {
+        if self.is_email_unreachable(&driver.email).await? {
+            return Ok(());
+        }
+
         let mail = self
             .mail_database_repository
             .create_mail(
@@ -159,6 +205,10 @@ where
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_reactivation_email(&self, driver: DriverRow) -> Result<(), MailError> {
+        if self.is_email_unreachable(&driver.email).await? {
+            return Ok(());
+        }
+
         let mail = self
             .mail_database_repository
             .create_mail(
@@ -294,15 +344,15 @@ where
             return Ok(cached);
         }
 
-        let bitmask = self
+        let notification_preferences = self
             .mail_database_repository
-            .get_driver_mail_preferences(driver_id)
+            .get_driver_notification_preferences(driver_id)
             .await?;
 
         let type_rows = self.mail_database_repository.get_mail_types().await?;
         let preferences: Vec<DriverMailPreference> = type_rows
             .iter()
-            .map(|t| t.to_mail_preference(bitmask))
+            .map(|t| t.to_mail_preference(&notification_preferences))
             .collect();
 
         let _ = self
@@ -316,12 +366,18 @@ where
     #[tracing::instrument(
         name = "mail_service.update_mail_preference",
         skip(self),
-        fields(driver_id = %driver_id, mail_type_id = %mail_type_id, is_enabled = %is_enabled)
+        fields(
+            driver_id = %driver_id,
+            mail_type_id = %mail_type_id,
+            channel = ?channel,
+            is_enabled = %is_enabled,
+        )
     )]
     async fn update_mail_preference(
         &self,
         driver_id: Uuid,
         mail_type_id: i32,
+        channel: NotificationChannel,
         is_enabled: bool,
     ) -> Result<DriverMailPreference, MailError> {
         let mail_type = self
@@ -333,21 +389,8 @@ where
             return Err(MailError::MailPreferenceNotEditable);
         }
 
-        let current = self
-            .mail_database_repository
-            .get_driver_mail_preferences(driver_id)
-            .await?;
-
-        let bit = 1 << (mail_type_id - 1);
-        let new_bitmask = if is_enabled {
-            current | bit
-        } else {
-            current & !bit
-        };
-
-        let saved = self
-            .mail_database_repository
-            .update_driver_mail_preferences(driver_id, new_bitmask)
+        self.mail_database_repository
+            .upsert_driver_notification_preference(driver_id, mail_type_id, channel, is_enabled)
             .await?;
 
         let _ = self
@@ -355,7 +398,37 @@ where
             .delete_mail_preferences(driver_id)
             .await;
 
-        Ok(mail_type.to_mail_preference(saved))
+        let preferences = self
+            .mail_database_repository
+            .get_driver_notification_preferences(driver_id)
+            .await?;
+
+        Ok(mail_type.to_mail_preference(&preferences))
+    }
+
+    #[tracing::instrument(
+        name = "mail_service.unsubscribe",
+        skip(self, signature),
+        fields(driver_id = %driver_id, mail_type_id = %mail_type_id)
+    )]
+    async fn unsubscribe(
+        &self,
+        driver_id: Uuid,
+        mail_type_id: i32,
+        signature: String,
+    ) -> Result<DriverMailPreference, MailError> {
+        if !self
+            .config
+            .mail_unsubscribe
+            .verify(driver_id, mail_type_id, &signature)
+        {
+            return Err(MailError::InvalidUnsubscribeSignature);
+        }
+
+        self.update_mail_preference(driver_id, mail_type_id, NotificationChannel::PUSH, false)
+            .await?;
+        self.update_mail_preference(driver_id, mail_type_id, NotificationChannel::EMAIL, false)
+            .await
     }
 
     #[tracing::instrument(
@@ -416,7 +489,8 @@ where
         &self,
         driver_id: Uuid,
         attachment_id: Uuid,
-    ) -> Result<(bytes::Bytes, String), MailError> {
+        range: Option<ByteRange>,
+    ) -> Result<(DocumentDownload, String), MailError> {
         let attachment_row = self
             .mail_database_repository
             .get_mail_attachment_by_id(attachment_id)
@@ -431,34 +505,219 @@ where
             return Err(MailError::MailAttachmentNotFound);
         }
 
-        let bytes = self
-            .storage_repository
-            .download(&attachment_row.s3_file_path)
+        let download = self
+            .deliver_document(&attachment_row.s3_file_path, range)
             .await
             .map_err(|e| match e {
                 StorageError::ObjectNotFound => MailError::MailAttachmentNotFound,
+                StorageError::RangeNotSatisfiable => MailError::MailAttachmentRangeNotSatisfiable,
                 _ => MailError::Internal,
             })?;
 
-        Ok((bytes, attachment_row.file_name))
+        Ok((download, attachment_row.file_name))
     }
 
     #[tracing::instrument(
-        name = "mail_service.send_email_change_notification",
+        name = "mail_service.resend_mail",
         skip(self),
-        fields(driver_id = %driver.pk_driver_id)
+        fields(driver_id = %driver_id, mail_id = %mail_id)
+    )]
+    async fn resend_mail(&self, driver_id: Uuid, mail_id: Uuid) -> Result<DriverMail, MailError> {
+        let original = self
+            .mail_database_repository
+            .get_mail_by_id(mail_id)
+            .await?;
+
+        if original.fk_driver_id != driver_id {
+            return Err(MailError::MailNotFound);
+        }
+
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await
+            .map_err(|_| MailError::Internal)?
+            .ok_or(MailError::MailNotFound)?;
+
+        let attachments = self
+            .mail_database_repository
+            .get_mail_attachments(vec![mail_id])
+            .await?;
+
+        let resend = match EnumDriverMailType::from_id(original.fk_mail_type_id) {
+            Some(EnumDriverMailType::AccountVerification) if driver.verified_at.is_none() => {
+                MailResend::AccountVerification
+            }
+            Some(EnumDriverMailType::PasswordReset) => MailResend::PasswordReset,
+            Some(EnumDriverMailType::MagicLink) => MailResend::MagicLink,
+            Some(EnumDriverMailType::MonthlyReports) => {
+                let attachment = attachments
+                    .first()
+                    .cloned()
+                    .ok_or(MailError::MailNotResendable)?;
+                let (month, year) = attachment
+                    .monthly_report_period()
+                    .ok_or(MailError::MailNotResendable)?;
+                MailResend::MonthlyReport {
+                    month,
+                    year,
+                    attachment,
+                }
+            }
+            _ => return Err(MailError::MailNotResendable),
+        };
+
+        if self.is_email_unreachable(&driver.email).await? {
+            return Err(MailError::EmailUnreachable);
+        }
+
+        let (rate_limit_key, rate_limit_ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::MailResend);
+        if self
+            .driver_cache_repository
+            .get_redis(rate_limit_key.clone())
+            .await
+            .map_err(|_| MailError::Internal)?
+            .is_some()
+        {
+            return Err(MailError::MailResendRateLimited);
+        }
+        self.driver_cache_repository
+            .set_redis(rate_limit_key, mail_id.to_string(), rate_limit_ttl)
+            .await
+            .map_err(|_| MailError::Internal)?;
+
+        let mail = self
+            .mail_database_repository
+            .create_resent_mail(driver.clone(), original.clone())
+            .await?;
+
+        if !attachments.is_empty() {
+            self.mail_database_repository
+                .copy_mail_attachments(mail_id, mail.pk_driver_mail_id)
+                .await?;
+        }
+
+        // Explicitly asked for by the driver, so sent whatever their preference for the type
+        let mail = match self.send_resent_mail(driver, resend).await {
+            Ok(_) => {
+                self.mail_database_repository
+                    .update_mail_status(
+                        mail.pk_driver_mail_id,
+                        MailStatus::SUCCESS,
+                        Some(Utc::now()),
+                    )
+                    .await?
+            }
+            Err(e) => {
+                let _ = self
+                    .mail_database_repository
+                    .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                    .await;
+                let _ = self.mail_cache_repository.delete_mails(driver_id).await;
+                return Err(e);
+            }
+        };
+
+        let _ = self.mail_cache_repository.delete_mails(driver_id).await;
+
+        let mail_type = self
+            .mail_database_repository
+            .get_mail_type_by_id(mail.fk_mail_type_id)
+            .await?
+            .to_driver_mail_type();
+
+        let attachments = self
+            .mail_database_repository
+            .get_mail_attachments(vec![mail.pk_driver_mail_id])
+            .await?
+            .iter()
+            .map(|a| a.to_driver_mail_attachment())
+            .collect();
+
+        Ok(mail.to_driver_mail(mail_type, attachments))
+    }
+
+    #[tracing::instrument(
+        name = "mail_service.cancel_mail",
+        skip(self),
+        fields(driver_id = %driver_id, mail_id = %mail_id)
     )]
-    async fn send_email_change_notification(&self, driver: DriverRow) -> Result<(), MailError> {
-        let bitmask = self
+    async fn cancel_mail(&self, driver_id: Uuid, mail_id: Uuid) -> Result<DriverMail, MailError> {
+        let mail = self
+            .mail_database_repository
+            .get_mail_by_id(mail_id)
+            .await?;
+
+        if mail.fk_driver_id != driver_id {
+            return Err(MailError::MailNotFound);
+        }
+
+        let key_type = mail_token_key_type(&mail).ok_or(MailError::MailNotCancellable)?;
+        let (redis_key, redis_ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, key_type);
+
+        // Past its TTL the link already stopped working, and its key may hold a newer token
+        let expires_at = mail.created_at + chrono::Duration::seconds(redis_ttl as i64);
+        if mail.status == MailStatus::CANCELLED || expires_at <= Utc::now() {
+            return Err(MailError::MailNotCancellable);
+        }
+
+        self.driver_cache_repository
+            .delete_redis(redis_key)
+            .await
+            .map_err(|_| MailError::Internal)?;
+
+        let mail = self
             .mail_database_repository
-            .get_driver_mail_preferences(driver.pk_driver_id)
+            .update_mail_status(mail_id, MailStatus::CANCELLED, mail.sent_at)
             .await?;
 
-        let bit = 1 << (EnumDriverMailType::AccountChangement.as_id() - 1);
-        if bitmask & bit == 0 {
+        let mail_type = self
+            .mail_database_repository
+            .get_mail_type_by_id(mail.fk_mail_type_id)
+            .await?
+            .to_driver_mail_type();
+
+        let mail = mail.to_driver_mail(mail_type, Vec::new());
+
+        let _ = self.mail_cache_repository.delete_mails(driver_id).await;
+        let _ = self
+            .mail_cache_repository
+            .set_mail(driver_id, mail_id, mail.clone())
+            .await;
+
+        Ok(mail)
+    }
+
+    #[tracing::instrument(
+        name = "mail_service.send_email_change_notification",
+        skip(self, email_change),
+        fields(driver_id = %driver.pk_driver_id)
+    )]
+    async fn send_email_change_notification(
+        &self,
+        mut driver: DriverRow,
+        email_change: DriverEmailChange,
+    ) -> Result<(), MailError> {
+        // Security mail holding the revert link: sent whatever the driver's preferences
+        if self
+            .is_email_unreachable(&email_change.previous_email)
+            .await?
+        {
             return Ok(());
         }
 
+        // The revert link stays valid until the confirmed change leaves the revert window
+        let (_, revert_ttl) = self.driver_cache_repository.get_key_by_type(
+            driver.pk_driver_id,
+            DriverCacheKeyType::ConfirmedEmailChange,
+        );
+
+        driver.email = email_change.previous_email;
+
         let mail = self
             .mail_database_repository
             .create_mail(
@@ -471,7 +730,12 @@ where
 
         match self
             .mail_smtp_repository
-            .send_driver_email_change_email(driver.clone())
+            .send_driver_email_change_email(
+                driver.clone(),
+                email_change.new_email,
+                email_change.revert_token,
+                revert_ttl,
+            )
             .await
         {
             Ok(_) => {
@@ -501,13 +765,20 @@ where
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_password_change_notification(&self, driver: DriverRow) -> Result<(), MailError> {
-        let bitmask = self
+        let is_enabled = self
             .mail_database_repository
-            .get_driver_mail_preferences(driver.pk_driver_id)
+            .is_notification_enabled(
+                driver.pk_driver_id,
+                EnumDriverMailType::AccountChangement,
+                NotificationChannel::EMAIL,
+            )
             .await?;
 
-        let bit = 1 << (EnumDriverMailType::AccountChangement.as_id() - 1);
-        if bitmask & bit == 0 {
+        if !is_enabled {
+            return Ok(());
+        }
+
+        if self.is_email_unreachable(&driver.email).await? {
             return Ok(());
         }
 
@@ -553,15 +824,23 @@ where
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_reset_password_email(&self, driver: DriverRow) -> Result<(), MailError> {
-        let bitmask = self
+        let is_enabled = self
             .mail_database_repository
-            .get_driver_mail_preferences(driver.pk_driver_id)
+            .is_notification_enabled(
+                driver.pk_driver_id,
+                EnumDriverMailType::PasswordReset,
+                NotificationChannel::EMAIL,
+            )
             .await?;
-        let bit = 1 << (EnumDriverMailType::PasswordReset.as_id() - 1);
-        if bitmask & bit == 0 {
+
+        if !is_enabled {
             return Err(MailError::MailPreferenceDisabled);
         }
 
+        if self.is_email_unreachable(&driver.email).await? {
+            return Err(MailError::EmailUnreachable);
+        }
+
         let reset_value = self
             .driver_cache_repository
             .generate_random_value(100)
@@ -613,14 +892,29 @@ where
     }
 
     #[tracing::instrument(
-        name = "mail_service.send_email_change_verification",
+        name = "mail_service.send_magic_link_email",
         skip(self),
-        fields(
-            driver_id = %driver.pk_driver_id,
-        )
+        fields(driver_id = %driver.pk_driver_id)
     )]
-    async fn send_email_change_verification(&self, driver: DriverRow) -> Result<(), MailError> {
-        let verify_value = self
+    async fn send_magic_link_email(&self, driver: DriverRow) -> Result<(), MailError> {
+        let is_enabled = self
+            .mail_database_repository
+            .is_notification_enabled(
+                driver.pk_driver_id,
+                EnumDriverMailType::MagicLink,
+                NotificationChannel::EMAIL,
+            )
+            .await?;
+
+        if !is_enabled {
+            return Err(MailError::MailPreferenceDisabled);
+        }
+
+        if self.is_email_unreachable(&driver.email).await? {
+            return Err(MailError::EmailUnreachable);
+        }
+
+        let magic_link_value = self
             .driver_cache_repository
             .generate_random_value(100)
             .await
@@ -628,11 +922,69 @@ where
 
         let (redis_key, redis_ttl) = self
             .driver_cache_repository
-            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::VerifyEmail);
-        let _ = self
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::MagicLink);
+        self.driver_cache_repository
+            .set_redis(redis_key, magic_link_value.clone(), redis_ttl)
+            .await
+            .map_err(|_| MailError::Internal)?;
+
+        let mail = self
+            .mail_database_repository
+            .create_mail(
+                driver.clone(),
+                EnumDriverMailType::MagicLink,
+                "Driver magic link login".to_string(),
+                None,
+            )
+            .await?;
+
+        match self
+            .mail_smtp_repository
+            .send_driver_magic_link_email(driver.clone(), magic_link_value, redis_ttl)
+            .await
+        {
+            Ok(_) => {
+                self.mail_database_repository
+                    .update_mail_status(
+                        mail.pk_driver_mail_id,
+                        MailStatus::SUCCESS,
+                        Some(Utc::now()),
+                    )
+                    .await?;
+            }
+            Err(_) => {
+                let _ = self
+                    .mail_database_repository
+                    .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                    .await?;
+                return Err(MailError::Internal);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "mail_service.send_email_change_verification",
+        skip(self, email_change),
+        fields(
+            driver_id = %driver.pk_driver_id,
+        )
+    )]
+    async fn send_email_change_verification(
+        &self,
+        mut driver: DriverRow,
+        email_change: DriverEmailChange,
+    ) -> Result<(), MailError> {
+        if self.is_email_unreachable(&email_change.new_email).await? {
+            return Err(MailError::EmailUnreachable);
+        }
+
+        let (_, confirm_ttl) = self
             .driver_cache_repository
-            .set_redis(redis_key, verify_value.clone(), redis_ttl)
-            .await;
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::PendingEmailChange);
+
+        driver.email = email_change.new_email;
 
         let mail = self
             .mail_database_repository
@@ -646,7 +998,11 @@ where
 
         match self
             .mail_smtp_repository
-            .send_driver_verification_email(driver.clone(), verify_value, redis_ttl)
+            .send_driver_email_change_confirmation_email(
+                driver.clone(),
+                email_change.confirm_token,
+                confirm_ttl,
+            )
             .await
         {
             Ok(_) => {
@@ -670,54 +1026,338 @@ where
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::sync::{Arc, Mutex};
+    #[tracing::instrument(
+        name = "mail_service.send_document_share_email",
+        skip(self, driver, share_url),
+        fields(driver_id = %driver.pk_driver_id, share_id = %share.pk_document_share_id)
+    )]
+    async fn send_document_share_email(
+        &self,
+        mut driver: DriverRow,
+        share: DocumentShareRow,
+        share_url: String,
+    ) -> Result<(), MailError> {
+        let recipient_email = share.recipient_email.clone().ok_or(MailError::Internal)?;
+        if self.is_email_unreachable(&recipient_email).await? {
+            return Err(MailError::EmailUnreachable);
+        }
 
-    use chrono::{DateTime, Utc};
-    use uuid::Uuid;
+        driver.email = recipient_email;
 
-    use crate::{
-        Service, ServiceConfig,
-        domain::{
-            common::constants::EnumDriverMailType,
-            document::port::MockDocumentExternalRepository,
-            driver::{
-                entities::DriverRow,
-                port::{MockDriverCacheRepository, MockDriverDatabaseRepository},
-            },
-            health::port::MockHealthRepository,
-            mail::{
-                entities::{DriverMailAttachmentRow, DriverMailRow, DriverMailTypeRow, MailStatus},
-                port::{
-                    MailDatabaseRepository, MailService, MockMailCacheRepository,
-                    MockMailSmtpRepository,
-                },
-            },
-            storage::port::MockStorageRepository,
+        let mail = self
+            .mail_database_repository
+            .create_mail(
+                driver.clone(),
+                EnumDriverMailType::DocumentShared,
+                "Driver document share".to_string(),
+                None,
+            )
+            .await?;
+
+        match self
+            .mail_smtp_repository
+            .send_driver_document_shared_email(driver, share, share_url)
+            .await
+        {
+            Ok(_) => {
+                self.mail_database_repository
+                    .update_mail_status(
+                        mail.pk_driver_mail_id,
+                        MailStatus::SUCCESS,
+                        Some(Utc::now()),
+                    )
+                    .await?;
+            }
+            Err(_) => {
+                let _ = self
+                    .mail_database_repository
+                    .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                    .await?;
+
+                return Err(MailError::Internal);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "mail_service.process_mail_events", skip_all)]
+    async fn process_mail_events(&self, token: String, body: Value) -> Result<usize, MailError> {
+        if !self.config.mail_events.verify(&token) {
+            return Err(MailError::InvalidMailEventsToken);
+        }
+
+        let events = MailEvent::from_webhook(&body).ok_or(MailError::InvalidMailEvents)?;
+        let count = events.len();
+
+        for event in events {
+            let event = self
+                .mail_database_repository
+                .create_mail_event(event)
+                .await?;
+
+            match event.event_type {
+                MailEventType::HardBounce => {
+                    self.mail_database_repository
+                        .suppress_email(event.email, event.pk_mail_event_id)
+                        .await?;
+                }
+                MailEventType::Complaint => {
+                    match event.fk_mail_type_id.and_then(EnumDriverMailType::from_id) {
+                        // The recipient of a shared document is not the driver who shared it
+                        Some(EnumDriverMailType::DocumentShared) => {
+                            self.mail_database_repository
+                                .suppress_email(event.email, event.pk_mail_event_id)
+                                .await?;
+                        }
+                        _ => {
+                            if let Some(driver_id) = event.fk_driver_id {
+                                self.disable_editable_mail_types(driver_id).await?;
+                            }
+                        }
+                    }
+                }
+                MailEventType::SoftBounce => {}
+            }
+
+            if let Some(driver_id) = event.fk_driver_id {
+                let _ = self.mail_cache_repository.delete_mails(driver_id).await;
+            }
+        }
+
+        Ok(count)
+    }
+
+    #[tracing::instrument(name = "mail_service.get_email_unreachable_at", skip_all)]
+    async fn get_email_unreachable_at(
+        &self,
+        email: String,
+    ) -> Result<Option<DateTime<Utc>>, MailError> {
+        Ok(self
+            .mail_database_repository
+            .get_email_suppression(email)
+            .await?
+            .map(|suppression| suppression.created_at))
+    }
+}
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+    Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+where
+    H: HealthRepository,
+    DD: DriverDatabaseRepository,
+    DC: DriverCacheRepository,
+    WD: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    MS: MailSmtpRepository,
+    MD: MailDatabaseRepository,
+    MC: MailCacheRepository,
+    UD: UpdateDatabaseRepository,
+    UC: UpdateCacheRepository,
+    DE: DocumentExternalRepository,
+    DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
+{
+    /// Hard bounced addresses get no mail at all, not to hurt the sender reputation.
+    async fn is_email_unreachable(&self, email: &str) -> Result<bool, MailError> {
+        Ok(self
+            .mail_database_repository
+            .get_email_suppression(email.to_string())
+            .await?
+            .is_some())
+    }
+
+    /// Stores a new random token under `key_type`, replacing the one of any previous mail.
+    async fn issue_mail_token(
+        &self,
+        driver_id: Uuid,
+        key_type: DriverCacheKeyType,
+    ) -> Result<(String, u64), MailError> {
+        let value = self
+            .driver_cache_repository
+            .generate_random_value(100)
+            .await
+            .map_err(|_| MailError::Internal)?;
+
+        let (redis_key, redis_ttl) = self
+            .driver_cache_repository
+            .get_key_by_type(driver_id, key_type);
+        self.driver_cache_repository
+            .set_redis(redis_key, value.clone(), redis_ttl)
+            .await
+            .map_err(|_| MailError::Internal)?;
+
+        Ok((value, redis_ttl))
+    }
+
+    async fn send_resent_mail(
+        &self,
+        driver: DriverRow,
+        resend: MailResend,
+    ) -> Result<(), MailError> {
+        let driver_id = driver.pk_driver_id;
+        let sent = match resend {
+            MailResend::AccountVerification => {
+                let (value, ttl) = self
+                    .issue_mail_token(driver_id, DriverCacheKeyType::VerifyEmail)
+                    .await?;
+                self.mail_smtp_repository
+                    .send_driver_creation_email(driver, value, ttl)
+                    .await
+            }
+            MailResend::PasswordReset => {
+                let (value, ttl) = self
+                    .issue_mail_token(driver_id, DriverCacheKeyType::ResetPassword)
+                    .await?;
+                self.mail_smtp_repository
+                    .send_driver_reset_password_email(driver, value, ttl)
+                    .await
+            }
+            MailResend::MagicLink => {
+                let (value, ttl) = self
+                    .issue_mail_token(driver_id, DriverCacheKeyType::MagicLink)
+                    .await?;
+                self.mail_smtp_repository
+                    .send_driver_magic_link_email(driver, value, ttl)
+                    .await
+            }
+            MailResend::MonthlyReport {
+                month,
+                year,
+                attachment,
+            } => {
+                let pdf_bytes = self
+                    .storage_repository
+                    .download(&attachment.s3_file_path)
+                    .await
+                    .map_err(|e| match e {
+                        StorageError::ObjectNotFound => MailError::MailAttachmentNotFound,
+                        _ => MailError::Internal,
+                    })?;
+                self.mail_smtp_repository
+                    .send_driver_monthly_report_email(
+                        driver,
+                        month,
+                        year,
+                        pdf_bytes,
+                        attachment.file_name,
+                    )
+                    .await
+            }
+        };
+
+        sent.map_err(|_| MailError::Internal)
+    }
+
+    /// Reacts to a spam complaint like to an unsubscribe from every optional mail.
+    async fn disable_editable_mail_types(&self, driver_id: Uuid) -> Result<(), MailError> {
+        let mail_types = self.mail_database_repository.get_mail_types().await?;
+        for mail_type in mail_types.iter().filter(|t| t.is_editable) {
+            self.mail_database_repository
+                .upsert_driver_notification_preference(
+                    driver_id,
+                    mail_type.pk_driver_mail_type_id,
+                    NotificationChannel::EMAIL,
+                    false,
+                )
+                .await?;
+        }
+
+        let _ = self
+            .mail_cache_repository
+            .delete_mail_preferences(driver_id)
+            .await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    use crate::{
+        Service, ServiceConfig,
+        domain::{
+            common::{
+                config::{MailEventsConfig, MailUnsubscribeConfig},
+                constants::EnumDriverMailType,
+            },
+            document::port::MockDocumentExternalRepository,
+            driver::{
+                entities::{CreateDriverRequest, DriverEmailChange, DriverLanguage, DriverRow},
+                port::{
+                    DriverCacheKeyType, DriverCacheRepository, DriverDatabaseRepository,
+                    MockDriverCacheRepository, MockDriverDatabaseRepository,
+                },
+            },
+            health::port::MockHealthRepository,
+            mail::{
+                entities::{
+                    DriverMailAttachmentRow, DriverMailRow, DriverMailTypeRow,
+                    DriverNotificationPreferenceRow, EmailSuppressionRow, MailEvent, MailEventRow,
+                    MailStatus, NotificationChannel, NotificationFrequency,
+                },
+                port::{
+                    MailDatabaseRepository, MailService, MockMailCacheRepository,
+                    MockMailSmtpRepository,
+                },
+            },
+            notification::port::{MockNotificationDatabaseRepository, MockSmsRepository},
+            storage::port::MockStorageRepository,
             update::port::{MockUpdateCacheRepository, MockUpdateDatabaseRepository},
             workday::port::{MockWorkdayCacheRepository, MockWorkdayDatabaseRepository},
         },
         infrastructure::mail::repositories::error::MailError,
     };
 
-    /// Spy for MailDatabaseRepository: lets tests control the preference bitmask
+    const MAIL_TYPE_IDS: [i32; 6] = [1, 2, 3, 4, 5, 8];
+
+    /// Spy for MailDatabaseRepository: lets tests control which mail types are enabled
     /// and observe how many times create_mail was called.
     #[derive(Clone)]
     struct MailDbSpy {
-        preferences_bitmask: i32,
+        enabled_mail_type_ids: Arc<Mutex<Vec<i32>>>,
         create_mail_calls: Arc<Mutex<u32>>,
         mails: Arc<Mutex<Vec<DriverMailRow>>>,
+        suppressed_emails: Arc<Mutex<Vec<String>>>,
     }
 
     impl MailDbSpy {
-        fn new(preferences_bitmask: i32) -> Self {
+        fn new(enabled_mail_type_ids: Vec<i32>) -> Self {
             Self {
-                preferences_bitmask,
+                enabled_mail_type_ids: Arc::new(Mutex::new(enabled_mail_type_ids)),
                 create_mail_calls: Arc::new(Mutex::new(0)),
                 mails: Arc::new(Mutex::new(Vec::new())),
+                suppressed_emails: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+
+        fn all_enabled() -> Self {
+            Self::new(MAIL_TYPE_IDS.to_vec())
+        }
+
+        fn preference(
+            &self,
+            driver_id: Uuid,
+            mail_type_id: i32,
+        ) -> DriverNotificationPreferenceRow {
+            DriverNotificationPreferenceRow {
+                fk_driver_id: driver_id,
+                fk_mail_type_id: mail_type_id,
+                channel: NotificationChannel::EMAIL,
+                frequency: NotificationFrequency::INSTANT,
+                is_enabled: self
+                    .enabled_mail_type_ids
+                    .lock()
+                    .unwrap()
+                    .contains(&mail_type_id),
+                updated_at: Utc::now(),
             }
         }
 
@@ -746,6 +1386,26 @@ mod tests {
                 status: MailStatus::PENDING,
                 created_at: Utc::now(),
                 sent_at: None,
+                fk_resent_from_mail_id: None,
+            };
+            self.mails.lock().unwrap().push(mail.clone());
+            Ok(mail)
+        }
+
+        async fn create_resent_mail(
+            &self,
+            driver: DriverRow,
+            original: DriverMailRow,
+        ) -> Result<DriverMailRow, MailError> {
+            *self.create_mail_calls.lock().unwrap() += 1;
+            let mail = DriverMailRow {
+                pk_driver_mail_id: Uuid::new_v4(),
+                email_used: driver.email.clone(),
+                status: MailStatus::PENDING,
+                created_at: Utc::now(),
+                sent_at: None,
+                fk_resent_from_mail_id: Some(original.pk_driver_mail_id),
+                ..original
             };
             self.mails.lock().unwrap().push(mail.clone());
             Ok(mail)
@@ -779,26 +1439,71 @@ mod tests {
         }
 
         async fn get_mail_types(&self) -> Result<Vec<DriverMailTypeRow>, MailError> {
-            Ok(Vec::new())
+            let mut mail_types = Vec::new();
+            for mail_type_id in MAIL_TYPE_IDS {
+                mail_types.push(self.get_mail_type_by_id(mail_type_id).await?);
+            }
+            Ok(mail_types)
         }
 
         async fn get_mail_type_by_id(
             &self,
-            _mail_type_id: i32,
+            mail_type_id: i32,
         ) -> Result<DriverMailTypeRow, MailError> {
-            Err(MailError::MailTypeNotFound)
+            if !MAIL_TYPE_IDS.contains(&mail_type_id) {
+                return Err(MailError::MailTypeNotFound);
+            }
+
+            // Only monthly reports can be switched off
+            let is_editable = mail_type_id == EnumDriverMailType::MonthlyReports.as_id();
+            Ok(DriverMailTypeRow {
+                pk_driver_mail_type_id: mail_type_id,
+                label: format!("MAIL_TYPE_{}", mail_type_id),
+                index: mail_type_id,
+                is_editable,
+                default_is_enabled: true,
+            })
         }
 
-        async fn get_driver_mail_preferences(&self, _driver_id: Uuid) -> Result<i32, MailError> {
-            Ok(self.preferences_bitmask)
+        async fn get_driver_notification_preferences(
+            &self,
+            driver_id: Uuid,
+        ) -> Result<Vec<DriverNotificationPreferenceRow>, MailError> {
+            Ok(MAIL_TYPE_IDS
+                .iter()
+                .map(|mail_type_id| self.preference(driver_id, *mail_type_id))
+                .collect())
         }
 
-        async fn update_driver_mail_preferences(
+        async fn is_notification_enabled(
             &self,
             _driver_id: Uuid,
-            mail_preferences: i32,
-        ) -> Result<i32, MailError> {
-            Ok(mail_preferences)
+            mail_type: EnumDriverMailType,
+            _channel: NotificationChannel,
+        ) -> Result<bool, MailError> {
+            Ok(self
+                .enabled_mail_type_ids
+                .lock()
+                .unwrap()
+                .contains(&mail_type.as_id()))
+        }
+
+        async fn upsert_driver_notification_preference(
+            &self,
+            driver_id: Uuid,
+            mail_type_id: i32,
+            _channel: NotificationChannel,
+            is_enabled: bool,
+        ) -> Result<DriverNotificationPreferenceRow, MailError> {
+            {
+                let mut enabled_mail_type_ids = self.enabled_mail_type_ids.lock().unwrap();
+                enabled_mail_type_ids.retain(|id| *id != mail_type_id);
+                if is_enabled {
+                    enabled_mail_type_ids.push(mail_type_id);
+                }
+            }
+
+            Ok(self.preference(driver_id, mail_type_id))
         }
 
         async fn get_mail_attachments(
@@ -808,8 +1513,14 @@ mod tests {
             Ok(Vec::new())
         }
 
-        async fn get_mail_by_id(&self, _mail_id: Uuid) -> Result<DriverMailRow, MailError> {
-            Err(MailError::MailNotFound)
+        async fn get_mail_by_id(&self, mail_id: Uuid) -> Result<DriverMailRow, MailError> {
+            self.mails
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|m| m.pk_driver_mail_id == mail_id)
+                .cloned()
+                .ok_or(MailError::MailNotFound)
         }
 
         async fn get_mail_attachment_by_id(
@@ -828,6 +1539,23 @@ mod tests {
             Ok(false)
         }
 
+        async fn has_workday_reminder_since(
+            &self,
+            _driver_id: Uuid,
+            _since: DateTime<Utc>,
+        ) -> Result<bool, MailError> {
+            Ok(false)
+        }
+
+        async fn count_mails_since(
+            &self,
+            _driver_id: Uuid,
+            _mail_type: EnumDriverMailType,
+            _since: DateTime<Utc>,
+        ) -> Result<i64, MailError> {
+            Ok(0)
+        }
+
         async fn has_document_at_path(&self, _s3_file_path: &str) -> Result<bool, MailError> {
             Ok(false)
         }
@@ -847,6 +1575,57 @@ mod tests {
         ) -> Result<(), MailError> {
             Ok(())
         }
+
+        async fn copy_mail_attachments(
+            &self,
+            _from_mail_id: Uuid,
+            _to_mail_id: Uuid,
+        ) -> Result<(), MailError> {
+            Ok(())
+        }
+
+        async fn create_mail_event(&self, event: MailEvent) -> Result<MailEventRow, MailError> {
+            let mails = self.mails.lock().unwrap();
+            let mail = mails.iter().rev().find(|m| m.email_used == event.email);
+
+            Ok(MailEventRow {
+                pk_mail_event_id: Uuid::new_v4(),
+                fk_driver_mail_id: mail.map(|m| m.pk_driver_mail_id),
+                fk_driver_id: mail.map(|m| m.fk_driver_id),
+                fk_mail_type_id: mail.map(|m| m.fk_mail_type_id),
+                email: event.email,
+                event_type: event.event_type,
+                provider: event.provider,
+                detail: event.detail,
+                occurred_at: event.occurred_at,
+                created_at: Utc::now(),
+            })
+        }
+
+        async fn suppress_email(
+            &self,
+            email: String,
+            mail_event_id: Uuid,
+        ) -> Result<EmailSuppressionRow, MailError> {
+            self.suppressed_emails.lock().unwrap().push(email.clone());
+            Ok(EmailSuppressionRow {
+                email,
+                fk_mail_event_id: mail_event_id,
+                created_at: Utc::now(),
+            })
+        }
+
+        async fn get_email_suppression(
+            &self,
+            email: String,
+        ) -> Result<Option<EmailSuppressionRow>, MailError> {
+            let suppressed = self.suppressed_emails.lock().unwrap().contains(&email);
+            Ok(suppressed.then(|| EmailSuppressionRow {
+                email,
+                fk_mail_event_id: Uuid::new_v4(),
+                created_at: Utc::now(),
+            }))
+        }
     }
 
     fn make_driver() -> DriverRow {
@@ -862,17 +1641,26 @@ mod tests {
             allow_request_professional_agreement: false,
             language: "fr".to_string(),
             rest_json: None,
-            mail_preferences: 0,
             created_at: Utc::now(),
             verified_at: None,
             last_login_at: None,
             deactivated_at: None,
+            phone_verified_at: None,
+            version: 1,
+        }
+    }
+
+    fn make_email_change() -> DriverEmailChange {
+        DriverEmailChange {
+            previous_email: "test@example.be".to_string(),
+            new_email: "new@example.be".to_string(),
+            confirm_token: "confirm".to_string(),
+            revert_token: "revert".to_string(),
+            confirmed_at: None,
         }
     }
 
-    fn make_service(
-        mail_db: MailDbSpy,
-    ) -> Service<
+    type SpyService = Service<
         MockHealthRepository,
         MockDriverDatabaseRepository,
         MockDriverCacheRepository,
@@ -885,7 +1673,11 @@ mod tests {
         MockUpdateCacheRepository,
         MockDocumentExternalRepository,
         MockStorageRepository,
-    > {
+        MockNotificationDatabaseRepository,
+        MockSmsRepository,
+    >;
+
+    fn make_service(mail_db: MailDbSpy) -> SpyService {
         Service::new(
             MockHealthRepository,
             MockDriverDatabaseRepository::new(),
@@ -899,18 +1691,32 @@ mod tests {
             MockUpdateCacheRepository::new(),
             MockDocumentExternalRepository,
             MockStorageRepository::new(),
+            MockNotificationDatabaseRepository::new(),
+            MockSmsRepository::new(),
             ServiceConfig {
                 workday_garbage_retention_days: 30,
                 account_deactivation_days: 30,
+                mail_unsubscribe: make_unsubscribe_config(),
+                mail_events: MailEventsConfig {
+                    webhook_token: Some("webhook-token".to_string()),
+                },
+                ..ServiceConfig::default()
             },
         )
     }
 
+    fn make_unsubscribe_config() -> MailUnsubscribeConfig {
+        MailUnsubscribeConfig {
+            api_url: "http://localhost:8080".to_string(),
+            secret: "unsubscribe-secret".to_string(),
+        }
+    }
+
     // ── send_creation_email ──────────────────────────────────────────────────────
 
     #[tokio::test]
     async fn send_creation_email_returns_err_when_account_verification_disabled() {
-        let spy = MailDbSpy::new(0);
+        let spy = MailDbSpy::new(Vec::new());
         let service = make_service(spy.clone());
 
         let result = service.send_creation_email(make_driver()).await;
@@ -925,8 +1731,7 @@ mod tests {
 
     #[tokio::test]
     async fn send_creation_email_succeeds_and_creates_mail_when_preference_enabled() {
-        let all_enabled = i32::MAX;
-        let spy = MailDbSpy::new(all_enabled);
+        let spy = MailDbSpy::all_enabled();
         let service = make_service(spy.clone());
 
         let result = service.send_creation_email(make_driver()).await;
@@ -942,27 +1747,30 @@ mod tests {
     // ── send_email_change_notification ──────────────────────────────────────────
 
     #[tokio::test]
-    async fn send_email_change_notification_skips_silently_when_preference_disabled() {
-        let spy = MailDbSpy::new(0);
+    async fn send_email_change_notification_sends_even_when_preference_disabled() {
+        let spy = MailDbSpy::new(Vec::new());
         let service = make_service(spy.clone());
 
-        let result = service.send_email_change_notification(make_driver()).await;
+        let result = service
+            .send_email_change_notification(make_driver(), make_email_change())
+            .await;
 
-        assert!(
-            result.is_ok(),
-            "expected Ok(()) silent skip, got {:?}",
-            result
+        assert!(result.is_ok(), "expected Ok, got {:?}", result);
+        assert_eq!(
+            spy.create_mail_call_count(),
+            1,
+            "the mail should be sent anyway"
         );
-        assert_eq!(spy.create_mail_call_count(), 0, "no mail should be created");
     }
 
     #[tokio::test]
     async fn send_email_change_notification_creates_mail_when_preference_enabled() {
-        let account_changement_bit = 1 << (EnumDriverMailType::AccountChangement.as_id() - 1);
-        let spy = MailDbSpy::new(account_changement_bit);
+        let spy = MailDbSpy::new(vec![EnumDriverMailType::AccountChangement.as_id()]);
         let service = make_service(spy.clone());
 
-        let result = service.send_email_change_notification(make_driver()).await;
+        let result = service
+            .send_email_change_notification(make_driver(), make_email_change())
+            .await;
 
         assert!(result.is_ok(), "expected Ok, got {:?}", result);
         assert_eq!(
@@ -976,7 +1784,7 @@ mod tests {
 
     #[tokio::test]
     async fn send_password_change_notification_skips_silently_when_preference_disabled() {
-        let spy = MailDbSpy::new(0);
+        let spy = MailDbSpy::new(Vec::new());
         let service = make_service(spy.clone());
 
         let result = service
@@ -993,8 +1801,7 @@ mod tests {
 
     #[tokio::test]
     async fn send_password_change_notification_creates_mail_when_preference_enabled() {
-        let account_changement_bit = 1 << (EnumDriverMailType::AccountChangement.as_id() - 1);
-        let spy = MailDbSpy::new(account_changement_bit);
+        let spy = MailDbSpy::new(vec![EnumDriverMailType::AccountChangement.as_id()]);
         let service = make_service(spy.clone());
 
         let result = service
@@ -1008,4 +1815,373 @@ mod tests {
             "one mail should be created"
         );
     }
+
+    // ── unsubscribe ─────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn unsubscribe_disables_preference_with_valid_signature() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy);
+        let driver_id = Uuid::new_v4();
+        let mail_type_id = EnumDriverMailType::MonthlyReports.as_id();
+        let signature = make_unsubscribe_config().signature(driver_id, mail_type_id);
+
+        let result = service
+            .unsubscribe(driver_id, mail_type_id, signature)
+            .await
+            .expect("unsubscribe should succeed");
+
+        assert_eq!(result.mail_type_id, mail_type_id);
+        assert!(!result.is_enabled, "preference should be disabled");
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_rejects_signature_of_another_driver() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy);
+        let mail_type_id = EnumDriverMailType::MonthlyReports.as_id();
+        let signature = make_unsubscribe_config().signature(Uuid::new_v4(), mail_type_id);
+
+        let result = service
+            .unsubscribe(Uuid::new_v4(), mail_type_id, signature)
+            .await;
+
+        assert!(
+            matches!(result, Err(MailError::InvalidUnsubscribeSignature)),
+            "expected InvalidUnsubscribeSignature, got {:?}",
+            result
+        );
+    }
+
+    // ── resend_mail ─────────────────────────────────────────────────────────────
+
+    async fn create_driver(service: &SpyService) -> DriverRow {
+        service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "Test".to_string(),
+                lastname: "Driver".to_string(),
+                gender: None,
+                email: "test@example.be".to_string(),
+                password: "hash".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn get_token(
+        service: &SpyService,
+        driver_id: Uuid,
+        key_type: DriverCacheKeyType,
+    ) -> String {
+        let (key, _) = service
+            .driver_cache_repository
+            .get_key_by_type(driver_id, key_type);
+        service
+            .driver_cache_repository
+            .get_redis(key)
+            .await
+            .unwrap()
+            .expect("token should be stored")
+    }
+
+    #[tokio::test]
+    async fn resend_mail_sends_password_reset_with_fresh_token() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        let driver = create_driver(&service).await;
+
+        service
+            .send_reset_password_email(driver.clone())
+            .await
+            .unwrap();
+        let original_id = spy.mails.lock().unwrap()[0].pk_driver_mail_id;
+        let previous_token = get_token(
+            &service,
+            driver.pk_driver_id,
+            DriverCacheKeyType::ResetPassword,
+        )
+        .await;
+
+        let mail = service
+            .resend_mail(driver.pk_driver_id, original_id)
+            .await
+            .expect("resend should succeed");
+
+        assert_ne!(mail.pk_driver_mail_id, original_id);
+        assert_eq!(mail.resent_from_mail_id, Some(original_id));
+        assert_eq!(mail.status, MailStatus::SUCCESS);
+        assert_eq!(spy.create_mail_call_count(), 2);
+        assert_ne!(
+            get_token(
+                &service,
+                driver.pk_driver_id,
+                DriverCacheKeyType::ResetPassword
+            )
+            .await,
+            previous_token,
+            "the reset token should be renewed"
+        );
+    }
+
+    #[tokio::test]
+    async fn resend_mail_is_rate_limited() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        let driver = create_driver(&service).await;
+
+        service.send_magic_link_email(driver.clone()).await.unwrap();
+        let original_id = spy.mails.lock().unwrap()[0].pk_driver_mail_id;
+
+        service
+            .resend_mail(driver.pk_driver_id, original_id)
+            .await
+            .expect("first resend should succeed");
+        let result = service.resend_mail(driver.pk_driver_id, original_id).await;
+
+        assert!(
+            matches!(result, Err(MailError::MailResendRateLimited)),
+            "expected MailResendRateLimited, got {:?}",
+            result
+        );
+        assert_eq!(spy.create_mail_call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn resend_mail_rejects_mail_types_that_cannot_be_resent() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        let driver = create_driver(&service).await;
+
+        service
+            .send_password_change_notification(driver.clone())
+            .await
+            .unwrap();
+        let original_id = spy.mails.lock().unwrap()[0].pk_driver_mail_id;
+
+        let result = service.resend_mail(driver.pk_driver_id, original_id).await;
+
+        assert!(
+            matches!(result, Err(MailError::MailNotResendable)),
+            "expected MailNotResendable, got {:?}",
+            result
+        );
+        assert_eq!(spy.create_mail_call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn resend_mail_of_another_driver_is_not_found() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        let driver = create_driver(&service).await;
+
+        service
+            .send_reset_password_email(driver.clone())
+            .await
+            .unwrap();
+        let original_id = spy.mails.lock().unwrap()[0].pk_driver_mail_id;
+
+        let result = service.resend_mail(Uuid::new_v4(), original_id).await;
+
+        assert!(
+            matches!(result, Err(MailError::MailNotFound)),
+            "expected MailNotFound, got {:?}",
+            result
+        );
+    }
+
+    // ── cancel_mail ─────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn cancel_mail_revokes_the_link_of_the_mail() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        let driver = create_driver(&service).await;
+
+        service.send_magic_link_email(driver.clone()).await.unwrap();
+        let mail_id = spy.mails.lock().unwrap()[0].pk_driver_mail_id;
+
+        let mail = service
+            .cancel_mail(driver.pk_driver_id, mail_id)
+            .await
+            .expect("cancel should succeed");
+
+        assert_eq!(mail.status, MailStatus::CANCELLED);
+        let (key, _) = service
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::MagicLink);
+        assert!(
+            service
+                .driver_cache_repository
+                .get_redis(key)
+                .await
+                .unwrap()
+                .is_none(),
+            "the magic link token should be deleted"
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_mail_rejects_mails_without_link() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        let driver = create_driver(&service).await;
+
+        service
+            .send_password_change_notification(driver.clone())
+            .await
+            .unwrap();
+        let mail_id = spy.mails.lock().unwrap()[0].pk_driver_mail_id;
+
+        let result = service.cancel_mail(driver.pk_driver_id, mail_id).await;
+
+        assert!(
+            matches!(result, Err(MailError::MailNotCancellable)),
+            "expected MailNotCancellable, got {:?}",
+            result
+        );
+    }
+
+    // ── process_mail_events ─────────────────────────────────────────────────────
+
+    fn make_bounce(event_type: &str) -> serde_json::Value {
+        serde_json::json!({ "email": "Test@Example.be", "type": event_type })
+    }
+
+    #[tokio::test]
+    async fn process_mail_events_rejects_invalid_token() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+
+        let result = service
+            .process_mail_events("wrong-token".to_string(), make_bounce("hard_bounce"))
+            .await;
+
+        assert!(
+            matches!(result, Err(MailError::InvalidMailEventsToken)),
+            "expected InvalidMailEventsToken, got {:?}",
+            result
+        );
+        assert!(spy.suppressed_emails.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_mail_events_hard_bounce_stops_mails_to_address() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+
+        let count = service
+            .process_mail_events("webhook-token".to_string(), make_bounce("hard_bounce"))
+            .await
+            .expect("events should be processed");
+        assert_eq!(count, 1);
+        assert!(
+            service
+                .get_email_unreachable_at("test@example.be".to_string())
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        let result = service.send_reset_password_email(make_driver()).await;
+        assert!(
+            matches!(result, Err(MailError::EmailUnreachable)),
+            "expected EmailUnreachable, got {:?}",
+            result
+        );
+
+        let result = service
+            .send_password_change_notification(make_driver())
+            .await;
+        assert!(
+            result.is_ok(),
+            "expected Ok(()) silent skip, got {:?}",
+            result
+        );
+        assert_eq!(spy.create_mail_call_count(), 0, "no mail should be created");
+    }
+
+    #[tokio::test]
+    async fn process_mail_events_soft_bounce_keeps_address_reachable() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+
+        service
+            .process_mail_events("webhook-token".to_string(), make_bounce("soft_bounce"))
+            .await
+            .expect("events should be processed");
+
+        let result = service.send_reset_password_email(make_driver()).await;
+        assert!(result.is_ok(), "expected Ok, got {:?}", result);
+    }
+
+    #[tokio::test]
+    async fn process_mail_events_complaint_disables_optional_mails() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        service
+            .send_reset_password_email(make_driver())
+            .await
+            .unwrap();
+
+        service
+            .process_mail_events("webhook-token".to_string(), make_bounce("complaint"))
+            .await
+            .expect("events should be processed");
+
+        let enabled_mail_type_ids = spy.enabled_mail_type_ids.lock().unwrap().clone();
+        assert!(!enabled_mail_type_ids.contains(&EnumDriverMailType::MonthlyReports.as_id()));
+        assert!(spy.suppressed_emails.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_mail_events_complaint_about_shared_document_spares_driver() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy.clone());
+        spy.mails.lock().unwrap().push(DriverMailRow {
+            pk_driver_mail_id: Uuid::new_v4(),
+            fk_driver_id: Uuid::new_v4(),
+            fk_employee_id: None,
+            fk_mail_type_id: EnumDriverMailType::DocumentShared.as_id(),
+            description: "Driver document share".to_string(),
+            content: None,
+            email_used: "test@example.be".to_string(),
+            status: MailStatus::SUCCESS,
+            created_at: Utc::now(),
+            sent_at: Some(Utc::now()),
+            fk_resent_from_mail_id: None,
+        });
+
+        service
+            .process_mail_events("webhook-token".to_string(), make_bounce("complaint"))
+            .await
+            .expect("events should be processed");
+
+        let enabled_mail_type_ids = spy.enabled_mail_type_ids.lock().unwrap().clone();
+        assert!(enabled_mail_type_ids.contains(&EnumDriverMailType::MonthlyReports.as_id()));
+        assert_eq!(
+            *spy.suppressed_emails.lock().unwrap(),
+            vec!["test@example.be".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_mail_events_rejects_unknown_format() {
+        let spy = MailDbSpy::all_enabled();
+        let service = make_service(spy);
+
+        let result = service
+            .process_mail_events(
+                "webhook-token".to_string(),
+                serde_json::json!({ "foo": "bar" }),
+            )
+            .await;
+
+        assert!(
+            matches!(result, Err(MailError::InvalidMailEvents)),
+            "expected InvalidMailEvents, got {:?}",
+            result
+        );
+    }
 }
@@ -5,6 +5,7 @@ pub mod employee;
 pub mod formating;
 pub mod health;
 pub mod mail;
+pub mod notification;
 pub mod storage;
 pub mod test;
 pub mod update;
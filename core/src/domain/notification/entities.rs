@@ -0,0 +1,321 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, Utc};
+use p256::PublicKey;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use url::{Host, Url};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::domain::common::constants::EnumDriverMailType;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct PushSubscriptionRow {
+    pub pk_push_subscription_id: Uuid,
+    pub fk_driver_id: Uuid,
+    pub endpoint: String,
+    pub p256dh: String,
+    pub auth: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct PushSubscription {
+    pub pk_push_subscription_id: Uuid,
+    pub endpoint: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PushSubscriptionRow {
+    pub fn to_push_subscription(&self) -> PushSubscription {
+        PushSubscription {
+            pk_push_subscription_id: self.pk_push_subscription_id,
+            endpoint: self.endpoint.clone(),
+            created_at: self.created_at,
+        }
+    }
+
+    pub fn keys(&self) -> PushSubscriptionKeys {
+        PushSubscriptionKeys {
+            p256dh: self.p256dh.clone(),
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+/// Body of a browser `PushSubscription.toJSON()`.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema, Clone)]
+pub struct CreatePushSubscriptionRequest {
+    #[validate(url(message = "endpoint must be a valid URL"))]
+    pub endpoint: String,
+
+    #[validate(nested)]
+    pub keys: PushSubscriptionKeys,
+}
+
+/// Whether a push subscription endpoint may be posted to: an `https` URL whose host is a domain
+/// name, never an IP address nor a local name. With `allow_insecure`, any `http(s)` URL is accepted.
+pub fn is_public_push_endpoint(endpoint: &str, allow_insecure: bool) -> bool {
+    let Ok(url) = Url::parse(endpoint) else {
+        return false;
+    };
+    if allow_insecure {
+        return matches!(url.scheme(), "http" | "https");
+    }
+    if url.scheme() != "https" {
+        return false;
+    }
+
+    match url.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain.contains('.')
+                && !domain.ends_with(".localhost")
+                && !domain.ends_with(".local")
+                && !domain.ends_with(".internal")
+        }
+        _ => false,
+    }
+}
+
+/// Whether an address is reachable on the public internet, as opposed to loopback, private,
+/// link-local (cloud metadata), shared, documentation or reserved ranges.
+pub fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_ipv4(address),
+        IpAddr::V6(address) => match address.to_ipv4_mapped() {
+            Some(address) => is_public_ipv4(address),
+            None => is_public_ipv6(address),
+        },
+    }
+}
+
+fn is_public_ipv4(address: Ipv4Addr) -> bool {
+    let [a, b, c, _] = address.octets();
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_broadcast()
+        || address.is_documentation()
+        || address.is_multicast()
+        || a == 0
+        // Shared address space (RFC 6598)
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking (RFC 2544)
+        || (a == 198 && (18..20).contains(&b))
+        // Reserved
+        || a >= 240)
+}
+
+fn is_public_ipv6(address: Ipv6Addr) -> bool {
+    let segments = address.segments();
+    !(address.is_unspecified()
+        || address.is_loopback()
+        || address.is_multicast()
+        // Unique local (fc00::/7)
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local and deprecated site-local (fe80::/10, fec0::/10)
+        || (segments[0] & 0xffc0) == 0xfe80
+        || (segments[0] & 0xffc0) == 0xfec0
+        // Documentation (2001:db8::/32)
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        // NAT64 (64:ff9b::/96), which can reach IPv4 private ranges
+        || (segments[0] == 0x0064 && segments[1] == 0xff9b))
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema, Clone)]
+pub struct PushSubscriptionKeys {
+    /// Base64url encoded P-256 public key of the browser.
+    #[validate(length(min = 1, message = "p256dh must not be empty"))]
+    pub p256dh: String,
+
+    /// Base64url encoded authentication secret of the browser.
+    #[validate(length(min = 1, message = "auth must not be empty"))]
+    pub auth: String,
+}
+
+impl PushSubscriptionKeys {
+    /// The browser public key and authentication secret, `None` when they are malformed.
+    pub fn decode(&self) -> Option<(PublicKey, [u8; 16])> {
+        let p256dh = URL_SAFE_NO_PAD
+            .decode(self.p256dh.trim_end_matches('='))
+            .ok()?;
+        let auth = URL_SAFE_NO_PAD
+            .decode(self.auth.trim_end_matches('='))
+            .ok()?;
+
+        let public_key = PublicKey::from_sec1_bytes(&p256dh).ok()?;
+        Some((public_key, auth.try_into().ok()?))
+    }
+}
+
+/// Event a driver is told about, on every channel enabled for its mail type.
+#[derive(Debug, Clone)]
+pub enum Notification {
+    MonthlyReport {
+        month: u32,
+        year: i32,
+        pdf_bytes: Bytes,
+        file_name: String,
+    },
+    /// Monthly workday documents generated during the same run, as `(month, year)`.
+    DocumentsReady { documents: Vec<(u32, i32)> },
+    /// `missing_dates` being sorted.
+    WorkdayReminder { missing_dates: Vec<NaiveDate> },
+}
+
+impl Notification {
+    pub fn mail_type(&self) -> EnumDriverMailType {
+        match self {
+            Notification::MonthlyReport { .. } => EnumDriverMailType::MonthlyReports,
+            Notification::DocumentsReady { .. } => EnumDriverMailType::DocumentsReady,
+            Notification::WorkdayReminder { .. } => EnumDriverMailType::WorkdayReminders,
+        }
+    }
+
+    /// Short localized message shown by the service worker of the web app.
+    pub fn to_push_message(&self, language: &str, app_url: &str) -> PushMessage {
+        let is_french = language == "fr";
+
+        let (title, body) = match self {
+            Notification::MonthlyReport { month, year, .. } => {
+                if is_french {
+                    (
+                        "Rapport mensuel disponible".to_string(),
+                        format!("Votre rapport de {:02}/{} est prêt.", month, year),
+                    )
+                } else {
+                    (
+                        "Monthly report available".to_string(),
+                        format!("Your report for {:02}/{} is ready.", month, year),
+                    )
+                }
+            }
+            Notification::DocumentsReady { documents } => {
+                let periods = documents
+                    .iter()
+                    .map(|(month, year)| format!("{:02}/{}", month, year))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                if is_french {
+                    (
+                        "Documents disponibles".to_string(),
+                        format!("Vos documents de {} sont prêts.", periods),
+                    )
+                } else {
+                    (
+                        "Documents available".to_string(),
+                        format!("Your documents for {} are ready.", periods),
+                    )
+                }
+            }
+            Notification::WorkdayReminder { missing_dates } => {
+                if is_french {
+                    (
+                        "Journées manquantes".to_string(),
+                        format!(
+                            "{} journée(s) de travail restent à compléter.",
+                            missing_dates.len()
+                        ),
+                    )
+                } else {
+                    (
+                        "Missing workdays".to_string(),
+                        format!(
+                            "{} workday(s) still have to be filled in.",
+                            missing_dates.len()
+                        ),
+                    )
+                }
+            }
+        };
+
+        PushMessage {
+            title,
+            body,
+            url: app_url.to_string(),
+            tag: self.mail_type().as_id().to_string(),
+        }
+    }
+}
+
+/// Payload of a web push message, read by the service worker of the web app.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PushMessage {
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    /// Replaces a previous notification of the same mail type still shown on the device.
+    pub tag: String,
+}
+
+/// Security event a driver with a verified phone number is warned about by SMS.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityAlert {
+    NewDeviceLogin,
+    PasswordChanged,
+}
+
+/// Text message sent to the phone number of a driver.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SmsMessage {
+    PhoneVerificationCode { code: String },
+    LoginCode { code: String },
+    SecurityAlert(SecurityAlert),
+}
+
+impl SmsMessage {
+    pub fn text(&self, language: &str) -> String {
+        let is_french = language == "fr";
+
+        match self {
+            SmsMessage::PhoneVerificationCode { code } => {
+                if is_french {
+                    format!(
+                        "Plannify : votre code de vérification est {}. Il expire dans 10 minutes.",
+                        code
+                    )
+                } else {
+                    format!(
+                        "Plannify: your verification code is {}. It expires in 10 minutes.",
+                        code
+                    )
+                }
+            }
+            SmsMessage::LoginCode { code } => {
+                if is_french {
+                    format!(
+                        "Plannify : votre code de connexion est {}. Ne le communiquez à personne.",
+                        code
+                    )
+                } else {
+                    format!(
+                        "Plannify: your login code is {}. Do not share it with anyone.",
+                        code
+                    )
+                }
+            }
+            SmsMessage::SecurityAlert(SecurityAlert::NewDeviceLogin) => {
+                if is_french {
+                    "Plannify : une connexion à votre compte a eu lieu depuis un nouvel appareil. Si ce n'était pas vous, changez votre mot de passe.".to_string()
+                } else {
+                    "Plannify: your account was accessed from a new device. If this was not you, change your password.".to_string()
+                }
+            }
+            SmsMessage::SecurityAlert(SecurityAlert::PasswordChanged) => {
+                if is_french {
+                    "Plannify : le mot de passe de votre compte a été modifié. Si ce n'était pas vous, contactez-nous.".to_string()
+                } else {
+                    "Plannify: the password of your account was changed. If this was not you, contact us.".to_string()
+                }
+            }
+        }
+    }
+}
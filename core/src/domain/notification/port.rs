@@ -0,0 +1,240 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        driver::entities::DriverRow,
+        mail::entities::NotificationChannel,
+        notification::entities::{
+            CreatePushSubscriptionRequest, Notification, PushSubscriptionRow, SecurityAlert,
+        },
+    },
+    infrastructure::notification::repositories::error::NotificationError,
+};
+
+/// A way of reaching a driver, enabled or not per mail type in the driver preferences.
+pub trait NotificationChannelRepository: Send + Sync {
+    fn channel(&self) -> NotificationChannel;
+
+    /// Fails with `NoPushSubscription` or `PushNotConfigured` when the driver cannot be
+    /// reached on this channel at all.
+    fn send_notification(
+        &self,
+        driver: &DriverRow,
+        notification: &Notification,
+    ) -> impl Future<Output = Result<(), NotificationError>> + Send;
+}
+
+pub trait NotificationDatabaseRepository: Send + Sync {
+    fn get_push_subscriptions(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<PushSubscriptionRow>, NotificationError>> + Send;
+
+    /// A browser has one subscription per endpoint, registering it again replaces its keys
+    /// and owner.
+    fn create_push_subscription(
+        &self,
+        driver_id: Uuid,
+        request: CreatePushSubscriptionRequest,
+    ) -> impl Future<Output = Result<PushSubscriptionRow, NotificationError>> + Send;
+
+    fn delete_push_subscription(
+        &self,
+        driver_id: Uuid,
+        push_subscription_id: Uuid,
+    ) -> impl Future<Output = Result<(), NotificationError>> + Send;
+
+    /// Forgets a subscription the push service reported as expired.
+    fn delete_push_subscription_by_endpoint(
+        &self,
+        endpoint: String,
+    ) -> impl Future<Output = Result<(), NotificationError>> + Send;
+}
+
+/// Provider delivering text messages to phone numbers.
+pub trait SmsRepository: Send + Sync {
+    /// `phone_number` is in the E.164 format checked by `validate_phone_number`.
+    fn send_sms(
+        &self,
+        phone_number: &str,
+        text: &str,
+    ) -> impl Future<Output = Result<(), NotificationError>> + Send;
+}
+
+pub trait NotificationService: Send + Sync {
+    fn get_push_subscriptions(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<PushSubscriptionRow>, NotificationError>> + Send;
+
+    fn create_push_subscription(
+        &self,
+        driver_id: Uuid,
+        request: CreatePushSubscriptionRequest,
+    ) -> impl Future<Output = Result<PushSubscriptionRow, NotificationError>> + Send;
+
+    fn delete_push_subscription(
+        &self,
+        driver_id: Uuid,
+        push_subscription_id: Uuid,
+    ) -> impl Future<Output = Result<(), NotificationError>> + Send;
+
+    /// Sends the alert by SMS when the driver has a verified phone number, does nothing
+    /// otherwise.
+    fn send_security_alert(
+        &self,
+        driver: &DriverRow,
+        alert: SecurityAlert,
+    ) -> impl Future<Output = Result<(), NotificationError>> + Send;
+}
+
+#[derive(Clone)]
+pub struct MockNotificationDatabaseRepository {
+    push_subscriptions: Arc<Mutex<Vec<PushSubscriptionRow>>>,
+}
+
+impl MockNotificationDatabaseRepository {
+    pub fn new() -> Self {
+        Self {
+            push_subscriptions: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl Default for MockNotificationDatabaseRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationDatabaseRepository for MockNotificationDatabaseRepository {
+    async fn get_push_subscriptions(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<PushSubscriptionRow>, NotificationError> {
+        let push_subscriptions = self.push_subscriptions.lock().unwrap();
+        Ok(push_subscriptions
+            .iter()
+            .filter(|s| s.fk_driver_id == driver_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_push_subscription(
+        &self,
+        driver_id: Uuid,
+        request: CreatePushSubscriptionRequest,
+    ) -> Result<PushSubscriptionRow, NotificationError> {
+        let mut push_subscriptions = self.push_subscriptions.lock().unwrap();
+        push_subscriptions.retain(|s| s.endpoint != request.endpoint);
+
+        let push_subscription = PushSubscriptionRow {
+            pk_push_subscription_id: Uuid::new_v4(),
+            fk_driver_id: driver_id,
+            endpoint: request.endpoint,
+            p256dh: request.keys.p256dh,
+            auth: request.keys.auth,
+            created_at: Utc::now(),
+        };
+        push_subscriptions.push(push_subscription.clone());
+        Ok(push_subscription)
+    }
+
+    async fn delete_push_subscription(
+        &self,
+        driver_id: Uuid,
+        push_subscription_id: Uuid,
+    ) -> Result<(), NotificationError> {
+        let mut push_subscriptions = self.push_subscriptions.lock().unwrap();
+        let count = push_subscriptions.len();
+        push_subscriptions.retain(|s| {
+            s.pk_push_subscription_id != push_subscription_id || s.fk_driver_id != driver_id
+        });
+
+        if push_subscriptions.len() == count {
+            return Err(NotificationError::PushSubscriptionNotFound);
+        }
+        Ok(())
+    }
+
+    async fn delete_push_subscription_by_endpoint(
+        &self,
+        endpoint: String,
+    ) -> Result<(), NotificationError> {
+        let mut push_subscriptions = self.push_subscriptions.lock().unwrap();
+        push_subscriptions.retain(|s| s.endpoint != endpoint);
+        Ok(())
+    }
+}
+
+/// Channel recording the notifications it is asked to send.
+#[derive(Clone)]
+pub struct MockNotificationChannelRepository {
+    channel: NotificationChannel,
+    result: Result<(), NotificationError>,
+    pub sent: Arc<Mutex<Vec<(Uuid, i32)>>>,
+}
+
+impl MockNotificationChannelRepository {
+    pub fn new(channel: NotificationChannel) -> Self {
+        Self {
+            channel,
+            result: Ok(()),
+            sent: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn failing(channel: NotificationChannel, error: NotificationError) -> Self {
+        Self {
+            result: Err(error),
+            ..Self::new(channel)
+        }
+    }
+}
+
+impl NotificationChannelRepository for MockNotificationChannelRepository {
+    fn channel(&self) -> NotificationChannel {
+        self.channel
+    }
+
+    async fn send_notification(
+        &self,
+        driver: &DriverRow,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        self.result.clone()?;
+        self.sent
+            .lock()
+            .unwrap()
+            .push((driver.pk_driver_id, notification.mail_type().as_id()));
+        Ok(())
+    }
+}
+
+/// Provider recording the messages it is asked to send, as `(phone_number, text)`.
+#[derive(Clone, Default)]
+pub struct MockSmsRepository {
+    pub sent: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl MockSmsRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SmsRepository for MockSmsRepository {
+    async fn send_sms(&self, phone_number: &str, text: &str) -> Result<(), NotificationError> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((phone_number.to_string(), text.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    Service,
+    domain::{
+        document::port::DocumentExternalRepository,
+        driver::{
+            entities::DriverRow,
+            port::{DriverCacheRepository, DriverDatabaseRepository},
+        },
+        health::port::HealthRepository,
+        mail::{
+            entities::NotificationChannel,
+            port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
+        },
+        notification::{
+            entities::{
+                CreatePushSubscriptionRequest, Notification, PushSubscriptionRow, SecurityAlert,
+                SmsMessage, is_public_push_endpoint,
+            },
+            port::{
+                NotificationChannelRepository, NotificationDatabaseRepository, NotificationService,
+                SmsRepository,
+            },
+        },
+        storage::port::StorageRepository,
+        update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
+        workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
+    },
+    infrastructure::notification::repositories::error::NotificationError,
+};
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> NotificationService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+where
+    H: HealthRepository,
+    DD: DriverDatabaseRepository,
+    DC: DriverCacheRepository,
+    WD: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    MS: MailSmtpRepository,
+    MD: MailDatabaseRepository,
+    MC: MailCacheRepository,
+    UD: UpdateDatabaseRepository,
+    UC: UpdateCacheRepository,
+    DE: DocumentExternalRepository,
+    DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
+{
+    #[tracing::instrument(
+        name = "notification_service.get_push_subscriptions",
+        skip(self),
+        fields(driver_id = %driver_id)
+    )]
+    async fn get_push_subscriptions(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<PushSubscriptionRow>, NotificationError> {
+        self.notification_database_repository
+            .get_push_subscriptions(driver_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "notification_service.create_push_subscription",
+        skip(self, request),
+        fields(driver_id = %driver_id)
+    )]
+    async fn create_push_subscription(
+        &self,
+        driver_id: Uuid,
+        request: CreatePushSubscriptionRequest,
+    ) -> Result<PushSubscriptionRow, NotificationError> {
+        if request.keys.decode().is_none()
+            || !is_public_push_endpoint(
+                &request.endpoint,
+                self.config.web_push.allow_insecure_endpoints,
+            )
+        {
+            return Err(NotificationError::InvalidPushSubscription);
+        }
+
+        self.notification_database_repository
+            .create_push_subscription(driver_id, request)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "notification_service.delete_push_subscription",
+        skip(self),
+        fields(driver_id = %driver_id, push_subscription_id = %push_subscription_id)
+    )]
+    async fn delete_push_subscription(
+        &self,
+        driver_id: Uuid,
+        push_subscription_id: Uuid,
+    ) -> Result<(), NotificationError> {
+        self.notification_database_repository
+            .delete_push_subscription(driver_id, push_subscription_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "notification_service.send_security_alert",
+        skip(self, driver),
+        fields(driver_id = %driver.pk_driver_id, alert = ?alert)
+    )]
+    async fn send_security_alert(
+        &self,
+        driver: &DriverRow,
+        alert: SecurityAlert,
+    ) -> Result<(), NotificationError> {
+        let Some(phone_number) = &driver.phone_number else {
+            return Ok(());
+        };
+        if driver.phone_verified_at.is_none() {
+            return Ok(());
+        }
+
+        self.sms_repository
+            .send_sms(
+                phone_number,
+                &SmsMessage::SecurityAlert(alert).text(&driver.language),
+            )
+            .await
+    }
+}
+
+/// Every mail sender is the email channel.
+impl<M: MailSmtpRepository> NotificationChannelRepository for M {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::EMAIL
+    }
+
+    async fn send_notification(
+        &self,
+        driver: &DriverRow,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        match notification {
+            Notification::MonthlyReport {
+                month,
+                year,
+                pdf_bytes,
+                file_name,
+            } => {
+                self.send_driver_monthly_report_email(
+                    driver.clone(),
+                    *month,
+                    *year,
+                    pdf_bytes.clone(),
+                    file_name.clone(),
+                )
+                .await?
+            }
+            Notification::DocumentsReady { documents } => {
+                self.send_driver_documents_ready_email(driver.clone(), documents.clone())
+                    .await?
+            }
+            Notification::WorkdayReminder { missing_dates } => {
+                self.send_driver_workday_reminder_email(driver.clone(), missing_dates.clone())
+                    .await?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sends the notifications of the jobs on the email and push channels, following the
+/// preferences of each driver.
+pub struct NotificationDispatcher<'a, MD, E, P> {
+    mail_database_repository: &'a MD,
+    email_channel: &'a E,
+    push_channel: &'a P,
+}
+
+impl<'a, MD, E, P> NotificationDispatcher<'a, MD, E, P>
+where
+    MD: MailDatabaseRepository,
+    E: NotificationChannelRepository,
+    P: NotificationChannelRepository,
+{
+    pub fn new(
+        mail_database_repository: &'a MD,
+        email_channel: &'a E,
+        push_channel: &'a P,
+    ) -> Self {
+        Self {
+            mail_database_repository,
+            email_channel,
+            push_channel,
+        }
+    }
+
+    /// Returns the channels the notification went through, empty when none is enabled or
+    /// reachable. Fails only when no channel delivered it and one of them failed.
+    #[tracing::instrument(
+        name = "notification_dispatcher.dispatch",
+        skip(self, driver, notification),
+        fields(driver_id = %driver.pk_driver_id, mail_type_id = notification.mail_type().as_id())
+    )]
+    pub async fn dispatch(
+        &self,
+        driver: &DriverRow,
+        notification: &Notification,
+    ) -> Result<Vec<NotificationChannel>, NotificationError> {
+        let results = [
+            Self::send_on(
+                self.mail_database_repository,
+                self.email_channel,
+                driver,
+                notification,
+            )
+            .await,
+            Self::send_on(
+                self.mail_database_repository,
+                self.push_channel,
+                driver,
+                notification,
+            )
+            .await,
+        ];
+
+        let mut delivered = Vec::new();
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(Some(channel)) => delivered.push(channel),
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(error = ?e, "Notification channel failed");
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if delivered.is_empty() => Err(e),
+            _ => Ok(delivered),
+        }
+    }
+
+    async fn send_on<C: NotificationChannelRepository>(
+        mail_database_repository: &MD,
+        channel: &C,
+        driver: &DriverRow,
+        notification: &Notification,
+    ) -> Result<Option<NotificationChannel>, NotificationError> {
+        let is_enabled = mail_database_repository
+            .is_notification_enabled(
+                driver.pk_driver_id,
+                notification.mail_type(),
+                channel.channel(),
+            )
+            .await?;
+
+        if !is_enabled {
+            return Ok(None);
+        }
+
+        if channel.channel() == NotificationChannel::EMAIL
+            && mail_database_repository
+                .get_email_suppression(driver.email.clone())
+                .await?
+                .is_some()
+        {
+            return Ok(None);
+        }
+
+        match channel.send_notification(driver, notification).await {
+            Ok(()) => Ok(Some(channel.channel())),
+            Err(NotificationError::NoPushSubscription | NotificationError::PushNotConfigured) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NotificationDispatcher;
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            common::constants::EnumDriverMailType,
+            driver::entities::DriverRow,
+            mail::{
+                entities::NotificationChannel,
+                port::{MailDatabaseRepository, MockMailDatabaseRepository},
+            },
+            notification::{entities::Notification, port::MockNotificationChannelRepository},
+        },
+        infrastructure::notification::repositories::error::NotificationError,
+    };
+
+    fn make_driver() -> DriverRow {
+        DriverRow {
+            pk_driver_id: Uuid::new_v4(),
+            firstname: "Test".to_string(),
+            lastname: "Driver".to_string(),
+            gender: None,
+            email: "test@example.be".to_string(),
+            password_hash: "hash".to_string(),
+            phone_number: None,
+            is_searchable: false,
+            allow_request_professional_agreement: false,
+            language: "fr".to_string(),
+            rest_json: None,
+            created_at: Utc::now(),
+            verified_at: None,
+            last_login_at: None,
+            deactivated_at: None,
+            phone_verified_at: None,
+            version: 1,
+        }
+    }
+
+    fn make_reminder() -> Notification {
+        Notification::WorkdayReminder {
+            missing_dates: vec![NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()],
+        }
+    }
+
+    async fn set_preference(
+        mail_db: &MockMailDatabaseRepository,
+        driver: &DriverRow,
+        channel: NotificationChannel,
+        is_enabled: bool,
+    ) {
+        mail_db
+            .upsert_driver_notification_preference(
+                driver.pk_driver_id,
+                EnumDriverMailType::WorkdayReminders.as_id(),
+                channel,
+                is_enabled,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_follows_channel_preferences() {
+        let driver = make_driver();
+        let mail_db = MockMailDatabaseRepository::new();
+        set_preference(&mail_db, &driver, NotificationChannel::EMAIL, false).await;
+
+        let email = MockNotificationChannelRepository::new(NotificationChannel::EMAIL);
+        let push = MockNotificationChannelRepository::new(NotificationChannel::PUSH);
+        let dispatcher = NotificationDispatcher::new(&mail_db, &email, &push);
+
+        let delivered = dispatcher
+            .dispatch(&driver, &make_reminder())
+            .await
+            .unwrap();
+
+        assert_eq!(delivered, vec![NotificationChannel::PUSH]);
+        assert!(email.sent.lock().unwrap().is_empty());
+        assert_eq!(
+            push.sent.lock().unwrap().as_slice(),
+            &[(
+                driver.pk_driver_id,
+                EnumDriverMailType::WorkdayReminders.as_id()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_defaults_to_mail_type_default() {
+        let driver = make_driver();
+        let mail_db = MockMailDatabaseRepository::new();
+
+        let email = MockNotificationChannelRepository::new(NotificationChannel::EMAIL);
+        let push = MockNotificationChannelRepository::new(NotificationChannel::PUSH);
+        let dispatcher = NotificationDispatcher::new(&mail_db, &email, &push);
+
+        let delivered = dispatcher
+            .dispatch(&driver, &make_reminder())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            delivered,
+            vec![NotificationChannel::EMAIL, NotificationChannel::PUSH]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_skips_unreachable_push_channel() {
+        let driver = make_driver();
+        let mail_db = MockMailDatabaseRepository::new();
+        // Both channels are enabled by the default of the mail type
+
+        let email = MockNotificationChannelRepository::new(NotificationChannel::EMAIL);
+        let push = MockNotificationChannelRepository::failing(
+            NotificationChannel::PUSH,
+            NotificationError::NoPushSubscription,
+        );
+        let dispatcher = NotificationDispatcher::new(&mail_db, &email, &push);
+
+        let delivered = dispatcher
+            .dispatch(&driver, &make_reminder())
+            .await
+            .unwrap();
+
+        assert_eq!(delivered, vec![NotificationChannel::EMAIL]);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_fails_when_no_channel_delivered() {
+        let driver = make_driver();
+        let mail_db = MockMailDatabaseRepository::new();
+        set_preference(&mail_db, &driver, NotificationChannel::EMAIL, false).await;
+
+        let email = MockNotificationChannelRepository::new(NotificationChannel::EMAIL);
+        let push = MockNotificationChannelRepository::failing(
+            NotificationChannel::PUSH,
+            NotificationError::CannotSendPushNotification,
+        );
+        let dispatcher = NotificationDispatcher::new(&mail_db, &email, &push);
+
+        let result = dispatcher.dispatch(&driver, &make_reminder()).await;
+
+        assert!(matches!(
+            result,
+            Err(NotificationError::CannotSendPushNotification)
+        ));
+    }
+}
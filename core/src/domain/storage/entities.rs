@@ -0,0 +1,219 @@
+use std::{fmt, pin::Pin};
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt, stream};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use utoipa::IntoParams;
+use validator::Validate;
+
+use crate::infrastructure::storage::repositories::error::StorageError;
+
+/// Body of a stored object, read chunk by chunk instead of being buffered.
+pub type StorageStream = Pin<Box<dyn Stream<Item = Result<Bytes, StorageError>> + Send>>;
+
+/// A single range of a `Range: bytes=...` request header, bounds included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRange {
+    /// `bytes=<start>-` or `bytes=<start>-<end>`.
+    From { start: u64, end: Option<u64> },
+    /// `bytes=-<length>`, the last `length` bytes.
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// `None` for anything but one well-formed range: the whole object is then sent, as
+    /// RFC 9110 allows for a range the server does not support.
+    pub fn parse(header: &str) -> Option<Self> {
+        let (start, end) = header.trim().strip_prefix("bytes=")?.split_once('-')?;
+        let (start, end) = (start.trim(), end.trim());
+
+        if start.is_empty() {
+            return end.parse().ok().filter(|l| *l > 0).map(ByteRange::Suffix);
+        }
+
+        let start = start.parse().ok()?;
+        let end = match end {
+            "" => None,
+            end => Some(end.parse().ok().filter(|e| *e >= start)?),
+        };
+        Some(ByteRange::From { start, end })
+    }
+
+    /// Bounds of the range within an object of `length` bytes, `None` when it lies outside.
+    pub fn bounds(&self, length: u64) -> Option<(u64, u64)> {
+        match *self {
+            ByteRange::From { start, .. } if start >= length => None,
+            ByteRange::From { start, end } => {
+                Some((start, end.map_or(length - 1, |e| e.min(length - 1))))
+            }
+            ByteRange::Suffix(_) if length == 0 => None,
+            ByteRange::Suffix(suffix) => Some((length.saturating_sub(suffix), length - 1)),
+        }
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteRange::From { start, end: None } => write!(f, "bytes={}-", start),
+            ByteRange::From {
+                start,
+                end: Some(end),
+            } => write!(f, "bytes={}-{}", start, end),
+            ByteRange::Suffix(length) => write!(f, "bytes=-{}", length),
+        }
+    }
+}
+
+/// A stored object, or the requested range of it, streamed from the storage.
+pub struct StorageObject {
+    pub body: StorageStream,
+    /// Length of `body`, the range only when one was requested.
+    pub content_length: u64,
+    /// `bytes <start>-<end>/<total>`, set when a range was requested.
+    pub content_range: Option<String>,
+    pub e_tag: Option<String>,
+}
+
+impl StorageObject {
+    /// An object already in memory, or the requested range of it.
+    pub fn from_bytes(
+        data: Bytes,
+        range: Option<ByteRange>,
+        e_tag: Option<String>,
+    ) -> Result<Self, StorageError> {
+        let total = data.len() as u64;
+        let (data, content_range) = match range {
+            Some(range) => {
+                let (start, end) = range
+                    .bounds(total)
+                    .ok_or(StorageError::RangeNotSatisfiable)?;
+                (
+                    data.slice(start as usize..=end as usize),
+                    Some(format!("bytes {}-{}/{}", start, end, total)),
+                )
+            }
+            None => (data, None),
+        };
+
+        Ok(Self {
+            content_length: data.len() as u64,
+            content_range,
+            e_tag,
+            body: Box::pin(stream::once(async move { Ok(data) })),
+        })
+    }
+
+    /// The whole body, read to the end.
+    pub async fn bytes(mut self) -> Result<Bytes, StorageError> {
+        let mut data = Vec::with_capacity(self.content_length as usize);
+        while let Some(chunk) = self.body.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        Ok(Bytes::from(data))
+    }
+
+    /// Hex SHA-256 digest of the body, read to the end chunk by chunk.
+    pub async fn sha256(mut self) -> Result<String, StorageError> {
+        let mut hasher = Sha256::new();
+        while let Some(chunk) = self.body.next().await {
+            hasher.update(chunk?);
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
+/// How a document reaches the client.
+pub enum DocumentDownload {
+    /// Streamed from the storage through the API.
+    Stream(StorageObject),
+    /// Downloaded by the client itself from a short-lived presigned URL.
+    Redirect(String),
+    /// Generated on the fly as it is not stored.
+    Generated(Bytes),
+}
+
+/// AES-256 key encrypting the stored objects of one driver. It is only stored wrapped.
+#[derive(Clone)]
+pub struct DataKey([u8; 32]);
+
+impl DataKey {
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        Self(key)
+    }
+
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataKey(..)")
+    }
+}
+
+/// A data key encrypted by a master key of the key management.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct WrappedDataKey {
+    pub master_key_id: String,
+    pub wrapped_key: Vec<u8>,
+}
+
+#[derive(Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PresignedObjectParams {
+    /// Unix timestamp the URL expires at.
+    pub expires: i64,
+
+    #[validate(length(min = 1, message = "signature is required"))]
+    pub signature: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteRange;
+
+    #[test]
+    fn test_parse_byte_ranges() {
+        assert_eq!(
+            ByteRange::parse("bytes=0-99"),
+            Some(ByteRange::From {
+                start: 0,
+                end: Some(99)
+            })
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=100-"),
+            Some(ByteRange::From {
+                start: 100,
+                end: None
+            })
+        );
+        assert_eq!(ByteRange::parse("bytes=-20"), Some(ByteRange::Suffix(20)));
+
+        assert_eq!(ByteRange::parse("bytes=10-5"), None);
+        assert_eq!(ByteRange::parse("bytes=0-1,4-5"), None);
+        assert_eq!(ByteRange::parse("items=0-1"), None);
+        assert_eq!(ByteRange::parse("bytes=-0"), None);
+    }
+
+    #[test]
+    fn test_byte_range_bounds() {
+        let range = ByteRange::parse("bytes=90-200").unwrap();
+        assert_eq!(range.bounds(100), Some((90, 99)));
+        assert_eq!(range.bounds(50), None);
+        assert_eq!(ByteRange::Suffix(20).bounds(100), Some((80, 99)));
+        assert_eq!(ByteRange::Suffix(200).bounds(100), Some((0, 99)));
+        assert_eq!(range.to_string(), "bytes=90-200");
+    }
+}
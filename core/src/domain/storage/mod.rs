@@ -1 +1,3 @@
+pub mod entities;
 pub mod port;
+pub mod services;
@@ -6,8 +6,12 @@ use std::{
 };
 
 use bytes::Bytes;
+use uuid::Uuid;
 
-use crate::infrastructure::storage::repositories::error::StorageError;
+use crate::{
+    domain::storage::entities::{ByteRange, DataKey, StorageObject, WrappedDataKey},
+    infrastructure::storage::repositories::error::StorageError,
+};
 
 pub trait StorageRepository: Send + Sync {
     fn upload(
@@ -19,6 +23,13 @@ pub trait StorageRepository: Send + Sync {
 
     fn download(&self, key: &str) -> impl Future<Output = Result<Bytes, StorageError>> + Send;
 
+    /// Streams the object, or only `range` of it, without loading it in memory.
+    fn stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> impl Future<Output = Result<StorageObject, StorageError>> + Send;
+
     fn delete(&self, key: &str) -> impl Future<Output = Result<(), StorageError>> + Send;
 
     fn generate_presigned_url(
@@ -36,6 +47,70 @@ pub trait StorageRepository: Send + Sync {
     ) -> impl Future<Output = Result<(Vec<String>, Option<String>), StorageError>> + Send;
 }
 
+/// Wraps the data keys with master keys it keeps to itself, a local key or a KMS.
+pub trait KeyManagementRepository: Send + Sync {
+    /// Id of the master key wrapping the new data keys, `None` when the objects are stored
+    /// unencrypted.
+    fn current_key_id(&self) -> Option<&str>;
+
+    fn wrap_key(
+        &self,
+        data_key: &DataKey,
+    ) -> impl Future<Output = Result<WrappedDataKey, StorageError>> + Send;
+
+    fn unwrap_key(
+        &self,
+        wrapped: &WrappedDataKey,
+    ) -> impl Future<Output = Result<DataKey, StorageError>> + Send;
+}
+
+/// The wrapped data key of each driver.
+pub trait DataKeyRepository: Send + Sync {
+    fn get_data_key(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Option<WrappedDataKey>, StorageError>> + Send;
+
+    /// Stores the first data key of the driver. Returns the one already stored when another
+    /// upload created it first.
+    fn create_data_key(
+        &self,
+        driver_id: Uuid,
+        wrapped: WrappedDataKey,
+    ) -> impl Future<Output = Result<WrappedDataKey, StorageError>> + Send;
+
+    /// Replaces the data key of the driver by the same key wrapped with another master key.
+    fn update_data_key(
+        &self,
+        driver_id: Uuid,
+        wrapped: WrappedDataKey,
+    ) -> impl Future<Output = Result<(), StorageError>> + Send;
+
+    /// Whether the driver had a data key. Its objects cannot be read anymore once it is gone.
+    fn delete_data_key(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<bool, StorageError>> + Send;
+
+    /// Data keys wrapped with another master key than `master_key_id`.
+    fn get_data_keys_to_rewrap(
+        &self,
+        master_key_id: &str,
+    ) -> impl Future<Output = Result<Vec<(Uuid, WrappedDataKey)>, StorageError>> + Send;
+}
+
+pub trait StorageService: Send + Sync {
+    /// Object behind a presigned URL of the `LocalFs` backend, once its signature and expiry
+    /// are checked.
+    fn get_presigned_object(
+        &self,
+        key: &str,
+        expires: i64,
+        signature: &str,
+        range: Option<ByteRange>,
+    ) -> impl Future<Output = Result<StorageObject, StorageError>> + Send;
+}
+
 #[derive(Clone, Default)]
 pub struct MockStorageRepository {
     store: Arc<Mutex<HashMap<String, Bytes>>>,
@@ -66,6 +141,16 @@ impl StorageRepository for MockStorageRepository {
         store.get(key).cloned().ok_or(StorageError::ObjectNotFound)
     }
 
+    async fn stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<StorageObject, StorageError> {
+        let data = self.download(key).await?;
+        let e_tag = format!("\"{:x}\"", data.len());
+        StorageObject::from_bytes(data, range, Some(e_tag))
+    }
+
     async fn delete(&self, key: &str) -> Result<(), StorageError> {
         let mut store = self.store.lock().unwrap();
         store.remove(key);
@@ -102,3 +187,54 @@ impl StorageRepository for MockStorageRepository {
         Ok((keys, None))
     }
 }
+
+#[derive(Clone, Default)]
+pub struct MockDataKeyRepository {
+    keys: Arc<Mutex<HashMap<Uuid, WrappedDataKey>>>,
+}
+
+impl MockDataKeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl DataKeyRepository for MockDataKeyRepository {
+    async fn get_data_key(&self, driver_id: Uuid) -> Result<Option<WrappedDataKey>, StorageError> {
+        Ok(self.keys.lock().unwrap().get(&driver_id).cloned())
+    }
+
+    async fn create_data_key(
+        &self,
+        driver_id: Uuid,
+        wrapped: WrappedDataKey,
+    ) -> Result<WrappedDataKey, StorageError> {
+        let mut keys = self.keys.lock().unwrap();
+        Ok(keys.entry(driver_id).or_insert(wrapped).clone())
+    }
+
+    async fn update_data_key(
+        &self,
+        driver_id: Uuid,
+        wrapped: WrappedDataKey,
+    ) -> Result<(), StorageError> {
+        self.keys.lock().unwrap().insert(driver_id, wrapped);
+        Ok(())
+    }
+
+    async fn delete_data_key(&self, driver_id: Uuid) -> Result<bool, StorageError> {
+        Ok(self.keys.lock().unwrap().remove(&driver_id).is_some())
+    }
+
+    async fn get_data_keys_to_rewrap(
+        &self,
+        master_key_id: &str,
+    ) -> Result<Vec<(Uuid, WrappedDataKey)>, StorageError> {
+        let keys = self.keys.lock().unwrap();
+        Ok(keys
+            .iter()
+            .filter(|(_, wrapped)| wrapped.master_key_id != master_key_id)
+            .map(|(driver_id, wrapped)| (*driver_id, wrapped.clone()))
+            .collect())
+    }
+}
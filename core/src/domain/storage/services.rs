@@ -0,0 +1,116 @@
+use chrono::Utc;
+
+use crate::{
+    Service,
+    domain::{
+        common::config::{DocumentDeliveryMode, StorageBackend},
+        document::port::DocumentExternalRepository,
+        driver::port::{DriverCacheRepository, DriverDatabaseRepository},
+        health::port::HealthRepository,
+        mail::port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
+        notification::port::{NotificationDatabaseRepository, SmsRepository},
+        storage::{
+            entities::{ByteRange, DocumentDownload, StorageObject},
+            port::{StorageRepository, StorageService},
+        },
+        update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
+        workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository},
+    },
+    infrastructure::storage::repositories::error::StorageError,
+};
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> StorageService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+where
+    H: HealthRepository,
+    DD: DriverDatabaseRepository,
+    DC: DriverCacheRepository,
+    WD: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    MS: MailSmtpRepository,
+    MD: MailDatabaseRepository,
+    MC: MailCacheRepository,
+    UD: UpdateDatabaseRepository,
+    UC: UpdateCacheRepository,
+    DE: DocumentExternalRepository,
+    DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
+{
+    #[tracing::instrument(
+        name = "storage_service.get_presigned_object",
+        skip(self, signature),
+        fields(key = %key, expires = %expires)
+    )]
+    async fn get_presigned_object(
+        &self,
+        key: &str,
+        expires: i64,
+        signature: &str,
+        range: Option<ByteRange>,
+    ) -> Result<StorageObject, StorageError> {
+        // S3 serves its presigned URLs itself
+        if self.config.storage.backend != StorageBackend::LocalFs {
+            return Err(StorageError::ObjectNotFound);
+        }
+
+        if !self
+            .config
+            .storage
+            .verify(key, expires, signature, Utc::now().timestamp())
+        {
+            return Err(StorageError::PresignedUrlInvalid);
+        }
+
+        self.storage_repository.stream(key, range).await
+    }
+}
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+    Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+where
+    H: HealthRepository,
+    DD: DriverDatabaseRepository,
+    DC: DriverCacheRepository,
+    WD: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    MS: MailSmtpRepository,
+    MD: MailDatabaseRepository,
+    MC: MailCacheRepository,
+    UD: UpdateDatabaseRepository,
+    UC: UpdateCacheRepository,
+    DE: DocumentExternalRepository,
+    DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
+{
+    /// Hands a stored document over in the configured delivery mode. `range` only applies to
+    /// streamed documents, the storage serves the ranges of the presigned URLs itself. Encrypted
+    /// documents S3 would hand out as stored are streamed whatever the mode.
+    #[tracing::instrument(
+        name = "storage_service.deliver_document",
+        skip(self),
+        fields(mode = ?self.config.document_delivery.mode)
+    )]
+    pub(crate) async fn deliver_document(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<DocumentDownload, StorageError> {
+        match self.config.document_delivery.mode {
+            DocumentDeliveryMode::Redirect
+                if self.config.storage.presigned_urls_serve_plaintext() =>
+            {
+                self.storage_repository
+                    .generate_presigned_url(key, self.config.document_delivery.presigned_url_ttl)
+                    .await
+                    .map(DocumentDownload::Redirect)
+            }
+            DocumentDeliveryMode::Stream | DocumentDeliveryMode::Redirect => self
+                .storage_repository
+                .stream(key, range)
+                .await
+                .map(DocumentDownload::Stream),
+        }
+    }
+}
@@ -0,0 +1,1093 @@
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            driver::{
+                entities::{
+                    ApiTokenScope, CreateDriverApiTokenRequest, CreateDriverRequest,
+                    DriverLanguage, DriverRow, DriverSmsCode, DriverSuspensionRow,
+                    PasswordPolicyRule, UpdateDriverRequest,
+                },
+                port::{
+                    DriverCacheKeyType, DriverCacheRepository, DriverDatabaseRepository,
+                    DriverService,
+                },
+            },
+            test::{MockService, create_mock_service},
+        },
+        infrastructure::driver::repositories::error::DriverError,
+    };
+
+    async fn create_driver(service: &MockService) -> DriverRow {
+        service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "John".to_string(),
+                lastname: "Doe".to_string(),
+                gender: None,
+                email: "john.doe@plannify.be".to_string(),
+                password: "securePassword123".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn create_suspension(
+        service: &MockService,
+        driver_id: Uuid,
+        can_access_restricted_space: bool,
+    ) -> DriverSuspensionRow {
+        service
+            .driver_database_repository
+            .create_driver_suspension(DriverSuspensionRow {
+                pk_driver_suspension_id: 1,
+                fk_driver_id: driver_id,
+                fk_created_employee_id: Uuid::new_v4(),
+                can_access_restricted_space,
+                driver_message: Some("Suspended".to_string()),
+                title: "Suspension".to_string(),
+                description: None,
+                start_at: chrono::Utc::now() - chrono::Duration::hours(1),
+                end_at: Some(chrono::Utc::now() + chrono::Duration::hours(1)),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_driver_suspension_without_suspension()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        service.check_driver_suspension(driver.pk_driver_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_driver_suspension_fail_suspended() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        create_suspension(&service, driver.pk_driver_id, false).await;
+
+        let result = service.check_driver_suspension(driver.pk_driver_id).await;
+
+        match result {
+            Err(DriverError::DriverSuspension {
+                message, end_at, ..
+            }) => {
+                assert_eq!(message, Some("Suspended".to_string()));
+                assert!(end_at.is_some());
+            }
+            other => panic!("Expected DriverSuspension, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_driver_suspension_can_access_restricted_space()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        create_suspension(&service, driver.pk_driver_id, true).await;
+
+        service.check_driver_suspension(driver.pk_driver_id).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_driver_suspension_uses_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        let suspension = create_suspension(&service, driver.pk_driver_id, false).await;
+
+        assert!(
+            service
+                .check_driver_suspension(driver.pk_driver_id)
+                .await
+                .is_err()
+        );
+
+        // The suspension is lifted in the database but the cached state still applies
+        service
+            .driver_database_repository
+            .delete_driver_suspension(suspension.pk_driver_suspension_id)
+            .await?;
+
+        assert!(
+            service
+                .check_driver_suspension(driver.pk_driver_id)
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_driver_for_refresh_fail_suspended() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        create_suspension(&service, driver.pk_driver_id, false).await;
+
+        let result = service.get_driver_for_refresh(driver.pk_driver_id).await;
+
+        assert!(matches!(result, Err(DriverError::DriverSuspension { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_api_token_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (token, api_token) = service
+            .create_api_token(
+                driver.pk_driver_id,
+                CreateDriverApiTokenRequest {
+                    label: "Tachograph".to_string(),
+                    scopes: vec![ApiTokenScope::WorkdaysWrite, ApiTokenScope::WorkdaysRead],
+                    expires_in_days: 30,
+                },
+            )
+            .await?;
+
+        assert!(token.starts_with("pdt_"));
+        assert_ne!(api_token.token_hash, token, "Only a hash must be stored");
+        assert_eq!(
+            api_token.to_scopes(),
+            vec![ApiTokenScope::WorkdaysRead, ApiTokenScope::WorkdaysWrite]
+        );
+        assert!(api_token.last_used_at.is_none());
+
+        let api_tokens = service.get_api_tokens(driver.pk_driver_id).await?;
+        assert_eq!(api_tokens.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_api_token_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (token, _) = service
+            .create_api_token(
+                driver.pk_driver_id,
+                CreateDriverApiTokenRequest {
+                    label: "Tachograph".to_string(),
+                    scopes: vec![ApiTokenScope::WorkdaysRead],
+                    expires_in_days: 30,
+                },
+            )
+            .await?;
+
+        let api_token = service.authenticate_api_token(token).await?;
+
+        assert_eq!(api_token.fk_driver_id, driver.pk_driver_id);
+        assert!(api_token.last_used_at.is_some());
+
+        let stored = service.get_api_tokens(driver.pk_driver_id).await?;
+        assert!(stored[0].last_used_at.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_api_token_fail_unknown() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let result = service
+            .authenticate_api_token("pdt_unknown".to_string())
+            .await;
+
+        assert!(matches!(result, Err(DriverError::InvalidApiToken)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_api_token_fail_expired() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (token, api_token) = service
+            .create_api_token(
+                driver.pk_driver_id,
+                CreateDriverApiTokenRequest {
+                    label: "Tachograph".to_string(),
+                    scopes: vec![ApiTokenScope::WorkdaysRead],
+                    expires_in_days: 1,
+                },
+            )
+            .await?;
+
+        let mut expired = api_token.clone();
+        expired.expires_at = chrono::Utc::now() - chrono::Duration::minutes(1);
+        service
+            .driver_database_repository
+            .delete_driver_api_token(driver.pk_driver_id, api_token.pk_driver_api_token_id)
+            .await?;
+        service
+            .driver_database_repository
+            .create_driver_api_token(expired)
+            .await?;
+
+        let result = service.authenticate_api_token(token).await;
+
+        assert!(matches!(result, Err(DriverError::InvalidApiToken)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_api_token_fail_other_driver() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (_, api_token) = service
+            .create_api_token(
+                driver.pk_driver_id,
+                CreateDriverApiTokenRequest {
+                    label: "Tachograph".to_string(),
+                    scopes: vec![ApiTokenScope::MailsRead],
+                    expires_in_days: 30,
+                },
+            )
+            .await?;
+
+        let result = service
+            .delete_api_token(Uuid::new_v4(), api_token.pk_driver_api_token_id)
+            .await;
+        assert!(matches!(result, Err(DriverError::ApiTokenNotFound)));
+
+        service
+            .delete_api_token(driver.pk_driver_id, api_token.pk_driver_api_token_id)
+            .await?;
+        assert!(
+            service
+                .get_api_tokens(driver.pk_driver_id)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    async fn store_magic_link(service: &MockService, driver_id: Uuid, token: &str) -> String {
+        let (redis_key, redis_ttl) = service
+            .driver_cache_repository
+            .get_key_by_type(driver_id, DriverCacheKeyType::MagicLink);
+        service
+            .driver_cache_repository
+            .set_redis(redis_key.clone(), token.to_string(), redis_ttl)
+            .await
+            .unwrap();
+        redis_key
+    }
+
+    #[tokio::test]
+    async fn test_request_magic_link_fail_already_pending() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        service
+            .request_magic_link("John.Doe@Plannify.be".to_string())
+            .await?;
+
+        store_magic_link(&service, driver.pk_driver_id, "pending-token").await;
+
+        let result = service
+            .request_magic_link("john.doe@plannify.be".to_string())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DriverError::MagicLinkTokenAlreadyExists)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_confirm_magic_link_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        let redis_key = store_magic_link(&service, driver.pk_driver_id, "valid-token").await;
+
+        let confirmed = service
+            .confirm_magic_link(driver.pk_driver_id, "valid-token".to_string())
+            .await?;
+        assert_eq!(confirmed.pk_driver_id, driver.pk_driver_id);
+
+        // Single use
+        assert!(
+            service
+                .driver_cache_repository
+                .get_redis(redis_key)
+                .await?
+                .is_none()
+        );
+        let result = service
+            .confirm_magic_link(driver.pk_driver_id, "valid-token".to_string())
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidMagicLinkToken)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_confirm_magic_link_fail_wrong_token_keeps_link()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        store_magic_link(&service, driver.pk_driver_id, "valid-token").await;
+
+        let result = service
+            .confirm_magic_link(driver.pk_driver_id, "guessed-token".to_string())
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidMagicLinkToken)));
+
+        let confirmed = service
+            .confirm_magic_link(driver.pk_driver_id, "valid-token".to_string())
+            .await?;
+        assert_eq!(confirmed.pk_driver_id, driver.pk_driver_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_confirm_magic_link_fail_suspended() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        create_suspension(&service, driver.pk_driver_id, false).await;
+        store_magic_link(&service, driver.pk_driver_id, "valid-token").await;
+
+        let result = service
+            .confirm_magic_link(driver.pk_driver_id, "valid-token".to_string())
+            .await;
+
+        assert!(matches!(result, Err(DriverError::DriverSuspension { .. })));
+
+        Ok(())
+    }
+
+    fn create_driver_request(password: &str) -> CreateDriverRequest {
+        CreateDriverRequest {
+            firstname: "John".to_string(),
+            lastname: "Doe".to_string(),
+            gender: None,
+            email: "john.doe@plannify.be".to_string(),
+            password: password.to_string(),
+            language: DriverLanguage::EN,
+        }
+    }
+
+    fn violated_rules(result: Result<DriverRow, DriverError>) -> Vec<PasswordPolicyRule> {
+        match result {
+            Err(DriverError::PasswordPolicyViolation { violations }) => {
+                violations.into_iter().map(|v| v.rule).collect()
+            }
+            other => panic!("Expected a password policy violation, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_driver_fail_weak_password() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let result = service
+            .create_driver(create_driver_request("azerty123"), vec![])
+            .await;
+
+        assert_eq!(violated_rules(result), vec![PasswordPolicyRule::TooWeak]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_driver_fail_password_with_personal_information()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let result = service
+            .create_driver(create_driver_request("Strong#DOE-2026!"), vec![])
+            .await;
+
+        assert_eq!(
+            violated_rules(result),
+            vec![PasswordPolicyRule::ContainsPersonalInformation]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_driver_fail_breached_password() -> Result<(), Box<dyn std::error::Error>> {
+        let dataset_dir = std::env::temp_dir().join(format!("breached-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dataset_dir)?;
+        // SHA-1 of "Tr0ub4dor&3"
+        std::fs::write(
+            dataset_dir.join("87457.txt"),
+            "0000000000000000000000000000000000A:3\r\n2E7A5AE6A49466A6AC578B98ADBA78C6AA6:1337\r\n",
+        )?;
+        // SHA-1 of "Tr0ub4dor&4"
+        std::fs::write(
+            dataset_dir.join("C042B.txt"),
+            "0000000000000000000000000000000000A:3\r\n",
+        )?;
+
+        let mut service = create_mock_service();
+        service.config.password_policy.breached_dataset_dir = Some(dataset_dir.clone());
+
+        let result = service
+            .create_driver(create_driver_request("Tr0ub4dor&3"), vec![])
+            .await;
+        let breached = violated_rules(result);
+
+        let result = service
+            .create_driver(create_driver_request("Tr0ub4dor&4"), vec![])
+            .await;
+
+        std::fs::remove_dir_all(&dataset_dir)?;
+
+        assert_eq!(breached, vec![PasswordPolicyRule::Breached]);
+        assert!(result.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_driver_fail_breached_range_missing()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let dataset_dir = std::env::temp_dir().join(format!("breached-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dataset_dir)?;
+
+        let mut service = create_mock_service();
+        service.config.password_policy.breached_dataset_dir = Some(dataset_dir.clone());
+
+        let result = service
+            .create_driver(create_driver_request("Tr0ub4dor&3"), vec![])
+            .await;
+
+        std::fs::remove_dir_all(&dataset_dir)?;
+
+        assert!(matches!(result, Err(DriverError::Internal)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_driver_info_password_policy_uses_driver_language()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let result = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                UpdateDriverRequest {
+                    firstname: None,
+                    lastname: None,
+                    gender: None,
+                    email: None,
+                    password: Some("password".to_string()),
+                    phone_number: None,
+                    language: None,
+                    version: None,
+                },
+                vec![],
+            )
+            .await;
+
+        match result {
+            Err(DriverError::PasswordPolicyViolation { violations }) => {
+                assert_eq!(
+                    violations[0].message,
+                    PasswordPolicyRule::TooWeak.message(&DriverLanguage::FR)
+                );
+            }
+            other => panic!("Expected a password policy violation, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    fn email_update_request(email: &str) -> UpdateDriverRequest {
+        UpdateDriverRequest {
+            firstname: None,
+            lastname: None,
+            gender: None,
+            email: Some(email.to_string()),
+            password: None,
+            phone_number: None,
+            language: None,
+            version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_driver_info_email_change_stays_pending()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (updated, email_change, _) = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                email_update_request("New.Address@Plannify.be"),
+                vec![],
+            )
+            .await?;
+
+        let email_change = email_change.expect("email change should be pending");
+        assert_eq!(updated.email, "john.doe@plannify.be");
+        assert_eq!(email_change.previous_email, "john.doe@plannify.be");
+        assert_eq!(email_change.new_email, "new.address@plannify.be");
+        assert_ne!(email_change.confirm_token, email_change.revert_token);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_driver_info_fail_stale_version() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        let rename = |firstname: &str, version: i32| UpdateDriverRequest {
+            firstname: Some(firstname.to_string()),
+            version: Some(version),
+            ..email_update_request("john.doe@plannify.be")
+        };
+
+        let (updated, _, _) = service
+            .update_driver_info(driver.pk_driver_id, rename("Jack", driver.version), vec![])
+            .await?;
+        assert_eq!(updated.version, driver.version + 1);
+
+        // A second device still editing the first version
+        let result = service
+            .update_driver_info(driver.pk_driver_id, rename("Jim", driver.version), vec![])
+            .await;
+        assert!(matches!(result, Err(DriverError::DriverVersionConflict)));
+
+        let current = service
+            .get_driver_by_id(driver.pk_driver_id)
+            .await?
+            .unwrap();
+        assert_eq!(current.firstname, "Jack");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_generate_tokens_keeps_concurrent_profile_update()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        // Read by the login before the profile is updated
+        let driver = create_driver(&service).await;
+
+        service
+            .update_driver_info(
+                driver.pk_driver_id,
+                UpdateDriverRequest {
+                    firstname: Some("Jack".to_string()),
+                    ..email_update_request("john.doe@plannify.be")
+                },
+                vec![],
+            )
+            .await?;
+
+        service
+            .generate_tokens(
+                driver.clone(),
+                |_| Ok(("access".to_string(), "refresh".to_string())),
+                60,
+                60,
+                "https://app.plannify.be",
+            )
+            .await?;
+
+        let current = service
+            .get_driver_by_id(driver.pk_driver_id)
+            .await?
+            .unwrap();
+        assert_eq!(current.firstname, "Jack");
+        assert_eq!(current.version, driver.version + 1);
+        assert!(current.last_login_at.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_confirm_email_change_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (_, email_change, _) = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                email_update_request("new.address@plannify.be"),
+                vec![],
+            )
+            .await?;
+        let email_change = email_change.unwrap();
+
+        let result = service
+            .confirm_email_change(driver.pk_driver_id, email_change.revert_token.clone())
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidEmailChangeToken)));
+
+        let confirmed = service
+            .confirm_email_change(driver.pk_driver_id, email_change.confirm_token.clone())
+            .await?;
+        assert_eq!(confirmed.email, "new.address@plannify.be");
+        assert!(confirmed.verified_at.is_some());
+
+        // Single use
+        let result = service
+            .confirm_email_change(driver.pk_driver_id, email_change.confirm_token)
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidEmailChangeToken)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_revert_pending_email_change() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let (_, email_change, _) = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                email_update_request("new.address@plannify.be"),
+                vec![],
+            )
+            .await?;
+        let email_change = email_change.unwrap();
+
+        let reverted = service
+            .revert_email_change(driver.pk_driver_id, email_change.revert_token)
+            .await?;
+        assert_eq!(reverted.email, "john.doe@plannify.be");
+
+        let result = service
+            .confirm_email_change(driver.pk_driver_id, email_change.confirm_token)
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidEmailChangeToken)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_revert_confirmed_email_change_revokes_sessions()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        let issued_at = chrono::Utc::now().timestamp() - 60;
+
+        service
+            .create_api_token(
+                driver.pk_driver_id,
+                CreateDriverApiTokenRequest {
+                    label: "Tachograph".to_string(),
+                    scopes: vec![ApiTokenScope::WorkdaysRead],
+                    expires_in_days: 30,
+                },
+            )
+            .await?;
+
+        let (_, email_change, _) = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                email_update_request("new.address@plannify.be"),
+                vec![],
+            )
+            .await?;
+        let email_change = email_change.unwrap();
+        service
+            .confirm_email_change(driver.pk_driver_id, email_change.confirm_token)
+            .await?;
+
+        service
+            .check_driver_session(driver.pk_driver_id, issued_at)
+            .await?;
+
+        let reverted = service
+            .revert_email_change(driver.pk_driver_id, email_change.revert_token.clone())
+            .await?;
+        assert_eq!(reverted.email, "john.doe@plannify.be");
+
+        let result = service
+            .check_driver_session(driver.pk_driver_id, issued_at)
+            .await;
+        assert!(matches!(result, Err(DriverError::SessionRevoked)));
+        service
+            .check_driver_session(driver.pk_driver_id, chrono::Utc::now().timestamp() + 1)
+            .await?;
+        assert!(
+            service
+                .get_api_tokens(driver.pk_driver_id)
+                .await?
+                .is_empty()
+        );
+
+        // Single use
+        let result = service
+            .revert_email_change(driver.pk_driver_id, email_change.revert_token)
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidEmailChangeToken)));
+
+        Ok(())
+    }
+
+    fn phone_update_request(phone_number: &str) -> UpdateDriverRequest {
+        UpdateDriverRequest {
+            phone_number: Some(Some(phone_number.to_string())),
+            ..email_update_request("john.doe@plannify.be")
+        }
+    }
+
+    async fn get_sms_code(
+        service: &MockService,
+        driver_id: Uuid,
+        key_type: DriverCacheKeyType,
+    ) -> Option<DriverSmsCode> {
+        let (key, _) = service
+            .driver_cache_repository
+            .get_key_by_type(driver_id, key_type);
+        service
+            .driver_cache_repository
+            .get_redis(key)
+            .await
+            .unwrap()
+            .map(|cached| serde_json::from_str(&cached).unwrap())
+    }
+
+    async fn create_driver_with_verified_phone(service: &MockService) -> DriverRow {
+        let driver = create_driver(service).await;
+        service
+            .update_driver_info(
+                driver.pk_driver_id,
+                phone_update_request("+32470123456"),
+                vec![],
+            )
+            .await
+            .unwrap();
+        service
+            .request_phone_verification(driver.pk_driver_id)
+            .await
+            .unwrap();
+        let sms_code = get_sms_code(
+            service,
+            driver.pk_driver_id,
+            DriverCacheKeyType::PhoneVerification,
+        )
+        .await
+        .unwrap();
+        let driver = service
+            .confirm_phone_verification(driver.pk_driver_id, sms_code.code)
+            .await
+            .unwrap();
+
+        // Lets the tests send their own code right away
+        let (cooldown_key, _) = service
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::SmsCooldown);
+        service
+            .driver_cache_repository
+            .delete_redis(cooldown_key)
+            .await
+            .unwrap();
+
+        driver
+    }
+
+    #[tokio::test]
+    async fn test_phone_verification() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        let result = service
+            .request_phone_verification(driver.pk_driver_id)
+            .await;
+        assert!(matches!(result, Err(DriverError::PhoneNumberMissing)));
+
+        service
+            .update_driver_info(
+                driver.pk_driver_id,
+                phone_update_request("+32470123456"),
+                vec![],
+            )
+            .await?;
+        service
+            .request_phone_verification(driver.pk_driver_id)
+            .await?;
+
+        let sms_code = get_sms_code(
+            &service,
+            driver.pk_driver_id,
+            DriverCacheKeyType::PhoneVerification,
+        )
+        .await
+        .unwrap();
+        assert_eq!(sms_code.code.len(), 6);
+        assert!(sms_code.code.chars().all(|c| c.is_ascii_digit()));
+        {
+            let sent = service.sms_repository.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].0, "+32470123456");
+            assert!(sent[0].1.contains(&sms_code.code));
+        }
+
+        let result = service
+            .request_phone_verification(driver.pk_driver_id)
+            .await;
+        assert!(matches!(result, Err(DriverError::SmsCodeAlreadyExists)));
+
+        let verified = service
+            .confirm_phone_verification(driver.pk_driver_id, sms_code.code.clone())
+            .await?;
+        assert!(verified.phone_verified_at.is_some());
+
+        // Single use
+        let result = service
+            .confirm_phone_verification(driver.pk_driver_id, sms_code.code)
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidSmsCode)));
+
+        let result = service
+            .request_phone_verification(driver.pk_driver_id)
+            .await;
+        assert!(matches!(
+            result,
+            Err(DriverError::PhoneNumberAlreadyVerified)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_phone_verification_code_discarded_after_attempts()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        service
+            .update_driver_info(
+                driver.pk_driver_id,
+                phone_update_request("+32470123456"),
+                vec![],
+            )
+            .await?;
+        service
+            .request_phone_verification(driver.pk_driver_id)
+            .await?;
+        let sms_code = get_sms_code(
+            &service,
+            driver.pk_driver_id,
+            DriverCacheKeyType::PhoneVerification,
+        )
+        .await
+        .unwrap();
+        let wrong_code = if sms_code.code == "000000" {
+            "111111"
+        } else {
+            "000000"
+        };
+
+        for _ in 0..5 {
+            let result = service
+                .confirm_phone_verification(driver.pk_driver_id, wrong_code.to_string())
+                .await;
+            assert!(matches!(result, Err(DriverError::InvalidSmsCode)));
+        }
+
+        let result = service
+            .confirm_phone_verification(driver.pk_driver_id, sms_code.code)
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidSmsCode)));
+        assert!(
+            get_sms_code(
+                &service,
+                driver.pk_driver_id,
+                DriverCacheKeyType::PhoneVerification
+            )
+            .await
+            .is_none()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_phone_verification_codes_rate_limited() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+        service
+            .update_driver_info(
+                driver.pk_driver_id,
+                phone_update_request("+32470123456"),
+                vec![],
+            )
+            .await?;
+        let (code_key, _) = service
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::PhoneVerification);
+        let (cooldown_key, _) = service
+            .driver_cache_repository
+            .get_key_by_type(driver.pk_driver_id, DriverCacheKeyType::SmsCooldown);
+
+        service
+            .request_phone_verification(driver.pk_driver_id)
+            .await?;
+
+        // A burned code does not allow a new one before the cooldown
+        service
+            .driver_cache_repository
+            .delete_redis(code_key.clone())
+            .await?;
+        let result = service
+            .request_phone_verification(driver.pk_driver_id)
+            .await;
+        assert!(matches!(result, Err(DriverError::SmsRateLimited)));
+
+        for _ in 1..5 {
+            service
+                .driver_cache_repository
+                .delete_redis(code_key.clone())
+                .await?;
+            service
+                .driver_cache_repository
+                .delete_redis(cooldown_key.clone())
+                .await?;
+            service
+                .request_phone_verification(driver.pk_driver_id)
+                .await?;
+        }
+
+        service
+            .driver_cache_repository
+            .delete_redis(code_key)
+            .await?;
+        service
+            .driver_cache_repository
+            .delete_redis(cooldown_key)
+            .await?;
+        let result = service
+            .request_phone_verification(driver.pk_driver_id)
+            .await;
+        assert!(matches!(result, Err(DriverError::SmsRateLimited)));
+        assert_eq!(service.sms_repository.sent.lock().unwrap().len(), 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_phone_number_change_resets_verification() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = create_driver_with_verified_phone(&service).await;
+
+        let (updated, _, _) = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                phone_update_request("+32470123456"),
+                vec![],
+            )
+            .await?;
+        assert!(updated.phone_verified_at.is_some());
+
+        let (updated, _, _) = service
+            .update_driver_info(
+                driver.pk_driver_id,
+                phone_update_request("+32470654321"),
+                vec![],
+            )
+            .await?;
+        assert!(updated.phone_verified_at.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sms_login() -> Result<(), Box<dyn std::error::Error>> {
+        let mut service = create_mock_service();
+        let driver = create_driver_with_verified_phone(&service).await;
+
+        let result = service.request_sms_login(driver.email.clone()).await;
+        assert!(matches!(result, Err(DriverError::SmsLoginDisabled)));
+
+        service.config.sms.login_enabled = true;
+        service.request_sms_login(driver.email.clone()).await?;
+        let sms_code = get_sms_code(&service, driver.pk_driver_id, DriverCacheKeyType::SmsLogin)
+            .await
+            .unwrap();
+
+        let logged_in = service
+            .confirm_sms_login(driver.email.clone(), sms_code.code.clone())
+            .await?;
+        assert_eq!(logged_in.pk_driver_id, driver.pk_driver_id);
+
+        let result = service
+            .confirm_sms_login(driver.email.clone(), sms_code.code)
+            .await;
+        assert!(matches!(result, Err(DriverError::InvalidSmsCode)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sms_login_requires_verified_phone() -> Result<(), Box<dyn std::error::Error>> {
+        let mut service = create_mock_service();
+        service.config.sms.login_enabled = true;
+        let driver = create_driver(&service).await;
+
+        let result = service.request_sms_login(driver.email).await;
+        assert!(matches!(result, Err(DriverError::PhoneNumberNotVerified)));
+        assert!(service.sms_repository.sent.lock().unwrap().is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_login_device() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = create_driver(&service).await;
+
+        assert!(
+            !service
+                .register_login_device(driver.pk_driver_id, "Firefox")
+                .await?
+        );
+        assert!(
+            !service
+                .register_login_device(driver.pk_driver_id, "Firefox")
+                .await?
+        );
+        assert!(
+            service
+                .register_login_device(driver.pk_driver_id, "Safari")
+                .await?
+        );
+        assert!(
+            !service
+                .register_login_device(driver.pk_driver_id, "Safari")
+                .await?
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            common::constants::EnumDriverMailType,
+            mail::{
+                entities::{DriverMailPreference, NotificationChannel},
+                port::{MailDatabaseRepository, MailService},
+            },
+            test::{MockService, create_mock_service},
+        },
+        infrastructure::mail::repositories::error::MailError,
+    };
+
+    async fn reminders_preference(service: &MockService, driver_id: Uuid) -> DriverMailPreference {
+        service
+            .get_mail_preferences(driver_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|p| p.mail_type_id == EnumDriverMailType::WorkdayReminders.as_id())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mail_preferences_default_to_every_channel()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let preference = reminders_preference(&service, Uuid::new_v4()).await;
+
+        assert!(preference.is_enabled);
+        assert!(preference.is_push_enabled);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_mail_preference_push_instead_of_email()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::new_v4();
+
+        let updated = service
+            .update_mail_preference(
+                driver_id,
+                EnumDriverMailType::WorkdayReminders.as_id(),
+                NotificationChannel::EMAIL,
+                false,
+            )
+            .await?;
+
+        assert!(!updated.is_enabled);
+        assert!(updated.is_push_enabled, "push keeps the mail type default");
+
+        let preference = reminders_preference(&service, driver_id).await;
+        assert!(!preference.is_enabled);
+        assert!(preference.is_push_enabled);
+
+        assert!(
+            !service
+                .mail_database_repository
+                .is_notification_enabled(
+                    driver_id,
+                    EnumDriverMailType::WorkdayReminders,
+                    NotificationChannel::EMAIL
+                )
+                .await?
+        );
+        assert!(
+            service
+                .mail_database_repository
+                .is_notification_enabled(
+                    driver_id,
+                    EnumDriverMailType::WorkdayReminders,
+                    NotificationChannel::PUSH
+                )
+                .await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_disables_every_channel() -> Result<(), Box<dyn std::error::Error>> {
+        let mut service = create_mock_service();
+        service.config.mail_unsubscribe.secret = "unsubscribe-secret".to_string();
+        let driver_id = Uuid::new_v4();
+        let mail_type_id = EnumDriverMailType::WorkdayReminders.as_id();
+        let signature = service
+            .config
+            .mail_unsubscribe
+            .signature(driver_id, mail_type_id);
+
+        let unsubscribed = service
+            .unsubscribe(driver_id, mail_type_id, signature)
+            .await?;
+
+        assert!(!unsubscribed.is_enabled);
+        assert!(!unsubscribed.is_push_enabled);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_fails_without_secret() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::new_v4();
+        let mail_type_id = EnumDriverMailType::WorkdayReminders.as_id();
+        // Computable by anyone, the key being empty
+        let signature = service
+            .config
+            .mail_unsubscribe
+            .signature(driver_id, mail_type_id);
+
+        let result = service
+            .unsubscribe(driver_id, mail_type_id, signature)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(MailError::InvalidUnsubscribeSignature)
+        ));
+
+        Ok(())
+    }
+}
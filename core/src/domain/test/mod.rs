@@ -5,12 +5,15 @@ use crate::{
         driver::port::{MockDriverCacheRepository, MockDriverDatabaseRepository},
         health::port::MockHealthRepository,
         mail::port::{MockMailCacheRepository, MockMailDatabaseRepository, MockMailSmtpRepository},
+        notification::port::{MockNotificationDatabaseRepository, MockSmsRepository},
         storage::port::MockStorageRepository,
         update::port::{MockUpdateCacheRepository, MockUpdateDatabaseRepository},
         workday::port::{MockWorkdayCacheRepository, MockWorkdayDatabaseRepository},
     },
 };
 
+pub mod driver;
+pub mod mail;
 pub mod workday;
 
 pub type MockService = Service<
@@ -26,6 +29,8 @@ pub type MockService = Service<
     MockUpdateCacheRepository,
     MockDocumentExternalRepository,
     MockStorageRepository,
+    MockNotificationDatabaseRepository,
+    MockSmsRepository,
 >;
 
 pub fn create_mock_service() -> MockService {
@@ -41,6 +46,8 @@ pub fn create_mock_service() -> MockService {
     let update_cache_repository = MockUpdateCacheRepository::new();
     let document_external_repository = MockDocumentExternalRepository;
     let storage_repository = MockStorageRepository::new();
+    let notification_database_repository = MockNotificationDatabaseRepository::new();
+    let sms_repository = MockSmsRepository::new();
 
     MockService::new(
         health_repository,
@@ -55,6 +62,8 @@ pub fn create_mock_service() -> MockService {
         update_cache_repository,
         document_external_repository,
         storage_repository,
+        notification_database_repository,
+        sms_repository,
         ServiceConfig::default(),
     )
 }
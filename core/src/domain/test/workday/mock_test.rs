@@ -2,16 +2,27 @@
 mod tests {
     use bytes::Bytes;
     use chrono::Datelike;
+    use futures_util::TryStreamExt;
     use uuid::Uuid;
 
     use crate::{
         domain::{
-            storage::port::StorageRepository,
-            test::create_mock_service,
+            common::{config::DocumentDeliveryMode, constants::EnumDriverMailType},
+            driver::{
+                entities::{CreateDriverRequest, DriverLanguage},
+                port::DriverDatabaseRepository,
+            },
+            mail::{entities::MailStatus, port::MailDatabaseRepository},
+            storage::{
+                entities::{ByteRange, DocumentDownload},
+                port::StorageRepository,
+            },
+            test::{MockService, create_mock_service},
             workday::{
                 entities::{
-                    CreateWorkdayRequest, UpdateWorkdayRequest, WorkdayDocument,
-                    WorkdayDocumentInformation,
+                    CreateDocumentShareRequest, CreateWorkdayRequest, DocumentIntegrity,
+                    UpdateWorkdayRequest, WorkdayDocument, WorkdayDocumentInformation,
+                    document_sha256,
                 },
                 port::{WorkdayCacheRepository, WorkdayDatabaseRepository, WorkdayService},
             },
@@ -356,6 +367,7 @@ mod tests {
                     ),
                     rest_time: chrono::NaiveTime::parse_from_str("00:45:00", "%H:%M:%S").unwrap(),
                     overnight_rest: true,
+                    version: None,
                 },
             )
             .await
@@ -398,6 +410,53 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_update_workday_fail_stale_version() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174001").unwrap();
+        let date = chrono::NaiveDate::parse_from_str("2026-01-01", "%Y-%m-%d").unwrap();
+        let update = |start_time: &str, version: i32| UpdateWorkdayRequest {
+            date,
+            start_time: chrono::NaiveTime::parse_from_str(start_time, "%H:%M:%S").unwrap(),
+            end_time: None,
+            rest_time: chrono::NaiveTime::parse_from_str("00:45:00", "%H:%M:%S").unwrap(),
+            overnight_rest: false,
+            version: Some(version),
+        };
+
+        let created = service
+            .workday_database_repository
+            .create_workday(
+                driver_id,
+                CreateWorkdayRequest {
+                    date,
+                    start_time: chrono::NaiveTime::parse_from_str("08:00:00", "%H:%M:%S").unwrap(),
+                    end_time: None,
+                    rest_time: chrono::NaiveTime::parse_from_str("01:00:00", "%H:%M:%S").unwrap(),
+                    overnight_rest: false,
+                },
+            )
+            .await?;
+
+        let updated = service
+            .update_workday(driver_id, update("09:00:00", created.version))
+            .await?;
+        assert_eq!(updated.version, created.version + 1);
+
+        // A second device still editing the first version gets the current workday back
+        match service
+            .update_workday(driver_id, update("10:00:00", created.version))
+            .await
+        {
+            Err(WorkdayError::WorkdayVersionConflict { current }) => {
+                assert_eq!(*current, updated.to_workday());
+            }
+            other => panic!("Expected a version conflict, got {:?}", other),
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_update_workday_fail_not_found() -> Result<(), Box<dyn std::error::Error>> {
         let service = create_mock_service();
@@ -431,6 +490,7 @@ mod tests {
                     ),
                     rest_time: chrono::NaiveTime::parse_from_str("00:45:00", "%H:%M:%S").unwrap(),
                     overnight_rest: true,
+                    version: None,
                 },
             )
             .await
@@ -774,6 +834,7 @@ mod tests {
                     end_time: None,
                     rest_time: chrono::NaiveTime::parse_from_str("00:30:00", "%H:%M:%S").unwrap(),
                     overnight_rest: false,
+                    version: None,
                 },
             )
             .await
@@ -958,6 +1019,9 @@ mod tests {
                     file_name: "workdays-2026-02.pdf".to_string(),
                     s3_file_path: s3_key.to_string(),
                     created_at: chrono::Utc::now(),
+                    sha256: None,
+                    verification_code: None,
+                    is_fallback: false,
                 }),
             )
             .await?;
@@ -1040,4 +1104,585 @@ mod tests {
 
         Ok(())
     }
+
+    // --- download_workday_document_by_month ---
+
+    const STORED_DOCUMENT_KEY: &str =
+        "drivers/123e4567-e89b-12d3-a456-426614174000/2026/03/workdays-2026-03.pdf";
+
+    async fn seed_stored_document(service: &MockService, driver_id: Uuid) -> Bytes {
+        service
+            .workday_cache_repository
+            .set_workday_document_record(
+                driver_id,
+                3,
+                2026,
+                Some(WorkdayDocument {
+                    fk_driver_id: driver_id,
+                    month: 3,
+                    year: 2026,
+                    file_name: "workdays-2026-03.pdf".to_string(),
+                    s3_file_path: STORED_DOCUMENT_KEY.to_string(),
+                    created_at: chrono::Utc::now(),
+                    sha256: None,
+                    verification_code: None,
+                    is_fallback: false,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let pdf_bytes = Bytes::from("%PDF-1.4 fake content");
+        service
+            .storage_repository
+            .upload(STORED_DOCUMENT_KEY, pdf_bytes.clone(), "application/pdf")
+            .await
+            .unwrap();
+        pdf_bytes
+    }
+
+    #[tokio::test]
+    async fn test_download_workday_document_by_month_streams_range()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        let pdf_bytes = seed_stored_document(&service, driver_id).await;
+
+        let download = service
+            .download_workday_document_by_month(driver_id, 3, 2026, ByteRange::parse("bytes=0-7"))
+            .await?;
+
+        let Some(DocumentDownload::Stream(object)) = download else {
+            panic!("stored document should be streamed");
+        };
+        assert_eq!(object.content_length, 8);
+        assert_eq!(
+            object.content_range.as_deref(),
+            Some(format!("bytes 0-7/{}", pdf_bytes.len()).as_str())
+        );
+        assert!(object.e_tag.is_some());
+
+        let chunks: Vec<Bytes> = object.body.try_collect().await?;
+        assert_eq!(chunks.concat(), pdf_bytes[..8]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_workday_document_by_month_range_not_satisfiable()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        seed_stored_document(&service, driver_id).await;
+
+        let result = service
+            .download_workday_document_by_month(driver_id, 3, 2026, ByteRange::parse("bytes=1000-"))
+            .await;
+
+        assert!(matches!(result, Err(WorkdayError::RangeNotSatisfiable)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_workday_document_by_month_redirects_to_presigned_url()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let mut service = create_mock_service();
+        service.config.document_delivery.mode = DocumentDeliveryMode::Redirect;
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        seed_stored_document(&service, driver_id).await;
+
+        let download = service
+            .download_workday_document_by_month(driver_id, 3, 2026, None)
+            .await?;
+
+        let Some(DocumentDownload::Redirect(url)) = download else {
+            panic!("stored document should be redirected to");
+        };
+        assert!(url.ends_with(STORED_DOCUMENT_KEY));
+
+        Ok(())
+    }
+
+    // --- download_yearly_workday_document ---
+
+    #[tokio::test]
+    async fn test_download_yearly_workday_document_streams_stored_recap()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        let key = "drivers/123e4567-e89b-12d3-a456-426614174000/workdays/yearly-reports/workdays-2025.pdf";
+        let pdf_bytes = Bytes::from("%PDF-1.4 yearly recap");
+        service
+            .storage_repository
+            .upload(key, pdf_bytes.clone(), "application/pdf")
+            .await?;
+        service
+            .workday_database_repository
+            .create_yearly_document(
+                driver_id,
+                2025,
+                key.to_string(),
+                "workdays-2025.pdf".to_string(),
+                document_sha256(&pdf_bytes),
+            )
+            .await?;
+
+        let download = service
+            .download_yearly_workday_document(driver_id, 2025, None)
+            .await?;
+
+        let Some(DocumentDownload::Stream(object)) = download else {
+            panic!("stored recap should be streamed");
+        };
+        let chunks: Vec<Bytes> = object.body.try_collect().await?;
+        assert_eq!(chunks.concat(), pdf_bytes[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_yearly_workday_document_without_workdays()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+
+        let download = service
+            .download_yearly_workday_document(driver_id, 2025, None)
+            .await?;
+
+        assert!(download.is_none());
+
+        Ok(())
+    }
+
+    // --- verify_workday_document ---
+
+    #[tokio::test]
+    async fn test_verify_workday_document_by_printed_code() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "John".to_string(),
+                lastname: "Doe".to_string(),
+                gender: None,
+                email: "john.doe@plannify.be".to_string(),
+                password: "securePassword123".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await?;
+        service
+            .workday_database_repository
+            .create_workday_document(
+                driver.pk_driver_id,
+                4,
+                2026,
+                "drivers/john/workdays-2026-04.pdf".to_string(),
+                "workdays-2026-04.pdf".to_string(),
+                DocumentIntegrity {
+                    sha256: document_sha256(b"%PDF-1.4"),
+                    verification_code: "7KQ2-M9XD-4HTA".to_string(),
+                },
+                false,
+            )
+            .await?;
+
+        // Typed by hand from the paper copy
+        let verification = service.verify_workday_document("7kq2 m9xd 4hta").await?;
+
+        assert_eq!(verification.driver_name, "John Doe");
+        assert_eq!((verification.month, verification.year), (4, 2026));
+        assert_eq!(verification.sha256, document_sha256(b"%PDF-1.4"));
+
+        assert!(matches!(
+            service.verify_workday_document("7KQ2-M9XD-4HTB").await,
+            Err(WorkdayError::WorkdayDocumentNotFound)
+        ));
+        assert!(matches!(
+            service.verify_workday_document("not-a-code").await,
+            Err(WorkdayError::WorkdayDocumentNotFound)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_regenerated_workday_document_by_superseded_code()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "John".to_string(),
+                lastname: "Doe".to_string(),
+                gender: None,
+                email: "john.doe@plannify.be".to_string(),
+                password: "securePassword123".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await?;
+        service
+            .workday_database_repository
+            .create_workday_document(
+                driver.pk_driver_id,
+                4,
+                2026,
+                "drivers/john/workdays-2026-04.pdf".to_string(),
+                "workdays-2026-04.pdf".to_string(),
+                DocumentIntegrity {
+                    sha256: document_sha256(b"%PDF-1.4 provisional"),
+                    verification_code: "7KQ2-M9XD-4HTA".to_string(),
+                },
+                true,
+            )
+            .await?;
+        service
+            .workday_database_repository
+            .update_regenerated_workday_document(
+                driver.pk_driver_id,
+                4,
+                2026,
+                DocumentIntegrity {
+                    sha256: document_sha256(b"%PDF-1.4"),
+                    verification_code: "8KQ2-M9XD-4HTA".to_string(),
+                },
+            )
+            .await?;
+
+        let superseded = service.verify_workday_document("7KQ2-M9XD-4HTA").await?;
+        assert_eq!(superseded.sha256, document_sha256(b"%PDF-1.4 provisional"));
+        assert!(superseded.superseded_at.is_some());
+
+        let current = service.verify_workday_document("8KQ2-M9XD-4HTA").await?;
+        assert_eq!(current.sha256, document_sha256(b"%PDF-1.4"));
+        assert_eq!(current.superseded_at, None);
+
+        Ok(())
+    }
+
+    // --- document shares ---
+
+    fn share_request(password: Option<&str>, send_email: bool) -> CreateDocumentShareRequest {
+        CreateDocumentShareRequest {
+            expires_in_days: 7,
+            recipient_email: Some("accounting@employer.be".to_string()),
+            password: password.map(str::to_string),
+            send_email,
+        }
+    }
+
+    fn share_token(url: &str) -> &str {
+        url.rsplit_once("/shared/").map(|(_, token)| token).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_shared_document_requires_its_password_and_logs_accesses()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver = service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "John".to_string(),
+                lastname: "Doe".to_string(),
+                gender: None,
+                email: "john.doe@plannify.be".to_string(),
+                password: "securePassword123".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await?;
+        let pdf_bytes = seed_stored_document(&service, driver.pk_driver_id).await;
+
+        let (url, share) = service
+            .create_document_share(
+                driver.pk_driver_id,
+                3,
+                2026,
+                share_request(Some("employer-2026"), true),
+            )
+            .await?;
+        assert!(url.starts_with("http://localhost:8080/shared/"));
+        assert_ne!(share.token_hash, share_token(&url));
+        assert!(share.to_document_share().is_password_protected);
+
+        let (mails, _) = service
+            .mail_database_repository
+            .get_mails(driver.pk_driver_id, 1, 10)
+            .await?;
+        assert_eq!(mails.len(), 1);
+        assert_eq!(
+            mails[0].fk_mail_type_id,
+            EnumDriverMailType::DocumentShared.as_id()
+        );
+        assert_eq!(mails[0].email_used, "accounting@employer.be");
+        assert_eq!(mails[0].status, MailStatus::SUCCESS);
+
+        let user_agent = Some("Mozilla/5.0".to_string());
+        assert!(matches!(
+            service
+                .get_shared_document(share_token(&url), None, user_agent.clone(), None)
+                .await,
+            Err(WorkdayError::DocumentSharePasswordRequired)
+        ));
+        assert!(matches!(
+            service
+                .get_shared_document(
+                    share_token(&url),
+                    Some("wrong-password".to_string()),
+                    user_agent.clone(),
+                    None
+                )
+                .await,
+            Err(WorkdayError::DocumentSharePasswordInvalid)
+        ));
+
+        let (download, file_name) = service
+            .get_shared_document(
+                share_token(&url),
+                Some("employer-2026".to_string()),
+                user_agent,
+                None,
+            )
+            .await?;
+        let DocumentDownload::Stream(object) = download else {
+            panic!("shared document should be streamed");
+        };
+        let chunks: Vec<Bytes> = object.body.try_collect().await?;
+        assert_eq!(chunks.concat(), pdf_bytes[..]);
+        assert_eq!(file_name, "workdays-2026-03.pdf");
+
+        let accesses = service
+            .get_document_share_accesses(driver.pk_driver_id, share.pk_document_share_id)
+            .await?;
+        assert_eq!(accesses.len(), 3);
+        assert_eq!(accesses.iter().filter(|a| a.is_granted).count(), 1);
+
+        assert!(matches!(
+            service
+                .get_shared_document("unknown-token", None, None, None)
+                .await,
+            Err(WorkdayError::DocumentShareNotFound)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_revoked_document_share_cannot_be_opened() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        seed_stored_document(&service, driver_id).await;
+
+        let (url, share) = service
+            .create_document_share(driver_id, 3, 2026, share_request(None, false))
+            .await?;
+        service
+            .get_shared_document(share_token(&url), None, None, None)
+            .await?;
+
+        let revoked = service
+            .revoke_document_share(driver_id, share.pk_document_share_id)
+            .await?;
+        assert!(revoked.revoked_at.is_some());
+        // Revoking again keeps the first revocation date
+        let revoked_again = service
+            .revoke_document_share(driver_id, share.pk_document_share_id)
+            .await?;
+        assert_eq!(revoked_again.revoked_at, revoked.revoked_at);
+
+        assert!(matches!(
+            service
+                .get_shared_document(share_token(&url), None, None, None)
+                .await,
+            Err(WorkdayError::DocumentShareRevoked)
+        ));
+        assert!(matches!(
+            service
+                .revoke_document_share(Uuid::new_v4(), share.pk_document_share_id)
+                .await,
+            Err(WorkdayError::DocumentShareNotFound)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_document_share_requires_stored_document_and_recipient()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+
+        assert!(matches!(
+            service
+                .create_document_share(driver_id, 3, 2026, share_request(None, false))
+                .await,
+            Err(WorkdayError::WorkdayDocumentNotFound)
+        ));
+
+        seed_stored_document(&service, driver_id).await;
+        let mut request = share_request(None, true);
+        request.recipient_email = None;
+        assert!(matches!(
+            service
+                .create_document_share(driver_id, 3, 2026, request)
+                .await,
+            Err(WorkdayError::DocumentShareRecipientRequired)
+        ));
+        assert!(service.get_document_shares(driver_id).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_document_share_locked_after_wrong_passwords()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        seed_stored_document(&service, driver_id).await;
+
+        let (url, _) = service
+            .create_document_share(driver_id, 3, 2026, share_request(Some("pass"), false))
+            .await?;
+        for _ in 0..10 {
+            assert!(matches!(
+                service
+                    .get_shared_document(share_token(&url), Some("guess".to_string()), None, None)
+                    .await,
+                Err(WorkdayError::DocumentSharePasswordInvalid)
+            ));
+        }
+
+        // Even the right password is refused until the failed accesses get older
+        assert!(matches!(
+            service
+                .get_shared_document(share_token(&url), Some("pass".to_string()), None, None)
+                .await,
+            Err(WorkdayError::DocumentShareLocked)
+        ));
+        // Browsers still get the password prompt
+        assert!(matches!(
+            service
+                .get_shared_document(share_token(&url), None, None, None)
+                .await,
+            Err(WorkdayError::DocumentSharePasswordRequired)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_document_share_not_locked_by_password_challenges()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let driver_id = Uuid::parse_str("123e4567-e89b-12d3-a456-426614174000").unwrap();
+        seed_stored_document(&service, driver_id).await;
+
+        let (url, _) = service
+            .create_document_share(driver_id, 3, 2026, share_request(Some("pass"), false))
+            .await?;
+        // The password prompt of browsers and the range requests of PDF viewers
+        for _ in 0..20 {
+            assert!(matches!(
+                service
+                    .get_shared_document(share_token(&url), None, None, None)
+                    .await,
+                Err(WorkdayError::DocumentSharePasswordRequired)
+            ));
+        }
+
+        service
+            .get_shared_document(share_token(&url), Some("pass".to_string()), None, None)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_document_share_mails_are_rate_limited() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "John".to_string(),
+                lastname: "Doe".to_string(),
+                gender: None,
+                email: "john.doe@plannify.be".to_string(),
+                password: "securePassword123".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await?;
+        seed_stored_document(&service, driver.pk_driver_id).await;
+
+        for _ in 0..5 {
+            service
+                .create_document_share(driver.pk_driver_id, 3, 2026, share_request(None, true))
+                .await?;
+        }
+        assert!(matches!(
+            service
+                .create_document_share(driver.pk_driver_id, 3, 2026, share_request(None, true))
+                .await,
+            Err(WorkdayError::DocumentShareRateLimited)
+        ));
+
+        // Shares without mail have their own, higher limit
+        for _ in 0..15 {
+            service
+                .create_document_share(driver.pk_driver_id, 3, 2026, share_request(None, false))
+                .await?;
+        }
+        assert!(matches!(
+            service
+                .create_document_share(driver.pk_driver_id, 3, 2026, share_request(None, false))
+                .await,
+            Err(WorkdayError::DocumentShareRateLimited)
+        ));
+        assert_eq!(
+            service
+                .get_document_shares(driver.pk_driver_id)
+                .await?
+                .len(),
+            20
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_document_share_revoked_when_mail_fails() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let driver = service
+            .driver_database_repository
+            .create_driver(CreateDriverRequest {
+                firstname: "John".to_string(),
+                lastname: "Doe".to_string(),
+                gender: None,
+                email: "john.doe@plannify.be".to_string(),
+                password: "securePassword123".to_string(),
+                language: DriverLanguage::FR,
+            })
+            .await?;
+        seed_stored_document(&service, driver.pk_driver_id).await;
+        service
+            .mail_database_repository
+            .suppress_email("accounting@employer.be".to_string(), Uuid::new_v4())
+            .await?;
+
+        assert!(matches!(
+            service
+                .create_document_share(driver.pk_driver_id, 3, 2026, share_request(None, true))
+                .await,
+            Err(WorkdayError::Internal)
+        ));
+
+        let shares = service.get_document_shares(driver.pk_driver_id).await?;
+        assert_eq!(shares.len(), 1);
+        assert!(shares[0].revoked_at.is_some());
+
+        Ok(())
+    }
 }
@@ -5,6 +5,7 @@ use crate::{
         driver::port::{DriverCacheRepository, DriverDatabaseRepository},
         health::port::HealthRepository,
         mail::port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
+        notification::port::{NotificationDatabaseRepository, SmsRepository},
         storage::port::StorageRepository,
         update::{
             entities::UpdateCache,
@@ -15,8 +16,8 @@ use crate::{
     infrastructure::update::repositories::error::UpdateError,
 };
 
-impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS> UpdateService
-    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> UpdateService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -30,6 +31,8 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     #[tracing::instrument(
         name = "update_service.get_updates_by_version",
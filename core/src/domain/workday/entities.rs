@@ -1,5 +1,8 @@
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use bytes::Bytes;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc, Weekday};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::prelude::FromRow;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
@@ -7,13 +10,15 @@ use validator::Validate;
 
 use crate::domain::common::entities::{validate_date, validate_time};
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
 pub struct Workday {
     pub date: NaiveDate,
     pub start_time: NaiveTime,
     pub end_time: Option<NaiveTime>,
     pub rest_time: NaiveTime,
     pub overnight_rest: bool,
+    /// Incremented by every update, to send back in `UpdateWorkdayRequest::version`.
+    pub version: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -24,6 +29,24 @@ pub struct WorkdayRow {
     pub rest_time: NaiveTime,
     pub overnight_rest: bool,
     pub fk_driver_id: Uuid,
+    pub version: i32,
+}
+
+impl Workday {
+    /// Time worked, rest excluded, `None` while the workday has no end. A workday ending
+    /// before it started ended the next day.
+    pub fn worked_minutes(&self) -> Option<u32> {
+        let start = self.start_time.num_seconds_from_midnight() / 60;
+        let end = self.end_time?.num_seconds_from_midnight() / 60;
+        let rest = self.rest_time.num_seconds_from_midnight() / 60;
+
+        let span = if end >= start {
+            end - start
+        } else {
+            end + 24 * 60 - start
+        };
+        Some(span.saturating_sub(rest))
+    }
 }
 
 impl WorkdayRow {
@@ -34,6 +57,7 @@ impl WorkdayRow {
             end_time: self.end_time,
             rest_time: self.rest_time,
             overnight_rest: self.overnight_rest,
+            version: self.version,
         }
     }
 }
@@ -114,6 +138,10 @@ pub struct UpdateWorkdayRequest {
     pub rest_time: NaiveTime,
 
     pub overnight_rest: bool,
+
+    /// Version of the workday the update was made from. The update is rejected when the workday
+    /// has changed since, instead of overwriting it.
+    pub version: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -158,7 +186,7 @@ pub struct WorkdayDocumentRow {
     pub fk_document_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
 pub struct WorkdayDocument {
     pub fk_driver_id: Uuid,
     pub month: i32,
@@ -166,6 +194,14 @@ pub struct WorkdayDocument {
     pub s3_file_path: String,
     pub file_name: String,
     pub created_at: DateTime<Utc>,
+    /// `None` for the documents generated before the checksums were recorded.
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub verification_code: Option<String>,
+    /// Rendered by the built-in renderer, to be regenerated once the PDF service is back.
+    #[serde(default)]
+    pub is_fallback: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
@@ -174,4 +210,373 @@ pub struct DocumentRow {
     pub s3_file_path: String,
     pub file_name: String,
     pub created_at: DateTime<Utc>,
+    pub sha256: Option<String>,
+    pub verification_code: Option<String>,
+    pub is_fallback: bool,
+}
+
+/// What makes a stored document tamper-evident: the hex SHA-256 digest of its content and
+/// the code printed on it, which looks the digest up through `/documents/verify/{code}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentIntegrity {
+    pub sha256: String,
+    pub verification_code: String,
+}
+
+/// A monthly document generated to be stored, with the code printed on it.
+#[derive(Debug, Clone)]
+pub struct GeneratedWorkdayDocument {
+    pub pdf: Bytes,
+    pub integrity: DocumentIntegrity,
+    /// Rendered by the built-in renderer while the PDF service was unavailable.
+    pub is_fallback: bool,
+}
+
+/// Path and recorded digest of a stored document, `None` for the older documents.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct DocumentChecksum {
+    pub s3_file_path: String,
+    pub sha256: Option<String>,
+}
+
+/// Verification code and digest of a fallback document, kept once it was regenerated.
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct SupersededWorkdayDocument {
+    pub fk_driver_id: Uuid,
+    pub month: i32,
+    pub year: i32,
+    pub sha256: String,
+    pub verification_code: String,
+    pub superseded_at: DateTime<Utc>,
+}
+
+/// What the verification endpoint tells about a document, nothing more.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct WorkdayDocumentVerification {
+    pub driver_name: String,
+    pub month: u32,
+    pub year: u32,
+    /// Hex SHA-256 digest of the PDF file as generated.
+    pub sha256: String,
+    /// When a regenerated document replaced this one, `None` for the current document.
+    #[serde(default)]
+    pub superseded_at: Option<DateTime<Utc>>,
+}
+
+/// Alphabet of the verification codes, without the characters read alike (0/O, 1/I/L, U).
+const VERIFICATION_CODE_ALPHABET: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const VERIFICATION_CODE_LENGTH: usize = 12;
+
+/// A random verification code, `XXXX-XXXX-XXXX` with 60 bits of entropy.
+pub fn new_verification_code() -> String {
+    let mut bytes = [0u8; VERIFICATION_CODE_LENGTH];
+    OsRng.fill_bytes(&mut bytes);
+    let chars: String = bytes
+        .iter()
+        .map(|b| VERIFICATION_CODE_ALPHABET[(*b % 32) as usize] as char)
+        .collect();
+    format_verification_code(&chars)
+}
+
+/// Canonical form of a code typed by hand: case and separators are ignored, and the
+/// characters read alike are mapped to the ones of the alphabet. `None` when it cannot be
+/// a verification code.
+pub fn normalize_verification_code(code: &str) -> Option<String> {
+    let chars: String = code
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect();
+
+    if chars.len() != VERIFICATION_CODE_LENGTH
+        || !chars
+            .bytes()
+            .all(|b| VERIFICATION_CODE_ALPHABET.contains(&b))
+    {
+        return None;
+    }
+    Some(format_verification_code(&chars))
+}
+
+fn format_verification_code(chars: &str) -> String {
+    format!("{}-{}-{}", &chars[0..4], &chars[4..8], &chars[8..12])
+}
+
+/// Hex SHA-256 digest of a document content.
+pub fn document_sha256(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// The stored yearly recap of a driver, in the `documents` table along with the monthly ones.
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct YearlyDocument {
+    pub fk_driver_id: Uuid,
+    pub year: i32,
+    pub s3_file_path: String,
+    pub file_name: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+/// Name of the yearly recap PDF, such as `workdays-2026.pdf`.
+pub fn yearly_report_file_name(year: i32) -> String {
+    format!("workdays-{}.pdf", year)
+}
+
+/// Totals of one month, as printed on the yearly recap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkdayMonthSummary {
+    pub month: u32,
+    pub worked_days: u32,
+    /// Over the finished workdays only.
+    pub worked_minutes: u32,
+    pub overnight_rests: u32,
+    /// Weekdays from Monday to Friday before `today` without any workday.
+    pub absences: u32,
+}
+
+/// Totals of the months of `year` from the first to the last one with a workday, none when
+/// the year has no workday.
+pub fn summarize_workday_months(
+    year: i32,
+    workdays: &[Workday],
+    today: NaiveDate,
+) -> Vec<WorkdayMonthSummary> {
+    let worked_months = workdays
+        .iter()
+        .filter(|w| w.date.year() == year)
+        .map(|w| w.date.month());
+    let (Some(first), Some(last)) = (worked_months.clone().min(), worked_months.max()) else {
+        return Vec::new();
+    };
+
+    (first..=last)
+        .map(|month| {
+            let month_workdays: Vec<&Workday> = workdays
+                .iter()
+                .filter(|w| w.date.year() == year && w.date.month() == month)
+                .collect();
+
+            let absences = NaiveDate::from_ymd_opt(year, month, 1)
+                .map(|first_day| {
+                    first_day
+                        .iter_days()
+                        .take_while(|d| d.month() == month && *d < today)
+                        .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+                        .filter(|d| !month_workdays.iter().any(|w| w.date == *d))
+                        .count() as u32
+                })
+                .unwrap_or_default();
+
+            WorkdayMonthSummary {
+                month,
+                worked_days: month_workdays.len() as u32,
+                worked_minutes: month_workdays
+                    .iter()
+                    .filter_map(|w| w.worked_minutes())
+                    .sum(),
+                overnight_rests: month_workdays.iter().filter(|w| w.overnight_rest).count() as u32,
+                absences,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct DocumentShareRow {
+    pub pk_document_share_id: Uuid,
+    pub fk_driver_id: Uuid,
+    pub month: i32,
+    pub year: i32,
+    pub token_hash: String,
+    pub recipient_email: Option<String>,
+    pub password_hash: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DocumentShareRow {
+    pub fn to_document_share(&self) -> DocumentShare {
+        DocumentShare {
+            share_id: self.pk_document_share_id,
+            month: self.month as u32,
+            year: self.year as u32,
+            recipient_email: self.recipient_email.clone(),
+            is_password_protected: self.password_hash.is_some(),
+            expires_at: self.expires_at,
+            revoked_at: self.revoked_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// A link giving access to a stored monthly document without an account.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct DocumentShare {
+    pub share_id: Uuid,
+    pub month: u32,
+    pub year: u32,
+    pub recipient_email: Option<String>,
+    pub is_password_protected: bool,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateDocumentShareRequest {
+    #[validate(range(
+        min = 1,
+        max = 30,
+        message = "expires_in_days must be between 1 and 30"
+    ))]
+    pub expires_in_days: i64,
+
+    #[validate(email(message = "Invalid email format"))]
+    pub recipient_email: Option<String>,
+
+    /// Asked by the browser when the link is opened, the username being ignored.
+    #[validate(length(
+        min = 8,
+        max = 128,
+        message = "password must be between 8 and 128 characters"
+    ))]
+    pub password: Option<String>,
+
+    /// Sends the link to `recipient_email`. The password is never sent.
+    #[serde(default)]
+    pub send_email: bool,
+}
+
+/// The link holds the token, it is only returned once, at creation time.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateDocumentShareResponse {
+    pub url: String,
+    pub share: DocumentShare,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+pub struct DocumentShareAccessRow {
+    pub pk_document_share_access_id: Uuid,
+    pub fk_document_share_id: Uuid,
+    pub user_agent: Option<String>,
+    pub is_granted: bool,
+    /// Refused for a wrong password, the only refusals counted to lock the share.
+    pub is_wrong_password: bool,
+    pub accessed_at: DateTime<Utc>,
+}
+
+impl DocumentShareAccessRow {
+    pub fn to_document_share_access(&self) -> DocumentShareAccess {
+        DocumentShareAccess {
+            user_agent: self.user_agent.clone(),
+            is_granted: self.is_granted,
+            accessed_at: self.accessed_at,
+        }
+    }
+}
+
+/// One opening of a share link, refused ones (wrong password, expired or revoked link)
+/// included.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct DocumentShareAccess {
+    pub user_agent: Option<String>,
+    pub is_granted: bool,
+    pub accessed_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::{
+        Workday, WorkdayMonthSummary, new_verification_code, normalize_verification_code,
+        summarize_workday_months,
+    };
+
+    #[test]
+    fn test_verification_codes_survive_normalization() {
+        let code = new_verification_code();
+        assert_eq!(code.len(), 14);
+        assert_eq!(normalize_verification_code(&code), Some(code.clone()));
+        assert_eq!(
+            normalize_verification_code(&code.replace('-', "").to_lowercase()),
+            Some(code)
+        );
+    }
+
+    #[test]
+    fn test_normalize_verification_code() {
+        assert_eq!(
+            normalize_verification_code("o1l2-abcd-efgh"),
+            Some("0112-ABCD-EFGH".to_string())
+        );
+        assert_eq!(normalize_verification_code("ABCD-EFGH-JKM"), None);
+        assert_eq!(normalize_verification_code("ABCD-EFGH-JKMU"), None);
+    }
+
+    fn workday(date: &str, start: &str, end: Option<&str>, overnight_rest: bool) -> Workday {
+        let time = |t: &str| NaiveTime::parse_from_str(t, "%H:%M").unwrap();
+        Workday {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            start_time: time(start),
+            end_time: end.map(time),
+            rest_time: time("00:45"),
+            overnight_rest,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_summarize_workday_months() {
+        let workdays = vec![
+            // Thursday and Friday
+            workday("2026-01-01", "08:00", Some("17:00"), false),
+            workday("2026-01-02", "22:00", Some("06:00"), true),
+            // Still running, counted as worked without any time
+            workday("2026-03-02", "08:00", None, false),
+            workday("2025-12-31", "08:00", Some("17:00"), false),
+        ];
+
+        let months = summarize_workday_months(
+            2026,
+            &workdays,
+            NaiveDate::from_ymd_opt(2026, 3, 4).unwrap(),
+        );
+
+        assert_eq!(
+            months,
+            vec![
+                WorkdayMonthSummary {
+                    month: 1,
+                    worked_days: 2,
+                    worked_minutes: 8 * 60 + 15 + 7 * 60 + 15,
+                    overnight_rests: 1,
+                    absences: 20,
+                },
+                WorkdayMonthSummary {
+                    month: 2,
+                    worked_days: 0,
+                    worked_minutes: 0,
+                    overnight_rests: 0,
+                    absences: 20,
+                },
+                // Only the days before today: Monday the 2nd worked, Tuesday the 3rd missed
+                WorkdayMonthSummary {
+                    month: 3,
+                    worked_days: 1,
+                    worked_minutes: 0,
+                    overnight_rests: 0,
+                    absences: 1,
+                },
+            ]
+        );
+        assert!(summarize_workday_months(2024, &workdays, NaiveDate::MAX).is_empty());
+    }
 }
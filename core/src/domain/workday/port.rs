@@ -1,4 +1,5 @@
 use std::{
+    cmp::Reverse,
     collections::HashMap,
     future::Future,
     sync::{Arc, Mutex},
@@ -10,9 +11,16 @@ use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use uuid::Uuid;
 
 use crate::{
-    domain::workday::entities::{
-        CreateWorkdayRequest, UpdateWorkdayRequest, Workday, WorkdayDocument,
-        WorkdayDocumentInformation, WorkdayGarbageRow, WorkdayRow,
+    domain::{
+        document::entities::RenderedDocument,
+        storage::entities::{ByteRange, DocumentDownload},
+        workday::entities::{
+            CreateDocumentShareRequest, CreateWorkdayRequest, DocumentChecksum, DocumentIntegrity,
+            DocumentShareAccessRow, DocumentShareRow, GeneratedWorkdayDocument,
+            SupersededWorkdayDocument, UpdateWorkdayRequest, Workday, WorkdayDocument,
+            WorkdayDocumentInformation, WorkdayDocumentVerification, WorkdayGarbageRow, WorkdayRow,
+            YearlyDocument,
+        },
     },
     infrastructure::workday::repositories::error::WorkdayError,
 };
@@ -101,6 +109,15 @@ pub trait WorkdayDatabaseRepository: Send + Sync {
         limit: u32,
     ) -> impl Future<Output = Result<(Vec<WorkdayRow>, u32), WorkdayError>> + Send;
 
+    /// Returns the sorted dates between `start_date` and `end_date` holding a workday,
+    /// including the workdays currently in the garbage.
+    fn get_recorded_workday_dates(
+        &self,
+        driver_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> impl Future<Output = Result<Vec<NaiveDate>, WorkdayError>> + Send;
+
     fn get_workday_years(
         &self,
         driver_id: Uuid,
@@ -118,6 +135,8 @@ pub trait WorkdayDatabaseRepository: Send + Sync {
         create_workday_request: CreateWorkdayRequest,
     ) -> impl Future<Output = Result<WorkdayRow, WorkdayError>> + Send;
 
+    /// Fails with `WorkdayVersionConflict` when `update_workday_request.version` is set and the
+    /// stored workday is no longer at that version.
     fn update_workday(
         &self,
         driver_id: Uuid,
@@ -176,6 +195,7 @@ pub trait WorkdayDatabaseRepository: Send + Sync {
         before: NaiveDate,
     ) -> impl Future<Output = Result<Vec<(Uuid, i32, i32)>, WorkdayError>> + Send;
 
+    #[allow(clippy::too_many_arguments)]
     fn create_workday_document(
         &self,
         driver_id: Uuid,
@@ -183,24 +203,126 @@ pub trait WorkdayDatabaseRepository: Send + Sync {
         year: i32,
         s3_file_path: String,
         file_name: String,
+        integrity: DocumentIntegrity,
+        is_fallback: bool,
+    ) -> impl Future<Output = Result<WorkdayDocument, WorkdayError>> + Send;
+
+    /// Drivers and months whose stored document was rendered by the built-in renderer.
+    fn get_fallback_document_months(
+        &self,
+    ) -> impl Future<Output = Result<Vec<(Uuid, i32, i32)>, WorkdayError>> + Send;
+
+    /// Records the digest and code of a fallback document rendered again by the PDF service
+    /// and stored in its place. The previous ones stay verifiable as superseded.
+    fn update_regenerated_workday_document(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        integrity: DocumentIntegrity,
     ) -> impl Future<Output = Result<WorkdayDocument, WorkdayError>> + Send;
 
+    fn get_workday_document_by_verification_code(
+        &self,
+        verification_code: &str,
+    ) -> impl Future<Output = Result<Option<WorkdayDocument>, WorkdayError>> + Send;
+
+    fn get_superseded_workday_document_by_verification_code(
+        &self,
+        verification_code: &str,
+    ) -> impl Future<Output = Result<Option<SupersededWorkdayDocument>, WorkdayError>> + Send;
+
+    fn get_yearly_document_record(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+    ) -> impl Future<Output = Result<Option<YearlyDocument>, WorkdayError>> + Send;
+
+    /// Drivers and years before `before_year` with workdays but no yearly recap yet.
+    fn get_pending_document_years(
+        &self,
+        before_year: i32,
+    ) -> impl Future<Output = Result<Vec<(Uuid, i32)>, WorkdayError>> + Send;
+
+    fn create_yearly_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+        s3_file_path: String,
+        file_name: String,
+        sha256: String,
+    ) -> impl Future<Output = Result<YearlyDocument, WorkdayError>> + Send;
+
     fn get_all_document_s3_paths(
         &self,
     ) -> impl Future<Output = Result<Vec<String>, WorkdayError>> + Send;
 
-    /// Returns up to `limit` s3_file_path values sorted ascending, starting strictly after `after`.
-    /// Fewer than `limit` results means the stream is exhausted.
-    fn get_document_s3_paths_batch(
+    /// Returns up to `limit` documents sorted by s3_file_path ascending, starting strictly after
+    /// `after`. Fewer than `limit` results means the stream is exhausted.
+    fn get_document_checksums_batch(
         &self,
         after: Option<&str>,
         limit: i64,
-    ) -> impl Future<Output = Result<Vec<String>, WorkdayError>> + Send;
+    ) -> impl Future<Output = Result<Vec<DocumentChecksum>, WorkdayError>> + Send;
 
     fn delete_document_by_s3_path(
         &self,
         s3_file_path: &str,
     ) -> impl Future<Output = Result<(), WorkdayError>> + Send;
+
+    fn create_document_share(
+        &self,
+        share: DocumentShareRow,
+    ) -> impl Future<Output = Result<DocumentShareRow, WorkdayError>> + Send;
+
+    /// Shares of the driver, the most recent first.
+    fn get_document_shares(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<DocumentShareRow>, WorkdayError>> + Send;
+
+    fn get_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> impl Future<Output = Result<Option<DocumentShareRow>, WorkdayError>> + Send;
+
+    fn get_document_share_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<DocumentShareRow>, WorkdayError>> + Send;
+
+    /// Revokes the share unless it already was, `None` when the driver has no such share.
+    fn revoke_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> impl Future<Output = Result<Option<DocumentShareRow>, WorkdayError>> + Send;
+
+    fn create_document_share_access(
+        &self,
+        access: DocumentShareAccessRow,
+    ) -> impl Future<Output = Result<(), WorkdayError>> + Send;
+
+    /// Access log of a share, the most recent first.
+    fn get_document_share_accesses(
+        &self,
+        share_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<DocumentShareAccessRow>, WorkdayError>> + Send;
+
+    /// Shares the driver created since `since`, revoked ones included.
+    fn count_document_shares_since(
+        &self,
+        driver_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<i64, WorkdayError>> + Send;
+
+    /// Accesses to a share refused for a wrong password since `since`.
+    fn count_wrong_password_document_share_accesses_since(
+        &self,
+        share_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<i64, WorkdayError>> + Send;
 }
 
 pub trait WorkdayCacheRepository: Send + Sync {
@@ -421,6 +543,84 @@ pub trait WorkdayService: Send + Sync {
         month: i32,
         year: i32,
     ) -> impl Future<Output = Result<Option<Bytes>, WorkdayError>> + Send;
+
+    /// Like `get_workday_document_by_month`, in the configured delivery mode of the stored
+    /// documents instead of buffered.
+    fn download_workday_document_by_month(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        range: Option<ByteRange>,
+    ) -> impl Future<Output = Result<Option<DocumentDownload>, WorkdayError>> + Send;
+
+    /// Generates the document of the month to be stored, with a new verification code printed
+    /// on it. `None` when the month has no workday.
+    fn generate_verifiable_workday_document(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+    ) -> impl Future<Output = Result<Option<GeneratedWorkdayDocument>, WorkdayError>> + Send;
+
+    /// Driver name, month and digest of the stored document a verification code was printed on.
+    fn verify_workday_document(
+        &self,
+        verification_code: &str,
+    ) -> impl Future<Output = Result<WorkdayDocumentVerification, WorkdayError>> + Send;
+
+    /// Yearly recap generated through the PDF service, without storing it. `None` when the
+    /// year has no workday.
+    fn generate_yearly_workday_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+    ) -> impl Future<Output = Result<Option<RenderedDocument>, WorkdayError>> + Send;
+
+    /// The stored yearly recap, or one generated on the fly until the year is stored.
+    fn download_yearly_workday_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+        range: Option<ByteRange>,
+    ) -> impl Future<Output = Result<Option<DocumentDownload>, WorkdayError>> + Send;
+
+    /// Shares the stored document of the month, returning the link to it along with the share.
+    /// The link is mailed to the recipient when asked to.
+    fn create_document_share(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        create_request: CreateDocumentShareRequest,
+    ) -> impl Future<Output = Result<(String, DocumentShareRow), WorkdayError>> + Send;
+
+    fn get_document_shares(
+        &self,
+        driver_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<DocumentShareRow>, WorkdayError>> + Send;
+
+    fn revoke_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> impl Future<Output = Result<DocumentShareRow, WorkdayError>> + Send;
+
+    fn get_document_share_accesses(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<DocumentShareAccessRow>, WorkdayError>> + Send;
+
+    /// Document behind a share link, along with its file name. Every attempt is logged, the
+    /// refused ones included.
+    fn get_shared_document(
+        &self,
+        token: &str,
+        password: Option<String>,
+        user_agent: Option<String>,
+        range: Option<ByteRange>,
+    ) -> impl Future<Output = Result<(DocumentDownload, String), WorkdayError>> + Send;
 }
 
 #[derive(Clone)]
@@ -428,6 +628,10 @@ pub struct MockWorkdayDatabaseRepository {
     workdays: Arc<Mutex<Vec<WorkdayRow>>>,
     workdays_garbage: Arc<Mutex<Vec<WorkdayGarbageRow>>>,
     workday_documents: Arc<Mutex<Vec<WorkdayDocument>>>,
+    superseded_workday_documents: Arc<Mutex<Vec<SupersededWorkdayDocument>>>,
+    yearly_documents: Arc<Mutex<Vec<YearlyDocument>>>,
+    document_shares: Arc<Mutex<Vec<DocumentShareRow>>>,
+    document_share_accesses: Arc<Mutex<Vec<DocumentShareAccessRow>>>,
 }
 
 impl MockWorkdayDatabaseRepository {
@@ -436,6 +640,10 @@ impl MockWorkdayDatabaseRepository {
             workdays: Arc::new(Mutex::new(Vec::new())),
             workdays_garbage: Arc::new(Mutex::new(Vec::new())),
             workday_documents: Arc::new(Mutex::new(Vec::new())),
+            superseded_workday_documents: Arc::new(Mutex::new(Vec::new())),
+            yearly_documents: Arc::new(Mutex::new(Vec::new())),
+            document_shares: Arc::new(Mutex::new(Vec::new())),
+            document_share_accesses: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -506,6 +714,32 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
         Ok((paginated, total_count))
     }
 
+    async fn get_recorded_workday_dates(
+        &self,
+        driver_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, WorkdayError> {
+        let workdays = self.workdays.lock().unwrap();
+        let workdays_garbage = self.workdays_garbage.lock().unwrap();
+        let mut dates: Vec<NaiveDate> = workdays
+            .iter()
+            .filter(|w| w.fk_driver_id == driver_id)
+            .map(|w| w.date)
+            .chain(
+                workdays_garbage
+                    .iter()
+                    .filter(|g| g.fk_driver_id == driver_id)
+                    .map(|g| g.workday_date),
+            )
+            .filter(|date| *date >= start_date && *date <= end_date)
+            .collect();
+        dates.sort_unstable();
+        dates.dedup();
+
+        Ok(dates)
+    }
+
     async fn get_workday_months_by_year(
         &self,
         driver_id: Uuid,
@@ -544,6 +778,7 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
             rest_time: create_workday_request.rest_time,
             overnight_rest: create_workday_request.overnight_rest,
             fk_driver_id: driver_id,
+            version: 1,
         };
 
         workdays.push(new_workday.clone());
@@ -560,10 +795,19 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
             .iter_mut()
             .find(|w| w.fk_driver_id == driver_id && w.date == update_workday_request.date)
         {
+            if update_workday_request
+                .version
+                .is_some_and(|version| version != workday.version)
+            {
+                return Err(WorkdayError::WorkdayVersionConflict {
+                    current: Box::new(workday.to_workday()),
+                });
+            }
             workday.start_time = update_workday_request.start_time;
             workday.end_time = update_workday_request.end_time;
             workday.rest_time = update_workday_request.rest_time;
             workday.overnight_rest = update_workday_request.overnight_rest;
+            workday.version += 1;
             Ok(workday.clone())
         } else {
             Err(WorkdayError::WorkdayNotFound)
@@ -715,6 +959,8 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
         year: i32,
         s3_file_path: String,
         file_name: String,
+        integrity: DocumentIntegrity,
+        is_fallback: bool,
     ) -> Result<WorkdayDocument, WorkdayError> {
         let mut documents = self.workday_documents.lock().unwrap();
         let doc = WorkdayDocument {
@@ -724,11 +970,131 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
             s3_file_path,
             file_name,
             created_at: Utc::now(),
+            sha256: Some(integrity.sha256),
+            verification_code: Some(integrity.verification_code),
+            is_fallback,
         };
         documents.push(doc.clone());
         Ok(doc)
     }
 
+    async fn get_fallback_document_months(&self) -> Result<Vec<(Uuid, i32, i32)>, WorkdayError> {
+        let documents = self.workday_documents.lock().unwrap();
+        Ok(documents
+            .iter()
+            .filter(|d| d.is_fallback)
+            .map(|d| (d.fk_driver_id, d.month, d.year))
+            .collect())
+    }
+
+    async fn update_regenerated_workday_document(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        integrity: DocumentIntegrity,
+    ) -> Result<WorkdayDocument, WorkdayError> {
+        let mut documents = self.workday_documents.lock().unwrap();
+        let doc = documents
+            .iter_mut()
+            .find(|d| d.fk_driver_id == driver_id && d.month == month && d.year == year)
+            .ok_or(WorkdayError::WorkdayDocumentNotFound)?;
+        if let (Some(sha256), Some(verification_code)) = (&doc.sha256, &doc.verification_code) {
+            self.superseded_workday_documents
+                .lock()
+                .unwrap()
+                .push(SupersededWorkdayDocument {
+                    fk_driver_id: driver_id,
+                    month,
+                    year,
+                    sha256: sha256.clone(),
+                    verification_code: verification_code.clone(),
+                    superseded_at: Utc::now(),
+                });
+        }
+        doc.sha256 = Some(integrity.sha256);
+        doc.verification_code = Some(integrity.verification_code);
+        doc.is_fallback = false;
+        Ok(doc.clone())
+    }
+
+    async fn get_workday_document_by_verification_code(
+        &self,
+        verification_code: &str,
+    ) -> Result<Option<WorkdayDocument>, WorkdayError> {
+        let documents = self.workday_documents.lock().unwrap();
+        Ok(documents
+            .iter()
+            .find(|d| d.verification_code.as_deref() == Some(verification_code))
+            .cloned())
+    }
+
+    async fn get_superseded_workday_document_by_verification_code(
+        &self,
+        verification_code: &str,
+    ) -> Result<Option<SupersededWorkdayDocument>, WorkdayError> {
+        let documents = self.superseded_workday_documents.lock().unwrap();
+        Ok(documents
+            .iter()
+            .find(|d| d.verification_code == verification_code)
+            .cloned())
+    }
+
+    async fn get_yearly_document_record(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+    ) -> Result<Option<YearlyDocument>, WorkdayError> {
+        let documents = self.yearly_documents.lock().unwrap();
+        Ok(documents
+            .iter()
+            .find(|d| d.fk_driver_id == driver_id && d.year == year)
+            .cloned())
+    }
+
+    async fn get_pending_document_years(
+        &self,
+        before_year: i32,
+    ) -> Result<Vec<(Uuid, i32)>, WorkdayError> {
+        let workdays = self.workdays.lock().unwrap();
+        let documents = self.yearly_documents.lock().unwrap();
+
+        let mut result: Vec<(Uuid, i32)> = workdays
+            .iter()
+            .filter(|w| w.date.year() < before_year)
+            .map(|w| (w.fk_driver_id, w.date.year()))
+            .filter(|(driver_id, year)| {
+                !documents
+                    .iter()
+                    .any(|d| &d.fk_driver_id == driver_id && &d.year == year)
+            })
+            .collect();
+
+        result.sort_unstable_by_key(|(driver_id, year)| (*year, *driver_id));
+        result.dedup();
+        Ok(result)
+    }
+
+    async fn create_yearly_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+        s3_file_path: String,
+        file_name: String,
+        sha256: String,
+    ) -> Result<YearlyDocument, WorkdayError> {
+        let document = YearlyDocument {
+            fk_driver_id: driver_id,
+            year,
+            s3_file_path,
+            file_name,
+            created_at: Utc::now(),
+            sha256: Some(sha256),
+        };
+        self.yearly_documents.lock().unwrap().push(document.clone());
+        Ok(document)
+    }
+
     async fn get_pending_document_months(
         &self,
         before: NaiveDate,
@@ -757,20 +1123,23 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
         Ok(documents.iter().map(|d| d.s3_file_path.clone()).collect())
     }
 
-    async fn get_document_s3_paths_batch(
+    async fn get_document_checksums_batch(
         &self,
         after: Option<&str>,
         limit: i64,
-    ) -> Result<Vec<String>, WorkdayError> {
+    ) -> Result<Vec<DocumentChecksum>, WorkdayError> {
         let documents = self.workday_documents.lock().unwrap();
-        let mut paths: Vec<String> = documents
+        let mut checksums: Vec<DocumentChecksum> = documents
             .iter()
-            .map(|d| d.s3_file_path.clone())
-            .filter(|p| after.map(|a| p.as_str() > a).unwrap_or(true))
+            .map(|d| DocumentChecksum {
+                s3_file_path: d.s3_file_path.clone(),
+                sha256: d.sha256.clone(),
+            })
+            .filter(|c| after.map(|a| c.s3_file_path.as_str() > a).unwrap_or(true))
             .collect();
-        paths.sort();
-        paths.truncate(limit as usize);
-        Ok(paths)
+        checksums.sort_by(|a, b| a.s3_file_path.cmp(&b.s3_file_path));
+        checksums.truncate(limit as usize);
+        Ok(checksums)
     }
 
     async fn delete_document_by_s3_path(&self, s3_file_path: &str) -> Result<(), WorkdayError> {
@@ -778,6 +1147,111 @@ impl WorkdayDatabaseRepository for MockWorkdayDatabaseRepository {
         documents.retain(|d| d.s3_file_path != s3_file_path);
         Ok(())
     }
+
+    async fn create_document_share(
+        &self,
+        share: DocumentShareRow,
+    ) -> Result<DocumentShareRow, WorkdayError> {
+        self.document_shares.lock().unwrap().push(share.clone());
+        Ok(share)
+    }
+
+    async fn get_document_shares(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+        let shares = self.document_shares.lock().unwrap();
+        let mut shares: Vec<DocumentShareRow> = shares
+            .iter()
+            .filter(|s| s.fk_driver_id == driver_id)
+            .cloned()
+            .collect();
+        shares.sort_by_key(|s| Reverse(s.created_at));
+        Ok(shares)
+    }
+
+    async fn get_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+        let shares = self.document_shares.lock().unwrap();
+        Ok(shares
+            .iter()
+            .find(|s| s.fk_driver_id == driver_id && s.pk_document_share_id == share_id)
+            .cloned())
+    }
+
+    async fn get_document_share_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+        let shares = self.document_shares.lock().unwrap();
+        Ok(shares.iter().find(|s| s.token_hash == token_hash).cloned())
+    }
+
+    async fn revoke_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+        let mut shares = self.document_shares.lock().unwrap();
+        Ok(shares
+            .iter_mut()
+            .find(|s| s.fk_driver_id == driver_id && s.pk_document_share_id == share_id)
+            .map(|share| {
+                share.revoked_at.get_or_insert_with(Utc::now);
+                share.clone()
+            }))
+    }
+
+    async fn create_document_share_access(
+        &self,
+        access: DocumentShareAccessRow,
+    ) -> Result<(), WorkdayError> {
+        self.document_share_accesses.lock().unwrap().push(access);
+        Ok(())
+    }
+
+    async fn get_document_share_accesses(
+        &self,
+        share_id: Uuid,
+    ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+        let accesses = self.document_share_accesses.lock().unwrap();
+        let mut accesses: Vec<DocumentShareAccessRow> = accesses
+            .iter()
+            .filter(|a| a.fk_document_share_id == share_id)
+            .cloned()
+            .collect();
+        accesses.sort_by_key(|a| Reverse(a.accessed_at));
+        Ok(accesses)
+    }
+
+    async fn count_document_shares_since(
+        &self,
+        driver_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<i64, WorkdayError> {
+        let shares = self.document_shares.lock().unwrap();
+        Ok(shares
+            .iter()
+            .filter(|s| s.fk_driver_id == driver_id && s.created_at >= since)
+            .count() as i64)
+    }
+
+    async fn count_wrong_password_document_share_accesses_since(
+        &self,
+        share_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<i64, WorkdayError> {
+        let accesses = self.document_share_accesses.lock().unwrap();
+        Ok(accesses
+            .iter()
+            .filter(|a| {
+                a.fk_document_share_id == share_id && a.is_wrong_password && a.accessed_at >= since
+            })
+            .count() as i64)
+    }
 }
 
 type MockWorkdayCacheType = HashMap<String, Vec<Workday>>;
@@ -1,28 +1,61 @@
-use chrono::{Datelike, NaiveDate};
+use argon2::{
+    Algorithm, Argon2, Params, Version,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sha2::{Digest, Sha256};
+use tracing::error;
 use uuid::Uuid;
 
 use crate::{
     Service,
     domain::{
-        document::port::DocumentExternalRepository,
+        common::constants::EnumDriverMailType,
+        document::{
+            entities::{DocumentVerificationCode, RenderedDocument},
+            port::DocumentExternalRepository,
+        },
         driver::port::{DriverCacheRepository, DriverDatabaseRepository},
         health::port::HealthRepository,
-        mail::port::{MailCacheRepository, MailDatabaseRepository, MailSmtpRepository},
-        storage::port::StorageRepository,
+        mail::port::{
+            MailCacheRepository, MailDatabaseRepository, MailService, MailSmtpRepository,
+        },
+        notification::port::{NotificationDatabaseRepository, SmsRepository},
+        storage::{
+            entities::{ByteRange, DocumentDownload},
+            port::StorageRepository,
+        },
         update::port::{UpdateCacheRepository, UpdateDatabaseRepository},
         workday::{
             entities::{
-                CreateWorkdayRequest, UpdateWorkdayRequest, Workday, WorkdayDocumentInformation,
-                WorkdayGarbageRow, WorkdayRow,
+                CreateDocumentShareRequest, CreateWorkdayRequest, DocumentIntegrity,
+                DocumentShareAccessRow, DocumentShareRow, GeneratedWorkdayDocument,
+                UpdateWorkdayRequest, Workday, WorkdayDocument, WorkdayDocumentInformation,
+                WorkdayDocumentVerification, WorkdayGarbageRow, WorkdayRow, document_sha256,
+                new_verification_code, normalize_verification_code, summarize_workday_months,
             },
             port::{WorkdayCacheRepository, WorkdayDatabaseRepository, WorkdayService},
         },
     },
-    infrastructure::workday::repositories::error::WorkdayError,
+    infrastructure::{
+        document::repositories::error::DocumentError, storage::repositories::error::StorageError,
+        workday::repositories::error::WorkdayError,
+    },
 };
 
-impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS> WorkdayService
-    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS>
+/// Shares a driver can create per day.
+const DOCUMENT_SHARES_PER_DAY: i64 = 20;
+
+/// Shares a driver can send by mail per day.
+const DOCUMENT_SHARE_MAILS_PER_DAY: i64 = 5;
+
+/// Wrong passwords after which a password protected share is locked, for the accesses of the
+/// last `DOCUMENT_SHARE_LOCK_MINUTES`.
+const DOCUMENT_SHARE_MAX_WRONG_PASSWORDS: i64 = 10;
+const DOCUMENT_SHARE_LOCK_MINUTES: i64 = 15;
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS> WorkdayService
+    for Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
 where
     H: HealthRepository,
     DD: DriverDatabaseRepository,
@@ -36,6 +69,8 @@ where
     UC: UpdateCacheRepository,
     DE: DocumentExternalRepository,
     DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
 {
     #[tracing::instrument(
         name = "workday_service.get_workday_by_date",
@@ -549,17 +584,502 @@ where
         month: i32,
         year: i32,
     ) -> Result<Option<bytes::Bytes>, WorkdayError> {
+        if let Some(record) = self
+            .get_workday_document_record(driver_id, month, year)
+            .await?
+        {
+            tracing::Span::current().record("source", "s3");
+
+            let pdf = self
+                .storage_repository
+                .download(&record.s3_file_path)
+                .await
+                .map_err(|_| WorkdayError::Internal)?;
+
+            return Ok(Some(pdf));
+        }
+
+        tracing::Span::current().record("source", "grpc");
+
+        Ok(self
+            .generate_workday_document(driver_id, month, year, None)
+            .await?
+            .map(|document| document.pdf))
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.download_workday_document_by_month",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+            month = %month,
+            year = %year,
+            source = tracing::field::Empty,
+        )
+    )]
+    async fn download_workday_document_by_month(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        range: Option<ByteRange>,
+    ) -> Result<Option<DocumentDownload>, WorkdayError> {
+        if let Some(record) = self
+            .get_workday_document_record(driver_id, month, year)
+            .await?
+        {
+            tracing::Span::current().record("source", "s3");
+
+            let download = self
+                .deliver_document(&record.s3_file_path, range)
+                .await
+                .map_err(|e| match e {
+                    StorageError::RangeNotSatisfiable => WorkdayError::RangeNotSatisfiable,
+                    _ => WorkdayError::Internal,
+                })?;
+
+            return Ok(Some(download));
+        }
+
+        tracing::Span::current().record("source", "grpc");
+
+        Ok(self
+            .generate_workday_document(driver_id, month, year, None)
+            .await?
+            .map(|document| DocumentDownload::Generated(document.pdf)))
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.generate_verifiable_workday_document",
+        skip(self),
+        fields(driver_id = %driver_id, month = %month, year = %year)
+    )]
+    async fn generate_verifiable_workday_document(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+    ) -> Result<Option<GeneratedWorkdayDocument>, WorkdayError> {
+        let verification_code = new_verification_code();
+        let verification = DocumentVerificationCode {
+            url: self.config.document_verification.url(&verification_code),
+            code: verification_code.clone(),
+        };
+
+        let document = self
+            .generate_workday_document(driver_id, month, year, Some(verification))
+            .await?;
+
+        Ok(document.map(|document| GeneratedWorkdayDocument {
+            integrity: DocumentIntegrity {
+                sha256: document_sha256(&document.pdf),
+                verification_code,
+            },
+            pdf: document.pdf,
+            is_fallback: document.is_fallback,
+        }))
+    }
+
+    #[tracing::instrument(name = "workday_service.verify_workday_document", skip_all)]
+    async fn verify_workday_document(
+        &self,
+        verification_code: &str,
+    ) -> Result<WorkdayDocumentVerification, WorkdayError> {
+        let verification_code = normalize_verification_code(verification_code)
+            .ok_or(WorkdayError::WorkdayDocumentNotFound)?;
+
+        let document = self
+            .workday_database_repository
+            .get_workday_document_by_verification_code(&verification_code)
+            .await?;
+        let (driver_id, month, year, sha256, superseded_at) = match document {
+            Some(document) => (
+                document.fk_driver_id,
+                document.month,
+                document.year,
+                document
+                    .sha256
+                    .ok_or(WorkdayError::WorkdayDocumentNotFound)?,
+                None,
+            ),
+            // Printed copies of a provisional document keep their code once it is regenerated
+            None => {
+                let superseded = self
+                    .workday_database_repository
+                    .get_superseded_workday_document_by_verification_code(&verification_code)
+                    .await?
+                    .ok_or(WorkdayError::WorkdayDocumentNotFound)?;
+                (
+                    superseded.fk_driver_id,
+                    superseded.month,
+                    superseded.year,
+                    superseded.sha256,
+                    Some(superseded.superseded_at),
+                )
+            }
+        };
+
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await
+            .map_err(|_| WorkdayError::Internal)?
+            .ok_or(WorkdayError::WorkdayDocumentNotFound)?;
+
+        Ok(WorkdayDocumentVerification {
+            driver_name: format!("{} {}", driver.firstname, driver.lastname),
+            month: month as u32,
+            year: year as u32,
+            sha256,
+            superseded_at,
+        })
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.generate_yearly_workday_document",
+        skip(self),
+        fields(driver_id = %driver_id, year = %year)
+    )]
+    async fn generate_yearly_workday_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+    ) -> Result<Option<RenderedDocument>, WorkdayError> {
+        let mut workdays = Vec::new();
+        for month in 1..=12 {
+            workdays.extend(self.get_workdays_by_month(driver_id, month, year).await?);
+        }
+
+        let months = summarize_workday_months(year, &workdays, Utc::now().date_naive());
+        if months.is_empty() {
+            return Ok(None);
+        }
+
+        let driver = self
+            .driver_database_repository
+            .get_driver_by_id(driver_id)
+            .await
+            .map_err(|_| WorkdayError::Internal)?
+            .ok_or(WorkdayError::Internal)?;
+
+        self.document_external_repository
+            .get_workday_documents_by_year(
+                driver.firstname,
+                driver.lastname,
+                driver.language,
+                year,
+                months,
+            )
+            .await
+            .map_err(|e| match e {
+                DocumentError::Unavailable => WorkdayError::DocumentServiceUnavailable,
+                DocumentError::Internal => WorkdayError::Internal,
+            })
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.download_yearly_workday_document",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+            year = %year,
+            source = tracing::field::Empty,
+        )
+    )]
+    async fn download_yearly_workday_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+        range: Option<ByteRange>,
+    ) -> Result<Option<DocumentDownload>, WorkdayError> {
+        if let Some(record) = self
+            .workday_database_repository
+            .get_yearly_document_record(driver_id, year)
+            .await?
+        {
+            tracing::Span::current().record("source", "s3");
+
+            let download = self
+                .deliver_document(&record.s3_file_path, range)
+                .await
+                .map_err(|e| match e {
+                    StorageError::RangeNotSatisfiable => WorkdayError::RangeNotSatisfiable,
+                    _ => WorkdayError::Internal,
+                })?;
+
+            return Ok(Some(download));
+        }
+
+        tracing::Span::current().record("source", "grpc");
+
+        Ok(self
+            .generate_yearly_workday_document(driver_id, year)
+            .await?
+            .map(|document| DocumentDownload::Generated(document.pdf)))
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.create_document_share",
+        skip(self, create_request),
+        fields(driver_id = %driver_id, month = %month, year = %year)
+    )]
+    async fn create_document_share(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        create_request: CreateDocumentShareRequest,
+    ) -> Result<(String, DocumentShareRow), WorkdayError> {
+        if create_request.send_email && create_request.recipient_email.is_none() {
+            return Err(WorkdayError::DocumentShareRecipientRequired);
+        }
+
+        // Only stored documents can be shared, a generated one could change afterwards
+        self.get_workday_document_record(driver_id, month, year)
+            .await?
+            .ok_or(WorkdayError::WorkdayDocumentNotFound)?;
+
+        let since = Utc::now() - Duration::days(1);
+        let share_count = self
+            .workday_database_repository
+            .count_document_shares_since(driver_id, since)
+            .await?;
+        if share_count >= DOCUMENT_SHARES_PER_DAY {
+            return Err(WorkdayError::DocumentShareRateLimited);
+        }
+
+        let driver = if create_request.send_email {
+            let mail_count = self
+                .mail_database_repository
+                .count_mails_since(driver_id, EnumDriverMailType::DocumentShared, since)
+                .await
+                .map_err(|_| WorkdayError::Internal)?;
+            if mail_count >= DOCUMENT_SHARE_MAILS_PER_DAY {
+                return Err(WorkdayError::DocumentShareRateLimited);
+            }
+
+            let driver = self
+                .driver_database_repository
+                .get_driver_by_id(driver_id)
+                .await
+                .map_err(|_| WorkdayError::Internal)?
+                .ok_or(WorkdayError::Internal)?;
+            Some(driver)
+        } else {
+            None
+        };
+
+        let token = self
+            .driver_cache_repository
+            .generate_random_value(40)
+            .await
+            .map_err(|_| WorkdayError::Internal)?;
+        let password_hash = match create_request.password {
+            Some(password) => Some(hash_share_password(&password)?),
+            None => None,
+        };
+
+        let now = Utc::now();
+        let share = self
+            .workday_database_repository
+            .create_document_share(DocumentShareRow {
+                pk_document_share_id: Uuid::new_v4(),
+                fk_driver_id: driver_id,
+                month,
+                year,
+                token_hash: hash_share_token(&token),
+                recipient_email: create_request.recipient_email,
+                password_hash,
+                expires_at: now + Duration::days(create_request.expires_in_days),
+                revoked_at: None,
+                created_at: now,
+            })
+            .await?;
+
+        let url = self.config.document_share.url(&token);
+
+        if let Some(driver) = driver {
+            let sent = self
+                .send_document_share_email(driver, share.clone(), url.clone())
+                .await;
+            if sent.is_err() {
+                // The driver is told the share failed, its link must not stay usable
+                self.workday_database_repository
+                    .revoke_document_share(driver_id, share.pk_document_share_id)
+                    .await?;
+                return Err(WorkdayError::Internal);
+            }
+        }
+
+        Ok((url, share))
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.get_document_shares",
+        skip(self),
+        fields(driver_id = %driver_id)
+    )]
+    async fn get_document_shares(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+        self.workday_database_repository
+            .get_document_shares(driver_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.revoke_document_share",
+        skip(self),
+        fields(driver_id = %driver_id, share_id = %share_id)
+    )]
+    async fn revoke_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<DocumentShareRow, WorkdayError> {
+        self.workday_database_repository
+            .revoke_document_share(driver_id, share_id)
+            .await?
+            .ok_or(WorkdayError::DocumentShareNotFound)
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.get_document_share_accesses",
+        skip(self),
+        fields(driver_id = %driver_id, share_id = %share_id)
+    )]
+    async fn get_document_share_accesses(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+        let share = self
+            .workday_database_repository
+            .get_document_share(driver_id, share_id)
+            .await?
+            .ok_or(WorkdayError::DocumentShareNotFound)?;
+
+        self.workday_database_repository
+            .get_document_share_accesses(share.pk_document_share_id)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "workday_service.get_shared_document",
+        skip_all,
+        fields(share_id = tracing::field::Empty)
+    )]
+    async fn get_shared_document(
+        &self,
+        token: &str,
+        password: Option<String>,
+        user_agent: Option<String>,
+        range: Option<ByteRange>,
+    ) -> Result<(DocumentDownload, String), WorkdayError> {
+        let share = self
+            .workday_database_repository
+            .get_document_share_by_token_hash(&hash_share_token(token))
+            .await?
+            .ok_or(WorkdayError::DocumentShareNotFound)?;
+        tracing::Span::current().record("share_id", share.pk_document_share_id.to_string());
+
+        let access = match password {
+            Some(_) if self.is_document_share_locked(&share).await? => {
+                Err(WorkdayError::DocumentShareLocked)
+            }
+            _ => check_document_share_access(&share, password.as_deref()),
+        };
+
+        self.workday_database_repository
+            .create_document_share_access(DocumentShareAccessRow {
+                pk_document_share_access_id: Uuid::new_v4(),
+                fk_document_share_id: share.pk_document_share_id,
+                user_agent,
+                is_granted: access.is_ok(),
+                is_wrong_password: matches!(
+                    access,
+                    Err(WorkdayError::DocumentSharePasswordInvalid)
+                ),
+                accessed_at: Utc::now(),
+            })
+            .await?;
+        access?;
+
+        let record = self
+            .get_workday_document_record(share.fk_driver_id, share.month, share.year)
+            .await?
+            .ok_or(WorkdayError::WorkdayDocumentNotFound)?;
+
+        let download = self
+            .deliver_document(&record.s3_file_path, range)
+            .await
+            .map_err(|e| match e {
+                StorageError::RangeNotSatisfiable => WorkdayError::RangeNotSatisfiable,
+                _ => WorkdayError::Internal,
+            })?;
+
+        Ok((download, record.file_name))
+    }
+}
+
+impl<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+    Service<H, DD, DC, WD, WC, MS, MD, MC, UD, UC, DE, DS, ND, SS>
+where
+    H: HealthRepository,
+    DD: DriverDatabaseRepository,
+    DC: DriverCacheRepository,
+    WD: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    MS: MailSmtpRepository,
+    MD: MailDatabaseRepository,
+    MC: MailCacheRepository,
+    UD: UpdateDatabaseRepository,
+    UC: UpdateCacheRepository,
+    DE: DocumentExternalRepository,
+    DS: StorageRepository,
+    ND: NotificationDatabaseRepository,
+    SS: SmsRepository,
+{
+    /// Whether wrong passwords were given too often lately for the password protected share, to
+    /// slow down password guessing.
+    async fn is_document_share_locked(
+        &self,
+        share: &DocumentShareRow,
+    ) -> Result<bool, WorkdayError> {
+        if share.password_hash.is_none() {
+            return Ok(false);
+        }
+
+        let wrong_passwords = self
+            .workday_database_repository
+            .count_wrong_password_document_share_accesses_since(
+                share.pk_document_share_id,
+                Utc::now() - Duration::minutes(DOCUMENT_SHARE_LOCK_MINUTES),
+            )
+            .await?;
+
+        Ok(wrong_passwords >= DOCUMENT_SHARE_MAX_WRONG_PASSWORDS)
+    }
+
+    /// Stored document of the month, if one was generated.
+    async fn get_workday_document_record(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+    ) -> Result<Option<WorkdayDocument>, WorkdayError> {
         // Check cache:
         //    - None        → cache miss, must query DB
         //    - Some(None)  → cached absence, skip DB entirely
-        //    - Some(Some)  → cached record, fetch from S3
+        //    - Some(Some)  → cached record
         let cached_record = self
             .workday_cache_repository
             .get_workday_document_record(driver_id, month, year)
             .await?;
 
-        let document_record = match cached_record {
-            Some(record) => record, // Some(None) = absence, Some(Some(doc)) = hit
+        match cached_record {
+            Some(record) => Ok(record), // Some(None) = absence, Some(Some(doc)) = hit
             None => {
                 let db_record = self
                     .workday_database_repository
@@ -571,24 +1091,20 @@ where
                     .set_workday_document_record(driver_id, month, year, db_record.clone())
                     .await;
 
-                db_record
+                Ok(db_record)
             }
-        };
-
-        if let Some(record) = document_record {
-            tracing::Span::current().record("source", "s3");
-
-            let pdf = self
-                .storage_repository
-                .download(&record.s3_file_path)
-                .await
-                .map_err(|_| WorkdayError::Internal)?;
-
-            return Ok(Some(pdf));
         }
+    }
 
-        tracing::Span::current().record("source", "grpc");
-
+    /// Generates the document of the month through the PDF service, without storing it.
+    /// `verification` is printed on the documents meant to be stored.
+    async fn generate_workday_document(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        verification: Option<DocumentVerificationCode>,
+    ) -> Result<Option<RenderedDocument>, WorkdayError> {
         let workdays = self.get_workdays_by_month(driver_id, month, year).await?;
 
         let driver = self
@@ -598,8 +1114,7 @@ where
             .map_err(|_| WorkdayError::Internal)?
             .ok_or(WorkdayError::Internal)?;
 
-        let pdf_opt = self
-            .document_external_repository
+        self.document_external_repository
             .get_workday_documents_by_month(
                 driver.firstname,
                 driver.lastname,
@@ -607,10 +1122,66 @@ where
                 month,
                 year,
                 workdays,
+                verification,
             )
             .await
-            .map_err(|_| WorkdayError::Internal)?;
+            .map_err(|e| match e {
+                DocumentError::Unavailable => WorkdayError::DocumentServiceUnavailable,
+                DocumentError::Internal => WorkdayError::Internal,
+            })
+    }
+}
 
-        Ok(pdf_opt)
+/// Share tokens are long random values, a plain SHA-256 is enough to look them up.
+fn hash_share_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn share_password_hasher() -> Result<Argon2<'static>, WorkdayError> {
+    let params = Params::new(19 * 1024, 2, 1, None).map_err(|e| {
+        error!(
+            "Failed to create Argon2 params for share password hashing: {}",
+            e
+        );
+        WorkdayError::Internal
+    })?; // 19 MiB, 2 itérations, 1 thread
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+fn hash_share_password(password: &str) -> Result<String, WorkdayError> {
+    let salt = SaltString::generate(&mut OsRng);
+    share_password_hasher()?
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| {
+            error!("Failed to hash share password: {}", e);
+            WorkdayError::Internal
+        })
+}
+
+/// Whether a share link can be opened now with `password`, revocation taking precedence over
+/// expiry.
+fn check_document_share_access(
+    share: &DocumentShareRow,
+    password: Option<&str>,
+) -> Result<(), WorkdayError> {
+    if share.revoked_at.is_some() {
+        return Err(WorkdayError::DocumentShareRevoked);
     }
+    if share.expires_at <= Utc::now() {
+        return Err(WorkdayError::DocumentShareExpired);
+    }
+
+    let Some(password_hash) = &share.password_hash else {
+        return Ok(());
+    };
+    let password = password.ok_or(WorkdayError::DocumentSharePasswordRequired)?;
+    let parsed_hash = PasswordHash::new(password_hash).map_err(|e| {
+        error!("Failed to parse share password hash: {}", e);
+        WorkdayError::Internal
+    })?;
+
+    share_password_hasher()?
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .map_err(|_| WorkdayError::DocumentSharePasswordInvalid)
 }
@@ -0,0 +1,127 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::domain::document::entities::CircuitState;
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A trial call started at this instant. Another one is let through if it never reported
+    /// back, e.g. because its caller went away.
+    HalfOpen {
+        trial_started: Instant,
+    },
+}
+
+/// Shared by every clone of the repository, so that all the calls of the process see the
+/// same circuit.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Arc<Mutex<State>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            state: Arc::new(Mutex::new(State::Closed { failures: 0 })),
+        }
+    }
+
+    /// Whether a call may go through. Past the open period, only one trial call is allowed.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { until } if now < until => false,
+            State::HalfOpen { trial_started } if now < trial_started + self.open_duration => false,
+            State::Open { .. } | State::HalfOpen { .. } => {
+                *state = State::HalfOpen { trial_started: now };
+                true
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = State::Closed { failures: 0 };
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let open = State::Open {
+            until: Instant::now() + self.open_duration,
+        };
+        *state = match *state {
+            State::Closed { failures } if failures + 1 < self.failure_threshold => State::Closed {
+                failures: failures + 1,
+            },
+            State::Open { until } => State::Open { until },
+            State::Closed { .. } | State::HalfOpen { .. } => open,
+        };
+    }
+
+    pub fn state(&self) -> CircuitState {
+        match *self.state.lock().unwrap() {
+            State::Closed { .. } => CircuitState::Closed,
+            State::Open { until } if Instant::now() < until => CircuitState::Open,
+            State::Open { .. } | State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::CircuitBreaker;
+    use crate::domain::document::entities::CircuitState;
+
+    #[test]
+    fn test_circuit_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.try_acquire());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.try_acquire());
+    }
+
+    #[test]
+    fn test_half_open_circuit_lets_a_single_trial_through() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+        breaker.record_failure();
+        assert!(!breaker.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.try_acquire());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.try_acquire());
+    }
+}
@@ -4,4 +4,7 @@ use thiserror::Error;
 pub enum DocumentError {
     #[error("An internal error occurred")]
     Internal,
+
+    #[error("The document service is unavailable")]
+    Unavailable,
 }
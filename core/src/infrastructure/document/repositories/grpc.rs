@@ -1,37 +1,243 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
 use bytes::Bytes;
-use tonic::transport::Channel;
+use opentelemetry::{
+    KeyValue,
+    metrics::{Counter, Histogram},
+};
+use tonic::{
+    Code, Request, Response, Status,
+    transport::{Channel, Endpoint},
+};
 
-use crate::domain::{document::port::DocumentExternalRepository, workday::entities::Workday};
+use crate::domain::{
+    common::config::DocumentServiceConfig,
+    document::{
+        entities::{CircuitState, DocumentVerificationCode, RenderedDocument},
+        port::DocumentExternalRepository,
+    },
+    workday::entities::{Workday, WorkdayMonthSummary},
+};
 use crate::infrastructure::document::repositories::{
+    circuit_breaker::CircuitBreaker,
     error::DocumentError,
     proto::{
-        GenerateMonthlyWorkdayReportRequest, Language as ProtoLanguage, Workday as ProtoWorkday,
+        DocumentVerification as ProtoDocumentVerification, GenerateMonthlyWorkdayReportRequest,
+        GenerateYearlyWorkdayReportRequest, Language as ProtoLanguage, Workday as ProtoWorkday,
+        WorkdayMonthSummary as ProtoWorkdayMonthSummary,
         workday_service_client::WorkdayServiceClient,
     },
 };
 
-use tracing::error;
+use tracing::{error, warn};
 
 #[derive(Clone)]
 pub struct GrpcDocumentRepository {
     channel: Channel,
+    config: DocumentServiceConfig,
+    circuit_breaker: CircuitBreaker,
+    metrics: DocumentServiceMetrics,
 }
 
 impl GrpcDocumentRepository {
-    pub fn new(channel: Channel) -> Self {
-        Self { channel }
+    pub fn new(channel: Channel, config: DocumentServiceConfig) -> Self {
+        Self {
+            channel,
+            circuit_breaker: CircuitBreaker::new(
+                config.circuit_failure_threshold,
+                config.circuit_open_duration,
+            ),
+            config,
+            metrics: DocumentServiceMetrics::new(),
+        }
     }
 
-    pub async fn connect(
+    /// The connection is only opened on the first call, and reopened as needed, so that the
+    /// API starts even when the PDF service is down.
+    pub fn connect_lazy(
         endpoint: impl Into<String>,
+        config: DocumentServiceConfig,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let endpoint = endpoint.into();
-        let channel = Channel::from_shared(endpoint.clone())
+        let channel = Endpoint::from_shared(endpoint.into())
             .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?
-            .connect()
-            .await
-            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
-        Ok(Self::new(channel))
+            .connect_timeout(config.connect_timeout)
+            .connect_lazy();
+        Ok(Self::new(channel, config))
+    }
+
+    /// Sends the request through the circuit breaker, within the configured deadline. The
+    /// rendering calls have no side effect, so they are retried while the service is
+    /// unavailable.
+    async fn call<R, T, F, Fut>(
+        &self,
+        rpc: &'static str,
+        message: R,
+        send: F,
+    ) -> Result<T, DocumentError>
+    where
+        R: Clone,
+        F: Fn(WorkdayServiceClient<Channel>, Request<R>) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let started = Instant::now();
+        let mut retries = 0;
+
+        let result = loop {
+            if !self.circuit_breaker.try_acquire() {
+                warn!(rpc, "Circuit open, call to the document service rejected");
+                break Err(CallOutcome::Rejected);
+            }
+
+            let status = match self.attempt(&message, &send).await {
+                Ok(response) => {
+                    self.circuit_breaker.record_success();
+                    break Ok(response);
+                }
+                Err(status) => status,
+            };
+
+            if !matches!(status.code(), Code::Unavailable | Code::DeadlineExceeded) {
+                // The service answered, it is up even though it refused this request.
+                self.circuit_breaker.record_success();
+                error!(rpc, error = %status, "gRPC call to WorkdayService failed");
+                break Err(CallOutcome::Error);
+            }
+            self.circuit_breaker.record_failure();
+
+            if status.code() == Code::Unavailable && retries < self.config.max_retries {
+                let delay = retry_delay(self.config.retry_base_delay, retries);
+                retries += 1;
+                self.metrics.retries.add(1, &[KeyValue::new("rpc", rpc)]);
+                warn!(
+                    rpc,
+                    retry = retries,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %status,
+                    "Document service unavailable, retrying"
+                );
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            error!(rpc, error = %status, "gRPC call to WorkdayService failed");
+            break Err(if status.code() == Code::DeadlineExceeded {
+                CallOutcome::Timeout
+            } else {
+                CallOutcome::Unavailable
+            });
+        };
+
+        let outcome = match &result {
+            Ok(_) => CallOutcome::Success,
+            Err(outcome) => *outcome,
+        };
+        self.metrics.record(rpc, outcome, started.elapsed());
+
+        result.map_err(|outcome| match outcome {
+            CallOutcome::Error => DocumentError::Internal,
+            _ => DocumentError::Unavailable,
+        })
+    }
+
+    async fn attempt<R, T, F, Fut>(&self, message: &R, send: &F) -> Result<T, Status>
+    where
+        R: Clone,
+        F: Fn(WorkdayServiceClient<Channel>, Request<R>) -> Fut,
+        Fut: Future<Output = Result<Response<T>, Status>>,
+    {
+        let timeout = self.config.request_timeout;
+        let mut request = Request::new(message.clone());
+        request.set_timeout(timeout);
+        let client = WorkdayServiceClient::new(self.channel.clone());
+
+        match tokio::time::timeout(timeout, send(client, request)).await {
+            Ok(Ok(response)) => Ok(response.into_inner()),
+            // Tonic enforces the deadline sent along the request, and reports it as cancelled.
+            Ok(Err(status)) if status.code() != Code::Cancelled => Err(status),
+            Ok(Err(_)) | Err(_) => {
+                Err(Status::deadline_exceeded("document service call timed out"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CallOutcome {
+    Success,
+    /// The service answered with an error.
+    Error,
+    Unavailable,
+    Timeout,
+    /// Not sent, the circuit is open.
+    Rejected,
+}
+
+impl CallOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CallOutcome::Success => "success",
+            CallOutcome::Error => "error",
+            CallOutcome::Unavailable => "unavailable",
+            CallOutcome::Timeout => "timeout",
+            CallOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DocumentServiceMetrics {
+    calls: Counter<u64>,
+    retries: Counter<u64>,
+    duration: Histogram<f64>,
+}
+
+impl DocumentServiceMetrics {
+    fn new() -> Self {
+        let meter = opentelemetry::global::meter("plannify_driver_api_core");
+        Self {
+            calls: meter
+                .u64_counter("document_service.calls")
+                .with_description("Calls to the PDF service, by RPC and outcome")
+                .build(),
+            retries: meter
+                .u64_counter("document_service.retries")
+                .with_description("Calls to the PDF service retried while it was unavailable")
+                .build(),
+            duration: meter
+                .f64_histogram("document_service.call.duration")
+                .with_description("Duration of the calls to the PDF service, retries included")
+                .with_unit("s")
+                .build(),
+        }
+    }
+
+    fn record(&self, rpc: &'static str, outcome: CallOutcome, elapsed: Duration) {
+        let attributes = [
+            KeyValue::new("rpc", rpc),
+            KeyValue::new("outcome", outcome.as_str()),
+        ];
+        self.calls.add(1, &attributes);
+        self.duration.record(elapsed.as_secs_f64(), &attributes);
+    }
+}
+
+/// Exponential backoff, drawn between half and all of the delay of the attempt.
+fn retry_delay(base: Duration, retries: u32) -> Duration {
+    let delay = base.saturating_mul(1 << retries.min(16));
+    rand::random_range(delay / 2..=delay)
+}
+
+fn rendered_document(pdf_content: Vec<u8>) -> Option<RenderedDocument> {
+    if pdf_content.is_empty() {
+        None
+    } else {
+        Some(RenderedDocument {
+            pdf: Bytes::from(pdf_content),
+            is_fallback: false,
+        })
     }
 }
 
@@ -45,6 +251,16 @@ fn workday_to_proto(w: &Workday) -> ProtoWorkday {
     }
 }
 
+fn month_summary_to_proto(m: &WorkdayMonthSummary) -> ProtoWorkdayMonthSummary {
+    ProtoWorkdayMonthSummary {
+        month: m.month,
+        worked_days: m.worked_days,
+        worked_minutes: m.worked_minutes,
+        overnight_rests: m.overnight_rests,
+        absences: m.absences,
+    }
+}
+
 fn language_to_proto(language: &str) -> ProtoLanguage {
     let s = language.to_lowercase();
     if s == "fr" || s == "french" || s == "français" {
@@ -65,6 +281,7 @@ impl DocumentExternalRepository for GrpcDocumentRepository {
             month = %month,
             year = %year,
             workday_count = %workdays.len(),
+            verifiable = %verification.is_some(),
         )
     )]
     async fn get_workday_documents_by_month(
@@ -75,7 +292,8 @@ impl DocumentExternalRepository for GrpcDocumentRepository {
         month: i32,
         year: i32,
         workdays: Vec<Workday>,
-    ) -> Result<Option<Bytes>, DocumentError> {
+        verification: Option<DocumentVerificationCode>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
         let month_u32 = u32::try_from(month).map_err(|_| DocumentError::Internal)?;
         let year_u32 = u32::try_from(year).map_err(|_| DocumentError::Internal)?;
 
@@ -86,22 +304,308 @@ impl DocumentExternalRepository for GrpcDocumentRepository {
             month: month_u32,
             year: year_u32,
             workdays: workdays.iter().map(workday_to_proto).collect(),
+            verification: verification.map(|v| ProtoDocumentVerification {
+                code: v.code,
+                url: v.url,
+            }),
+        };
+
+        let pdf_content = self
+            .call(
+                "generate_monthly_workday_report",
+                request,
+                |mut client, request| async move {
+                    client.generate_monthly_workday_report(request).await
+                },
+            )
+            .await?
+            .pdf_content;
+        Ok(rendered_document(pdf_content))
+    }
+
+    #[tracing::instrument(
+        name = "grpc.documents.get_yearly_workday_report",
+        skip(self, months),
+        fields(
+            driver_firstname = %driver_firstname,
+            driver_lastname = %driver_lastname,
+            language = %language,
+            year = %year,
+            month_count = %months.len(),
+        )
+    )]
+    async fn get_workday_documents_by_year(
+        &self,
+        driver_firstname: String,
+        driver_lastname: String,
+        language: String,
+        year: i32,
+        months: Vec<WorkdayMonthSummary>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        let year_u32 = u32::try_from(year).map_err(|_| DocumentError::Internal)?;
+
+        let request = GenerateYearlyWorkdayReportRequest {
+            driver_firstname,
+            driver_lastname,
+            language: language_to_proto(&language) as i32,
+            year: year_u32,
+            months: months.iter().map(month_summary_to_proto).collect(),
         };
 
-        let mut client = WorkdayServiceClient::new(self.channel.clone());
-        let response = client
-            .generate_monthly_workday_report(request)
+        let pdf_content = self
+            .call(
+                "generate_yearly_workday_report",
+                request,
+                |mut client, request| async move {
+                    client.generate_yearly_workday_report(request).await
+                },
+            )
+            .await?
+            .pdf_content;
+        Ok(rendered_document(pdf_content))
+    }
+
+    fn circuit_state(&self) -> CircuitState {
+        self.circuit_breaker.state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicU32, Ordering},
+        },
+        time::Duration,
+    };
+
+    use tonic::{
+        Code, Request, Response, Status,
+        transport::{Server, server::TcpIncoming},
+    };
+
+    use super::GrpcDocumentRepository;
+    use crate::{
+        domain::{
+            common::config::{DocumentRenderer, DocumentServiceConfig},
+            document::{
+                entities::{CircuitState, RenderedDocument},
+                port::DocumentExternalRepository,
+            },
+        },
+        infrastructure::document::repositories::{
+            error::DocumentError,
+            proto::{
+                GenerateMonthlyWorkdayReportRequest, GenerateMonthlyWorkdayReportResponse,
+                GenerateYearlyWorkdayReportRequest, GenerateYearlyWorkdayReportResponse,
+                workday_service_server::{WorkdayService, WorkdayServiceServer},
+            },
+        },
+    };
+
+    /// Fails its first calls with `failure`, then answers after `delay`.
+    #[derive(Clone)]
+    struct MockPdfService {
+        calls: Arc<AtomicU32>,
+        failing_calls: u32,
+        failure: Code,
+        delay: Duration,
+    }
+
+    impl MockPdfService {
+        fn new() -> Self {
+            Self {
+                calls: Arc::new(AtomicU32::new(0)),
+                failing_calls: 0,
+                failure: Code::Unavailable,
+                delay: Duration::ZERO,
+            }
+        }
+
+        fn failing(mut self, failing_calls: u32, failure: Code) -> Self {
+            self.failing_calls = failing_calls;
+            self.failure = failure;
+            self
+        }
+
+        fn slow(mut self, delay: Duration) -> Self {
+            self.delay = delay;
+            self
+        }
+
+        async fn answer(&self) -> Result<Vec<u8>, Status> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failing_calls {
+                return Err(Status::new(self.failure, "mock failure"));
+            }
+            tokio::time::sleep(self.delay).await;
+            Ok(b"%PDF-1.4".to_vec())
+        }
+    }
+
+    #[tonic::async_trait]
+    impl WorkdayService for MockPdfService {
+        async fn generate_monthly_workday_report(
+            &self,
+            _: Request<GenerateMonthlyWorkdayReportRequest>,
+        ) -> Result<Response<GenerateMonthlyWorkdayReportResponse>, Status> {
+            let pdf_content = self.answer().await?;
+            Ok(Response::new(GenerateMonthlyWorkdayReportResponse {
+                pdf_content,
+            }))
+        }
+
+        async fn generate_yearly_workday_report(
+            &self,
+            _: Request<GenerateYearlyWorkdayReportRequest>,
+        ) -> Result<Response<GenerateYearlyWorkdayReportResponse>, Status> {
+            let pdf_content = self.answer().await?;
+            Ok(Response::new(GenerateYearlyWorkdayReportResponse {
+                pdf_content,
+            }))
+        }
+    }
+
+    async fn serve(service: MockPdfService) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(
+            Server::builder()
+                .add_service(WorkdayServiceServer::new(service))
+                .serve_with_incoming(TcpIncoming::from(listener)),
+        );
+        endpoint
+    }
+
+    fn config() -> DocumentServiceConfig {
+        DocumentServiceConfig {
+            renderer: DocumentRenderer::Grpc,
+            connect_timeout: Duration::from_millis(500),
+            request_timeout: Duration::from_secs(2),
+            max_retries: 2,
+            retry_base_delay: Duration::from_millis(5),
+            circuit_failure_threshold: 3,
+            circuit_open_duration: Duration::from_secs(60),
+        }
+    }
+
+    async fn monthly_report(
+        repository: &GrpcDocumentRepository,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        repository
+            .get_workday_documents_by_month(
+                "John".to_string(),
+                "Doe".to_string(),
+                "en".to_string(),
+                3,
+                2026,
+                vec![],
+                None,
+            )
             .await
-            .map_err(|e| {
-                error!(error = %e, "gRPC call to WorkdayService failed");
-                DocumentError::Internal
-            })?;
-
-        let pdf_content = response.into_inner().pdf_content;
-        Ok(if pdf_content.is_empty() {
-            None
-        } else {
-            Some(Bytes::from(pdf_content))
-        })
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_calls_are_retried() {
+        let service = MockPdfService::new().failing(2, Code::Unavailable);
+        let calls = service.calls.clone();
+        let repository =
+            GrpcDocumentRepository::connect_lazy(serve(service).await, config()).unwrap();
+
+        let document = monthly_report(&repository).await.unwrap().unwrap();
+
+        assert_eq!(document.pdf, &b"%PDF-1.4"[..]);
+        assert!(!document.is_fallback);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(repository.circuit_state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_refused_calls_are_not_retried() {
+        let service = MockPdfService::new().failing(1, Code::InvalidArgument);
+        let calls = service.calls.clone();
+        let repository =
+            GrpcDocumentRepository::connect_lazy(serve(service).await, config()).unwrap();
+
+        let result = monthly_report(&repository).await;
+
+        assert_eq!(result, Err(DocumentError::Internal));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(repository.circuit_state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_slow_calls_time_out() {
+        let service = MockPdfService::new().slow(Duration::from_millis(500));
+        let calls = service.calls.clone();
+        let repository = GrpcDocumentRepository::connect_lazy(
+            serve(service).await,
+            DocumentServiceConfig {
+                request_timeout: Duration::from_millis(50),
+                ..config()
+            },
+        )
+        .unwrap();
+
+        let result = monthly_report(&repository).await;
+
+        assert_eq!(result, Err(DocumentError::Unavailable));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_while_service_is_down() {
+        // Nothing listens on the port: the repository is still created, calls fail.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let repository = GrpcDocumentRepository::connect_lazy(endpoint, config()).unwrap();
+
+        // 3 failed attempts, the first call and its 2 retries, open the circuit.
+        assert_eq!(
+            monthly_report(&repository).await,
+            Err(DocumentError::Unavailable)
+        );
+        assert_eq!(repository.circuit_state(), CircuitState::Open);
+
+        assert_eq!(
+            monthly_report(&repository).await,
+            Err(DocumentError::Unavailable)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circuit_closes_once_service_is_back() {
+        let service = MockPdfService::new().failing(3, Code::Unavailable);
+        let calls = service.calls.clone();
+        let repository = GrpcDocumentRepository::connect_lazy(
+            serve(service).await,
+            DocumentServiceConfig {
+                circuit_open_duration: Duration::from_millis(50),
+                ..config()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            monthly_report(&repository).await,
+            Err(DocumentError::Unavailable)
+        );
+        assert_eq!(repository.circuit_state(), CircuitState::Open);
+
+        // Rejected without reaching the service.
+        assert_eq!(
+            monthly_report(&repository).await,
+            Err(DocumentError::Unavailable)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(repository.circuit_state(), CircuitState::HalfOpen);
+
+        assert!(monthly_report(&repository).await.unwrap().is_some());
+        assert_eq!(repository.circuit_state(), CircuitState::Closed);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
     }
 }
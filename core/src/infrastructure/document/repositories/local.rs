@@ -0,0 +1,592 @@
+use bytes::Bytes;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use crate::domain::{
+    document::{
+        entities::{CircuitState, DocumentVerificationCode, RenderedDocument},
+        port::DocumentExternalRepository,
+    },
+    workday::entities::{Workday, WorkdayMonthSummary},
+};
+use crate::infrastructure::document::repositories::error::DocumentError;
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const ROW_HEIGHT: f32 = 16.0;
+
+const REGULAR: Name<'static> = Name(b"F1");
+const BOLD: Name<'static> = Name(b"F2");
+
+/// Renders the documents in process as a plain table, with the standard Helvetica fonts, for
+/// when the PDF service cannot. Its documents are marked as fallback ones.
+#[derive(Clone, Default)]
+pub struct LocalPdfDocumentRepository;
+
+struct Labels {
+    months: [&'static str; 12],
+    monthly_title: &'static str,
+    yearly_title: &'static str,
+    driver: &'static str,
+    /// Between a label and its value, with the space French typography puts before.
+    colon: &'static str,
+    provisional: &'static str,
+    date_format: &'static str,
+    date: &'static str,
+    start: &'static str,
+    end: &'static str,
+    rest: &'static str,
+    worked: &'static str,
+    overnight_rest: &'static str,
+    month: &'static str,
+    worked_days: &'static str,
+    overnight_rests: &'static str,
+    absences: &'static str,
+    yes: &'static str,
+    no: &'static str,
+    verification: &'static str,
+}
+
+const ENGLISH: Labels = Labels {
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    monthly_title: "Workday report",
+    yearly_title: "Yearly workday recap",
+    driver: "Driver",
+    colon: ": ",
+    provisional: "Provisional version, generated while the document service was unavailable.",
+    date_format: "%m/%d/%Y",
+    date: "Date",
+    start: "Start",
+    end: "End",
+    rest: "Rest",
+    worked: "Worked",
+    overnight_rest: "Overnight rest",
+    month: "Month",
+    worked_days: "Days worked",
+    overnight_rests: "Overnight rests",
+    absences: "Absences",
+    yes: "Yes",
+    no: "No",
+    verification: "Verification code",
+};
+
+const FRENCH: Labels = Labels {
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    monthly_title: "Relevé des journées de travail",
+    yearly_title: "Récapitulatif annuel des journées de travail",
+    driver: "Conducteur",
+    colon: " : ",
+    provisional: "Version provisoire, générée pendant l'indisponibilité du service de documents.",
+    date_format: "%d/%m/%Y",
+    date: "Date",
+    start: "Début",
+    end: "Fin",
+    rest: "Pause",
+    worked: "Travaillé",
+    overnight_rest: "Repos de nuit",
+    month: "Mois",
+    worked_days: "Jours travaillés",
+    overnight_rests: "Repos de nuit",
+    absences: "Absences",
+    yes: "Oui",
+    no: "Non",
+    verification: "Code de vérification",
+};
+
+fn labels(language: &str) -> &'static Labels {
+    let s = language.to_lowercase();
+    if s == "fr" || s == "french" || s == "français" {
+        &FRENCH
+    } else {
+        &ENGLISH
+    }
+}
+
+fn month_name(labels: &Labels, month: i32) -> Result<&'static str, DocumentError> {
+    usize::try_from(month - 1)
+        .ok()
+        .and_then(|index| labels.months.get(index).copied())
+        .ok_or(DocumentError::Internal)
+}
+
+fn labelled(labels: &Labels, label: &str, value: impl std::fmt::Display) -> String {
+    format!("{}{}{}", label, labels.colon, value)
+}
+
+fn format_minutes(minutes: u32) -> String {
+    format!("{}h{:02}", minutes / 60, minutes % 60)
+}
+
+/// The standard fonts only cover WinAnsi, the characters outside of it are printed as `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '€' => 0x80,
+            '’' => 0x92,
+            '–' => 0x96,
+            '—' => 0x97,
+            'œ' => 0x9C,
+            'Œ' => 0x8C,
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+struct Table {
+    title: String,
+    driver: String,
+    columns: Vec<(&'static str, f32)>,
+    rows: Vec<Vec<String>>,
+    totals: Vec<String>,
+    verification: Option<DocumentVerificationCode>,
+}
+
+/// Content of the pages written so far, breaking to a new page when one is full.
+struct Layout {
+    pages: Vec<Content>,
+    content: Content,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            content: Content::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn text(&mut self, font: Name, size: f32, x: f32, text: &str) {
+        self.content
+            .begin_text()
+            .set_font(font, size)
+            .next_line(x, self.y)
+            .show(Str(&win_ansi(text)))
+            .end_text();
+    }
+
+    fn rule(&mut self) {
+        self.content
+            .set_line_width(0.5)
+            .move_to(MARGIN, self.y)
+            .line_to(PAGE_WIDTH - MARGIN, self.y)
+            .stroke();
+    }
+
+    fn down(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Starts a new page when less than `height` is left on this one.
+    fn reserve(&mut self, height: f32) -> bool {
+        if self.y - height >= MARGIN {
+            return false;
+        }
+        let full = std::mem::replace(&mut self.content, Content::new());
+        self.pages.push(full);
+        self.y = PAGE_HEIGHT - MARGIN;
+        true
+    }
+
+    fn header(&mut self, columns: &[(&str, f32)]) {
+        for (label, x) in columns {
+            self.text(BOLD, 10.0, *x, label);
+        }
+        self.down(6.0);
+        self.rule();
+        self.down(ROW_HEIGHT);
+    }
+
+    fn finish(mut self) -> Vec<Vec<u8>> {
+        self.pages.push(self.content);
+        self.pages.into_iter().map(Content::finish).collect()
+    }
+}
+
+fn render(table: Table, labels: &Labels) -> Bytes {
+    let mut layout = Layout::new();
+
+    layout.text(BOLD, 16.0, MARGIN, &table.title);
+    layout.down(22.0);
+    layout.text(REGULAR, 11.0, MARGIN, &table.driver);
+    layout.down(18.0);
+    layout.content.set_fill_gray(0.4);
+    layout.text(REGULAR, 9.0, MARGIN, labels.provisional);
+    layout.content.set_fill_gray(0.0);
+    layout.down(30.0);
+
+    layout.header(&table.columns);
+    for row in &table.rows {
+        if layout.reserve(ROW_HEIGHT) {
+            layout.header(&table.columns);
+        }
+        for (cell, (_, x)) in row.iter().zip(&table.columns) {
+            layout.text(REGULAR, 10.0, *x, cell);
+        }
+        layout.down(ROW_HEIGHT);
+    }
+
+    layout.reserve(ROW_HEIGHT * (table.totals.len() + 1) as f32);
+    layout.down(ROW_HEIGHT - 12.0);
+    layout.rule();
+    layout.down(ROW_HEIGHT);
+    for total in &table.totals {
+        layout.text(BOLD, 10.0, MARGIN, total);
+        layout.down(ROW_HEIGHT);
+    }
+
+    if let Some(verification) = &table.verification {
+        layout.reserve(40.0);
+        layout.down(12.0);
+        let code = labelled(labels, labels.verification, &verification.code);
+        layout.text(REGULAR, 8.0, MARGIN, &code);
+        layout.down(12.0);
+        layout.text(REGULAR, 8.0, MARGIN, &verification.url);
+    }
+
+    write_pdf(&table.title, layout.finish())
+}
+
+fn write_pdf(title: &str, pages: Vec<Vec<u8>>) -> Bytes {
+    let catalog_id = Ref::new(1);
+    let tree_id = Ref::new(2);
+    let regular_id = Ref::new(3);
+    let bold_id = Ref::new(4);
+    let info_id = Ref::new(5);
+    let page_ids: Vec<(Ref, Ref)> = (0..pages.len() as i32)
+        .map(|i| (Ref::new(6 + 2 * i), Ref::new(7 + 2 * i)))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(tree_id);
+    pdf.pages(tree_id)
+        .kids(page_ids.iter().map(|(page_id, _)| *page_id))
+        .count(page_ids.len() as i32);
+
+    for ((page_id, content_id), content) in page_ids.iter().zip(&pages) {
+        let mut page = pdf.page(*page_id);
+        page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        page.parent(tree_id);
+        page.contents(*content_id);
+        page.resources()
+            .fonts()
+            .pair(REGULAR, regular_id)
+            .pair(BOLD, bold_id);
+        page.finish();
+        pdf.stream(*content_id, content);
+    }
+
+    pdf.type1_font(regular_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.document_info(info_id)
+        .title(TextStr(title))
+        .producer(TextStr("Fallback document renderer"));
+
+    Bytes::from(pdf.finish())
+}
+
+fn rendered_document(pdf: Bytes) -> RenderedDocument {
+    RenderedDocument {
+        pdf,
+        is_fallback: true,
+    }
+}
+
+impl DocumentExternalRepository for LocalPdfDocumentRepository {
+    #[tracing::instrument(
+        name = "local.documents.get_workday_report",
+        skip(self, workdays, verification),
+        fields(month = %month, year = %year, workday_count = %workdays.len())
+    )]
+    async fn get_workday_documents_by_month(
+        &self,
+        driver_firstname: String,
+        driver_lastname: String,
+        language: String,
+        month: i32,
+        year: i32,
+        mut workdays: Vec<Workday>,
+        verification: Option<DocumentVerificationCode>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        let labels = labels(&language);
+        let month_name = month_name(labels, month)?;
+        if workdays.is_empty() {
+            return Ok(None);
+        }
+        workdays.sort_by_key(|w| w.date);
+
+        let rows = workdays
+            .iter()
+            .map(|w| {
+                vec![
+                    w.date.format(labels.date_format).to_string(),
+                    w.start_time.format("%H:%M").to_string(),
+                    w.end_time
+                        .map(|t| t.format("%H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    w.rest_time.format("%H:%M").to_string(),
+                    w.worked_minutes()
+                        .map(format_minutes)
+                        .unwrap_or_else(|| "-".to_string()),
+                    if w.overnight_rest {
+                        labels.yes
+                    } else {
+                        labels.no
+                    }
+                    .to_string(),
+                ]
+            })
+            .collect();
+        let worked_minutes: u32 = workdays.iter().filter_map(Workday::worked_minutes).sum();
+        let overnight_rests = workdays.iter().filter(|w| w.overnight_rest).count();
+
+        let table = Table {
+            title: format!("{} - {} {}", labels.monthly_title, month_name, year),
+            driver: labelled(
+                labels,
+                labels.driver,
+                format!("{} {}", driver_firstname, driver_lastname),
+            ),
+            columns: vec![
+                (labels.date, MARGIN),
+                (labels.start, 140.0),
+                (labels.end, 210.0),
+                (labels.rest, 280.0),
+                (labels.worked, 350.0),
+                (labels.overnight_rest, 430.0),
+            ],
+            rows,
+            totals: vec![
+                labelled(labels, labels.worked_days, workdays.len()),
+                labelled(labels, labels.worked, format_minutes(worked_minutes)),
+                labelled(labels, labels.overnight_rests, overnight_rests),
+            ],
+            verification,
+        };
+        Ok(Some(rendered_document(render(table, labels))))
+    }
+
+    #[tracing::instrument(
+        name = "local.documents.get_yearly_workday_report",
+        skip(self, months),
+        fields(year = %year, month_count = %months.len())
+    )]
+    async fn get_workday_documents_by_year(
+        &self,
+        driver_firstname: String,
+        driver_lastname: String,
+        language: String,
+        year: i32,
+        months: Vec<WorkdayMonthSummary>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        let labels = labels(&language);
+        if months.is_empty() {
+            return Ok(None);
+        }
+
+        let rows = months
+            .iter()
+            .map(|m| {
+                Ok(vec![
+                    month_name(labels, m.month as i32)?.to_string(),
+                    m.worked_days.to_string(),
+                    format_minutes(m.worked_minutes),
+                    m.overnight_rests.to_string(),
+                    m.absences.to_string(),
+                ])
+            })
+            .collect::<Result<_, DocumentError>>()?;
+        let total = |field: fn(&WorkdayMonthSummary) -> u32| months.iter().map(field).sum::<u32>();
+
+        let table = Table {
+            title: format!("{} {}", labels.yearly_title, year),
+            driver: labelled(
+                labels,
+                labels.driver,
+                format!("{} {}", driver_firstname, driver_lastname),
+            ),
+            columns: vec![
+                (labels.month, MARGIN),
+                (labels.worked_days, 150.0),
+                (labels.worked, 250.0),
+                (labels.overnight_rests, 350.0),
+                (labels.absences, 460.0),
+            ],
+            rows,
+            totals: vec![
+                labelled(labels, labels.worked_days, total(|m| m.worked_days)),
+                labelled(
+                    labels,
+                    labels.worked,
+                    format_minutes(total(|m| m.worked_minutes)),
+                ),
+                labelled(labels, labels.overnight_rests, total(|m| m.overnight_rests)),
+                labelled(labels, labels.absences, total(|m| m.absences)),
+            ],
+            verification: None,
+        };
+        Ok(Some(rendered_document(render(table, labels))))
+    }
+
+    fn circuit_state(&self) -> CircuitState {
+        CircuitState::Closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::{LocalPdfDocumentRepository, win_ansi};
+    use crate::domain::{
+        document::{entities::DocumentVerificationCode, port::DocumentExternalRepository},
+        workday::entities::{Workday, WorkdayMonthSummary},
+    };
+    use crate::infrastructure::document::repositories::error::DocumentError;
+
+    fn workday(day: u32) -> Workday {
+        Workday {
+            date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            start_time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            end_time: Some(NaiveTime::from_hms_opt(17, 0, 0).unwrap()),
+            rest_time: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+            overnight_rest: day.is_multiple_of(2),
+            version: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_monthly_document_is_a_fallback_pdf() {
+        let repository = LocalPdfDocumentRepository;
+        let verification = DocumentVerificationCode {
+            code: "ABCD-1234".to_string(),
+            url: "https://example.com/documents/verify/ABCD-1234".to_string(),
+        };
+
+        let document = repository
+            .get_workday_documents_by_month(
+                "Zoé".to_string(),
+                "Lefèvre".to_string(),
+                "fr".to_string(),
+                3,
+                2025,
+                (1..=31).rev().map(workday).collect(),
+                Some(verification),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(document.is_fallback);
+        assert!(document.pdf.starts_with(b"%PDF"));
+        let pdf = String::from_utf8_lossy(&document.pdf);
+        assert!(pdf.contains("/Helvetica"));
+        assert!(pdf.contains("Fallback document renderer"));
+    }
+
+    #[tokio::test]
+    async fn test_nothing_is_rendered_without_workdays() {
+        let repository = LocalPdfDocumentRepository;
+
+        let monthly = repository
+            .get_workday_documents_by_month(
+                "Jean".to_string(),
+                "Dupont".to_string(),
+                "en".to_string(),
+                3,
+                2025,
+                Vec::new(),
+                None,
+            )
+            .await
+            .unwrap();
+        let yearly = repository
+            .get_workday_documents_by_year(
+                "Jean".to_string(),
+                "Dupont".to_string(),
+                "en".to_string(),
+                2025,
+                Vec::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(monthly.is_none());
+        assert!(yearly.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_yearly_document_rejects_invalid_months() {
+        let repository = LocalPdfDocumentRepository;
+        let month = |month| WorkdayMonthSummary {
+            month,
+            worked_days: 20,
+            worked_minutes: 20 * 8 * 60,
+            overnight_rests: 2,
+            absences: 1,
+        };
+
+        let document = repository
+            .get_workday_documents_by_year(
+                "Jean".to_string(),
+                "Dupont".to_string(),
+                "en".to_string(),
+                2025,
+                (1..=12).map(month).collect(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        let invalid = repository
+            .get_workday_documents_by_year(
+                "Jean".to_string(),
+                "Dupont".to_string(),
+                "en".to_string(),
+                2025,
+                vec![month(13)],
+            )
+            .await;
+
+        assert!(document.is_fallback);
+        assert!(document.pdf.starts_with(b"%PDF"));
+        assert_eq!(invalid.unwrap_err(), DocumentError::Internal);
+    }
+
+    #[test]
+    fn test_text_outside_win_ansi_is_replaced() {
+        assert_eq!(win_ansi("Août – 5 €"), b"Ao\xFBt \x96 5 \x80");
+        assert_eq!(win_ansi("Łukasz 日本"), b"?ukasz ??");
+    }
+}
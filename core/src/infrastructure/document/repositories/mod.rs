@@ -1,3 +1,6 @@
+pub mod circuit_breaker;
 pub mod error;
 pub mod grpc;
+pub mod local;
 pub mod proto;
+pub mod renderer;
@@ -0,0 +1,169 @@
+use tracing::warn;
+
+use crate::{
+    domain::{
+        document::{
+            entities::{CircuitState, DocumentVerificationCode, RenderedDocument},
+            port::DocumentExternalRepository,
+        },
+        workday::entities::{Workday, WorkdayMonthSummary},
+    },
+    infrastructure::document::repositories::{
+        error::DocumentError, grpc::GrpcDocumentRepository, local::LocalPdfDocumentRepository,
+    },
+};
+
+/// The renderer selected by `DocumentServiceConfig::renderer`.
+#[derive(Clone)]
+pub enum DocumentRendererRepository {
+    Grpc(GrpcDocumentRepository),
+    Local(LocalPdfDocumentRepository),
+    /// The PDF service, and the local renderer while it is unavailable.
+    GrpcWithFallback(GrpcDocumentRepository, LocalPdfDocumentRepository),
+}
+
+impl DocumentExternalRepository for DocumentRendererRepository {
+    async fn get_workday_documents_by_month(
+        &self,
+        driver_firstname: String,
+        driver_lastname: String,
+        language: String,
+        month: i32,
+        year: i32,
+        workdays: Vec<Workday>,
+        verification: Option<DocumentVerificationCode>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        match self {
+            DocumentRendererRepository::Grpc(repository) => {
+                repository
+                    .get_workday_documents_by_month(
+                        driver_firstname,
+                        driver_lastname,
+                        language,
+                        month,
+                        year,
+                        workdays,
+                        verification,
+                    )
+                    .await
+            }
+            DocumentRendererRepository::Local(repository) => {
+                repository
+                    .get_workday_documents_by_month(
+                        driver_firstname,
+                        driver_lastname,
+                        language,
+                        month,
+                        year,
+                        workdays,
+                        verification,
+                    )
+                    .await
+            }
+            DocumentRendererRepository::GrpcWithFallback(grpc, local) => {
+                let rendered = grpc
+                    .get_workday_documents_by_month(
+                        driver_firstname.clone(),
+                        driver_lastname.clone(),
+                        language.clone(),
+                        month,
+                        year,
+                        workdays.clone(),
+                        verification.clone(),
+                    )
+                    .await;
+                match rendered {
+                    Err(DocumentError::Unavailable) => {
+                        warn!(
+                            month,
+                            year, "PDF service unavailable, rendering a fallback document"
+                        );
+                        local
+                            .get_workday_documents_by_month(
+                                driver_firstname,
+                                driver_lastname,
+                                language,
+                                month,
+                                year,
+                                workdays,
+                                verification,
+                            )
+                            .await
+                    }
+                    rendered => rendered,
+                }
+            }
+        }
+    }
+
+    async fn get_workday_documents_by_year(
+        &self,
+        driver_firstname: String,
+        driver_lastname: String,
+        language: String,
+        year: i32,
+        months: Vec<WorkdayMonthSummary>,
+    ) -> Result<Option<RenderedDocument>, DocumentError> {
+        match self {
+            DocumentRendererRepository::Grpc(repository) => {
+                repository
+                    .get_workday_documents_by_year(
+                        driver_firstname,
+                        driver_lastname,
+                        language,
+                        year,
+                        months,
+                    )
+                    .await
+            }
+            DocumentRendererRepository::Local(repository) => {
+                repository
+                    .get_workday_documents_by_year(
+                        driver_firstname,
+                        driver_lastname,
+                        language,
+                        year,
+                        months,
+                    )
+                    .await
+            }
+            DocumentRendererRepository::GrpcWithFallback(grpc, local) => {
+                let rendered = grpc
+                    .get_workday_documents_by_year(
+                        driver_firstname.clone(),
+                        driver_lastname.clone(),
+                        language.clone(),
+                        year,
+                        months.clone(),
+                    )
+                    .await;
+                match rendered {
+                    Err(DocumentError::Unavailable) => {
+                        warn!(year, "PDF service unavailable, rendering a fallback recap");
+                        local
+                            .get_workday_documents_by_year(
+                                driver_firstname,
+                                driver_lastname,
+                                language,
+                                year,
+                                months,
+                            )
+                            .await
+                    }
+                    rendered => rendered,
+                }
+            }
+        }
+    }
+
+    /// The state of the PDF service circuit, even when its documents can be rendered locally.
+    fn circuit_state(&self) -> CircuitState {
+        match self {
+            DocumentRendererRepository::Grpc(repository)
+            | DocumentRendererRepository::GrpcWithFallback(repository, _) => {
+                repository.circuit_state()
+            }
+            DocumentRendererRepository::Local(repository) => repository.circuit_state(),
+        }
+    }
+}
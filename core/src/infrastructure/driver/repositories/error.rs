@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::domain::driver::entities::PasswordPolicyViolation;
+
 #[derive(Error, Debug, Clone)]
 pub enum DriverError {
     #[error("A database error occurred")]
@@ -20,6 +22,9 @@ pub enum DriverError {
     #[error("Driver not found")]
     DriverNotFound,
 
+    #[error("Driver was modified since it was read")]
+    DriverVersionConflict,
+
     #[error("Driver limit reached")]
     DriverLimitReached {
         start_at: String,
@@ -65,4 +70,51 @@ pub enum DriverError {
 
     #[error("Invalid password reset token")]
     InvalidResetPasswordToken,
+
+    #[error("Password does not satisfy the password policy")]
+    PasswordPolicyViolation {
+        violations: Vec<PasswordPolicyViolation>,
+    },
+
+    #[error("A magic link token already exists")]
+    MagicLinkTokenAlreadyExists,
+
+    #[error("Invalid magic link token")]
+    InvalidMagicLinkToken,
+
+    #[error("Invalid email change token")]
+    InvalidEmailChangeToken,
+
+    #[error("Session has been revoked")]
+    SessionRevoked,
+
+    #[error("API token not found")]
+    ApiTokenNotFound,
+
+    #[error("Invalid API token")]
+    InvalidApiToken,
+
+    #[error("Driver has no phone number")]
+    PhoneNumberMissing,
+
+    #[error("Phone number already verified")]
+    PhoneNumberAlreadyVerified,
+
+    #[error("Phone number is not verified")]
+    PhoneNumberNotVerified,
+
+    #[error("An SMS code already exists")]
+    SmsCodeAlreadyExists,
+
+    #[error("Too many SMS codes sent")]
+    SmsRateLimited,
+
+    #[error("Invalid SMS code")]
+    InvalidSmsCode,
+
+    #[error("Login by SMS is disabled")]
+    SmsLoginDisabled,
+
+    #[error("Failed to send SMS")]
+    SmsSendError,
 }
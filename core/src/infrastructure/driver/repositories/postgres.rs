@@ -1,13 +1,17 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
-    domain::driver::{
-        entities::{
-            CreateDriverRequest, DriverLimitationRow, DriverRestPeriod, DriverRow,
-            DriverSuspensionRow, EntityType,
+    domain::{
+        common::constants::EnumDriverMailType,
+        driver::{
+            entities::{
+                CreateDriverRequest, DriverApiTokenRow, DriverLimitationRow, DriverRestPeriod,
+                DriverRow, DriverSuspensionRow, EntityType,
+            },
+            port::DriverDatabaseRepository,
         },
-        port::DriverDatabaseRepository,
     },
     infrastructure::driver::repositories::error::DriverError,
 };
@@ -76,6 +80,37 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
         })
     }
 
+    #[tracing::instrument(
+        name = "db.drivers.get_driver_data_version",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            driver_id = %driver_id,
+        )
+    )]
+    async fn get_driver_data_version(&self, driver_id: Uuid) -> Result<i64, DriverError> {
+        let version = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT version
+            FROM driver_data_versions
+            WHERE fk_driver_id = $1
+            "#,
+        )
+        .bind(driver_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to get data version of driver {}: {:?}",
+                driver_id, e
+            );
+            DriverError::DatabaseError
+        })?;
+
+        Ok(version.unwrap_or(0))
+    }
+
     #[tracing::instrument(
         name = "db.drivers.get_driver_by_email",
         skip(self),
@@ -119,11 +154,8 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
         sqlx::query_as!(
             DriverRow,
             r#"
-            INSERT INTO drivers (firstname, lastname, gender, email, password_hash, language, mail_preferences)
-            VALUES (
-                $1, $2, $3, $4, $5, $6,
-                (SELECT COALESCE(SUM(POWER(2, pk_driver_mail_type_id - 1))::integer, 0) FROM driver_mail_types)
-            )
+            INSERT INTO drivers (firstname, lastname, gender, email, password_hash, language)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING *
             "#,
             create_request.firstname,
@@ -154,7 +186,7 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
         )
     )]
     async fn update_driver(&self, driver: DriverRow) -> Result<DriverRow, DriverError> {
-        sqlx::query_as!(
+        let result = sqlx::query_as!(
             DriverRow,
             r#"
             UPDATE drivers
@@ -168,11 +200,12 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
                 allow_request_professional_agreement = $8,
                 language = $9,
                 rest_json = $10,
-                mail_preferences = $11,
-                verified_at = $12,
-                last_login_at = $13,
-                deactivated_at = $14
-            WHERE pk_driver_id = $15
+                verified_at = $11,
+                deactivated_at = $12,
+                phone_verified_at = $13,
+                version = version + 1
+            WHERE pk_driver_id = $14
+            AND version = $15
             RETURNING *
             "#,
             driver.firstname,
@@ -185,28 +218,73 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
             driver.allow_request_professional_agreement,
             driver.language,
             driver.rest_json,
-            driver.mail_preferences,
             driver.verified_at,
-            driver.last_login_at,
             driver.deactivated_at,
-            driver.pk_driver_id
+            driver.phone_verified_at,
+            driver.pk_driver_id,
+            driver.version
         )
         .fetch_one(&self.pool)
-        .await
-        .map_err(|e| {
-            if let sqlx::Error::RowNotFound = e {
-                return DriverError::DriverNotFound;
+        .await;
+
+        match result {
+            Ok(updated) => Ok(updated),
+            // Either the driver is gone or it was updated since `driver` was read
+            Err(sqlx::Error::RowNotFound) => {
+                match self.get_driver_by_id(driver.pk_driver_id).await? {
+                    Some(_) => Err(DriverError::DriverVersionConflict),
+                    None => Err(DriverError::DriverNotFound),
+                }
             }
-            if e.as_database_error()
-                .and_then(|db| db.code())
-                .map(|c| c == "23505")
-                .unwrap_or(false)
-            {
-                return DriverError::DriverAlreadyExists;
+            Err(e) => {
+                if e.as_database_error()
+                    .and_then(|db| db.code())
+                    .map(|c| c == "23505")
+                    .unwrap_or(false)
+                {
+                    return Err(DriverError::DriverAlreadyExists);
+                }
+                error!("Failed to update driver {}: {:?}", driver.pk_driver_id, e);
+                Err(DriverError::DatabaseError)
             }
-            error!("Failed to update driver {}: {:?}", driver.pk_driver_id, e);
+        }
+    }
+
+    #[tracing::instrument(
+        name = "db.drivers.update_driver_last_login",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "UPDATE",
+            driver_id = %driver_id,
+        )
+    )]
+    async fn update_driver_last_login(
+        &self,
+        driver_id: Uuid,
+        last_login_at: DateTime<Utc>,
+    ) -> Result<(), DriverError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE drivers
+            SET last_login_at = $2
+            WHERE pk_driver_id = $1
+            "#,
+        )
+        .bind(driver_id)
+        .bind(last_login_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to update driver {} last login: {:?}", driver_id, e);
             DriverError::DatabaseError
-        })
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DriverError::DriverNotFound);
+        }
+
+        Ok(())
     }
 
     #[tracing::instrument(
@@ -575,15 +653,33 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
     async fn get_drivers_with_monthly_report_preference(
         &self,
     ) -> Result<Vec<DriverRow>, DriverError> {
-        sqlx::query_as!(
-            DriverRow,
+        sqlx::query_as::<_, DriverRow>(
             r#"
-            SELECT *
-            FROM drivers
-            WHERE (mail_preferences & 8) != 0
-            AND deactivated_at IS NULL
+            SELECT d.*
+            FROM drivers d
+            JOIN driver_mail_types t ON t.pk_driver_mail_type_id = $1
+            LEFT JOIN driver_notification_preferences pe
+                ON pe.fk_driver_id = d.pk_driver_id
+                AND pe.fk_mail_type_id = t.pk_driver_mail_type_id
+                AND pe.channel = 'EMAIL'
+            LEFT JOIN driver_notification_preferences pp
+                ON pp.fk_driver_id = d.pk_driver_id
+                AND pp.fk_mail_type_id = t.pk_driver_mail_type_id
+                AND pp.channel = 'PUSH'
+            WHERE (
+                COALESCE(pe.is_enabled, t.default_is_enabled)
+                OR (
+                    COALESCE(pp.is_enabled, t.default_is_enabled)
+                    AND EXISTS (
+                        SELECT 1 FROM push_subscriptions s
+                        WHERE s.fk_driver_id = d.pk_driver_id
+                    )
+                )
+            )
+            AND d.deactivated_at IS NULL
             "#,
         )
+        .bind(EnumDriverMailType::MonthlyReports.as_id())
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
@@ -595,6 +691,53 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
         })
     }
 
+    #[tracing::instrument(
+        name = "db.drivers.get_drivers_with_workday_reminder_preference",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_drivers_with_workday_reminder_preference(
+        &self,
+    ) -> Result<Vec<DriverRow>, DriverError> {
+        sqlx::query_as::<_, DriverRow>(
+            r#"
+            SELECT d.*
+            FROM drivers d
+            JOIN driver_mail_types t ON t.pk_driver_mail_type_id = $1
+            LEFT JOIN driver_notification_preferences pe
+                ON pe.fk_driver_id = d.pk_driver_id
+                AND pe.fk_mail_type_id = t.pk_driver_mail_type_id
+                AND pe.channel = 'EMAIL'
+            LEFT JOIN driver_notification_preferences pp
+                ON pp.fk_driver_id = d.pk_driver_id
+                AND pp.fk_mail_type_id = t.pk_driver_mail_type_id
+                AND pp.channel = 'PUSH'
+            WHERE (
+                COALESCE(pe.is_enabled, t.default_is_enabled)
+                OR (
+                    COALESCE(pp.is_enabled, t.default_is_enabled)
+                    AND EXISTS (
+                        SELECT 1 FROM push_subscriptions s
+                        WHERE s.fk_driver_id = d.pk_driver_id
+                    )
+                )
+            )
+            AND d.verified_at IS NOT NULL
+            AND d.deactivated_at IS NULL
+            "#,
+        )
+        .bind(EnumDriverMailType::WorkdayReminders.as_id())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to get drivers with workday reminder preference: {:?}",
+                e
+            );
+            DriverError::DatabaseError
+        })
+    }
+
     #[tracing::instrument(
         name = "db.drivers.get_drivers_to_delete",
         skip(self),
@@ -659,4 +802,196 @@ impl DriverDatabaseRepository for PostgresDriverRepository {
 
         Ok(rows.into_iter().map(|(path,)| path).collect())
     }
+
+    #[tracing::instrument(
+        name = "db.drivers.create_driver_api_token",
+        skip(self, api_token),
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            driver_id = %api_token.fk_driver_id,
+        )
+    )]
+    async fn create_driver_api_token(
+        &self,
+        api_token: DriverApiTokenRow,
+    ) -> Result<DriverApiTokenRow, DriverError> {
+        sqlx::query_as::<_, DriverApiTokenRow>(
+            r#"
+            INSERT INTO driver_api_tokens (pk_driver_api_token_id, fk_driver_id, label, token_hash, scopes, expires_at, last_used_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING pk_driver_api_token_id, fk_driver_id, label, token_hash, scopes, expires_at, last_used_at, created_at
+            "#,
+        )
+        .bind(api_token.pk_driver_api_token_id)
+        .bind(api_token.fk_driver_id)
+        .bind(&api_token.label)
+        .bind(&api_token.token_hash)
+        .bind(&api_token.scopes)
+        .bind(api_token.expires_at)
+        .bind(api_token.last_used_at)
+        .bind(api_token.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to create driver API token: {:?}", e);
+            DriverError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.drivers.get_driver_api_tokens",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            driver_id = %driver_id,
+        )
+    )]
+    async fn get_driver_api_tokens(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<DriverApiTokenRow>, DriverError> {
+        sqlx::query_as::<_, DriverApiTokenRow>(
+            r#"
+            SELECT pk_driver_api_token_id, fk_driver_id, label, token_hash, scopes, expires_at, last_used_at, created_at
+            FROM driver_api_tokens
+            WHERE fk_driver_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(driver_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get driver API tokens: {:?}", e);
+            DriverError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.drivers.get_driver_api_token_by_hash",
+        skip(self, token_hash),
+        fields(
+            db.system = "postgresql",
+            db.operation = "SELECT",
+        )
+    )]
+    async fn get_driver_api_token_by_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<DriverApiTokenRow>, DriverError> {
+        sqlx::query_as::<_, DriverApiTokenRow>(
+            r#"
+            SELECT pk_driver_api_token_id, fk_driver_id, label, token_hash, scopes, expires_at, last_used_at, created_at
+            FROM driver_api_tokens
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get driver API token by hash: {:?}", e);
+            DriverError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.drivers.update_driver_api_token_last_used",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "UPDATE",
+            api_token_id = %api_token_id,
+        )
+    )]
+    async fn update_driver_api_token_last_used(
+        &self,
+        api_token_id: Uuid,
+        last_used_at: DateTime<Utc>,
+    ) -> Result<(), DriverError> {
+        sqlx::query(
+            r#"
+            UPDATE driver_api_tokens
+            SET last_used_at = $2
+            WHERE pk_driver_api_token_id = $1
+            "#,
+        )
+        .bind(api_token_id)
+        .bind(last_used_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to update driver API token last use: {:?}", e);
+            DriverError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "db.drivers.delete_driver_api_token",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "DELETE",
+            driver_id = %driver_id,
+            api_token_id = %api_token_id,
+        )
+    )]
+    async fn delete_driver_api_token(
+        &self,
+        driver_id: Uuid,
+        api_token_id: Uuid,
+    ) -> Result<(), DriverError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM driver_api_tokens
+            WHERE pk_driver_api_token_id = $1
+            AND fk_driver_id = $2
+            "#,
+        )
+        .bind(api_token_id)
+        .bind(driver_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete driver API token: {:?}", e);
+            DriverError::DatabaseError
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DriverError::ApiTokenNotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "db.drivers.delete_driver_api_tokens",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "DELETE",
+            driver_id = %driver_id,
+        )
+    )]
+    async fn delete_driver_api_tokens(&self, driver_id: Uuid) -> Result<(), DriverError> {
+        sqlx::query(
+            r#"
+            DELETE FROM driver_api_tokens
+            WHERE fk_driver_id = $1
+            "#,
+        )
+        .bind(driver_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete driver API tokens: {:?}", e);
+            DriverError::DatabaseError
+        })?;
+
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use passwords::PasswordGenerator;
-use redis::{AsyncCommands, aio::ConnectionManager};
+use redis::{AsyncCommands, Script, aio::ConnectionManager};
 use tracing::error;
 use uuid::Uuid;
 
@@ -106,4 +106,36 @@ impl DriverCacheRepository for RedisDriverCacheRepository {
 
         Ok(())
     }
+
+    #[tracing::instrument(
+        name = "cache.drivers.increment_redis",
+        skip(self),
+        fields(
+            db.system = "redis",
+            db.operation = "INCR",
+        )
+    )]
+    async fn increment_redis(&self, key: String, ttl_seconds: u64) -> Result<i64, DriverError> {
+        // Single script, so that the counter never exists without its expiry
+        let script = Script::new(
+            r#"
+            local count = redis.call('INCR', KEYS[1])
+            if count == 1 then
+                redis.call('EXPIRE', KEYS[1], ARGV[1])
+            end
+            return count
+            "#,
+        );
+
+        let mut conn = self.connection.clone();
+        script
+            .key(key.clone())
+            .arg(ttl_seconds)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to increment redis key {}: {:?}", key, e);
+                DriverError::Internal
+            })
+    }
 }
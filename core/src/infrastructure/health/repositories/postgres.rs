@@ -1,29 +1,24 @@
-use aws_sdk_s3::Client as S3Client;
 use redis::{AsyncCommands, aio::ConnectionManager};
 use sqlx::PgPool;
 
-use crate::domain::health::{entities::IsHealthy, port::HealthRepository};
+use crate::{
+    domain::health::{entities::IsHealthy, port::HealthRepository},
+    infrastructure::storage::repositories::backend::StorageBackendRepository,
+};
 
 #[derive(Clone)]
 pub struct PostgresHealthRepository {
     pub(crate) pool: PgPool,
     pub(crate) cache: ConnectionManager,
-    pub(crate) s3_client: S3Client,
-    pub(crate) s3_bucket: String,
+    pub(crate) storage: StorageBackendRepository,
 }
 
 impl PostgresHealthRepository {
-    pub fn new(
-        pool: PgPool,
-        cache: ConnectionManager,
-        s3_client: S3Client,
-        s3_bucket: String,
-    ) -> Self {
+    pub fn new(pool: PgPool, cache: ConnectionManager, storage: StorageBackendRepository) -> Self {
         Self {
             pool,
             cache,
-            s3_client,
-            s3_bucket,
+            storage,
         }
     }
 }
@@ -39,13 +34,7 @@ impl HealthRepository for PostgresHealthRepository {
 
         let cache = conn.ping::<()>().await.is_ok();
 
-        let storage = self
-            .s3_client
-            .head_bucket()
-            .bucket(&self.s3_bucket)
-            .send()
-            .await
-            .is_ok();
+        let storage = self.storage.is_reachable().await;
 
         IsHealthy::new(database, cache, storage)
     }
@@ -28,4 +28,43 @@ pub enum MailError {
 
     #[error("Mail preference is disabled")]
     MailPreferenceDisabled,
+
+    #[error("Invalid unsubscribe link signature")]
+    InvalidUnsubscribeSignature,
+
+    #[error("Email address is unreachable")]
+    EmailUnreachable,
+
+    #[error("Invalid mail events webhook token")]
+    InvalidMailEventsToken,
+
+    #[error("Unknown mail events format")]
+    InvalidMailEvents,
+
+    #[error("Mail cannot be resent")]
+    MailNotResendable,
+
+    #[error("A mail was resent too recently")]
+    MailResendRateLimited,
+
+    #[error("Mail cannot be cancelled")]
+    MailNotCancellable,
+
+    #[error("Requested range lies outside the attachment")]
+    MailAttachmentRangeNotSatisfiable,
+}
+
+#[derive(Error, Debug)]
+pub enum MailTemplatesError {
+    #[error("Cannot parse mail templates: {0}")]
+    Templates(#[from] tera::Error),
+
+    #[error("Cannot load the {0} message catalog: {1}")]
+    Catalog(String, String),
+
+    #[error("The {0} mail templates are missing {1}")]
+    Missing(String, String),
+
+    #[error("Invalid {1} in the {0} message catalog")]
+    InvalidFormat(String, String),
 }
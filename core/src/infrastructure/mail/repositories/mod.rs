@@ -1,4 +1,5 @@
 pub mod error;
 pub mod postgres;
 pub mod redis;
+pub mod renderer;
 pub mod smtp;
@@ -7,7 +7,11 @@ use crate::{
         common::constants::EnumDriverMailType,
         driver::entities::DriverRow,
         mail::{
-            entities::{DriverMailAttachmentRow, DriverMailRow, DriverMailTypeRow, MailStatus},
+            entities::{
+                DriverMailAttachmentRow, DriverMailRow, DriverMailTypeRow,
+                DriverNotificationPreferenceRow, EmailSuppressionRow, MailEvent, MailEventRow,
+                MailStatus, NotificationChannel,
+            },
             port::MailDatabaseRepository,
         },
     },
@@ -52,7 +56,7 @@ impl MailDatabaseRepository for PostgresMailRepository {
             r#"
             INSERT INTO driver_mails (fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status, description, content, created_at)
             VALUES ($1, NULL, $2, $3, $4, $5, $6, NOW())
-            RETURNING pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status as "status: MailStatus", description, content, created_at, sent_at
+            RETURNING pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status as "status: MailStatus", description, content, created_at, sent_at, fk_resent_from_mail_id
             "#,
             driver.pk_driver_id,
             mail_type.as_id(),
@@ -76,6 +80,45 @@ impl MailDatabaseRepository for PostgresMailRepository {
         })
     }
 
+    #[tracing::instrument(
+        name = "db.mails.create_resent_mail",
+        skip(self, driver, original),
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            original_mail_id = %original.pk_driver_mail_id,
+        )
+    )]
+    async fn create_resent_mail(
+        &self,
+        driver: DriverRow,
+        original: DriverMailRow,
+    ) -> Result<DriverMailRow, MailError> {
+        sqlx::query_as::<_, DriverMailRow>(
+            r#"
+            INSERT INTO driver_mails (fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status, description, content, created_at, fk_resent_from_mail_id)
+            VALUES ($1, NULL, $2, $3, $4, $5, $6, NOW(), $7)
+            RETURNING pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status, description, content, created_at, sent_at, fk_resent_from_mail_id
+            "#,
+        )
+        .bind(driver.pk_driver_id)
+        .bind(original.fk_mail_type_id)
+        .bind(driver.email.clone())
+        .bind(MailStatus::PENDING)
+        .bind(original.description)
+        .bind(original.content)
+        .bind(original.pk_driver_mail_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to create resend of mail {}: {:?}",
+                original.pk_driver_mail_id, e
+            );
+            MailError::DatabaseError
+        })
+    }
+
     #[tracing::instrument(
         name = "db.mails.update_mail_status",
         skip(self),
@@ -99,7 +142,7 @@ impl MailDatabaseRepository for PostgresMailRepository {
             UPDATE driver_mails
             SET status = $2, sent_at = $3
             WHERE pk_driver_mail_id = $1
-            RETURNING pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status as "status: MailStatus", description, content, created_at, sent_at
+            RETURNING pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id, email_used, status as "status: MailStatus", description, content, created_at, sent_at, fk_resent_from_mail_id
             "#,
             mail_id,
             status as MailStatus,
@@ -153,7 +196,7 @@ impl MailDatabaseRepository for PostgresMailRepository {
             DriverMailRow,
             r#"
             SELECT pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id,
-                   email_used, status as "status: MailStatus", description, content, created_at, sent_at
+                   email_used, status as "status: MailStatus", description, content, created_at, sent_at, fk_resent_from_mail_id
             FROM driver_mails
             WHERE fk_driver_id = $1
             ORDER BY created_at DESC
@@ -181,7 +224,7 @@ impl MailDatabaseRepository for PostgresMailRepository {
     async fn get_mail_types(&self) -> Result<Vec<DriverMailTypeRow>, MailError> {
         sqlx::query_as!(
             DriverMailTypeRow,
-            r#"SELECT pk_driver_mail_type_id, label, index, is_editable FROM driver_mail_types ORDER BY index ASC"#
+            r#"SELECT pk_driver_mail_type_id, label, index, is_editable, default_is_enabled FROM driver_mail_types ORDER BY index ASC"#
         )
         .fetch_all(&self.pool)
         .await
@@ -199,7 +242,7 @@ impl MailDatabaseRepository for PostgresMailRepository {
     async fn get_mail_type_by_id(&self, mail_type_id: i32) -> Result<DriverMailTypeRow, MailError> {
         sqlx::query_as!(
             DriverMailTypeRow,
-            r#"SELECT pk_driver_mail_type_id, label, index, is_editable FROM driver_mail_types WHERE pk_driver_mail_type_id = $1"#,
+            r#"SELECT pk_driver_mail_type_id, label, index, is_editable, default_is_enabled FROM driver_mail_types WHERE pk_driver_mail_type_id = $1"#,
             mail_type_id
         )
         .fetch_optional(&self.pool)
@@ -212,51 +255,115 @@ impl MailDatabaseRepository for PostgresMailRepository {
     }
 
     #[tracing::instrument(
-        name = "db.mails.get_driver_mail_preferences",
+        name = "db.mails.get_driver_notification_preferences",
         skip(self),
         fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
     )]
-    async fn get_driver_mail_preferences(&self, driver_id: Uuid) -> Result<i32, MailError> {
-        sqlx::query_scalar!(
-            r#"SELECT mail_preferences FROM drivers WHERE pk_driver_id = $1"#,
-            driver_id
+    async fn get_driver_notification_preferences(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<DriverNotificationPreferenceRow>, MailError> {
+        sqlx::query_as::<_, DriverNotificationPreferenceRow>(
+            r#"
+            SELECT fk_driver_id, fk_mail_type_id, channel, frequency, is_enabled, updated_at
+            FROM driver_notification_preferences
+            WHERE fk_driver_id = $1
+            "#,
         )
-        .fetch_optional(&self.pool)
+        .bind(driver_id)
+        .fetch_all(&self.pool)
         .await
         .map_err(|e| {
-            error!("Failed to get driver mail preferences: {:?}", e);
+            error!("Failed to get driver notification preferences: {:?}", e);
             MailError::DatabaseError
-        })?
-        .ok_or(MailError::Internal)
+        })
     }
 
     #[tracing::instrument(
-        name = "db.mails.update_driver_mail_preferences",
+        name = "db.mails.is_notification_enabled",
         skip(self),
         fields(
             db.system = "postgresql",
-            db.operation = "UPDATE",
+            db.operation = "SELECT",
             driver_id = %driver_id,
-            mail_preferences = %mail_preferences,
+            mail_type = ?mail_type,
+            channel = ?channel,
         )
     )]
-    async fn update_driver_mail_preferences(
+    async fn is_notification_enabled(
         &self,
         driver_id: Uuid,
-        mail_preferences: i32,
-    ) -> Result<i32, MailError> {
-        sqlx::query_scalar!(
-            r#"UPDATE drivers SET mail_preferences = $2 WHERE pk_driver_id = $1 RETURNING mail_preferences"#,
-            driver_id,
-            mail_preferences,
+        mail_type: EnumDriverMailType,
+        channel: NotificationChannel,
+    ) -> Result<bool, MailError> {
+        sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT COALESCE(p.is_enabled, t.default_is_enabled)
+            FROM driver_mail_types t
+            LEFT JOIN driver_notification_preferences p
+                ON p.fk_mail_type_id = t.pk_driver_mail_type_id
+                AND p.fk_driver_id = $1
+                AND p.channel = $3
+            WHERE t.pk_driver_mail_type_id = $2
+            "#,
         )
+        .bind(driver_id)
+        .bind(mail_type.as_id())
+        .bind(channel)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| {
-            error!("Failed to update driver mail preferences: {:?}", e);
+            error!("Failed to check driver notification preference: {:?}", e);
             MailError::DatabaseError
         })?
-        .ok_or(MailError::Internal)
+        .ok_or(MailError::MailTypeNotFound)
+    }
+
+    #[tracing::instrument(
+        name = "db.mails.upsert_driver_notification_preference",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            driver_id = %driver_id,
+            mail_type_id = %mail_type_id,
+            channel = ?channel,
+            is_enabled = %is_enabled,
+        )
+    )]
+    async fn upsert_driver_notification_preference(
+        &self,
+        driver_id: Uuid,
+        mail_type_id: i32,
+        channel: NotificationChannel,
+        is_enabled: bool,
+    ) -> Result<DriverNotificationPreferenceRow, MailError> {
+        sqlx::query_as::<_, DriverNotificationPreferenceRow>(
+            r#"
+            INSERT INTO driver_notification_preferences (fk_driver_id, fk_mail_type_id, channel, is_enabled)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (fk_driver_id, fk_mail_type_id, channel)
+            DO UPDATE SET is_enabled = EXCLUDED.is_enabled, updated_at = NOW()
+            RETURNING fk_driver_id, fk_mail_type_id, channel, frequency, is_enabled, updated_at
+            "#,
+        )
+        .bind(driver_id)
+        .bind(mail_type_id)
+        .bind(channel)
+        .bind(is_enabled)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            if e.as_database_error()
+                .and_then(|db_err| db_err.code().map(|code| code == "23503"))
+                .unwrap_or(false)
+            {
+                return MailError::MailTypeNotFound;
+            }
+
+            error!("Failed to update driver notification preference: {:?}", e);
+            MailError::DatabaseError
+        })
     }
 
     #[tracing::instrument(
@@ -269,7 +376,7 @@ impl MailDatabaseRepository for PostgresMailRepository {
             DriverMailRow,
             r#"
             SELECT pk_driver_mail_id, fk_driver_id, fk_employee_id, fk_mail_type_id,
-                   email_used, status as "status: MailStatus", description, content, created_at, sent_at
+                   email_used, status as "status: MailStatus", description, content, created_at, sent_at, fk_resent_from_mail_id
             FROM driver_mails
             WHERE pk_driver_mail_id = $1
             "#,
@@ -322,6 +429,74 @@ impl MailDatabaseRepository for PostgresMailRepository {
         Ok(count > 0)
     }
 
+    #[tracing::instrument(
+        name = "db.mails.has_workday_reminder_since",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
+    )]
+    async fn has_workday_reminder_since(
+        &self,
+        driver_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<bool, MailError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM driver_mails
+            WHERE fk_driver_id = $1
+            AND fk_mail_type_id = $2
+            AND created_at >= $3
+            AND status = $4
+            "#,
+        )
+        .bind(driver_id)
+        .bind(EnumDriverMailType::WorkdayReminders.as_id())
+        .bind(since)
+        .bind(MailStatus::SUCCESS)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to check workday reminder for driver {}: {:?}",
+                driver_id, e
+            );
+            MailError::DatabaseError
+        })?;
+
+        Ok(count > 0)
+    }
+
+    #[tracing::instrument(
+        name = "db.mails.count_mails_since",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
+    )]
+    async fn count_mails_since(
+        &self,
+        driver_id: Uuid,
+        mail_type: EnumDriverMailType,
+        since: DateTime<Utc>,
+    ) -> Result<i64, MailError> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM driver_mails
+            WHERE fk_driver_id = $1
+            AND fk_mail_type_id = $2
+            AND created_at >= $3
+            "#,
+        )
+        .bind(driver_id)
+        .bind(mail_type.as_id())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to count mails for driver {}: {:?}", driver_id, e);
+            MailError::DatabaseError
+        })
+    }
+
     #[tracing::instrument(
         name = "db.mails.has_document_at_path",
         skip(self),
@@ -403,6 +578,44 @@ impl MailDatabaseRepository for PostgresMailRepository {
         Ok(())
     }
 
+    #[tracing::instrument(
+        name = "db.mails.copy_mail_attachments",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            from_mail_id = %from_mail_id,
+            to_mail_id = %to_mail_id,
+        )
+    )]
+    async fn copy_mail_attachments(
+        &self,
+        from_mail_id: Uuid,
+        to_mail_id: Uuid,
+    ) -> Result<(), MailError> {
+        sqlx::query(
+            r#"
+            INSERT INTO driver_mail_attachments (fk_driver_mail_id, fk_document_id)
+            SELECT $2, fk_document_id
+            FROM driver_mail_attachments
+            WHERE fk_driver_mail_id = $1
+            "#,
+        )
+        .bind(from_mail_id)
+        .bind(to_mail_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to copy attachments of mail {} to mail {}: {:?}",
+                from_mail_id, to_mail_id, e
+            );
+            MailError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
     #[tracing::instrument(
         name = "db.mails.get_mail_attachment_by_id",
         skip(self),
@@ -472,4 +685,104 @@ impl MailDatabaseRepository for PostgresMailRepository {
             MailError::DatabaseError
         })
     }
+
+    #[tracing::instrument(
+        name = "db.mails.create_mail_event",
+        skip(self, event),
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            event_type = ?event.event_type,
+            provider = %event.provider,
+        )
+    )]
+    async fn create_mail_event(&self, event: MailEvent) -> Result<MailEventRow, MailError> {
+        sqlx::query_as::<_, MailEventRow>(
+            r#"
+            WITH mail AS (
+                SELECT pk_driver_mail_id, fk_driver_id, fk_mail_type_id
+                FROM driver_mails
+                WHERE LOWER(email_used) = $1 AND created_at <= $5
+                ORDER BY created_at DESC
+                LIMIT 1
+            ), failed AS (
+                UPDATE driver_mails
+                SET status = 'FAILED'
+                WHERE $2 = 'HARD_BOUNCE'::mail_event_type
+                    AND pk_driver_mail_id IN (SELECT pk_driver_mail_id FROM mail)
+            ), event AS (
+                INSERT INTO mail_events (fk_driver_mail_id, email, event_type, provider, detail, occurred_at)
+                VALUES ((SELECT pk_driver_mail_id FROM mail), $1, $2, $3, $4, $5)
+                RETURNING pk_mail_event_id, fk_driver_mail_id, email, event_type, provider, detail, occurred_at, created_at
+            )
+            SELECT event.*, mail.fk_driver_id, mail.fk_mail_type_id
+            FROM event
+            LEFT JOIN mail ON mail.pk_driver_mail_id = event.fk_driver_mail_id
+            "#,
+        )
+        .bind(event.email)
+        .bind(event.event_type)
+        .bind(event.provider)
+        .bind(event.detail)
+        .bind(event.occurred_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to create mail event: {:?}", e);
+            MailError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.mails.suppress_email",
+        skip(self, email),
+        fields(db.system = "postgresql", db.operation = "INSERT", mail_event_id = %mail_event_id)
+    )]
+    async fn suppress_email(
+        &self,
+        email: String,
+        mail_event_id: Uuid,
+    ) -> Result<EmailSuppressionRow, MailError> {
+        sqlx::query_as::<_, EmailSuppressionRow>(
+            r#"
+            INSERT INTO email_suppressions (email, fk_mail_event_id)
+            VALUES ($1, $2)
+            ON CONFLICT (email) DO UPDATE SET fk_mail_event_id = EXCLUDED.fk_mail_event_id
+            RETURNING email, fk_mail_event_id, created_at
+            "#,
+        )
+        .bind(email)
+        .bind(mail_event_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to suppress email: {:?}", e);
+            MailError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.mails.get_email_suppression",
+        skip(self, email),
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_email_suppression(
+        &self,
+        email: String,
+    ) -> Result<Option<EmailSuppressionRow>, MailError> {
+        sqlx::query_as::<_, EmailSuppressionRow>(
+            r#"
+            SELECT email, fk_mail_event_id, created_at
+            FROM email_suppressions
+            WHERE email = LOWER($1)
+            "#,
+        )
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get email suppression: {:?}", e);
+            MailError::DatabaseError
+        })
+    }
 }
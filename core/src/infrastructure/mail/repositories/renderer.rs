@@ -0,0 +1,750 @@
+use std::{collections::HashMap, fs, sync::Arc};
+
+use chrono::{
+    DateTime, Datelike, NaiveDate, TimeZone, Utc,
+    format::{Item, StrftimeItems},
+};
+use serde::Deserialize;
+use tera::{Context, Tera};
+
+use crate::infrastructure::mail::repositories::error::{MailError, MailTemplatesError};
+
+use tracing::{error, warn};
+
+/// Languages every mail template is translated into, one template directory each.
+pub const MAIL_LANGUAGES: [&str; 2] = ["en", "fr"];
+
+/// Language mails fall back to when the driver language has no templates.
+pub const DEFAULT_MAIL_LANGUAGE: &str = "en";
+
+/// The HTML body and its plain-text alternative, one template file each.
+const TEMPLATE_EXTENSIONS: [&str; 2] = ["html", "txt"];
+
+/// A mail along with the data its template needs, rendered the same way whether it is sent
+/// or previewed.
+#[derive(Debug, Clone)]
+pub enum MailTemplate {
+    AccountCreation {
+        full_name: String,
+        token_url: String,
+        duration_minutes: u64,
+    },
+    AccountDeactivation {
+        full_name: String,
+        deactivated_at: Option<DateTime<Utc>>,
+    },
+    AccountReactivation {
+        full_name: String,
+    },
+    PasswordReset {
+        full_name: String,
+        token_url: String,
+        duration_minutes: u64,
+    },
+    PasswordChange {
+        full_name: String,
+    },
+    MagicLink {
+        full_name: String,
+        token_url: String,
+        duration_minutes: u64,
+    },
+    EmailChange {
+        full_name: String,
+        new_email: String,
+        token_url: String,
+        duration_days: u64,
+    },
+    EmailChangeConfirmation {
+        full_name: String,
+        token_url: String,
+        duration_hours: u64,
+    },
+    MonthlyReport {
+        full_name: String,
+        month: u32,
+        year: i32,
+    },
+    WorkdayReminder {
+        full_name: String,
+        missing_dates: Vec<NaiveDate>,
+        app_url: String,
+    },
+    DocumentsReady {
+        full_name: String,
+        documents: Vec<(u32, i32)>,
+        app_url: String,
+    },
+    /// Sent to the recipient of a share link, in the language of the driver sharing it.
+    DocumentShared {
+        driver_name: String,
+        month: u32,
+        year: i32,
+        share_url: String,
+        expires_at: DateTime<Utc>,
+        is_password_protected: bool,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedMail {
+    pub subject: String,
+    pub html_body: String,
+    pub text_body: String,
+}
+
+impl MailTemplate {
+    /// Template file name, looked up under the directory of the driver language.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MailTemplate::AccountCreation { .. } => "account_creation",
+            MailTemplate::AccountDeactivation { .. } => "account_deactivation",
+            MailTemplate::AccountReactivation { .. } => "account_reactivation",
+            MailTemplate::PasswordReset { .. } => "password_reset",
+            MailTemplate::PasswordChange { .. } => "password_change",
+            MailTemplate::MagicLink { .. } => "magic_link",
+            MailTemplate::EmailChange { .. } => "email_change",
+            MailTemplate::EmailChangeConfirmation { .. } => "email_change_confirmation",
+            MailTemplate::MonthlyReport { .. } => "monthly_report",
+            MailTemplate::WorkdayReminder { .. } => "workday_reminder",
+            MailTemplate::DocumentsReady { .. } => "documents_ready",
+            MailTemplate::DocumentShared { .. } => "document_shared",
+        }
+    }
+
+    /// One instance of every template filled with sample data, used to preview the mails and
+    /// to check every template renders in every language.
+    pub fn fixtures(frontend_url: &str) -> Vec<MailTemplate> {
+        let full_name = "John".to_string();
+        let token_url = |path: &str| {
+            format!(
+                "{}/authentication/token/{}?token=abc&id=1",
+                frontend_url, path
+            )
+        };
+
+        vec![
+            MailTemplate::AccountCreation {
+                full_name: full_name.clone(),
+                token_url: token_url("verify-account"),
+                duration_minutes: 60,
+            },
+            MailTemplate::AccountDeactivation {
+                full_name: full_name.clone(),
+                deactivated_at: Utc.with_ymd_and_hms(2026, 10, 12, 8, 30, 0).single(),
+            },
+            MailTemplate::AccountReactivation {
+                full_name: full_name.clone(),
+            },
+            MailTemplate::PasswordReset {
+                full_name: full_name.clone(),
+                token_url: token_url("reset-password"),
+                duration_minutes: 15,
+            },
+            MailTemplate::PasswordChange {
+                full_name: full_name.clone(),
+            },
+            MailTemplate::MagicLink {
+                full_name: full_name.clone(),
+                token_url: token_url("magic-link"),
+                duration_minutes: 15,
+            },
+            MailTemplate::EmailChange {
+                full_name: full_name.clone(),
+                new_email: "john.new@plannify.be".to_string(),
+                token_url: token_url("revert-email-change"),
+                duration_days: 7,
+            },
+            MailTemplate::EmailChangeConfirmation {
+                full_name: full_name.clone(),
+                token_url: token_url("confirm-email-change"),
+                duration_hours: 24,
+            },
+            MailTemplate::MonthlyReport {
+                full_name: full_name.clone(),
+                month: 9,
+                year: 2026,
+            },
+            MailTemplate::WorkdayReminder {
+                full_name: full_name.clone(),
+                missing_dates: [12, 13]
+                    .into_iter()
+                    .filter_map(|day| NaiveDate::from_ymd_opt(2026, 10, day))
+                    .collect(),
+                app_url: frontend_url.to_string(),
+            },
+            MailTemplate::DocumentsReady {
+                full_name,
+                documents: vec![(8, 2026), (9, 2026)],
+                app_url: frontend_url.to_string(),
+            },
+            MailTemplate::DocumentShared {
+                driver_name: "John Doe".to_string(),
+                month: 9,
+                year: 2026,
+                share_url: "https://api.plannify.be/shared/abc".to_string(),
+                expires_at: Utc
+                    .with_ymd_and_hms(2026, 10, 19, 8, 30, 0)
+                    .single()
+                    .unwrap_or_default(),
+                is_password_protected: true,
+            },
+        ]
+    }
+
+    fn subject_arguments(&self, catalog: &MessageCatalog) -> Vec<(&'static str, String)> {
+        match self {
+            MailTemplate::MonthlyReport { month, year, .. } => {
+                vec![("period", catalog.format_period(*month, *year))]
+            }
+            MailTemplate::DocumentShared {
+                driver_name,
+                month,
+                year,
+                ..
+            } => vec![
+                ("driver_name", driver_name.clone()),
+                ("period", catalog.format_period(*month, *year)),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    fn context(&self, catalog: &MessageCatalog) -> Context {
+        let mut context = Context::new();
+
+        match self {
+            MailTemplate::AccountCreation {
+                full_name,
+                token_url,
+                duration_minutes,
+            }
+            | MailTemplate::PasswordReset {
+                full_name,
+                token_url,
+                duration_minutes,
+            }
+            | MailTemplate::MagicLink {
+                full_name,
+                token_url,
+                duration_minutes,
+            } => {
+                context.insert("full_name", full_name);
+                context.insert("token_url", token_url);
+                context.insert(
+                    "duration",
+                    &catalog.format_duration("minutes", *duration_minutes),
+                );
+            }
+            MailTemplate::AccountDeactivation {
+                full_name,
+                deactivated_at,
+            } => {
+                let deactivation_date = deactivated_at
+                    .map(|date| catalog.format_date(date.date_naive()))
+                    .unwrap_or_default();
+
+                context.insert("full_name", full_name);
+                context.insert("deactivation_date", &deactivation_date);
+            }
+            MailTemplate::AccountReactivation { full_name }
+            | MailTemplate::PasswordChange { full_name } => {
+                context.insert("full_name", full_name);
+            }
+            MailTemplate::EmailChange {
+                full_name,
+                new_email,
+                token_url,
+                duration_days,
+            } => {
+                context.insert("full_name", full_name);
+                context.insert("new_email", new_email);
+                context.insert("token_url", token_url);
+                context.insert("duration", &catalog.format_duration("days", *duration_days));
+            }
+            MailTemplate::EmailChangeConfirmation {
+                full_name,
+                token_url,
+                duration_hours,
+            } => {
+                context.insert("full_name", full_name);
+                context.insert("token_url", token_url);
+                context.insert(
+                    "duration",
+                    &catalog.format_duration("hours", *duration_hours),
+                );
+            }
+            MailTemplate::MonthlyReport {
+                full_name,
+                month,
+                year,
+            } => {
+                context.insert("full_name", full_name);
+                context.insert("period", &catalog.format_period(*month, *year));
+            }
+            MailTemplate::WorkdayReminder {
+                full_name,
+                missing_dates,
+                app_url,
+            } => {
+                let formatted_dates = missing_dates
+                    .iter()
+                    .map(|date| catalog.format_long_date(*date))
+                    .collect::<Vec<String>>();
+
+                context.insert("full_name", full_name);
+                context.insert("missing_dates", &formatted_dates);
+                context.insert("app_url", app_url);
+            }
+            MailTemplate::DocumentsReady {
+                full_name,
+                documents,
+                app_url,
+            } => {
+                let periods = documents
+                    .iter()
+                    .map(|(month, year)| catalog.format_period(*month, *year))
+                    .collect::<Vec<String>>();
+
+                context.insert("full_name", full_name);
+                context.insert("periods", &periods);
+                context.insert("app_url", app_url);
+            }
+            MailTemplate::DocumentShared {
+                driver_name,
+                month,
+                year,
+                share_url,
+                expires_at,
+                is_password_protected,
+            } => {
+                context.insert("driver_name", driver_name);
+                context.insert("period", &catalog.format_period(*month, *year));
+                context.insert("share_url", share_url);
+                context.insert(
+                    "expiration_date",
+                    &catalog.format_date(expires_at.date_naive()),
+                );
+                context.insert("is_password_protected", is_password_protected);
+            }
+        }
+
+        context
+    }
+}
+
+/// Duration units every catalog spells out, singular and plural.
+const DURATION_UNITS: [&str; 3] = ["minutes", "hours", "days"];
+
+/// Mail copy of one locale, read from the `messages.json` file next to its templates.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MessageCatalog {
+    /// Subject of each template, `{name}` placeholders being filled by the template data.
+    subjects: HashMap<String, String>,
+    date_format: String,
+    /// Date with its weekday, `{weekday}` and `{month}` taking the catalog names.
+    long_date_format: String,
+    period_format: String,
+    weekdays: [String; 7],
+    months: [String; 12],
+    thousands_separator: String,
+    /// Counts taking the singular form, French using it for 0 as well.
+    singular_counts: Vec<u64>,
+    durations: HashMap<String, PluralMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluralMessage {
+    one: String,
+    other: String,
+}
+
+impl MessageCatalog {
+    fn validate(&self, language: &str) -> Result<(), MailTemplatesError> {
+        let missing = |key: String| MailTemplatesError::Missing(language.to_string(), key);
+
+        for template in MailTemplate::fixtures("") {
+            if !self.subjects.contains_key(template.name()) {
+                return Err(missing(format!("subjects.{}", template.name())));
+            }
+        }
+        for unit in DURATION_UNITS {
+            if !self.durations.contains_key(unit) {
+                return Err(missing(format!("durations.{}", unit)));
+            }
+        }
+        for (key, format) in [
+            ("date_format", &self.date_format),
+            ("long_date_format", &self.long_date_format),
+            ("period_format", &self.period_format),
+        ] {
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(MailTemplatesError::InvalidFormat(
+                    language.to_string(),
+                    key.to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn subject(&self, template: &MailTemplate) -> String {
+        let subject = self
+            .subjects
+            .get(template.name())
+            .cloned()
+            .unwrap_or_default();
+
+        template
+            .subject_arguments(self)
+            .into_iter()
+            .fold(subject, |subject, (name, value)| {
+                subject.replace(&format!("{{{}}}", name), &value)
+            })
+    }
+
+    fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.date_format).to_string()
+    }
+
+    fn format_long_date(&self, date: NaiveDate) -> String {
+        let format = self
+            .long_date_format
+            .replace(
+                "{weekday}",
+                &self.weekdays[date.weekday().num_days_from_monday() as usize],
+            )
+            .replace("{month}", &self.months[date.month0() as usize]);
+
+        date.format(&format).to_string()
+    }
+
+    fn format_period(&self, month: u32, year: i32) -> String {
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .map(|date| date.format(&self.period_format).to_string())
+            .unwrap_or_else(|| format!("{:02}/{}", month, year))
+    }
+
+    fn format_number(&self, number: u64) -> String {
+        let digits = number.to_string();
+        let mut formatted = String::with_capacity(digits.len() + 4);
+
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                formatted.push_str(&self.thousands_separator);
+            }
+            formatted.push(digit);
+        }
+
+        formatted
+    }
+
+    fn format_duration(&self, unit: &str, count: u64) -> String {
+        let Some(message) = self.durations.get(unit) else {
+            return self.format_number(count);
+        };
+        let pattern = if self.singular_counts.contains(&count) {
+            &message.one
+        } else {
+            &message.other
+        };
+
+        pattern.replace("{count}", &self.format_number(count))
+    }
+}
+
+/// Renders the mail templates to their subject, HTML and plain-text bodies, without any
+/// transport involved.
+#[derive(Clone)]
+pub struct MailRenderer {
+    tera: Arc<Tera>,
+    catalogs: Arc<HashMap<String, MessageCatalog>>,
+}
+
+impl MailRenderer {
+    /// Loads the templates and message catalog of every language found under `templates_dir`,
+    /// failing if any language misses a template, a subject or a format. Every mail has an HTML
+    /// template and a plain-text one.
+    pub fn load(templates_dir: &str) -> Result<Self, MailTemplatesError> {
+        let templates_dir = templates_dir.trim_end_matches('/');
+        let tera = Tera::new(&format!("{}/**/*.{{html,txt}}", templates_dir))?;
+        let mut catalogs = HashMap::new();
+
+        for language in MAIL_LANGUAGES {
+            let catalog_path = format!("{}/{}/messages.json", templates_dir, language);
+            let catalog = fs::read_to_string(&catalog_path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| {
+                    serde_json::from_str::<MessageCatalog>(&content).map_err(|e| e.to_string())
+                })
+                .map_err(|e| MailTemplatesError::Catalog(language.to_string(), e))?;
+            catalog.validate(language)?;
+
+            for template in MailTemplate::fixtures("") {
+                for extension in TEMPLATE_EXTENSIONS {
+                    let template_path = format!("{}/{}.{}", language, template.name(), extension);
+                    if tera.get_template(&template_path).is_err() {
+                        return Err(MailTemplatesError::Missing(
+                            language.to_string(),
+                            template_path,
+                        ));
+                    }
+                }
+            }
+
+            catalogs.insert(language.to_string(), catalog);
+        }
+
+        Ok(Self {
+            tera: Arc::new(tera),
+            catalogs: Arc::new(catalogs),
+        })
+    }
+
+    /// Language the mail is rendered in, the default one when the driver language has no
+    /// templates.
+    fn language<'a>(&self, language: &'a str) -> &'a str {
+        if self.catalogs.contains_key(language) {
+            language
+        } else {
+            warn!(
+                "No mail templates for language {}, falling back to {}",
+                language, DEFAULT_MAIL_LANGUAGE
+            );
+            DEFAULT_MAIL_LANGUAGE
+        }
+    }
+
+    #[tracing::instrument(
+        name = "renderer.mails.render",
+        skip(self, template),
+        fields(template = template.name())
+    )]
+    pub fn render(
+        &self,
+        language: &str,
+        template: &MailTemplate,
+    ) -> Result<RenderedMail, MailError> {
+        let language = self.language(language);
+        let Some(catalog) = self.catalogs.get(language) else {
+            error!("Missing message catalog for language {}", language);
+            return Err(MailError::Internal);
+        };
+
+        let context = template.context(catalog);
+        let render = |extension: &str| {
+            let template_path = format!("{}/{}.{}", language, template.name(), extension);
+            self.tera.render(&template_path, &context).map_err(|e| {
+                error!("Could not render email template {}: {:?}", template_path, e);
+                MailError::CannotCreateMessage
+            })
+        };
+
+        Ok(RenderedMail {
+            subject: catalog.subject(template),
+            html_body: render("html")?,
+            text_body: render("txt")?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs};
+
+    use chrono::NaiveDate;
+
+    use super::{MAIL_LANGUAGES, MailRenderer, MailTemplate, MessageCatalog, TEMPLATE_EXTENSIONS};
+
+    const TEMPLATES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/templates/mails");
+
+    fn renderer() -> MailRenderer {
+        MailRenderer::load(TEMPLATES_DIR).expect("mail templates should parse")
+    }
+
+    fn template_names(language: &str, extension: &str) -> BTreeSet<String> {
+        fs::read_dir(format!("{}/{}", TEMPLATES_DIR, language))
+            .expect("language directory should exist")
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|file_name| {
+                file_name
+                    .strip_suffix(&format!(".{}", extension))
+                    .map(str::to_string)
+            })
+            .filter(|name| name != "base")
+            .collect()
+    }
+
+    #[test]
+    fn test_every_template_renders_with_its_context_in_every_language() {
+        let renderer = renderer();
+
+        for language in MAIL_LANGUAGES {
+            for template in MailTemplate::fixtures("https://app.plannify.be") {
+                // Tera fails on any variable missing from the context, render directly to
+                // report which one.
+                for extension in TEMPLATE_EXTENSIONS {
+                    let template_path = format!("{}/{}.{}", language, template.name(), extension);
+                    if let Err(e) = renderer.tera.render(
+                        &template_path,
+                        &template.context(&renderer.catalogs[language]),
+                    ) {
+                        let causes =
+                            std::iter::successors(std::error::Error::source(&e), |e| e.source())
+                                .map(|cause| cause.to_string())
+                                .collect::<Vec<String>>();
+                        panic!("{} does not render: {} {:?}", template_path, e, causes);
+                    }
+                }
+
+                let template_path = format!("{}/{}", language, template.name());
+                let mail = renderer.render(language, &template).unwrap();
+                assert!(
+                    mail.html_body
+                        .contains(&format!("<html lang=\"{}\">", language)),
+                    "{} does not extend the {} base template",
+                    template_path,
+                    language
+                );
+                assert!(mail.text_body.contains("John"), "{}", template_path);
+                assert!(
+                    !mail.text_body.contains('<') && !mail.text_body.contains("&#"),
+                    "{} plain-text body holds HTML",
+                    template_path
+                );
+                assert!(
+                    mail.text_body.ends_with("contact@plannify.be\n"),
+                    "{} does not extend the {} plain-text base template",
+                    template_path,
+                    language
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_template_file_has_a_fixture_in_every_language() {
+        let fixtures = MailTemplate::fixtures("https://app.plannify.be")
+            .iter()
+            .map(|template| template.name().to_string())
+            .collect::<BTreeSet<String>>();
+
+        for language in MAIL_LANGUAGES {
+            for extension in TEMPLATE_EXTENSIONS {
+                assert_eq!(
+                    template_names(language, extension),
+                    fixtures,
+                    "{} {}",
+                    language,
+                    extension
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_subject_is_translated() {
+        let renderer = renderer();
+
+        for template in MailTemplate::fixtures("https://app.plannify.be") {
+            let subjects = MAIL_LANGUAGES
+                .iter()
+                .map(|language| renderer.catalogs[*language].subject(&template))
+                .collect::<BTreeSet<String>>();
+
+            assert!(
+                subjects.iter().all(|subject| !subject.trim().is_empty()),
+                "{} has an empty subject",
+                template.name()
+            );
+            assert_eq!(
+                subjects.len(),
+                MAIL_LANGUAGES.len(),
+                "{} subject is not translated",
+                template.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_catalog_missing_a_key_is_rejected() {
+        let content = fs::read_to_string(format!("{}/en/messages.json", TEMPLATES_DIR)).unwrap();
+
+        let mut catalog = serde_json::from_str::<MessageCatalog>(&content).unwrap();
+        catalog.subjects.remove("magic_link");
+        assert!(catalog.validate("en").is_err());
+
+        let mut catalog = serde_json::from_str::<MessageCatalog>(&content).unwrap();
+        catalog.durations.remove("hours");
+        assert!(catalog.validate("en").is_err());
+
+        let mut catalog = serde_json::from_str::<MessageCatalog>(&content).unwrap();
+        catalog.date_format = "%d/%Q".to_string();
+        assert!(catalog.validate("en").is_err());
+    }
+
+    #[test]
+    fn test_catalog_formats_follow_the_locale() {
+        let renderer = renderer();
+        let (en, fr) = (&renderer.catalogs["en"], &renderer.catalogs["fr"]);
+        let date = NaiveDate::from_ymd_opt(2026, 8, 3).unwrap();
+
+        assert_eq!(en.format_date(date), "08/03/2026");
+        assert_eq!(fr.format_date(date), "03/08/2026");
+        assert_eq!(en.format_long_date(date), "Monday, 3 August 2026");
+        assert_eq!(fr.format_long_date(date), "lundi 03/08/2026");
+        assert_eq!(fr.format_period(8, 2026), "08/2026");
+        assert_eq!(en.format_duration("minutes", 1), "1 minute");
+        assert_eq!(en.format_duration("hours", 0), "0 hours");
+        assert_eq!(fr.format_duration("hours", 0), "0 heure");
+        assert_eq!(fr.format_duration("days", 7), "7 jours");
+        assert_eq!(en.format_duration("minutes", 1440), "1,440 minutes");
+        assert_eq!(fr.format_duration("minutes", 1440), "1 440 minutes");
+    }
+
+    #[test]
+    fn test_render_falls_back_to_default_language() {
+        let template = MailTemplate::PasswordChange {
+            full_name: "John".to_string(),
+        };
+
+        let mail = renderer().render("de", &template).unwrap();
+        assert_eq!(mail.subject, "Your Plannify password has been changed");
+        assert!(mail.html_body.contains("<html lang=\"en\">"));
+    }
+
+    #[test]
+    fn test_plain_text_body_keeps_paragraphs_lists_and_links() {
+        let template = MailTemplate::WorkdayReminder {
+            full_name: "John".to_string(),
+            missing_dates: vec![
+                NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 10, 13).unwrap(),
+            ],
+            app_url: "https://app.plannify.be/workdays?from=a&to=b".to_string(),
+        };
+
+        assert_eq!(
+            renderer().render("en", &template).unwrap().text_body,
+            "Hello John,\n\
+             \n\
+             It looks like you usually work on these days, but they have not been filled in yet:\n\
+             \n\
+             - Monday, 12 October 2026\n\
+             - Tuesday, 13 October 2026\n\
+             \n\
+             Fill in my workdays:\n\
+             https://app.plannify.be/workdays?from=a&to=b\n\
+             \n\
+             If you did not work on these days, you can ignore this email. These reminders can be switched off from your mail preferences.\n\
+             \n\
+             Best regards,\n\
+             Baptiste, the moderator of Plannify\n\
+             \n\
+             A problem or question? Contact us at contact@plannify.be\n"
+        );
+    }
+}
@@ -1,15 +1,28 @@
 use std::sync::Arc;
 
 use bytes::Bytes;
+use chrono::NaiveDate;
 use lettre::{
     SmtpTransport, Transport,
-    message::{Attachment, MessageBuilder, MultiPart, SinglePart, header::ContentType},
+    message::{
+        Attachment, MessageBuilder, MultiPart, SinglePart,
+        dkim::DkimConfig,
+        header::{ContentType, HeaderName, HeaderValue},
+    },
 };
-use tera::{Context, Tera};
+use uuid::Uuid;
 
 use crate::{
-    domain::{driver::entities::DriverRow, mail::port::MailSmtpRepository},
-    infrastructure::mail::repositories::error::MailError,
+    domain::{
+        common::{config::MailUnsubscribeConfig, constants::EnumDriverMailType},
+        driver::entities::DriverRow,
+        mail::port::MailSmtpRepository,
+        workday::entities::DocumentShareRow,
+    },
+    infrastructure::mail::repositories::{
+        error::MailError,
+        renderer::{MailRenderer, MailTemplate, RenderedMail},
+    },
 };
 
 use tracing::{error, warn};
@@ -18,39 +31,89 @@ use tracing::{error, warn};
 pub struct SmtpMailRepository {
     mail_client: MessageBuilder,
     transport: SmtpTransport,
-    tera: Arc<Tera>,
+    renderer: MailRenderer,
     frontend_url: String,
+    mail_unsubscribe: MailUnsubscribeConfig,
     is_test_environment: bool,
+    dkim: Option<Arc<DkimConfig>>,
 }
 
 impl SmtpMailRepository {
     pub fn new(
         mail_client: MessageBuilder,
         transport: SmtpTransport,
-        tera: Arc<Tera>,
+        renderer: MailRenderer,
         frontend_url: String,
+        mail_unsubscribe: MailUnsubscribeConfig,
         is_test_environment: bool,
+        dkim: Option<DkimConfig>,
     ) -> Self {
         Self {
             mail_client,
             transport,
-            tera,
+            renderer,
             frontend_url,
+            mail_unsubscribe,
             is_test_environment,
+            dkim: dkim.map(Arc::new),
         }
     }
-}
 
-impl MailSmtpRepository for SmtpMailRepository {
-    #[tracing::instrument(
-        name = "smtp.mails.send_email",
-        skip(self),
-        fields(
-            to = ?to,
-            subject = ?subject,
-        )
-    )]
-    fn send_email(&self, to: String, subject: String, body: String) -> Result<(), MailError> {
+    /// Adds the RFC 8058 one-click unsubscribe headers of a mail type the driver can switch off.
+    fn with_unsubscribe_headers(
+        &self,
+        mail_client: MessageBuilder,
+        driver_id: Uuid,
+        mail_type: EnumDriverMailType,
+    ) -> MessageBuilder {
+        let unsubscribe_url = self.mail_unsubscribe.url(driver_id, mail_type.as_id());
+
+        mail_client
+            .raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                format!("<{}>", unsubscribe_url),
+            ))
+            .raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                "List-Unsubscribe=One-Click".to_string(),
+            ))
+    }
+
+    /// Sends the HTML body along with its plain-text version, some clients flagging HTML-only
+    /// mails as spam.
+    fn deliver(
+        &self,
+        mail_client: MessageBuilder,
+        to: &str,
+        mail: RenderedMail,
+        attachment: Option<(String, Bytes)>,
+    ) -> Result<(), MailError> {
+        let body = MultiPart::alternative_plain_html(mail.text_body, mail.html_body);
+        let body = match attachment {
+            Some((file_name, bytes)) => {
+                let content_type = ContentType::parse("application/pdf").map_err(|e| {
+                    error!("Failed to parse PDF content type: {:?}", e);
+                    MailError::CannotCreateMessage
+                })?;
+                MultiPart::mixed()
+                    .multipart(body)
+                    .singlepart(Attachment::new(file_name).body(bytes.to_vec(), content_type))
+            }
+            None => body,
+        };
+
+        self.send_message(mail_client, to, mail.subject, body)
+    }
+
+    /// Sends the message, DKIM signed when configured. Test environments stop here, once the
+    /// templates have rendered.
+    fn send_message(
+        &self,
+        mail_client: MessageBuilder,
+        to: &str,
+        subject: String,
+        body: MultiPart,
+    ) -> Result<(), MailError> {
         if self.is_test_environment {
             warn!(
                 "Test Environment: Email to {} with subject '{}' not sent.",
@@ -59,16 +122,20 @@ impl MailSmtpRepository for SmtpMailRepository {
             return Ok(());
         }
 
-        let email = self
-            .mail_client
-            .clone()
-            .to(to.parse().unwrap())
+        let mut email = mail_client
+            .to(to.parse().map_err(|e| {
+                error!("Failed to parse recipient address: {:?}", e);
+                MailError::CannotCreateMessage
+            })?)
             .subject(subject)
-            .body(body)
+            .multipart(body)
             .map_err(|e| {
                 error!("Could not create email content: {:?}", e);
                 MailError::CannotCreateMessage
             })?;
+        if let Some(dkim) = &self.dkim {
+            email.sign(dkim);
+        }
 
         match self.transport.send(&email) {
             Ok(_) => Ok(()),
@@ -79,51 +146,51 @@ impl MailSmtpRepository for SmtpMailRepository {
         }
     }
 
+    /// Renders a transactional mail, which carries no unsubscribe headers, and sends it.
+    fn send_template(&self, driver: &DriverRow, template: MailTemplate) -> Result<(), MailError> {
+        let mail = self.renderer.render(&driver.language, &template)?;
+        self.deliver(self.mail_client.clone(), &driver.email, mail, None)
+    }
+
+    fn token_url(&self, path: &str, token: &str, driver_id: Uuid) -> String {
+        format!(
+            "{}/authentication/token/{}?token={}&id={}",
+            self.frontend_url, path, token, driver_id
+        )
+    }
+}
+
+impl MailSmtpRepository for SmtpMailRepository {
+    #[tracing::instrument(
+        name = "smtp.mails.send_email",
+        skip(self),
+        fields(
+            to = ?to,
+            subject = ?subject,
+        )
+    )]
+    fn send_email(&self, to: String, subject: String, body: String) -> Result<(), MailError> {
+        self.send_message(
+            self.mail_client.clone(),
+            &to,
+            subject,
+            MultiPart::mixed().singlepart(SinglePart::plain(body)),
+        )
+    }
+
     #[tracing::instrument(
         name = "smtp.mails.send_driver_deactivation_email",
         skip(self),
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_driver_deactivation_email(&self, driver: DriverRow) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver deactivation email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let deactivation_date = driver
-            .deactivated_at
-            .map(|dt| {
-                let fmt = match driver.language.as_str() {
-                    "fr" => "%d/%m/%Y",
-                    _ => "%m/%d/%Y",
-                };
-                dt.format(fmt).to_string()
-            })
-            .unwrap_or_default();
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-        context.insert("deactivation_date", &deactivation_date);
-
-        let template_path = format!("{}/account_deactivation.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Désactivation de votre compte Plannify".to_string(),
-            "en" => "Your Plannify account has been deactivated".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
-
-        self.send_email(driver.email, subject, html_body)
+        self.send_template(
+            &driver,
+            MailTemplate::AccountDeactivation {
+                full_name: driver.firstname.clone(),
+                deactivated_at: driver.deactivated_at,
+            },
+        )
     }
 
     #[tracing::instrument(
@@ -132,33 +199,12 @@ impl MailSmtpRepository for SmtpMailRepository {
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_driver_reactivation_email(&self, driver: DriverRow) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver reactivation email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-
-        let template_path = format!("{}/account_reactivation.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Réactivation de votre compte Plannify".to_string(),
-            "en" => "Your Plannify account has been reactivated".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
-
-        self.send_email(driver.email, subject, html_body)
+        self.send_template(
+            &driver,
+            MailTemplate::AccountReactivation {
+                full_name: driver.firstname.clone(),
+            },
+        )
     }
 
     #[tracing::instrument(
@@ -172,43 +218,35 @@ impl MailSmtpRepository for SmtpMailRepository {
         reset_value: String,
         reset_ttl: u64,
     ) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver reset password email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-        context.insert(
-            "token_url",
-            &format!(
-                "{}/authentication/token/reset-password?token={}&id={}",
-                self.frontend_url,
-                reset_value.as_str(),
-                driver.pk_driver_id
-            ),
-        );
-        context.insert("duration", &(reset_ttl / 60).to_string());
-
-        let template_path = format!("{}/password_reset.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Réinitialisation de votre mot de passe Plannify".to_string(),
-            "en" => "Reset your Plannify password".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
+        self.send_template(
+            &driver,
+            MailTemplate::PasswordReset {
+                full_name: driver.firstname.clone(),
+                token_url: self.token_url("reset-password", &reset_value, driver.pk_driver_id),
+                duration_minutes: reset_ttl / 60,
+            },
+        )
+    }
 
-        self.send_email(driver.email, subject, html_body)
+    #[tracing::instrument(
+        name = "smtp.mails.send_driver_magic_link_email",
+        skip(self, magic_link_value),
+        fields(driver_id = %driver.pk_driver_id)
+    )]
+    async fn send_driver_magic_link_email(
+        &self,
+        driver: DriverRow,
+        magic_link_value: String,
+        magic_link_ttl: u64,
+    ) -> Result<(), MailError> {
+        self.send_template(
+            &driver,
+            MailTemplate::MagicLink {
+                full_name: driver.firstname.clone(),
+                token_url: self.token_url("magic-link", &magic_link_value, driver.pk_driver_id),
+                duration_minutes: magic_link_ttl / 60,
+            },
+        )
     }
 
     #[tracing::instrument(
@@ -224,45 +262,14 @@ impl MailSmtpRepository for SmtpMailRepository {
         verify_value: String,
         verify_ttl: u64,
     ) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver creation email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-        context.insert(
-            "token_url",
-            &format!(
-                "{}/authentication/token/verify-account?token={}&id={}",
-                self.frontend_url,
-                verify_value.as_str(),
-                driver.pk_driver_id
-            ),
-        );
-        context.insert("duration", &(verify_ttl / 60).to_string());
-
-        let template_path = format!("{}/account_creation.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let to = driver.email;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Bienvenue sur Plannify !".to_string(),
-            "en" => "Welcome to Plannify!".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
-
-        self.send_email(to, subject, html_body)
+        self.send_template(
+            &driver,
+            MailTemplate::AccountCreation {
+                full_name: driver.firstname.clone(),
+                token_url: self.token_url("verify-account", &verify_value, driver.pk_driver_id),
+                duration_minutes: verify_ttl / 60,
+            },
+        )
     }
 
     #[tracing::instrument(
@@ -278,65 +285,87 @@ impl MailSmtpRepository for SmtpMailRepository {
         pdf_bytes: Bytes,
         file_name: String,
     ) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Monthly report email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-        let month_padded = format!("{:02}", month);
-        context.insert("month", &month_padded);
-        context.insert("year", &year);
-
-        let template_path = format!("{}/monthly_report.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render monthly report template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let subject = match driver.language.as_str() {
-            "fr" => format!("Votre rapport mensuel Plannify - {}/{}", month_padded, year),
-            "en" => format!("Your Plannify monthly report - {}/{}", month_padded, year),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
-
-        let content_type = ContentType::parse("application/pdf").map_err(|e| {
-            error!("Failed to parse PDF content type: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
+        let mail = self.renderer.render(
+            &driver.language,
+            &MailTemplate::MonthlyReport {
+                full_name: driver.firstname.clone(),
+                month,
+                year,
+            },
+        )?;
+
+        self.deliver(
+            self.with_unsubscribe_headers(
+                self.mail_client.clone(),
+                driver.pk_driver_id,
+                EnumDriverMailType::MonthlyReports,
+            ),
+            &driver.email,
+            mail,
+            Some((file_name, pdf_bytes)),
+        )
+    }
 
-        let email = self
-            .mail_client
-            .clone()
-            .to(driver.email.parse().map_err(|e| {
-                error!("Failed to parse recipient address: {:?}", e);
-                MailError::CannotCreateMessage
-            })?)
-            .subject(subject)
-            .multipart(
-                MultiPart::mixed()
-                    .singlepart(SinglePart::html(html_body))
-                    .singlepart(Attachment::new(file_name).body(pdf_bytes.to_vec(), content_type)),
-            )
-            .map_err(|e| {
-                error!("Could not build monthly report email: {:?}", e);
-                MailError::CannotCreateMessage
-            })?;
+    #[tracing::instrument(
+        name = "smtp.mails.send_driver_workday_reminder_email",
+        skip(self, missing_dates),
+        fields(driver_id = %driver.pk_driver_id, missing_days = missing_dates.len())
+    )]
+    async fn send_driver_workday_reminder_email(
+        &self,
+        driver: DriverRow,
+        missing_dates: Vec<NaiveDate>,
+    ) -> Result<(), MailError> {
+        let mail = self.renderer.render(
+            &driver.language,
+            &MailTemplate::WorkdayReminder {
+                full_name: driver.firstname.clone(),
+                missing_dates,
+                app_url: self.frontend_url.clone(),
+            },
+        )?;
+
+        self.deliver(
+            self.with_unsubscribe_headers(
+                self.mail_client.clone(),
+                driver.pk_driver_id,
+                EnumDriverMailType::WorkdayReminders,
+            ),
+            &driver.email,
+            mail,
+            None,
+        )
+    }
 
-        match self.transport.send(&email) {
-            Ok(_) => Ok(()),
-            Err(e) => {
-                error!("Could not send monthly report email: {:?}", e);
-                Err(MailError::CannotSendMessage)
-            }
-        }
+    #[tracing::instrument(
+        name = "smtp.mails.send_driver_documents_ready_email",
+        skip(self, documents),
+        fields(driver_id = %driver.pk_driver_id, documents = documents.len())
+    )]
+    async fn send_driver_documents_ready_email(
+        &self,
+        driver: DriverRow,
+        documents: Vec<(u32, i32)>,
+    ) -> Result<(), MailError> {
+        let mail = self.renderer.render(
+            &driver.language,
+            &MailTemplate::DocumentsReady {
+                full_name: driver.firstname.clone(),
+                documents,
+                app_url: self.frontend_url.clone(),
+            },
+        )?;
+
+        self.deliver(
+            self.with_unsubscribe_headers(
+                self.mail_client.clone(),
+                driver.pk_driver_id,
+                EnumDriverMailType::DocumentsReady,
+            ),
+            &driver.email,
+            mail,
+            None,
+        )
     }
 
     #[tracing::instrument(
@@ -344,34 +373,26 @@ impl MailSmtpRepository for SmtpMailRepository {
         skip(self),
         fields(driver_id = %driver.pk_driver_id)
     )]
-    async fn send_driver_email_change_email(&self, driver: DriverRow) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver email change email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-
-        let template_path = format!("{}/email_change.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Votre adresse e-mail Plannify a été modifiée".to_string(),
-            "en" => "Your Plannify email address has been updated".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
-
-        self.send_email(driver.email, subject, html_body)
+    async fn send_driver_email_change_email(
+        &self,
+        driver: DriverRow,
+        new_email: String,
+        revert_value: String,
+        revert_ttl: u64,
+    ) -> Result<(), MailError> {
+        self.send_template(
+            &driver,
+            MailTemplate::EmailChange {
+                full_name: driver.firstname.clone(),
+                new_email,
+                token_url: self.token_url(
+                    "revert-email-change",
+                    &revert_value,
+                    driver.pk_driver_id,
+                ),
+                duration_days: revert_ttl / 86400,
+            },
+        )
     }
 
     #[tracing::instrument(
@@ -380,79 +401,340 @@ impl MailSmtpRepository for SmtpMailRepository {
         fields(driver_id = %driver.pk_driver_id)
     )]
     async fn send_driver_password_change_email(&self, driver: DriverRow) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver password change email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
-        }
-
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-
-        let template_path = format!("{}/password_change.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Votre mot de passe Plannify a été modifié".to_string(),
-            "en" => "Your Plannify password has been changed".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
-            }
-        };
+        self.send_template(
+            &driver,
+            MailTemplate::PasswordChange {
+                full_name: driver.firstname.clone(),
+            },
+        )
+    }
 
-        self.send_email(driver.email, subject, html_body)
+    async fn send_driver_email_change_confirmation_email(
+        &self,
+        driver: DriverRow,
+        confirm_value: String,
+        confirm_ttl: u64,
+    ) -> Result<(), MailError> {
+        self.send_template(
+            &driver,
+            MailTemplate::EmailChangeConfirmation {
+                full_name: driver.firstname.clone(),
+                token_url: self.token_url(
+                    "confirm-email-change",
+                    &confirm_value,
+                    driver.pk_driver_id,
+                ),
+                duration_hours: confirm_ttl / 3600,
+            },
+        )
     }
 
-    async fn send_driver_verification_email(
+    #[tracing::instrument(
+        name = "smtp.mails.send_driver_document_shared_email",
+        skip(self, driver, share_url),
+        fields(driver_id = %driver.pk_driver_id, share_id = %share.pk_document_share_id)
+    )]
+    async fn send_driver_document_shared_email(
         &self,
         driver: DriverRow,
-        verify_value: String,
-        verify_ttl: u64,
+        share: DocumentShareRow,
+        share_url: String,
     ) -> Result<(), MailError> {
-        if self.is_test_environment {
-            warn!(
-                "Test Environment: Driver verification email to {} not sent.",
-                driver.email
-            );
-            return Ok(());
+        self.send_template(
+            &driver,
+            MailTemplate::DocumentShared {
+                driver_name: format!("{} {}", driver.firstname, driver.lastname),
+                month: share.month as u32,
+                year: share.year,
+                share_url,
+                expires_at: share.expires_at,
+                is_password_protected: share.password_hash.is_some(),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use chrono::{NaiveDate, Utc};
+    use ed25519_dalek::{Signature, SigningKey, Verifier};
+    use lettre::{SmtpTransport, message::MessageBuilder};
+    use sha2::{Digest, Sha256};
+    use uuid::Uuid;
+
+    use super::SmtpMailRepository;
+    use crate::{
+        domain::{
+            common::config::{MailDkimConfig, MailUnsubscribeConfig},
+            driver::entities::DriverRow,
+            mail::port::MailSmtpRepository,
+        },
+        infrastructure::mail::repositories::renderer::MailRenderer,
+    };
+
+    const TEMPLATES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/templates/mails");
+
+    fn make_driver() -> DriverRow {
+        DriverRow {
+            pk_driver_id: Uuid::new_v4(),
+            firstname: "John".to_string(),
+            lastname: "Driver".to_string(),
+            gender: None,
+            email: "john@example.be".to_string(),
+            password_hash: "hash".to_string(),
+            phone_number: None,
+            is_searchable: false,
+            allow_request_professional_agreement: false,
+            language: "en".to_string(),
+            rest_json: None,
+            created_at: Utc::now(),
+            verified_at: Some(Utc::now()),
+            last_login_at: None,
+            deactivated_at: None,
+            phone_verified_at: None,
+            version: 1,
         }
+    }
 
-        let mut context = Context::new();
-        context.insert("full_name", driver.firstname.as_str());
-        context.insert(
-            "token_url",
-            &format!(
-                "{}/authentication/token/verify-account?token={}&id={}",
-                self.frontend_url,
-                verify_value.as_str(),
-                driver.pk_driver_id
-            ),
-        );
-        context.insert("duration", &(verify_ttl / 60).to_string());
-
-        let template_path = format!("{}/verify_account.html", driver.language.as_str());
-        let html_body = self.tera.render(&template_path, &context).map_err(|e| {
-            error!("Could not render email template: {:?}", e);
-            MailError::CannotCreateMessage
-        })?;
-
-        let to = driver.email;
-
-        let subject = match driver.language.as_str() {
-            "fr" => "Vérifier votre compte Plannify".to_string(),
-            "en" => "Verify your Plannify account".to_string(),
-            _ => {
-                error!("Unsupported driver language: {}", driver.language);
-                return Err(MailError::Internal);
+    /// Accepts a single SMTP session on a local port and hands over the received message.
+    fn spawn_smtp_server() -> (u16, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            writer.write_all(b"220 localhost ESMTP\r\n").unwrap();
+
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 {
+                let command = line.trim_end().to_ascii_uppercase();
+                line.clear();
+
+                if command.starts_with("EHLO") {
+                    writer
+                        .write_all(b"250-localhost\r\n250 8BITMIME\r\n")
+                        .unwrap();
+                } else if command == "DATA" {
+                    writer
+                        .write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n")
+                        .unwrap();
+                    let mut message = String::new();
+                    loop {
+                        reader.read_line(&mut line).unwrap();
+                        if line == ".\r\n" {
+                            line.clear();
+                            break;
+                        }
+                        message.push_str(line.strip_prefix('.').unwrap_or(&line));
+                        line.clear();
+                    }
+                    writer.write_all(b"250 OK\r\n").unwrap();
+                    sender.send(message).unwrap();
+                } else if command == "QUIT" {
+                    writer.write_all(b"221 Bye\r\n").unwrap();
+                    break;
+                } else {
+                    writer.write_all(b"250 OK\r\n").unwrap();
+                }
             }
+        });
+
+        (port, receiver)
+    }
+
+    /// Unfolded headers of a raw message, in order, along with its body.
+    fn parse_message(message: &str) -> (Vec<(String, String)>, String) {
+        let (head, body) = message.split_once("\r\n\r\n").unwrap();
+        let mut headers: Vec<(String, String)> = Vec::new();
+
+        for line in head.split("\r\n") {
+            if line.starts_with([' ', '\t']) {
+                headers
+                    .last_mut()
+                    .unwrap()
+                    .1
+                    .push_str(&format!("\r\n{}", line));
+            } else {
+                let (name, value) = line.split_once(':').unwrap();
+                headers.push((name.to_string(), value.to_string()));
+            }
+        }
+
+        (headers, body.to_string())
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Relaxed canonicalization of RFC 6376 section 3.4.2.
+    fn relaxed_header(name: &str, value: &str) -> String {
+        let value = value
+            .replace("\r\n", "")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        format!("{}:{}", name.to_ascii_lowercase(), value)
+    }
+
+    /// Relaxed canonicalization of RFC 6376 section 3.4.4.
+    fn relaxed_body(body: &str) -> String {
+        let mut lines = body
+            .split("\r\n")
+            .map(|line| {
+                let mut canonical = String::new();
+                for character in line.trim_end_matches([' ', '\t']).chars() {
+                    if !matches!(character, ' ' | '\t') {
+                        canonical.push(character);
+                    } else if !canonical.ends_with(' ') {
+                        canonical.push(' ');
+                    }
+                }
+                canonical
+            })
+            .collect::<Vec<String>>();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        lines
+            .into_iter()
+            .map(|line| format!("{}\r\n", line))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_sends_dkim_signed_mail_with_unsubscribe_headers() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let private_key_path = std::env::temp_dir().join(format!("dkim-{}.key", Uuid::new_v4()));
+        fs::write(&private_key_path, STANDARD.encode(signing_key.to_bytes())).unwrap();
+        let dkim = MailDkimConfig {
+            selector: Some("mail".to_string()),
+            domain: "plannify.be".to_string(),
+            private_key_path: Some(private_key_path.clone()),
+        }
+        .signer()
+        .unwrap();
+        fs::remove_file(private_key_path).unwrap();
+
+        let (port, received) = spawn_smtp_server();
+        let repository = SmtpMailRepository::new(
+            MessageBuilder::new()
+                .from("Plannify <noreply@plannify.be>".parse().unwrap())
+                .reply_to("contact@plannify.be".parse().unwrap()),
+            SmtpTransport::builder_dangerous("127.0.0.1")
+                .port(port)
+                .build(),
+            MailRenderer::load(TEMPLATES_DIR).unwrap(),
+            "https://app.plannify.be".to_string(),
+            MailUnsubscribeConfig {
+                api_url: "https://api.plannify.be".to_string(),
+                secret: "secret".to_string(),
+            },
+            false,
+            dkim,
+        );
+
+        repository
+            .send_driver_workday_reminder_email(
+                make_driver(),
+                vec![NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()],
+            )
+            .await
+            .unwrap();
+        let message = received.recv_timeout(Duration::from_secs(10)).unwrap();
+        let (headers, body) = parse_message(&message);
+
+        assert_eq!(header(&headers, "To").unwrap().trim(), "john@example.be");
+        assert_eq!(
+            header(&headers, "Subject").unwrap().trim(),
+            "Some workdays are missing from your Plannify"
+        );
+        assert!(
+            header(&headers, "List-Unsubscribe")
+                .unwrap()
+                .contains("https://api.plannify.be")
+        );
+        assert_eq!(
+            header(&headers, "List-Unsubscribe-Post").unwrap().trim(),
+            "List-Unsubscribe=One-Click"
+        );
+
+        let signature_header = header(&headers, "DKIM-Signature").unwrap();
+        let tags = signature_header
+            .replace("\r\n", "")
+            .split(';')
+            .filter_map(|tag| {
+                let (name, value) = tag.split_once('=')?;
+                Some((
+                    name.trim().to_string(),
+                    value.split_whitespace().collect::<String>(),
+                ))
+            })
+            .collect::<Vec<(String, String)>>();
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|(tag_name, _)| tag_name == name)
+                .map(|(_, value)| value.clone())
+                .unwrap()
         };
 
-        self.send_email(to, subject, html_body)
+        assert_eq!(tag("a"), "ed25519-sha256");
+        assert_eq!(tag("c"), "relaxed/relaxed");
+        assert_eq!(tag("d"), "plannify.be");
+        assert_eq!(tag("s"), "mail");
+        let signed_headers = tag("h");
+        for name in [
+            "from",
+            "to",
+            "subject",
+            "list-unsubscribe",
+            "list-unsubscribe-post",
+        ] {
+            assert!(
+                signed_headers.split(':').any(|header| header == name),
+                "{}",
+                name
+            );
+        }
+
+        let body_hash = STANDARD.encode(Sha256::digest(relaxed_body(&body)));
+        assert_eq!(tag("bh"), body_hash);
+
+        let mut signed = signed_headers
+            .split(':')
+            .filter_map(|name| header(&headers, name).map(|value| relaxed_header(name, value)))
+            .map(|header| format!("{}\r\n", header))
+            .collect::<String>();
+        let unsigned_signature_header = signature_header
+            .rsplit_once("b=")
+            .map(|(tags, _)| format!("{}b=", tags))
+            .unwrap();
+        signed.push_str(&relaxed_header(
+            "DKIM-Signature",
+            &unsigned_signature_header,
+        ));
+
+        let signature = Signature::from_slice(&STANDARD.decode(tag("b")).unwrap()).unwrap();
+        signing_key
+            .verifying_key()
+            .verify(&Sha256::digest(signed.as_bytes()), &signature)
+            .expect("DKIM signature should verify");
     }
 }
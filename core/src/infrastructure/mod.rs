@@ -3,6 +3,7 @@ pub mod driver;
 pub mod employee;
 pub mod health;
 pub mod mail;
+pub mod notification;
 pub mod storage;
 pub mod update;
 pub mod workday;
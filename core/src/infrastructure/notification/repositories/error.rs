@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use crate::infrastructure::mail::repositories::error::MailError;
+
+#[derive(Error, Debug, Clone)]
+pub enum NotificationError {
+    #[error("Internal server error")]
+    Internal,
+
+    #[error("Database error")]
+    DatabaseError,
+
+    #[error("Push subscription not found")]
+    PushSubscriptionNotFound,
+
+    #[error("Invalid push subscription")]
+    InvalidPushSubscription,
+
+    #[error("Driver has no push subscription")]
+    NoPushSubscription,
+
+    #[error("Web push is not configured")]
+    PushNotConfigured,
+
+    #[error("Cannot send push notification")]
+    CannotSendPushNotification,
+
+    #[error("Cannot send SMS")]
+    CannotSendSms,
+
+    #[error(transparent)]
+    Mail(#[from] MailError),
+}
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod postgres;
+pub mod sms;
+pub mod web_push;
@@ -0,0 +1,147 @@
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    domain::notification::{
+        entities::{CreatePushSubscriptionRequest, PushSubscriptionRow},
+        port::NotificationDatabaseRepository,
+    },
+    infrastructure::notification::repositories::error::NotificationError,
+};
+
+#[derive(Clone)]
+pub struct PostgresNotificationRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl NotificationDatabaseRepository for PostgresNotificationRepository {
+    #[tracing::instrument(
+        name = "db.push_subscriptions.get_by_driver",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
+    )]
+    async fn get_push_subscriptions(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<PushSubscriptionRow>, NotificationError> {
+        sqlx::query_as::<_, PushSubscriptionRow>(
+            r#"
+            SELECT pk_push_subscription_id, fk_driver_id, endpoint, p256dh, auth, created_at
+            FROM push_subscriptions
+            WHERE fk_driver_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(driver_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get push subscriptions: {:?}", e);
+            NotificationError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.push_subscriptions.create",
+        skip(self, request),
+        fields(db.system = "postgresql", db.operation = "INSERT", driver_id = %driver_id)
+    )]
+    async fn create_push_subscription(
+        &self,
+        driver_id: Uuid,
+        request: CreatePushSubscriptionRequest,
+    ) -> Result<PushSubscriptionRow, NotificationError> {
+        sqlx::query_as::<_, PushSubscriptionRow>(
+            r#"
+            INSERT INTO push_subscriptions (fk_driver_id, endpoint, p256dh, auth)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (endpoint) DO UPDATE
+            SET fk_driver_id = EXCLUDED.fk_driver_id,
+                p256dh = EXCLUDED.p256dh,
+                auth = EXCLUDED.auth
+            RETURNING pk_push_subscription_id, fk_driver_id, endpoint, p256dh, auth, created_at
+            "#,
+        )
+        .bind(driver_id)
+        .bind(request.endpoint)
+        .bind(request.keys.p256dh)
+        .bind(request.keys.auth)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to create push subscription: {:?}", e);
+            NotificationError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.push_subscriptions.delete",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "DELETE",
+            driver_id = %driver_id,
+            push_subscription_id = %push_subscription_id,
+        )
+    )]
+    async fn delete_push_subscription(
+        &self,
+        driver_id: Uuid,
+        push_subscription_id: Uuid,
+    ) -> Result<(), NotificationError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM push_subscriptions
+            WHERE pk_push_subscription_id = $1
+            AND fk_driver_id = $2
+            "#,
+        )
+        .bind(push_subscription_id)
+        .bind(driver_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete push subscription: {:?}", e);
+            NotificationError::DatabaseError
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(NotificationError::PushSubscriptionNotFound);
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "db.push_subscriptions.delete_by_endpoint",
+        skip(self, endpoint),
+        fields(db.system = "postgresql", db.operation = "DELETE")
+    )]
+    async fn delete_push_subscription_by_endpoint(
+        &self,
+        endpoint: String,
+    ) -> Result<(), NotificationError> {
+        sqlx::query(
+            r#"
+            DELETE FROM push_subscriptions
+            WHERE endpoint = $1
+            "#,
+        )
+        .bind(endpoint)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete push subscription by endpoint: {:?}", e);
+            NotificationError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+}
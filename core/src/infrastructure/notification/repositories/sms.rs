@@ -0,0 +1,199 @@
+use std::{fs::OpenOptions, io::Write, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use reqwest::Client;
+use serde_json::json;
+use tracing::{error, info};
+
+use crate::{
+    domain::{common::config::SmsConfig, notification::port::SmsRepository},
+    infrastructure::notification::repositories::error::NotificationError,
+};
+
+/// Gateway accepting messages as `POST {"from", "to", "text"}` with a bearer token.
+#[derive(Clone)]
+pub struct HttpSmsRepository {
+    client: Client,
+    url: String,
+    api_key: String,
+    sender: String,
+}
+
+impl HttpSmsRepository {
+    pub fn new(url: String, api_key: String, sender: String) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url,
+            api_key,
+            sender,
+        }
+    }
+}
+
+impl SmsRepository for HttpSmsRepository {
+    #[tracing::instrument(name = "sms.http.send_sms", skip_all)]
+    async fn send_sms(&self, phone_number: &str, text: &str) -> Result<(), NotificationError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .bearer_auth(&self.api_key)
+            .json(&json!({
+                "from": self.sender,
+                "to": phone_number,
+                "text": text,
+            }))
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Could not reach SMS gateway: {:?}", e);
+                NotificationError::CannotSendSms
+            })?;
+
+        if !response.status().is_success() {
+            error!("SMS gateway refused the message: {}", response.status());
+            return Err(NotificationError::CannotSendSms);
+        }
+
+        Ok(())
+    }
+}
+
+/// Stand-in for local development and tests, appending the messages to a file, or to the
+/// logs when no file is given.
+#[derive(Clone)]
+pub struct LogSmsRepository {
+    path: Option<PathBuf>,
+}
+
+impl LogSmsRepository {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
+
+impl SmsRepository for LogSmsRepository {
+    async fn send_sms(&self, phone_number: &str, text: &str) -> Result<(), NotificationError> {
+        let Some(path) = &self.path else {
+            info!(to = %phone_number, "SMS not sent (no gateway configured): {}", text);
+            return Ok(());
+        };
+
+        let line = format!("{}\t{}\t{}\n", Utc::now().to_rfc3339(), phone_number, text);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                error!("Could not open SMS log file {}: {:?}", path.display(), e);
+                NotificationError::CannotSendSms
+            })?;
+        file.write_all(line.as_bytes()).map_err(|e| {
+            error!("Could not write SMS log file {}: {:?}", path.display(), e);
+            NotificationError::CannotSendSms
+        })
+    }
+}
+
+/// Provider picked from the configuration.
+#[derive(Clone)]
+pub enum SmsGatewayRepository {
+    Http(HttpSmsRepository),
+    Log(LogSmsRepository),
+}
+
+impl SmsGatewayRepository {
+    pub fn new(config: &SmsConfig) -> Self {
+        match &config.gateway_url {
+            Some(url) => Self::Http(HttpSmsRepository::new(
+                url.clone(),
+                config.gateway_api_key.clone(),
+                config.sender.clone(),
+            )),
+            None => Self::Log(LogSmsRepository::new(config.log_path.clone())),
+        }
+    }
+}
+
+impl SmsRepository for SmsGatewayRepository {
+    async fn send_sms(&self, phone_number: &str, text: &str) -> Result<(), NotificationError> {
+        match self {
+            Self::Http(repository) => repository.send_sms(phone_number, text).await,
+            Self::Log(repository) => repository.send_sms(phone_number, text).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HttpSmsRepository, LogSmsRepository};
+    use uuid::Uuid;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, header, method, path},
+    };
+
+    use crate::{
+        domain::notification::port::SmsRepository,
+        infrastructure::notification::repositories::error::NotificationError,
+    };
+
+    #[tokio::test]
+    async fn test_http_gateway_receives_message() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sms"))
+            .and(header("authorization", "Bearer api-key"))
+            .and(body_json(serde_json::json!({
+                "from": "Plannify",
+                "to": "+32470123456",
+                "text": "Hello",
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let repository = HttpSmsRepository::new(
+            format!("{}/sms", server.uri()),
+            "api-key".to_string(),
+            "Plannify".to_string(),
+        );
+
+        repository.send_sms("+32470123456", "Hello").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_http_gateway_error_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let repository =
+            HttpSmsRepository::new(server.uri(), "api-key".to_string(), "Plannify".to_string());
+
+        let result = repository.send_sms("+32470123456", "Hello").await;
+
+        assert!(matches!(result, Err(NotificationError::CannotSendSms)));
+    }
+
+    #[tokio::test]
+    async fn test_log_stand_in_appends_to_file() {
+        let path = std::env::temp_dir().join(format!("sms-{}.log", Uuid::new_v4()));
+        let repository = LogSmsRepository::new(Some(path.clone()));
+
+        repository.send_sms("+32470123456", "First").await.unwrap();
+        repository.send_sms("+32470123456", "Second").await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("\t+32470123456\tFirst"));
+        assert!(lines[1].ends_with("\t+32470123456\tSecond"));
+    }
+}
@@ -0,0 +1,692 @@
+use std::{net::SocketAddr, time::Duration};
+
+use aes_gcm::{Aes128Gcm, KeyInit, Nonce, aead::Aead};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use hkdf::Hkdf;
+use p256::{
+    PublicKey, SecretKey,
+    ecdh::diffie_hellman,
+    ecdsa::{Signature, SigningKey, signature::Signer},
+    elliptic_curve::{
+        rand_core::{OsRng, RngCore},
+        sec1::ToEncodedPoint,
+    },
+};
+use reqwest::{
+    Client, StatusCode, Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE},
+    redirect,
+};
+use serde_json::json;
+use sha2::Sha256;
+use tracing::{error, warn};
+
+use crate::{
+    domain::{
+        common::{CoreError, config::WebPushConfig},
+        driver::entities::DriverRow,
+        mail::entities::NotificationChannel,
+        notification::{
+            entities::{
+                Notification, PushSubscriptionRow, is_public_address, is_public_push_endpoint,
+            },
+            port::{NotificationChannelRepository, NotificationDatabaseRepository},
+        },
+    },
+    infrastructure::notification::repositories::error::NotificationError,
+};
+
+/// Time the push service keeps a message for a device that is offline.
+const PUSH_TTL_SECONDS: u32 = 24 * 60 * 60;
+
+/// Validity of the VAPID tokens, the push services refusing more than 24 hours.
+const VAPID_TOKEN_TTL_SECONDS: i64 = 12 * 60 * 60;
+
+/// Single record size of the aes128gcm content coding (RFC 8188).
+const RECORD_SIZE: u32 = 4096;
+
+/// Tag appended by AES-GCM to the encrypted record.
+const TAG_LENGTH: usize = 16;
+
+enum PushOutcome {
+    Delivered,
+    /// The push service no longer knows the subscription, which has been removed.
+    Gone,
+}
+
+/// Resolves the push service hosts to their public addresses only, so that an endpoint chosen by a
+/// driver cannot make the server post to its own network, even once its DNS record changes.
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| is_public_address(address.ip()))
+                .collect();
+            if addresses.is_empty() {
+                warn!(host = %name.as_str(), "Push service host has no public address");
+                return Err("push service host has no public address".into());
+            }
+
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Web Push (RFC 8030) channel, sending to every browser subscription of the driver.
+#[derive(Clone)]
+pub struct WebPushRepository<S> {
+    client: Client,
+    allow_insecure_endpoints: bool,
+    signing_key: Option<SigningKey>,
+    vapid_subject: String,
+    app_url: String,
+    subscriptions: S,
+}
+
+impl<S> WebPushRepository<S>
+where
+    S: NotificationDatabaseRepository,
+{
+    /// Fails rather than falling back to a client without the public address resolver and the
+    /// redirect policy.
+    pub fn new(
+        config: WebPushConfig,
+        app_url: String,
+        subscriptions: S,
+    ) -> Result<Self, CoreError> {
+        let signing_key = config.signing_key();
+        if config.vapid_private_key.is_some() && signing_key.is_none() {
+            error!("Invalid VAPID private key, web push notifications are disabled");
+        }
+
+        let mut client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .redirect(redirect::Policy::none());
+        if !config.allow_insecure_endpoints {
+            client = client.dns_resolver(PublicAddressResolver);
+        }
+
+        let client = client.build().map_err(|e| {
+            CoreError::ServiceUnavailable(format!("Failed to build the web push client: {}", e))
+        })?;
+
+        Ok(Self {
+            client,
+            allow_insecure_endpoints: config.allow_insecure_endpoints,
+            signing_key,
+            vapid_subject: config.vapid_subject,
+            app_url,
+            subscriptions,
+        })
+    }
+
+    async fn push(
+        &self,
+        signing_key: &SigningKey,
+        subscription: &PushSubscriptionRow,
+        payload: &[u8],
+    ) -> Result<PushOutcome, NotificationError> {
+        // Also covers the subscriptions stored before endpoints were checked
+        if !is_public_push_endpoint(&subscription.endpoint, self.allow_insecure_endpoints) {
+            return Err(NotificationError::InvalidPushSubscription);
+        }
+
+        let (ua_public, auth_secret) = subscription
+            .keys()
+            .decode()
+            .ok_or(NotificationError::InvalidPushSubscription)?;
+
+        let body = encrypt_payload(&ua_public, &auth_secret, payload)?;
+        let authorization =
+            vapid_authorization(signing_key, &self.vapid_subject, &subscription.endpoint)?;
+
+        let response = self
+            .client
+            .post(&subscription.endpoint)
+            .header("TTL", PUSH_TTL_SECONDS.to_string())
+            .header(CONTENT_ENCODING, "aes128gcm")
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(AUTHORIZATION, authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| {
+                error!("Could not reach push service: {:?}", e);
+                NotificationError::CannotSendPushNotification
+            })?;
+
+        match response.status() {
+            status if status.is_success() => Ok(PushOutcome::Delivered),
+            StatusCode::NOT_FOUND | StatusCode::GONE => {
+                self.subscriptions
+                    .delete_push_subscription_by_endpoint(subscription.endpoint.clone())
+                    .await?;
+                Ok(PushOutcome::Gone)
+            }
+            status => {
+                error!("Push service refused the message: {}", status);
+                Err(NotificationError::CannotSendPushNotification)
+            }
+        }
+    }
+}
+
+impl<S> NotificationChannelRepository for WebPushRepository<S>
+where
+    S: NotificationDatabaseRepository,
+{
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::PUSH
+    }
+
+    #[tracing::instrument(
+        name = "web_push.send_notification",
+        skip(self, driver, notification),
+        fields(driver_id = %driver.pk_driver_id, subscriptions = tracing::field::Empty)
+    )]
+    async fn send_notification(
+        &self,
+        driver: &DriverRow,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        let Some(signing_key) = &self.signing_key else {
+            return Err(NotificationError::PushNotConfigured);
+        };
+
+        let subscriptions = self
+            .subscriptions
+            .get_push_subscriptions(driver.pk_driver_id)
+            .await?;
+        tracing::Span::current().record("subscriptions", subscriptions.len());
+
+        if subscriptions.is_empty() {
+            return Err(NotificationError::NoPushSubscription);
+        }
+
+        let message = notification.to_push_message(&driver.language, &self.app_url);
+        let payload = serde_json::to_vec(&message).map_err(|e| {
+            error!("Could not serialize push message: {:?}", e);
+            NotificationError::Internal
+        })?;
+
+        let mut delivered = 0;
+        let mut gone = 0;
+        for subscription in &subscriptions {
+            match self.push(signing_key, subscription, &payload).await {
+                Ok(PushOutcome::Delivered) => delivered += 1,
+                Ok(PushOutcome::Gone) => gone += 1,
+                Err(e) => warn!(
+                    push_subscription_id = %subscription.pk_push_subscription_id,
+                    error = ?e,
+                    "Could not send push notification"
+                ),
+            }
+        }
+
+        if delivered > 0 {
+            Ok(())
+        } else if gone == subscriptions.len() {
+            Err(NotificationError::NoPushSubscription)
+        } else {
+            Err(NotificationError::CannotSendPushNotification)
+        }
+    }
+}
+
+/// `Authorization` header of RFC 8292, a JWT signed for the origin of the push service.
+fn vapid_authorization(
+    signing_key: &SigningKey,
+    subject: &str,
+    endpoint: &str,
+) -> Result<String, NotificationError> {
+    let audience = Url::parse(endpoint)
+        .map_err(|_| NotificationError::InvalidPushSubscription)?
+        .origin()
+        .ascii_serialization();
+
+    let header = URL_SAFE_NO_PAD.encode(json!({ "typ": "JWT", "alg": "ES256" }).to_string());
+    let claims = URL_SAFE_NO_PAD.encode(
+        json!({
+            "aud": audience,
+            "exp": Utc::now().timestamp() + VAPID_TOKEN_TTL_SECONDS,
+            "sub": subject,
+        })
+        .to_string(),
+    );
+    let signing_input = format!("{}.{}", header, claims);
+    let signature: Signature = signing_key.sign(signing_input.as_bytes());
+
+    let public_key = signing_key.verifying_key().to_encoded_point(false);
+
+    Ok(format!(
+        "vapid t={}.{}, k={}",
+        signing_input,
+        URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+        URL_SAFE_NO_PAD.encode(public_key.as_bytes())
+    ))
+}
+
+/// Encrypts the payload for the browser with the aes128gcm content coding of RFC 8291, in a
+/// single record preceded by its header.
+fn encrypt_payload(
+    ua_public: &PublicKey,
+    auth_secret: &[u8; 16],
+    payload: &[u8],
+) -> Result<Vec<u8>, NotificationError> {
+    if payload.len() + 1 + TAG_LENGTH > RECORD_SIZE as usize {
+        error!("Push payload of {} bytes is too large", payload.len());
+        return Err(NotificationError::Internal);
+    }
+
+    let as_secret = SecretKey::random(&mut OsRng);
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    encrypt_record(ua_public, auth_secret, &as_secret, &salt, payload)
+}
+
+/// The record and its header, encrypted with the key pair `as_secret` of the application server
+/// and `salt`, both new for every message.
+fn encrypt_record(
+    ua_public: &PublicKey,
+    auth_secret: &[u8; 16],
+    as_secret: &SecretKey,
+    salt: &[u8; 16],
+    payload: &[u8],
+) -> Result<Vec<u8>, NotificationError> {
+    let as_public = as_secret.public_key();
+    let shared_secret = diffie_hellman(as_secret.to_nonzero_scalar(), ua_public.as_affine());
+
+    let (content_key, nonce) = derive_content_key(
+        shared_secret.raw_secret_bytes(),
+        auth_secret,
+        ua_public,
+        &as_public,
+        salt,
+    )?;
+
+    // Padding delimiter of the last record, without any padding
+    let mut plaintext = payload.to_vec();
+    plaintext.push(0x02);
+
+    let ciphertext = Aes128Gcm::new(&content_key.into())
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|e| {
+            error!("Could not encrypt push payload: {:?}", e);
+            NotificationError::Internal
+        })?;
+
+    let as_public = as_public.to_encoded_point(false);
+    let mut body = Vec::with_capacity(21 + as_public.len() + ciphertext.len());
+    body.extend_from_slice(salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(as_public.as_bytes());
+    body.extend_from_slice(&ciphertext);
+
+    Ok(body)
+}
+
+/// Content encryption key and nonce of the record, from the ECDH secret shared with the browser.
+fn derive_content_key(
+    shared_secret: &[u8],
+    auth_secret: &[u8; 16],
+    ua_public: &PublicKey,
+    as_public: &PublicKey,
+    salt: &[u8; 16],
+) -> Result<([u8; 16], [u8; 12]), NotificationError> {
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(ua_public.to_encoded_point(false).as_bytes());
+    key_info.extend_from_slice(as_public.to_encoded_point(false).as_bytes());
+
+    let mut ikm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(auth_secret), shared_secret)
+        .expand(&key_info, &mut ikm)
+        .map_err(|_| NotificationError::Internal)?;
+
+    let hkdf = Hkdf::<Sha256>::new(Some(salt), &ikm);
+    let mut content_key = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    hkdf.expand(b"Content-Encoding: aes128gcm\0", &mut content_key)
+        .and_then(|_| hkdf.expand(b"Content-Encoding: nonce\0", &mut nonce))
+        .map_err(|_| NotificationError::Internal)?;
+
+    Ok((content_key, nonce))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PublicAddressResolver, WebPushRepository, derive_content_key, encrypt_record};
+    use aes_gcm::{Aes128Gcm, KeyInit, Nonce, aead::Aead};
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+    use chrono::Utc;
+    use p256::{
+        PublicKey, SecretKey,
+        ecdh::diffie_hellman,
+        ecdsa::{Signature, SigningKey, VerifyingKey, signature::Verifier},
+        elliptic_curve::{rand_core::OsRng, sec1::ToEncodedPoint},
+    };
+    use reqwest::dns::{Name, Resolve};
+    use uuid::Uuid;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use crate::{
+        domain::{
+            common::config::WebPushConfig,
+            driver::entities::DriverRow,
+            notification::{
+                entities::{
+                    CreatePushSubscriptionRequest, Notification, PushMessage, PushSubscriptionKeys,
+                    is_public_push_endpoint,
+                },
+                port::{
+                    MockNotificationDatabaseRepository, NotificationChannelRepository,
+                    NotificationDatabaseRepository,
+                },
+            },
+        },
+        infrastructure::notification::repositories::error::NotificationError,
+    };
+
+    struct Browser {
+        secret: SecretKey,
+        auth_secret: [u8; 16],
+    }
+
+    impl Browser {
+        fn new() -> Self {
+            Self {
+                secret: SecretKey::random(&mut OsRng),
+                auth_secret: *b"0123456789abcdef",
+            }
+        }
+
+        fn subscription(&self, endpoint: String) -> CreatePushSubscriptionRequest {
+            CreatePushSubscriptionRequest {
+                endpoint,
+                keys: PushSubscriptionKeys {
+                    p256dh: URL_SAFE_NO_PAD
+                        .encode(self.secret.public_key().to_encoded_point(false).as_bytes()),
+                    auth: URL_SAFE_NO_PAD.encode(self.auth_secret),
+                },
+            }
+        }
+
+        /// Reverses the aes128gcm record the way the browser does.
+        fn decrypt(&self, body: &[u8]) -> Vec<u8> {
+            let salt: [u8; 16] = body[..16].try_into().unwrap();
+            assert_eq!(u32::from_be_bytes(body[16..20].try_into().unwrap()), 4096);
+            let key_length = body[20] as usize;
+            let as_public = PublicKey::from_sec1_bytes(&body[21..21 + key_length]).unwrap();
+
+            let shared_secret =
+                diffie_hellman(self.secret.to_nonzero_scalar(), as_public.as_affine());
+            let (content_key, nonce) = derive_content_key(
+                shared_secret.raw_secret_bytes(),
+                &self.auth_secret,
+                &self.secret.public_key(),
+                &as_public,
+                &salt,
+            )
+            .unwrap();
+
+            let mut plaintext = Aes128Gcm::new(&content_key.into())
+                .decrypt(Nonce::from_slice(&nonce), &body[21 + key_length..])
+                .unwrap();
+            assert_eq!(plaintext.pop(), Some(0x02));
+            plaintext
+        }
+    }
+
+    #[test]
+    fn test_encrypt_record_matches_rfc_8291_example() {
+        // Appendix A of RFC 8291
+        let decode = |value: &str| URL_SAFE_NO_PAD.decode(value).unwrap();
+        let ua_public = PublicKey::from_sec1_bytes(&decode(
+            "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+        ))
+        .unwrap();
+        let as_secret =
+            SecretKey::from_slice(&decode("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw")).unwrap();
+        let auth_secret: [u8; 16] = decode("BTBZMqHH6r4Tts7J_aSIgg").try_into().unwrap();
+        let salt: [u8; 16] = decode("DGv6ra1nlYgDCS1FRnbzlw").try_into().unwrap();
+
+        let body = encrypt_record(
+            &ua_public,
+            &auth_secret,
+            &as_secret,
+            &salt,
+            b"When I grow up, I want to be a watermelon",
+        )
+        .unwrap();
+
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(body),
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN"
+        );
+    }
+
+    fn make_driver() -> DriverRow {
+        DriverRow {
+            pk_driver_id: Uuid::new_v4(),
+            firstname: "Test".to_string(),
+            lastname: "Driver".to_string(),
+            gender: None,
+            email: "test@example.be".to_string(),
+            password_hash: "hash".to_string(),
+            phone_number: None,
+            is_searchable: false,
+            allow_request_professional_agreement: false,
+            language: "en".to_string(),
+            rest_json: None,
+            created_at: Utc::now(),
+            verified_at: Some(Utc::now()),
+            last_login_at: None,
+            deactivated_at: None,
+            phone_verified_at: None,
+            version: 1,
+        }
+    }
+
+    fn make_repository(
+        signing_key: &SigningKey,
+        subscriptions: MockNotificationDatabaseRepository,
+    ) -> WebPushRepository<MockNotificationDatabaseRepository> {
+        WebPushRepository::new(
+            WebPushConfig {
+                vapid_private_key: Some(URL_SAFE_NO_PAD.encode(signing_key.to_bytes())),
+                vapid_subject: "mailto:contact@plannify.be".to_string(),
+                // The mock push service listens on http://127.0.0.1
+                allow_insecure_endpoints: true,
+            },
+            "https://app.plannify.be".to_string(),
+            subscriptions,
+        )
+        .unwrap()
+    }
+
+    fn make_notification() -> Notification {
+        Notification::DocumentsReady {
+            documents: vec![(1, 2026)],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_notification_encrypts_payload_for_subscription() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/push/browser"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let driver = make_driver();
+        let browser = Browser::new();
+        let subscriptions = MockNotificationDatabaseRepository::new();
+        subscriptions
+            .create_push_subscription(
+                driver.pk_driver_id,
+                browser.subscription(format!("{}/push/browser", server.uri())),
+            )
+            .await
+            .unwrap();
+
+        let signing_key = SigningKey::random(&mut OsRng);
+        let repository = make_repository(&signing_key, subscriptions);
+
+        repository
+            .send_notification(&driver, &make_notification())
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let request = &requests[0];
+        assert_eq!(request.headers["content-encoding"], "aes128gcm");
+        assert_eq!(request.headers["ttl"], "86400");
+
+        let message: PushMessage = serde_json::from_slice(&browser.decrypt(&request.body)).unwrap();
+        assert_eq!(
+            message,
+            make_notification().to_push_message("en", "https://app.plannify.be")
+        );
+
+        let authorization = request.headers["authorization"].to_str().unwrap();
+        let (token, key) = authorization
+            .strip_prefix("vapid t=")
+            .and_then(|v| v.split_once(", k="))
+            .unwrap();
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(&URL_SAFE_NO_PAD.decode(key).unwrap()).unwrap();
+        assert_eq!(&verifying_key, signing_key.verifying_key());
+
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        let signature = Signature::from_slice(&URL_SAFE_NO_PAD.decode(signature).unwrap()).unwrap();
+        verifying_key
+            .verify(signing_input.as_bytes(), &signature)
+            .unwrap();
+
+        let claims: serde_json::Value = serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(signing_input.split('.').nth(1).unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims["aud"], server.uri());
+        assert_eq!(claims["sub"], "mailto:contact@plannify.be");
+    }
+
+    #[tokio::test]
+    async fn test_expired_subscription_is_removed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(410))
+            .mount(&server)
+            .await;
+
+        let driver = make_driver();
+        let subscriptions = MockNotificationDatabaseRepository::new();
+        subscriptions
+            .create_push_subscription(
+                driver.pk_driver_id,
+                Browser::new().subscription(format!("{}/push/expired", server.uri())),
+            )
+            .await
+            .unwrap();
+
+        let repository = make_repository(&SigningKey::random(&mut OsRng), subscriptions.clone());
+
+        let result = repository
+            .send_notification(&driver, &make_notification())
+            .await;
+
+        assert!(matches!(result, Err(NotificationError::NoPushSubscription)));
+        assert!(
+            subscriptions
+                .get_push_subscriptions(driver.pk_driver_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_private_endpoint_not_posted_to() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let driver = make_driver();
+        let subscriptions = MockNotificationDatabaseRepository::new();
+        subscriptions
+            .create_push_subscription(
+                driver.pk_driver_id,
+                Browser::new().subscription(format!("{}/push/browser", server.uri())),
+            )
+            .await
+            .unwrap();
+
+        let signing_key = SigningKey::random(&mut OsRng);
+        let repository = WebPushRepository::new(
+            WebPushConfig {
+                vapid_private_key: Some(URL_SAFE_NO_PAD.encode(signing_key.to_bytes())),
+                vapid_subject: "mailto:contact@plannify.be".to_string(),
+                allow_insecure_endpoints: false,
+            },
+            "https://app.plannify.be".to_string(),
+            subscriptions,
+        )
+        .unwrap();
+
+        let result = repository
+            .send_notification(&driver, &make_notification())
+            .await;
+        assert!(matches!(
+            result,
+            Err(NotificationError::CannotSendPushNotification)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_push_endpoint_must_be_public_https() {
+        for endpoint in [
+            "https://fcm.googleapis.com/fcm/send/abc",
+            "https://updates.push.services.mozilla.com/wpush/v2/abc",
+        ] {
+            assert!(is_public_push_endpoint(endpoint, false), "{}", endpoint);
+        }
+        for endpoint in [
+            "http://fcm.googleapis.com/fcm/send/abc",
+            "https://169.254.169.254/latest/meta-data",
+            "https://127.0.0.1/push",
+            "https://[::1]/push",
+            "https://10.0.0.2/push",
+            "https://localhost/push",
+            "https://redis/push",
+            "https://metadata.google.internal/push",
+            "ftp://push.example.com/push",
+            "not a url",
+        ] {
+            assert!(!is_public_push_endpoint(endpoint, false), "{}", endpoint);
+        }
+        assert!(is_public_push_endpoint("http://127.0.0.1:8080/push", true));
+
+        assert!(
+            PublicAddressResolver
+                .resolve("localhost".parse::<Name>().unwrap())
+                .await
+                .is_err()
+        );
+    }
+}
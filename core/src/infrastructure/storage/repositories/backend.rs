@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::{
+    domain::storage::{
+        entities::{ByteRange, StorageObject},
+        port::StorageRepository,
+    },
+    infrastructure::storage::repositories::{
+        error::StorageError, local_fs::LocalFsStorageRepository, s3::S3StorageRepository,
+    },
+};
+
+/// The storage backend selected by `StorageConfig::backend`.
+#[derive(Clone)]
+pub enum StorageBackendRepository {
+    S3(S3StorageRepository),
+    LocalFs(LocalFsStorageRepository),
+}
+
+impl StorageBackendRepository {
+    /// Whether the backend can be reached, for the health checks.
+    pub async fn is_reachable(&self) -> bool {
+        match self {
+            StorageBackendRepository::S3(repository) => repository.is_reachable().await,
+            StorageBackendRepository::LocalFs(repository) => repository.is_reachable().await,
+        }
+    }
+}
+
+impl StorageRepository for StorageBackendRepository {
+    async fn upload(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), StorageError> {
+        match self {
+            StorageBackendRepository::S3(repository) => {
+                repository.upload(key, data, content_type).await
+            }
+            StorageBackendRepository::LocalFs(repository) => {
+                repository.upload(key, data, content_type).await
+            }
+        }
+    }
+
+    async fn download(&self, key: &str) -> Result<Bytes, StorageError> {
+        match self {
+            StorageBackendRepository::S3(repository) => repository.download(key).await,
+            StorageBackendRepository::LocalFs(repository) => repository.download(key).await,
+        }
+    }
+
+    async fn stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<StorageObject, StorageError> {
+        match self {
+            StorageBackendRepository::S3(repository) => repository.stream(key, range).await,
+            StorageBackendRepository::LocalFs(repository) => repository.stream(key, range).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        match self {
+            StorageBackendRepository::S3(repository) => repository.delete(key).await,
+            StorageBackendRepository::LocalFs(repository) => repository.delete(key).await,
+        }
+    }
+
+    async fn generate_presigned_url(
+        &self,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<String, StorageError> {
+        match self {
+            StorageBackendRepository::S3(repository) => {
+                repository.generate_presigned_url(key, expires_in).await
+            }
+            StorageBackendRepository::LocalFs(repository) => {
+                repository.generate_presigned_url(key, expires_in).await
+            }
+        }
+    }
+
+    async fn list_objects_page(
+        &self,
+        prefix: Option<&str>,
+        continuation_token: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>), StorageError> {
+        match self {
+            StorageBackendRepository::S3(repository) => {
+                repository
+                    .list_objects_page(prefix, continuation_token)
+                    .await
+            }
+            StorageBackendRepository::LocalFs(repository) => {
+                repository
+                    .list_objects_page(prefix, continuation_token)
+                    .await
+            }
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use std::time::Duration;
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use bytes::Bytes;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    domain::storage::{
+        entities::{ByteRange, DataKey, StorageObject},
+        port::{DataKeyRepository, KeyManagementRepository, StorageRepository},
+    },
+    infrastructure::storage::repositories::error::StorageError,
+};
+
+/// Prefix of the encrypted objects, telling them apart from the ones stored before.
+const MAGIC: &[u8] = b"PLE1";
+const NONCE_LENGTH: usize = 12;
+
+/// Encrypts the objects stored under `drivers/{id}/` with the data key of the driver
+/// (AES-256-GCM, the object key as associated data). Deleting the data key makes every object
+/// of the driver unreadable, wherever copies of them remain.
+///
+/// Objects outside of a driver prefix, and all of them when no master key is configured, are
+/// stored as is. Objects stored before the encryption are still read as is.
+#[derive(Clone)]
+pub struct EncryptedStorageRepository<S, D, K>
+where
+    S: StorageRepository,
+    D: DataKeyRepository,
+    K: KeyManagementRepository,
+{
+    inner: S,
+    data_keys: D,
+    kms: K,
+}
+
+impl<S, D, K> EncryptedStorageRepository<S, D, K>
+where
+    S: StorageRepository,
+    D: DataKeyRepository,
+    K: KeyManagementRepository,
+{
+    pub fn new(inner: S, data_keys: D, kms: K) -> Self {
+        Self {
+            inner,
+            data_keys,
+            kms,
+        }
+    }
+
+    async fn encryption_key(&self, driver_id: Uuid) -> Result<DataKey, StorageError> {
+        if let Some(wrapped) = self.data_keys.get_data_key(driver_id).await? {
+            return self.kms.unwrap_key(&wrapped).await;
+        }
+
+        let data_key = DataKey::generate();
+        let wrapped = self.kms.wrap_key(&data_key).await?;
+        let stored = self.data_keys.create_data_key(driver_id, wrapped).await?;
+        // Another upload may have created the key of the driver in the meantime.
+        self.kms.unwrap_key(&stored).await
+    }
+
+    async fn decryption_key(&self, driver_id: Uuid) -> Result<DataKey, StorageError> {
+        let wrapped = self
+            .data_keys
+            .get_data_key(driver_id)
+            .await?
+            .ok_or(StorageError::DataKeyNotFound)?;
+        self.kms.unwrap_key(&wrapped).await
+    }
+
+    async fn decrypt(&self, key: &str, data: Bytes) -> Result<Bytes, StorageError> {
+        if !data.starts_with(MAGIC) || data.len() < MAGIC.len() + NONCE_LENGTH {
+            return Ok(data);
+        }
+        let Some(driver_id) = driver_id(key) else {
+            return Ok(data);
+        };
+
+        let data_key = self.decryption_key(driver_id).await?;
+        let (nonce, ciphertext) = data[MAGIC.len()..].split_at(NONCE_LENGTH);
+        let plaintext = cipher(&data_key)
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: key.as_bytes(),
+                },
+            )
+            .map_err(|e| {
+                error!(key = %key, "Could not decrypt object: {:?}", e);
+                StorageError::EncryptionError
+            })?;
+
+        Ok(Bytes::from(plaintext))
+    }
+}
+
+/// The driver owning an object stored under `drivers/{id}/`.
+fn driver_id(key: &str) -> Option<Uuid> {
+    let mut segments = key.split('/');
+    match (segments.next(), segments.next()) {
+        (Some("drivers"), Some(id)) => Uuid::parse_str(id).ok(),
+        _ => None,
+    }
+}
+
+fn cipher(data_key: &DataKey) -> Aes256Gcm {
+    Aes256Gcm::new(data_key.as_bytes().into())
+}
+
+impl<S, D, K> StorageRepository for EncryptedStorageRepository<S, D, K>
+where
+    S: StorageRepository,
+    D: DataKeyRepository,
+    K: KeyManagementRepository,
+{
+    async fn upload(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), StorageError> {
+        let driver_id = match driver_id(key) {
+            Some(driver_id) if self.kms.current_key_id().is_some() => driver_id,
+            _ => return self.inner.upload(key, data, content_type).await,
+        };
+
+        let data_key = self.encryption_key(driver_id).await?;
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher(&data_key)
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &data,
+                    aad: key.as_bytes(),
+                },
+            )
+            .map_err(|e| {
+                error!(key = %key, "Could not encrypt object: {:?}", e);
+                StorageError::EncryptionError
+            })?;
+
+        let encrypted = [MAGIC, nonce.as_slice(), &ciphertext].concat();
+        self.inner
+            .upload(key, Bytes::from(encrypted), content_type)
+            .await
+    }
+
+    async fn download(&self, key: &str) -> Result<Bytes, StorageError> {
+        let data = self.inner.download(key).await?;
+        self.decrypt(key, data).await
+    }
+
+    /// Encrypted objects are authenticated as a whole, so they are read to the end before the
+    /// range is taken out of them.
+    async fn stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<StorageObject, StorageError> {
+        if driver_id(key).is_none() {
+            return self.inner.stream(key, range).await;
+        }
+
+        let object = self.inner.stream(key, None).await?;
+        let e_tag = object.e_tag.clone();
+        let data = self.decrypt(key, object.bytes().await?).await?;
+        StorageObject::from_bytes(data, range, e_tag)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.inner.delete(key).await
+    }
+
+    async fn generate_presigned_url(
+        &self,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<String, StorageError> {
+        self.inner.generate_presigned_url(key, expires_in).await
+    }
+
+    async fn list_objects_page(
+        &self,
+        prefix: Option<&str>,
+        continuation_token: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>), StorageError> {
+        self.inner
+            .list_objects_page(prefix, continuation_token)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use uuid::Uuid;
+
+    use super::{EncryptedStorageRepository, MAGIC};
+    use crate::{
+        domain::{
+            common::config::StorageEncryptionConfig,
+            storage::{
+                entities::ByteRange,
+                port::{
+                    DataKeyRepository, MockDataKeyRepository, MockStorageRepository,
+                    StorageRepository,
+                },
+            },
+        },
+        infrastructure::storage::repositories::{
+            error::StorageError, local_kms::LocalKeyManagementRepository,
+        },
+    };
+
+    const MASTER_KEY: &str = "2026:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+
+    fn repository(
+        master_keys: &[&str],
+    ) -> (
+        EncryptedStorageRepository<
+            MockStorageRepository,
+            MockDataKeyRepository,
+            LocalKeyManagementRepository,
+        >,
+        MockStorageRepository,
+        MockDataKeyRepository,
+    ) {
+        let inner = MockStorageRepository::new();
+        let data_keys = MockDataKeyRepository::new();
+        let kms = LocalKeyManagementRepository::new(&StorageEncryptionConfig {
+            master_keys: master_keys.iter().map(|k| k.to_string()).collect(),
+        })
+        .unwrap();
+        (
+            EncryptedStorageRepository::new(inner.clone(), data_keys.clone(), kms),
+            inner,
+            data_keys,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_driver_objects_encrypted_at_rest() {
+        let (repository, inner, data_keys) = repository(&[MASTER_KEY]);
+        let driver_id = Uuid::new_v4();
+        let key = format!(
+            "drivers/{}/workdays/monthly-reports/workdays-2026-09.pdf",
+            driver_id
+        );
+        let pdf = Bytes::from_static(b"%PDF-1.7 workdays of september");
+
+        repository
+            .upload(&key, pdf.clone(), "application/pdf")
+            .await
+            .unwrap();
+
+        let stored = inner.download(&key).await.unwrap();
+        assert!(stored.starts_with(MAGIC));
+        assert!(!stored.windows(9).any(|w| w == b"september"));
+        assert!(data_keys.get_data_key(driver_id).await.unwrap().is_some());
+
+        assert_eq!(repository.download(&key).await.unwrap(), pdf);
+        let range = repository
+            .stream(
+                &key,
+                Some(ByteRange::From {
+                    start: 0,
+                    end: Some(7),
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            range.bytes().await.unwrap(),
+            Bytes::from_static(b"%PDF-1.7")
+        );
+
+        // An object copied under another key does not decrypt.
+        let other = format!("drivers/{}/mails/monthly-2026-09.pdf", driver_id);
+        inner
+            .upload(&other, stored, "application/pdf")
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.download(&other).await.unwrap_err(),
+            StorageError::EncryptionError
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deleted_data_key_shreds_driver_objects() {
+        let (repository, _, data_keys) = repository(&[MASTER_KEY]);
+        let driver_id = Uuid::new_v4();
+        let key = format!("drivers/{}/mails/monthly-2026-09.pdf", driver_id);
+
+        repository
+            .upload(&key, Bytes::from_static(b"%PDF"), "application/pdf")
+            .await
+            .unwrap();
+        assert!(data_keys.delete_data_key(driver_id).await.unwrap());
+
+        assert_eq!(
+            repository.download(&key).await.unwrap_err(),
+            StorageError::DataKeyNotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_objects_passed_through() {
+        let (repository, inner, data_keys) = repository(&[MASTER_KEY]);
+        let driver_id = Uuid::new_v4();
+        let legacy = format!("drivers/{}/mails/monthly-2025-01.pdf", driver_id);
+        inner
+            .upload(
+                &legacy,
+                Bytes::from_static(b"%PDF legacy"),
+                "application/pdf",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.download(&legacy).await.unwrap(),
+            Bytes::from_static(b"%PDF legacy")
+        );
+
+        repository
+            .upload("exports/drivers.csv", Bytes::from_static(b"id"), "text/csv")
+            .await
+            .unwrap();
+        assert_eq!(
+            inner.download("exports/drivers.csv").await.unwrap(),
+            Bytes::from_static(b"id")
+        );
+
+        let (disabled, inner, data_keys_disabled) = self::repository(&[]);
+        disabled
+            .upload(&legacy, Bytes::from_static(b"%PDF"), "application/pdf")
+            .await
+            .unwrap();
+        assert_eq!(
+            inner.download(&legacy).await.unwrap(),
+            Bytes::from_static(b"%PDF")
+        );
+        assert!(
+            data_keys_disabled
+                .get_data_key(driver_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(data_keys.get_data_key(driver_id).await.unwrap().is_none());
+    }
+}
@@ -5,21 +5,36 @@ pub enum StorageError {
     #[error("Object not found")]
     ObjectNotFound,
 
+    #[error("Invalid object key")]
+    InvalidKey,
+
     #[error("Failed to upload object: {0}")]
     UploadError(String),
 
     #[error("Failed to download object")]
     DownloadError,
 
+    #[error("Requested range lies outside the object")]
+    RangeNotSatisfiable,
+
     #[error("Failed to delete object")]
     DeleteError,
 
     #[error("Failed to generate presigned URL")]
     PresignedUrlError,
 
+    #[error("Presigned URL is invalid or expired")]
+    PresignedUrlInvalid,
+
     #[error("Failed to list objects")]
     ListError,
 
+    #[error("The data key of the object is gone")]
+    DataKeyNotFound,
+
+    #[error("Failed to encrypt or decrypt object")]
+    EncryptionError,
+
     #[error("An internal error occurred")]
     Internal,
 }
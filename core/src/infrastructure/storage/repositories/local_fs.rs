@@ -0,0 +1,404 @@
+use std::{
+    io::{ErrorKind, SeekFrom},
+    path::{Component, Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use futures_util::stream;
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        common::config::StorageConfig,
+        storage::{
+            entities::{ByteRange, StorageObject},
+            port::StorageRepository,
+        },
+    },
+    infrastructure::storage::repositories::error::StorageError,
+};
+
+/// Keys returned per `list_objects_page` call, the default of S3 `ListObjectsV2`.
+const LIST_PAGE_SIZE: usize = 1000;
+/// Size of the chunks objects are streamed in.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Stores each object as a file at `<root_dir>/<key>`. Presigned URLs point to the
+/// `/storage` endpoint of the API, signed with the storage URL secret.
+#[derive(Clone)]
+pub struct LocalFsStorageRepository {
+    config: StorageConfig,
+    page_size: usize,
+}
+
+impl LocalFsStorageRepository {
+    pub fn new(config: StorageConfig) -> Self {
+        Self {
+            config,
+            page_size: LIST_PAGE_SIZE,
+        }
+    }
+
+    /// Whether the root directory can be used, for the health checks.
+    pub async fn is_reachable(&self) -> bool {
+        fs::metadata(&self.config.local_root_dir)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+    }
+
+    /// Path of the file of `key`. Keys are relative `/` separated paths, any segment that
+    /// could escape the root directory or clash with the temporary upload files is refused.
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        let valid = !key.is_empty()
+            && key.split('/').all(|segment| {
+                !segment.is_empty() && !segment.starts_with('.') && !segment.contains('\\')
+            })
+            && Path::new(key)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+
+        if !valid {
+            return Err(StorageError::InvalidKey);
+        }
+        Ok(self.config.local_root_dir.join(key))
+    }
+
+    /// Keys of the files under `dir`, temporary upload files excluded.
+    async fn walk(&self, dir: PathBuf) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        let mut dirs = vec![dir];
+
+        while let Some(dir) = dirs.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => {
+                    error!(error = %e, "local_fs.ListObjects failed to read {}", dir.display());
+                    return Err(StorageError::ListError);
+                }
+            };
+
+            while let Some(entry) = entries.next_entry().await.map_err(|e| {
+                error!(error = %e, "local_fs.ListObjects failed to read {}", dir.display());
+                StorageError::ListError
+            })? {
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                let file_type = entry
+                    .file_type()
+                    .await
+                    .map_err(|_| StorageError::ListError)?;
+                if file_type.is_dir() {
+                    dirs.push(entry.path());
+                } else if let Ok(relative) = entry.path().strip_prefix(&self.config.local_root_dir)
+                {
+                    let segments: Vec<_> = relative
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                        .collect();
+                    keys.push(segments.join("/"));
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+impl StorageRepository for LocalFsStorageRepository {
+    #[tracing::instrument(
+        name = "local_fs.PutObject",
+        skip(self, data),
+        fields(key = %key, content_length = data.len())
+    )]
+    async fn upload(
+        &self,
+        key: &str,
+        data: Bytes,
+        _content_type: &str,
+    ) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        let upload_error = |e: std::io::Error| {
+            error!(error = %e, "local_fs.PutObject failed");
+            StorageError::UploadError(e.to_string())
+        };
+
+        let dir = path.parent().ok_or(StorageError::InvalidKey)?;
+        fs::create_dir_all(dir).await.map_err(upload_error)?;
+
+        // Written aside then renamed, readers never see a partially written object
+        let temporary = dir.join(format!(".{}.tmp", Uuid::new_v4()));
+        fs::write(&temporary, &data).await.map_err(upload_error)?;
+        if let Err(e) = fs::rename(&temporary, &path).await {
+            let _ = fs::remove_file(&temporary).await;
+            return Err(upload_error(e));
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "local_fs.GetObject", skip(self), fields(key = %key))]
+    async fn download(&self, key: &str) -> Result<Bytes, StorageError> {
+        match fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Bytes::from(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(StorageError::ObjectNotFound),
+            Err(e) => {
+                error!(error = %e, "local_fs.GetObject failed");
+                Err(StorageError::DownloadError)
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "local_fs.GetObject", skip(self), fields(key = %key, range = ?range))]
+    async fn stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<StorageObject, StorageError> {
+        let download_error = |e: std::io::Error| {
+            error!(error = %e, "local_fs.GetObject failed");
+            StorageError::DownloadError
+        };
+
+        let mut file = match fs::File::open(self.path(key)?).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(StorageError::ObjectNotFound),
+            Err(e) => return Err(download_error(e)),
+        };
+        let metadata = file.metadata().await.map_err(download_error)?;
+        let total = metadata.len();
+
+        let (start, content_length, content_range) = match range {
+            Some(range) => {
+                let (start, end) = range
+                    .bounds(total)
+                    .ok_or(StorageError::RangeNotSatisfiable)?;
+                (
+                    start,
+                    end - start + 1,
+                    Some(format!("bytes {}-{}/{}", start, end, total)),
+                )
+            }
+            None => (0, total, None),
+        };
+        file.seek(SeekFrom::Start(start))
+            .await
+            .map_err(download_error)?;
+
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+
+        let body = stream::unfold(file.take(content_length), |mut reader| async move {
+            let mut chunk = BytesMut::with_capacity(STREAM_CHUNK_SIZE);
+            match reader.read_buf(&mut chunk).await {
+                Ok(0) => None,
+                Ok(_) => Some((Ok(chunk.freeze()), reader)),
+                Err(e) => {
+                    error!(error = %e, "local_fs.GetObject body read failed");
+                    Some((Err(StorageError::DownloadError), reader))
+                }
+            }
+        });
+
+        Ok(StorageObject {
+            body: Box::pin(body),
+            content_length,
+            content_range,
+            e_tag: Some(format!("\"{:x}-{:x}\"", total, modified.as_nanos())),
+        })
+    }
+
+    #[tracing::instrument(name = "local_fs.DeleteObject", skip(self), fields(key = %key))]
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        // Deleting a missing object succeeds, as on S3
+        match fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => {
+                error!(error = %e, "local_fs.DeleteObject failed");
+                Err(StorageError::DeleteError)
+            }
+        }
+    }
+
+    async fn generate_presigned_url(
+        &self,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<String, StorageError> {
+        self.path(key)?;
+        if self.config.url_secret.is_empty() {
+            error!("local_fs presigned URL requested without a storage URL secret");
+            return Err(StorageError::PresignedUrlError);
+        }
+
+        let expires = Utc::now().timestamp() + expires_in.as_secs() as i64;
+        Ok(self.config.url(key, expires))
+    }
+
+    #[tracing::instrument(
+        name = "local_fs.ListObjects",
+        skip(self),
+        fields(prefix = ?prefix, continuation_token = ?continuation_token)
+    )]
+    async fn list_objects_page(
+        &self,
+        prefix: Option<&str>,
+        continuation_token: Option<String>,
+    ) -> Result<(Vec<String>, Option<String>), StorageError> {
+        let prefix = prefix.unwrap_or_default();
+        // Only the directory the prefix points into needs to be walked
+        let dir = match prefix.rsplit_once('/') {
+            Some((dir, _)) => self.path(dir)?,
+            None => self.config.local_root_dir.clone(),
+        };
+
+        let mut keys: Vec<String> = self
+            .walk(dir)
+            .await?
+            .into_iter()
+            .filter(|key| key.starts_with(prefix))
+            .filter(|key| {
+                continuation_token
+                    .as_deref()
+                    .is_none_or(|token| key.as_str() > token)
+            })
+            .collect();
+        keys.sort();
+
+        // The token is the last key of the page, the next page starts after it
+        let next_token = if keys.len() > self.page_size {
+            keys.truncate(self.page_size);
+            keys.last().cloned()
+        } else {
+            None
+        };
+
+        Ok((keys, next_token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+
+    use super::*;
+
+    fn make_repository(page_size: usize) -> LocalFsStorageRepository {
+        LocalFsStorageRepository {
+            config: StorageConfig {
+                local_root_dir: std::env::temp_dir().join(format!("storage-{}", Uuid::new_v4())),
+                url_secret: "storage-secret".to_string(),
+                ..StorageConfig::default()
+            },
+            page_size,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_then_stream_range() {
+        let repository = make_repository(LIST_PAGE_SIZE);
+        let key = "drivers/123/2026/02/workdays-2026-02.pdf";
+
+        repository
+            .upload(key, Bytes::from("%PDF-1.4 content"), "application/pdf")
+            .await
+            .unwrap();
+        assert_eq!(repository.download(key).await.unwrap(), "%PDF-1.4 content");
+
+        let object = repository
+            .stream(key, ByteRange::parse("bytes=9-"))
+            .await
+            .unwrap();
+        assert_eq!(object.content_length, 7);
+        assert_eq!(object.content_range.as_deref(), Some("bytes 9-15/16"));
+        let chunks: Vec<Bytes> = object.body.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), b"content");
+
+        repository.delete(key).await.unwrap();
+        assert_eq!(
+            repository.download(key).await,
+            Err(StorageError::ObjectNotFound)
+        );
+        repository.delete(key).await.unwrap();
+
+        fs::remove_dir_all(&repository.config.local_root_dir)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_pages_sorted_keys() {
+        let repository = make_repository(2);
+        for key in ["b/2.pdf", "a/1.pdf", "b/1.pdf", "c.pdf"] {
+            repository
+                .upload(key, Bytes::from("x"), "application/pdf")
+                .await
+                .unwrap();
+        }
+
+        let (page, token) = repository.list_objects_page(None, None).await.unwrap();
+        assert_eq!(page, vec!["a/1.pdf", "b/1.pdf"]);
+        let (page, token) = repository.list_objects_page(None, token).await.unwrap();
+        assert_eq!(page, vec!["b/2.pdf", "c.pdf"]);
+        assert_eq!(token, None);
+
+        let (page, _) = repository
+            .list_objects_page(Some("b/"), None)
+            .await
+            .unwrap();
+        assert_eq!(page, vec!["b/1.pdf", "b/2.pdf"]);
+
+        fs::remove_dir_all(&repository.config.local_root_dir)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_keys_escaping_root_dir_are_refused() {
+        let repository = make_repository(LIST_PAGE_SIZE);
+
+        for key in ["../outside.pdf", "/etc/passwd", "a//b.pdf", "a/.hidden", ""] {
+            assert_eq!(
+                repository.download(key).await,
+                Err(StorageError::InvalidKey),
+                "{key}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_presigned_url_is_signed_for_the_key() {
+        let repository = make_repository(LIST_PAGE_SIZE);
+        let url = repository
+            .generate_presigned_url("drivers/1/mails/a b.pdf", Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        let (path, query) = url.split_once('?').unwrap();
+        assert_eq!(
+            path,
+            "http://localhost:8080/storage/drivers/1/mails/a%20b.pdf"
+        );
+        let params: Vec<_> = query.split('&').collect();
+        let expires: i64 = params[0].strip_prefix("expires=").unwrap().parse().unwrap();
+        let signature = params[1].strip_prefix("signature=").unwrap();
+
+        let now = Utc::now().timestamp();
+        let config = &repository.config;
+        assert!(config.verify("drivers/1/mails/a b.pdf", expires, signature, now));
+        assert!(!config.verify("drivers/2/mails/a b.pdf", expires, signature, now));
+        assert!(!config.verify("drivers/1/mails/a b.pdf", expires, signature, expires + 1));
+    }
+}
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use tracing::error;
+
+use crate::{
+    domain::{
+        common::{CoreError, config::StorageEncryptionConfig},
+        storage::{
+            entities::{DataKey, WrappedDataKey},
+            port::KeyManagementRepository,
+        },
+    },
+    infrastructure::storage::repositories::error::StorageError,
+};
+
+const NONCE_LENGTH: usize = 12;
+
+/// Wraps the data keys with AES-256-GCM under master keys read from the configuration.
+#[derive(Clone)]
+pub struct LocalKeyManagementRepository {
+    /// The current key first.
+    master_keys: Arc<Vec<(String, Aes256Gcm)>>,
+}
+
+impl LocalKeyManagementRepository {
+    pub fn new(config: &StorageEncryptionConfig) -> Result<Self, CoreError> {
+        let master_keys = config
+            .master_keys
+            .iter()
+            .map(|entry| {
+                let (id, key) = entry.split_once(':').ok_or_else(|| {
+                    CoreError::ServiceUnavailable(
+                        "Storage master keys must be <id>:<base64 key> entries".to_string(),
+                    )
+                })?;
+                let key = STANDARD.decode(key.trim()).map_err(|e| {
+                    CoreError::ServiceUnavailable(format!(
+                        "Invalid storage master key {}: {}",
+                        id, e
+                    ))
+                })?;
+                let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| {
+                    CoreError::ServiceUnavailable(format!(
+                        "Storage master key {} must be 32 bytes long",
+                        id
+                    ))
+                })?;
+                Ok((id.trim().to_string(), cipher))
+            })
+            .collect::<Result<Vec<_>, CoreError>>()?;
+
+        Ok(Self {
+            master_keys: Arc::new(master_keys),
+        })
+    }
+}
+
+impl KeyManagementRepository for LocalKeyManagementRepository {
+    fn current_key_id(&self) -> Option<&str> {
+        self.master_keys.first().map(|(id, _)| id.as_str())
+    }
+
+    async fn wrap_key(&self, data_key: &DataKey) -> Result<WrappedDataKey, StorageError> {
+        let (id, cipher) = self.master_keys.first().ok_or(StorageError::Internal)?;
+
+        let mut nonce = [0u8; NONCE_LENGTH];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: data_key.as_bytes(),
+                    aad: id.as_bytes(),
+                },
+            )
+            .map_err(|e| {
+                error!("Could not wrap data key: {:?}", e);
+                StorageError::EncryptionError
+            })?;
+
+        Ok(WrappedDataKey {
+            master_key_id: id.clone(),
+            wrapped_key: [nonce.as_slice(), &ciphertext].concat(),
+        })
+    }
+
+    async fn unwrap_key(&self, wrapped: &WrappedDataKey) -> Result<DataKey, StorageError> {
+        let Some((id, cipher)) = self
+            .master_keys
+            .iter()
+            .find(|(id, _)| *id == wrapped.master_key_id)
+        else {
+            error!(
+                master_key_id = %wrapped.master_key_id,
+                "Data key wrapped with an unknown master key"
+            );
+            return Err(StorageError::EncryptionError);
+        };
+        if wrapped.wrapped_key.len() < NONCE_LENGTH {
+            return Err(StorageError::EncryptionError);
+        }
+
+        let (nonce, ciphertext) = wrapped.wrapped_key.split_at(NONCE_LENGTH);
+        let key = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: id.as_bytes(),
+                },
+            )
+            .map_err(|e| {
+                error!("Could not unwrap data key: {:?}", e);
+                StorageError::EncryptionError
+            })?;
+
+        let key: [u8; 32] = key.try_into().map_err(|_| StorageError::EncryptionError)?;
+        Ok(DataKey::from_bytes(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalKeyManagementRepository;
+    use crate::{
+        domain::{
+            common::config::StorageEncryptionConfig,
+            storage::{entities::DataKey, port::KeyManagementRepository},
+        },
+        infrastructure::storage::repositories::error::StorageError,
+    };
+
+    const OLD_KEY: &str = "2025:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+    const NEW_KEY: &str = "2026:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+
+    fn kms(master_keys: &[&str]) -> LocalKeyManagementRepository {
+        LocalKeyManagementRepository::new(&StorageEncryptionConfig {
+            master_keys: master_keys.iter().map(|k| k.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_data_key_unwrapped_by_older_master_key() {
+        let old = kms(&[OLD_KEY]);
+        let rotated = kms(&[NEW_KEY, OLD_KEY]);
+        let data_key = DataKey::generate();
+
+        let wrapped = old.wrap_key(&data_key).await.unwrap();
+        assert_eq!(wrapped.master_key_id, "2025");
+        assert_eq!(rotated.current_key_id(), Some("2026"));
+
+        let unwrapped = rotated.unwrap_key(&wrapped).await.unwrap();
+        assert_eq!(unwrapped.as_bytes(), data_key.as_bytes());
+
+        let rewrapped = rotated.wrap_key(&unwrapped).await.unwrap();
+        assert_eq!(rewrapped.master_key_id, "2026");
+        assert_eq!(
+            kms(&[NEW_KEY])
+                .unwrap_key(&rewrapped)
+                .await
+                .unwrap()
+                .as_bytes(),
+            data_key.as_bytes()
+        );
+        assert_eq!(
+            kms(&[NEW_KEY]).unwrap_key(&wrapped).await.unwrap_err(),
+            StorageError::EncryptionError
+        );
+    }
+
+    #[test]
+    fn test_invalid_master_keys_are_rejected() {
+        let config = |key: &str| StorageEncryptionConfig {
+            master_keys: vec![key.to_string()],
+        };
+
+        assert!(LocalKeyManagementRepository::new(&config("no-separator")).is_err());
+        assert!(LocalKeyManagementRepository::new(&config("2026:not base64")).is_err());
+        assert!(LocalKeyManagementRepository::new(&config("2026:c2hvcnQ=")).is_err());
+        assert_eq!(
+            LocalKeyManagementRepository::new(&StorageEncryptionConfig::default())
+                .unwrap()
+                .current_key_id(),
+            None
+        );
+    }
+}
@@ -1,2 +1,7 @@
+pub mod backend;
+pub mod encrypted;
 pub mod error;
+pub mod local_fs;
+pub mod local_kms;
+pub mod postgres;
 pub mod s3;
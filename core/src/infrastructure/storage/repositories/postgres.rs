@@ -0,0 +1,171 @@
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    domain::storage::{entities::WrappedDataKey, port::DataKeyRepository},
+    infrastructure::storage::repositories::error::StorageError,
+};
+
+#[derive(Clone)]
+pub struct PostgresDataKeyRepository {
+    pool: PgPool,
+}
+
+impl PostgresDataKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl DataKeyRepository for PostgresDataKeyRepository {
+    #[tracing::instrument(
+        name = "db.driver_data_keys.get",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
+    )]
+    async fn get_data_key(&self, driver_id: Uuid) -> Result<Option<WrappedDataKey>, StorageError> {
+        sqlx::query_as::<_, WrappedDataKey>(
+            r#"
+            SELECT master_key_id, wrapped_key
+            FROM driver_data_keys
+            WHERE fk_driver_id = $1
+            "#,
+        )
+        .bind(driver_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get data key: {:?}", e);
+            StorageError::Internal
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.driver_data_keys.create",
+        skip(self, wrapped),
+        fields(db.system = "postgresql", db.operation = "INSERT", driver_id = %driver_id)
+    )]
+    async fn create_data_key(
+        &self,
+        driver_id: Uuid,
+        wrapped: WrappedDataKey,
+    ) -> Result<WrappedDataKey, StorageError> {
+        // The no-op update returns the key already stored by a concurrent upload.
+        sqlx::query_as::<_, WrappedDataKey>(
+            r#"
+            INSERT INTO driver_data_keys (fk_driver_id, master_key_id, wrapped_key)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (fk_driver_id) DO UPDATE
+            SET fk_driver_id = driver_data_keys.fk_driver_id
+            RETURNING master_key_id, wrapped_key
+            "#,
+        )
+        .bind(driver_id)
+        .bind(wrapped.master_key_id)
+        .bind(wrapped.wrapped_key)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to create data key: {:?}", e);
+            StorageError::Internal
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.driver_data_keys.update",
+        skip(self, wrapped),
+        fields(
+            db.system = "postgresql",
+            db.operation = "UPDATE",
+            driver_id = %driver_id,
+            master_key_id = %wrapped.master_key_id
+        )
+    )]
+    async fn update_data_key(
+        &self,
+        driver_id: Uuid,
+        wrapped: WrappedDataKey,
+    ) -> Result<(), StorageError> {
+        sqlx::query(
+            r#"
+            UPDATE driver_data_keys
+            SET master_key_id = $2, wrapped_key = $3, rotated_at = NOW()
+            WHERE fk_driver_id = $1
+            "#,
+        )
+        .bind(driver_id)
+        .bind(wrapped.master_key_id)
+        .bind(wrapped.wrapped_key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to update data key: {:?}", e);
+            StorageError::Internal
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "db.driver_data_keys.delete",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "DELETE", driver_id = %driver_id)
+    )]
+    async fn delete_data_key(&self, driver_id: Uuid) -> Result<bool, StorageError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM driver_data_keys
+            WHERE fk_driver_id = $1
+            "#,
+        )
+        .bind(driver_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete data key: {:?}", e);
+            StorageError::Internal
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(
+        name = "db.driver_data_keys.get_to_rewrap",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_data_keys_to_rewrap(
+        &self,
+        master_key_id: &str,
+    ) -> Result<Vec<(Uuid, WrappedDataKey)>, StorageError> {
+        let rows = sqlx::query_as::<_, (Uuid, String, Vec<u8>)>(
+            r#"
+            SELECT fk_driver_id, master_key_id, wrapped_key
+            FROM driver_data_keys
+            WHERE master_key_id <> $1
+            ORDER BY fk_driver_id
+            "#,
+        )
+        .bind(master_key_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get data keys to rewrap: {:?}", e);
+            StorageError::Internal
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(driver_id, master_key_id, wrapped_key)| {
+                (
+                    driver_id,
+                    WrappedDataKey {
+                        master_key_id,
+                        wrapped_key,
+                    },
+                )
+            })
+            .collect())
+    }
+}
@@ -8,10 +8,14 @@ use aws_sdk_s3::{
     primitives::ByteStream,
 };
 use bytes::Bytes;
+use futures_util::stream;
 use tracing::{Span, error};
 
 use crate::{
-    domain::storage::port::StorageRepository,
+    domain::storage::{
+        entities::{ByteRange, StorageObject},
+        port::StorageRepository,
+    },
     infrastructure::storage::repositories::error::StorageError,
 };
 
@@ -48,6 +52,16 @@ impl S3StorageRepository {
     pub fn new_from_client(client: aws_sdk_s3::Client, bucket: String) -> Self {
         Self { client, bucket }
     }
+
+    /// Whether the bucket can be reached, for the health checks.
+    pub async fn is_reachable(&self) -> bool {
+        self.client
+            .head_bucket()
+            .bucket(&self.bucket)
+            .send()
+            .await
+            .is_ok()
+    }
 }
 
 impl StorageRepository for S3StorageRepository {
@@ -131,6 +145,65 @@ impl StorageRepository for S3StorageRepository {
         Ok(data)
     }
 
+    #[tracing::instrument(
+        name = "s3.GetObject",
+        skip(self),
+        fields(
+            db.system = "aws.s3",
+            db.operation = "GetObject",
+            aws.s3.bucket = %self.bucket,
+            aws.s3.key = %key,
+            aws.s3.range = ?range,
+            aws.s3.content_length = tracing::field::Empty,
+        )
+    )]
+    async fn stream(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<StorageObject, StorageError> {
+        let response = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .set_range(range.map(|r| r.to_string()))
+            .send()
+            .await
+            .map_err(|e| match e {
+                SdkError::ServiceError(se) => match se.err() {
+                    GetObjectError::NoSuchKey(_) => StorageError::ObjectNotFound,
+                    _ if se.raw().status().as_u16() == 416 => StorageError::RangeNotSatisfiable,
+                    _ => {
+                        error!("aws.s3.GetObject unexpected service error");
+                        StorageError::DownloadError
+                    }
+                },
+                _ => {
+                    error!("aws.s3.GetObject failed");
+                    StorageError::DownloadError
+                }
+            })?;
+
+        let content_length = response.content_length().unwrap_or_default().max(0) as u64;
+        Span::current().record("aws.s3.content_length", content_length);
+
+        let body = stream::unfold(response.body, |mut body| async move {
+            let chunk = body.next().await?.map_err(|e| {
+                error!(error = %e, "aws.s3.GetObject body read failed");
+                StorageError::DownloadError
+            });
+            Some((chunk, body))
+        });
+
+        Ok(StorageObject {
+            body: Box::pin(body),
+            content_length,
+            content_range: response.content_range.filter(|_| range.is_some()),
+            e_tag: response.e_tag,
+        })
+    }
+
     #[tracing::instrument(
         name = "s3.DeleteObject",
         skip(self),
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::domain::workday::entities::Workday;
+
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum WorkdayError {
     #[error("A database error occurred")]
@@ -14,6 +16,9 @@ pub enum WorkdayError {
     #[error("Workday not found")]
     WorkdayNotFound,
 
+    #[error("Workday was modified since it was read")]
+    WorkdayVersionConflict { current: Box<Workday> },
+
     #[error("Workday garbage already exists for the given date")]
     WorkdayGarbageAlreadyExists,
 
@@ -22,4 +27,37 @@ pub enum WorkdayError {
 
     #[error("A document has already been generated for this month")]
     WorkdayDocumentAlreadyGenerated,
+
+    #[error("No document was generated with this verification code")]
+    WorkdayDocumentNotFound,
+
+    #[error("Document share not found")]
+    DocumentShareNotFound,
+
+    #[error("Document share has expired")]
+    DocumentShareExpired,
+
+    #[error("Document share has been revoked")]
+    DocumentShareRevoked,
+
+    #[error("Document share is protected by a password")]
+    DocumentSharePasswordRequired,
+
+    #[error("Invalid document share password")]
+    DocumentSharePasswordInvalid,
+
+    #[error("A recipient email is required to send the document share")]
+    DocumentShareRecipientRequired,
+
+    #[error("Too many document shares created recently")]
+    DocumentShareRateLimited,
+
+    #[error("Document share is locked after too many wrong passwords")]
+    DocumentShareLocked,
+
+    #[error("The document service is unavailable")]
+    DocumentServiceUnavailable,
+
+    #[error("Requested range lies outside the document")]
+    RangeNotSatisfiable,
 }
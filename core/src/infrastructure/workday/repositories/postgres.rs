@@ -6,8 +6,10 @@ use uuid::Uuid;
 use crate::{
     domain::workday::{
         entities::{
-            CreateWorkdayRequest, DocumentRow, UpdateWorkdayRequest, WorkdayDocument,
-            WorkdayDocumentInformation, WorkdayDocumentRow, WorkdayGarbageRow, WorkdayRow,
+            CreateWorkdayRequest, DocumentChecksum, DocumentIntegrity, DocumentRow,
+            DocumentShareAccessRow, DocumentShareRow, SupersededWorkdayDocument,
+            UpdateWorkdayRequest, WorkdayDocument, WorkdayDocumentInformation, WorkdayDocumentRow,
+            WorkdayGarbageRow, WorkdayRow, YearlyDocument,
         },
         port::WorkdayDatabaseRepository,
     },
@@ -172,6 +174,46 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
         Ok((workdays, total_count_record.count.unwrap_or(0) as u32))
     }
 
+    #[tracing::instrument(
+        name = "db.workdays.get_recorded_workday_dates",
+        skip(self),
+        fields(
+            driver_id = %driver_id,
+            start_date = %start_date,
+            end_date = %end_date,
+        )
+    )]
+    async fn get_recorded_workday_dates(
+        &self,
+        driver_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<NaiveDate>, WorkdayError> {
+        sqlx::query_scalar::<_, NaiveDate>(
+            r#"
+            SELECT date
+            FROM workdays
+            WHERE fk_driver_id = $1
+            AND date BETWEEN $2 AND $3
+            UNION
+            SELECT workday_date
+            FROM workday_garbage
+            WHERE fk_driver_id = $1
+            AND workday_date BETWEEN $2 AND $3
+            ORDER BY 1
+            "#,
+        )
+        .bind(driver_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get recorded workday dates: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
     #[tracing::instrument(
         name = "db.workdays.get_workday_years",
         skip(self),
@@ -296,13 +338,15 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
         driver_id: Uuid,
         update_workday_request: UpdateWorkdayRequest,
     ) -> Result<WorkdayRow, WorkdayError> {
-        sqlx::query_as!(
+        let result = sqlx::query_as!(
             WorkdayRow,
             r#"
             UPDATE workdays
-            SET start_time = $1, end_time = $2, rest_time = $3, overnight_rest = $4
+            SET start_time = $1, end_time = $2, rest_time = $3, overnight_rest = $4,
+                version = version + 1
             WHERE date = $5
             AND fk_driver_id = $6
+            AND ($7::INT IS NULL OR version = $7)
             RETURNING *
             "#,
             update_workday_request.start_time,
@@ -310,18 +354,31 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
             update_workday_request.rest_time,
             update_workday_request.overnight_rest,
             update_workday_request.date,
-            driver_id
+            driver_id,
+            update_workday_request.version
         )
         .fetch_one(&self.pool)
-        .await
-        .map_err(|e| {
-            if matches!(e, sqlx::Error::RowNotFound) {
-                return WorkdayError::WorkdayNotFound;
-            }
+        .await;
 
-            error!("Failed to update workday: {:?}", e);
-            WorkdayError::DatabaseError
-        })
+        match result {
+            Ok(updated) => Ok(updated),
+            // Either the workday does not exist or it was updated since the requested version
+            Err(sqlx::Error::RowNotFound) => {
+                match self
+                    .get_workday_by_date(driver_id, update_workday_request.date)
+                    .await?
+                {
+                    Some(current) => Err(WorkdayError::WorkdayVersionConflict {
+                        current: Box::new(current.to_workday()),
+                    }),
+                    None => Err(WorkdayError::WorkdayNotFound),
+                }
+            }
+            Err(e) => {
+                error!("Failed to update workday: {:?}", e);
+                Err(WorkdayError::DatabaseError)
+            }
+        }
     }
 
     #[tracing::instrument(
@@ -571,19 +628,26 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
         year: i32,
         s3_file_path: String,
         file_name: String,
+        integrity: DocumentIntegrity,
+        is_fallback: bool,
     ) -> Result<WorkdayDocument, WorkdayError> {
         let document_id = Uuid::new_v4();
 
         let document = sqlx::query_as::<_, DocumentRow>(
             r#"
-            INSERT INTO documents (pk_document_id, s3_file_path, file_name, created_at)
-            VALUES ($1, $2, $3, NOW())
+            INSERT INTO documents
+                (pk_document_id, s3_file_path, file_name, created_at, sha256, verification_code,
+                 is_fallback)
+            VALUES ($1, $2, $3, NOW(), $4, $5, $6)
             RETURNING *
             "#,
         )
         .bind(document_id)
         .bind(&s3_file_path)
         .bind(&file_name)
+        .bind(&integrity.sha256)
+        .bind(&integrity.verification_code)
+        .bind(is_fallback)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| {
@@ -615,6 +679,272 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
             s3_file_path: document.s3_file_path,
             file_name: document.file_name,
             created_at: document.created_at,
+            sha256: document.sha256,
+            verification_code: document.verification_code,
+            is_fallback: document.is_fallback,
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_fallback_document_months",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_fallback_document_months(&self) -> Result<Vec<(Uuid, i32, i32)>, WorkdayError> {
+        sqlx::query_as::<_, (Uuid, i32, i32)>(
+            r#"
+            SELECT wd.fk_driver_id, wd.month, wd.year
+            FROM workday_documents wd
+            JOIN documents d ON d.pk_document_id = wd.fk_document_id
+            WHERE d.is_fallback
+            ORDER BY wd.year, wd.month, wd.fk_driver_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get fallback document months: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.update_regenerated_workday_document",
+        skip(self, integrity),
+        fields(
+            db.system = "postgresql",
+            db.operation = "UPDATE",
+            driver_id = %driver_id,
+            month = %month,
+            year = %year,
+        )
+    )]
+    async fn update_regenerated_workday_document(
+        &self,
+        driver_id: Uuid,
+        month: i32,
+        year: i32,
+        integrity: DocumentIntegrity,
+    ) -> Result<WorkdayDocument, WorkdayError> {
+        sqlx::query_as::<_, WorkdayDocument>(
+            r#"
+            WITH superseded AS (
+                INSERT INTO superseded_document_verifications
+                    (verification_code, fk_document_id, sha256, superseded_at)
+                SELECT d.verification_code, d.pk_document_id, d.sha256, NOW()
+                FROM documents d
+                JOIN workday_documents wd ON wd.fk_document_id = d.pk_document_id
+                WHERE wd.fk_driver_id = $1 AND wd.month = $2 AND wd.year = $3
+                  AND d.verification_code IS NOT NULL AND d.sha256 IS NOT NULL
+                ON CONFLICT (verification_code) DO NOTHING
+            )
+            UPDATE documents d
+            SET sha256 = $4, verification_code = $5, is_fallback = FALSE, created_at = NOW()
+            FROM workday_documents wd
+            WHERE wd.fk_document_id = d.pk_document_id
+              AND wd.fk_driver_id = $1 AND wd.month = $2 AND wd.year = $3
+            RETURNING wd.fk_driver_id, wd.month, wd.year, d.s3_file_path, d.file_name,
+                      d.created_at, d.sha256, d.verification_code, d.is_fallback
+            "#,
+        )
+        .bind(driver_id)
+        .bind(month)
+        .bind(year)
+        .bind(&integrity.sha256)
+        .bind(&integrity.verification_code)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to update regenerated document: {:?}", e);
+            WorkdayError::DatabaseError
+        })?
+        .ok_or(WorkdayError::WorkdayDocumentNotFound)
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_workday_document_by_verification_code",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_workday_document_by_verification_code(
+        &self,
+        verification_code: &str,
+    ) -> Result<Option<WorkdayDocument>, WorkdayError> {
+        sqlx::query_as::<_, WorkdayDocument>(
+            r#"
+            SELECT wd.fk_driver_id, wd.month, wd.year,
+                   d.s3_file_path, d.file_name, d.created_at, d.sha256, d.verification_code,
+                   d.is_fallback
+            FROM documents d
+            JOIN workday_documents wd ON wd.fk_document_id = d.pk_document_id
+            WHERE d.verification_code = $1
+            "#,
+        )
+        .bind(verification_code)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get document by verification code: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_superseded_workday_document_by_verification_code",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_superseded_workday_document_by_verification_code(
+        &self,
+        verification_code: &str,
+    ) -> Result<Option<SupersededWorkdayDocument>, WorkdayError> {
+        sqlx::query_as::<_, SupersededWorkdayDocument>(
+            r#"
+            SELECT wd.fk_driver_id, wd.month, wd.year,
+                   s.sha256, s.verification_code, s.superseded_at
+            FROM superseded_document_verifications s
+            JOIN workday_documents wd ON wd.fk_document_id = s.fk_document_id
+            WHERE s.verification_code = $1
+            "#,
+        )
+        .bind(verification_code)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to get superseded document by verification code: {:?}",
+                e
+            );
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_yearly_document_record",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            driver_id = %driver_id,
+            year = %year,
+        )
+    )]
+    async fn get_yearly_document_record(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+    ) -> Result<Option<YearlyDocument>, WorkdayError> {
+        sqlx::query_as::<_, YearlyDocument>(
+            r#"
+            SELECT yd.fk_driver_id, yd.year, d.s3_file_path, d.file_name, d.created_at, d.sha256
+            FROM yearly_documents yd
+            JOIN documents d ON d.pk_document_id = yd.fk_document_id
+            WHERE yd.fk_driver_id = $1 AND yd.year = $2
+            "#,
+        )
+        .bind(driver_id)
+        .bind(year)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get yearly document record: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_pending_document_years",
+        skip(self),
+        fields(before_year = %before_year)
+    )]
+    async fn get_pending_document_years(
+        &self,
+        before_year: i32,
+    ) -> Result<Vec<(Uuid, i32)>, WorkdayError> {
+        sqlx::query_as::<_, (Uuid, i32)>(
+            r#"
+            SELECT DISTINCT
+                w.fk_driver_id,
+                EXTRACT(YEAR FROM w.date)::INTEGER AS year
+            FROM workdays w
+            WHERE EXTRACT(YEAR FROM w.date)::INTEGER < $1
+            AND w.date NOT IN (
+                SELECT wg.workday_date FROM workday_garbage wg
+                WHERE wg.fk_driver_id = w.fk_driver_id
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM yearly_documents yd
+                WHERE yd.fk_driver_id = w.fk_driver_id
+                AND yd.year = EXTRACT(YEAR FROM w.date)::INTEGER
+            )
+            ORDER BY year ASC
+            "#,
+        )
+        .bind(before_year)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get pending document years: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.create_yearly_document",
+        skip(self, sha256),
+        fields(driver_id = %driver_id, year = %year)
+    )]
+    async fn create_yearly_document(
+        &self,
+        driver_id: Uuid,
+        year: i32,
+        s3_file_path: String,
+        file_name: String,
+        sha256: String,
+    ) -> Result<YearlyDocument, WorkdayError> {
+        let document_id = Uuid::new_v4();
+
+        let document = sqlx::query_as::<_, DocumentRow>(
+            r#"
+            INSERT INTO documents (pk_document_id, s3_file_path, file_name, created_at, sha256)
+            VALUES ($1, $2, $3, NOW(), $4)
+            RETURNING *
+            "#,
+        )
+        .bind(document_id)
+        .bind(&s3_file_path)
+        .bind(&file_name)
+        .bind(&sha256)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert document: {:?}", e);
+            WorkdayError::DatabaseError
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO yearly_documents (fk_driver_id, year, fk_document_id)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(driver_id)
+        .bind(year)
+        .bind(document_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert yearly_document record: {:?}", e);
+            WorkdayError::DatabaseError
+        })?;
+
+        Ok(YearlyDocument {
+            fk_driver_id: driver_id,
+            year,
+            s3_file_path: document.s3_file_path,
+            file_name: document.file_name,
+            created_at: document.created_at,
+            sha256: document.sha256,
         })
     }
 
@@ -697,7 +1027,8 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
         };
 
         let document = sqlx::query_as::<_, DocumentRow>(
-            "SELECT pk_document_id, s3_file_path, file_name, created_at
+            "SELECT pk_document_id, s3_file_path, file_name, created_at, sha256, verification_code,
+                    is_fallback
              FROM documents
              WHERE pk_document_id = $1",
         )
@@ -719,6 +1050,9 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
             s3_file_path: document.s3_file_path,
             file_name: document.file_name,
             created_at: document.created_at,
+            sha256: document.sha256,
+            verification_code: document.verification_code,
+            is_fallback: document.is_fallback,
         }))
     }
 
@@ -740,18 +1074,18 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
     }
 
     #[tracing::instrument(
-        name = "db.workdays.get_document_s3_paths_batch",
+        name = "db.workdays.get_document_checksums_batch",
         skip(self),
         fields(db.system = "postgresql", db.operation = "SELECT", after = ?after, limit = %limit)
     )]
-    async fn get_document_s3_paths_batch(
+    async fn get_document_checksums_batch(
         &self,
         after: Option<&str>,
         limit: i64,
-    ) -> Result<Vec<String>, WorkdayError> {
-        let rows = sqlx::query_as::<_, (String,)>(
+    ) -> Result<Vec<DocumentChecksum>, WorkdayError> {
+        sqlx::query_as::<_, DocumentChecksum>(
             r#"
-            SELECT s3_file_path
+            SELECT s3_file_path, sha256
             FROM documents
             WHERE ($1::TEXT IS NULL OR s3_file_path > $1)
             ORDER BY s3_file_path ASC
@@ -763,11 +1097,9 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
-            error!("Failed to get document s3 paths batch: {:?}", e);
+            error!("Failed to get document checksums batch: {:?}", e);
             WorkdayError::DatabaseError
-        })?;
-
-        Ok(rows.into_iter().map(|(path,)| path).collect())
+        })
     }
 
     #[tracing::instrument(
@@ -789,4 +1121,276 @@ impl WorkdayDatabaseRepository for PostgresWorkdayRepository {
 
         Ok(())
     }
+
+    #[tracing::instrument(
+        name = "db.workdays.create_document_share",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            driver_id = %share.fk_driver_id,
+            share_id = %share.pk_document_share_id,
+        )
+    )]
+    async fn create_document_share(
+        &self,
+        share: DocumentShareRow,
+    ) -> Result<DocumentShareRow, WorkdayError> {
+        sqlx::query_as::<_, DocumentShareRow>(
+            r#"
+            INSERT INTO document_shares
+                (pk_document_share_id, fk_driver_id, month, year, token_hash,
+                 recipient_email, password_hash, expires_at, revoked_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(share.pk_document_share_id)
+        .bind(share.fk_driver_id)
+        .bind(share.month)
+        .bind(share.year)
+        .bind(&share.token_hash)
+        .bind(&share.recipient_email)
+        .bind(&share.password_hash)
+        .bind(share.expires_at)
+        .bind(share.revoked_at)
+        .bind(share.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert document share: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_document_shares",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
+    )]
+    async fn get_document_shares(
+        &self,
+        driver_id: Uuid,
+    ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+        sqlx::query_as::<_, DocumentShareRow>(
+            r#"
+            SELECT * FROM document_shares
+            WHERE fk_driver_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(driver_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get document shares: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_document_share",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            driver_id = %driver_id,
+            share_id = %share_id,
+        )
+    )]
+    async fn get_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+        sqlx::query_as::<_, DocumentShareRow>(
+            r#"
+            SELECT * FROM document_shares
+            WHERE fk_driver_id = $1 AND pk_document_share_id = $2
+            "#,
+        )
+        .bind(driver_id)
+        .bind(share_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get document share: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_document_share_by_token_hash",
+        skip_all,
+        fields(db.system = "postgresql", db.operation = "SELECT")
+    )]
+    async fn get_document_share_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+        sqlx::query_as::<_, DocumentShareRow>(
+            r#"SELECT * FROM document_shares WHERE token_hash = $1"#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get document share by token: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.revoke_document_share",
+        skip(self),
+        fields(
+            db.system = "postgresql",
+            db.operation = "UPDATE",
+            driver_id = %driver_id,
+            share_id = %share_id,
+        )
+    )]
+    async fn revoke_document_share(
+        &self,
+        driver_id: Uuid,
+        share_id: Uuid,
+    ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+        sqlx::query_as::<_, DocumentShareRow>(
+            r#"
+            UPDATE document_shares
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE fk_driver_id = $1 AND pk_document_share_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(driver_id)
+        .bind(share_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to revoke document share: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.create_document_share_access",
+        skip_all,
+        fields(
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            share_id = %access.fk_document_share_id,
+        )
+    )]
+    async fn create_document_share_access(
+        &self,
+        access: DocumentShareAccessRow,
+    ) -> Result<(), WorkdayError> {
+        sqlx::query(
+            r#"
+            INSERT INTO document_share_accesses
+                (pk_document_share_access_id, fk_document_share_id, user_agent, is_granted, is_wrong_password, accessed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(access.pk_document_share_access_id)
+        .bind(access.fk_document_share_id)
+        .bind(&access.user_agent)
+        .bind(access.is_granted)
+        .bind(access.is_wrong_password)
+        .bind(access.accessed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert document share access: {:?}", e);
+            WorkdayError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.get_document_share_accesses",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", share_id = %share_id)
+    )]
+    async fn get_document_share_accesses(
+        &self,
+        share_id: Uuid,
+    ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+        sqlx::query_as::<_, DocumentShareAccessRow>(
+            r#"
+            SELECT * FROM document_share_accesses
+            WHERE fk_document_share_id = $1
+            ORDER BY accessed_at DESC
+            "#,
+        )
+        .bind(share_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to get document share accesses: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.count_document_shares_since",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", driver_id = %driver_id)
+    )]
+    async fn count_document_shares_since(
+        &self,
+        driver_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<i64, WorkdayError> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM document_shares
+            WHERE fk_driver_id = $1
+            AND created_at >= $2
+            "#,
+        )
+        .bind(driver_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to count document shares: {:?}", e);
+            WorkdayError::DatabaseError
+        })
+    }
+
+    #[tracing::instrument(
+        name = "db.workdays.count_wrong_password_document_share_accesses_since",
+        skip(self),
+        fields(db.system = "postgresql", db.operation = "SELECT", share_id = %share_id)
+    )]
+    async fn count_wrong_password_document_share_accesses_since(
+        &self,
+        share_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<i64, WorkdayError> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM document_share_accesses
+            WHERE fk_document_share_id = $1
+            AND is_wrong_password
+            AND accessed_at >= $2
+            "#,
+        )
+        .bind(share_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Failed to count wrong password document share accesses: {:?}",
+                e
+            );
+            WorkdayError::DatabaseError
+        })
+    }
 }
@@ -1,8 +1,15 @@
-use clap::Parser;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, ValueEnum};
 use lettre::SmtpTransport;
-use lettre::message::MessageBuilder;
-use lettre::message::header::ContentType;
+use lettre::message::{Mailbox, MessageBuilder};
+use lettre::transport::smtp::PoolConfig;
 use lettre::transport::smtp::authentication::Credentials;
+use plannify_driver_api_core::domain::common::config::{
+    DocumentRenderer, DocumentServiceConfig, MailDkimConfig, StorageBackend, StorageConfig,
+    StorageEncryptionConfig,
+};
 
 #[derive(Clone, Parser, Debug)]
 pub struct Config {
@@ -27,6 +34,26 @@ pub struct Config {
     )]
     pub frontend_url: String,
 
+    #[arg(
+        long = "public-api-url",
+        env = "PUBLIC_API_URL",
+        default_value = "https://api.plannify.be"
+    )]
+    pub public_api_url: String,
+
+    #[arg(long = "mail-unsubscribe-secret", env = "MAIL_UNSUBSCRIBE_SECRET")]
+    pub mail_unsubscribe_secret: String,
+
+    #[arg(long = "vapid-private-key", env = "VAPID_PRIVATE_KEY")]
+    pub vapid_private_key: Option<String>,
+
+    #[arg(
+        long = "vapid-subject",
+        env = "VAPID_SUBJECT",
+        default_value = "mailto:contact@plannify.be"
+    )]
+    pub vapid_subject: String,
+
     #[arg(
         long = "pdf-service-endpoint",
         env = "PDF_SERVICE_ENDPOINT",
@@ -34,6 +61,13 @@ pub struct Config {
     )]
     pub pdf_service_endpoint: String,
 
+    #[arg(
+        long = "pdf-renderer",
+        env = "PDF_RENDERER",
+        default_value = "grpc-with-fallback"
+    )]
+    pub pdf_renderer: PdfRendererKind,
+
     #[arg(
         long = "workday-garbage-retention-days",
         env = "WORKDAY_GARBAGE_RETENTION_DAYS",
@@ -51,6 +85,29 @@ pub struct Config {
     pub otel: OtelConfig,
 }
 
+impl Config {
+    pub fn document_service(&self) -> DocumentServiceConfig {
+        DocumentServiceConfig {
+            renderer: match self.pdf_renderer {
+                PdfRendererKind::Grpc => DocumentRenderer::Grpc,
+                PdfRendererKind::GrpcWithFallback => DocumentRenderer::GrpcWithFallback,
+                PdfRendererKind::Local => DocumentRenderer::Local,
+            },
+            ..DocumentServiceConfig::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum PdfRendererKind {
+    /// PDF service only
+    Grpc,
+    /// PDF service, built-in renderer while it is unavailable
+    GrpcWithFallback,
+    /// Built-in renderer only
+    Local,
+}
+
 #[derive(Clone, Parser, Debug)]
 pub struct SmtpConfig {
     #[arg(
@@ -75,6 +132,44 @@ pub struct SmtpConfig {
 
     #[arg(long = "smtp-domain", env = "SMTP_DOMAIN", default_value = "localhost")]
     pub domain: String,
+
+    /// Defaults to 465 with implicit TLS, 587 with STARTTLS and 25 without encryption
+    #[arg(long = "smtp-port", env = "SMTP_PORT")]
+    pub port: Option<u16>,
+
+    #[arg(long = "smtp-tls", env = "SMTP_TLS", default_value = "tls")]
+    pub tls: SmtpTlsMode,
+
+    #[arg(long = "smtp-pool-size", env = "SMTP_POOL_SIZE", default_value = "10")]
+    pub pool_size: u32,
+
+    #[arg(long = "smtp-timeout", env = "SMTP_TIMEOUT", default_value = "60")]
+    pub timeout_secs: u64,
+
+    /// Mails are DKIM signed when both the selector and the private key file are set
+    #[arg(long = "smtp-dkim-selector", env = "SMTP_DKIM_SELECTOR")]
+    pub dkim_selector: Option<String>,
+
+    /// Defaults to the domain of the default sender address
+    #[arg(long = "smtp-dkim-domain", env = "SMTP_DKIM_DOMAIN")]
+    pub dkim_domain: Option<String>,
+
+    /// PKCS#1 PEM RSA private key, or base64 Ed25519 private key
+    #[arg(
+        long = "smtp-dkim-private-key-path",
+        env = "SMTP_DKIM_PRIVATE_KEY_PATH"
+    )]
+    pub dkim_private_key_path: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq)]
+pub enum SmtpTlsMode {
+    /// Implicit TLS, usually on port 465
+    Tls,
+    /// Plain connection upgraded with STARTTLS, usually on port 587
+    Starttls,
+    /// No encryption, for local test servers only
+    None,
 }
 
 impl SmtpConfig {
@@ -82,16 +177,44 @@ impl SmtpConfig {
         MessageBuilder::new()
             .from(self.default_sender.parse().unwrap())
             .reply_to(self.default_sender_reply_to.parse().unwrap())
-            .header(ContentType::TEXT_HTML)
     }
 
     pub fn to_transport(&self) -> SmtpTransport {
         let creds = Credentials::new(self.username.clone(), self.password.clone());
-        SmtpTransport::relay(&self.domain)
-            .unwrap_or_else(|_| SmtpTransport::builder_dangerous("localhost"))
+
+        let builder = match self.tls {
+            SmtpTlsMode::Tls => SmtpTransport::relay(&self.domain)
+                .unwrap_or_else(|_| SmtpTransport::builder_dangerous("localhost")),
+            SmtpTlsMode::Starttls => SmtpTransport::starttls_relay(&self.domain)
+                .unwrap_or_else(|_| SmtpTransport::builder_dangerous("localhost")),
+            SmtpTlsMode::None => SmtpTransport::builder_dangerous(&self.domain),
+        };
+        let builder = match self.port {
+            Some(port) => builder.port(port),
+            None => builder,
+        };
+
+        builder
             .credentials(creds)
+            .timeout(Some(Duration::from_secs(self.timeout_secs)))
+            .pool_config(PoolConfig::new().max_size(self.pool_size))
             .build()
     }
+
+    pub fn dkim(&self) -> MailDkimConfig {
+        let domain = self.dkim_domain.clone().unwrap_or_else(|| {
+            self.default_sender
+                .parse::<Mailbox>()
+                .map(|mailbox| mailbox.email.domain().to_string())
+                .unwrap_or_default()
+        });
+
+        MailDkimConfig {
+            selector: self.dkim_selector.clone(),
+            domain,
+            private_key_path: self.dkim_private_key_path.clone(),
+        }
+    }
 }
 
 #[derive(Clone, Parser, Debug)]
@@ -118,6 +241,60 @@ pub struct S3Config {
 
     #[arg(long = "s3-region", env = "S3_REGION", default_value = "garage")]
     pub region: String,
+
+    #[arg(
+        long = "storage-backend",
+        env = "STORAGE_BACKEND",
+        default_value = "s3"
+    )]
+    pub storage_backend: StorageBackendKind,
+
+    #[arg(
+        long = "storage-local-root-dir",
+        env = "STORAGE_LOCAL_ROOT_DIR",
+        default_value = "storage"
+    )]
+    pub storage_local_root_dir: PathBuf,
+
+    #[arg(
+        long = "storage-url-secret",
+        env = "STORAGE_URL_SECRET",
+        default_value = ""
+    )]
+    pub storage_url_secret: String,
+
+    /// `<id>:<base64 32-byte key>` master keys, the current one first.
+    #[arg(
+        long = "storage-master-keys",
+        env = "STORAGE_MASTER_KEYS",
+        value_delimiter = ','
+    )]
+    pub storage_master_keys: Vec<String>,
+}
+
+impl S3Config {
+    pub fn storage(&self, public_api_url: &str) -> StorageConfig {
+        StorageConfig {
+            backend: match self.storage_backend {
+                StorageBackendKind::S3 => StorageBackend::S3,
+                StorageBackendKind::LocalFs => StorageBackend::LocalFs,
+            },
+            local_root_dir: self.storage_local_root_dir.clone(),
+            api_url: public_api_url.to_string(),
+            url_secret: self.storage_url_secret.clone(),
+            encryption: StorageEncryptionConfig {
+                master_keys: self.storage_master_keys.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum StorageBackendKind {
+    /// S3 compatible bucket
+    S3,
+    /// Local directory
+    LocalFs,
 }
 
 #[derive(Clone, Parser, Debug)]
@@ -34,8 +34,9 @@ mod tests {
     use plannify_driver_api_core::{
         domain::workday::{
             entities::{
-                CreateWorkdayRequest, UpdateWorkdayRequest, WorkdayDocument,
-                WorkdayDocumentInformation, WorkdayGarbageRow, WorkdayRow,
+                CreateWorkdayRequest, DocumentChecksum, DocumentIntegrity, DocumentShareAccessRow,
+                DocumentShareRow, SupersededWorkdayDocument, UpdateWorkdayRequest, WorkdayDocument,
+                WorkdayDocumentInformation, WorkdayGarbageRow, WorkdayRow, YearlyDocument,
             },
             port::WorkdayDatabaseRepository,
         },
@@ -89,6 +90,14 @@ mod tests {
         ) -> Result<(Vec<WorkdayRow>, u32), WorkdayError> {
             unreachable!()
         }
+        async fn get_recorded_workday_dates(
+            &self,
+            _: Uuid,
+            _: NaiveDate,
+            _: NaiveDate,
+        ) -> Result<Vec<NaiveDate>, WorkdayError> {
+            unreachable!()
+        }
         async fn get_workday_years(&self, _: Uuid) -> Result<Vec<i32>, WorkdayError> {
             unreachable!()
         }
@@ -159,9 +168,37 @@ mod tests {
             _: i32,
             _: String,
             _: String,
+            _: DocumentIntegrity,
+            _: bool,
         ) -> Result<WorkdayDocument, WorkdayError> {
             unreachable!()
         }
+        async fn get_fallback_document_months(
+            &self,
+        ) -> Result<Vec<(Uuid, i32, i32)>, WorkdayError> {
+            unreachable!()
+        }
+        async fn update_regenerated_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: DocumentIntegrity,
+        ) -> Result<WorkdayDocument, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workday_document_by_verification_code(
+            &self,
+            _: &str,
+        ) -> Result<Option<WorkdayDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_superseded_workday_document_by_verification_code(
+            &self,
+            _: &str,
+        ) -> Result<Option<SupersededWorkdayDocument>, WorkdayError> {
+            unreachable!()
+        }
         async fn get_pending_document_months(
             &self,
             _: NaiveDate,
@@ -171,16 +208,97 @@ mod tests {
         async fn get_all_document_s3_paths(&self) -> Result<Vec<String>, WorkdayError> {
             unreachable!()
         }
-        async fn get_document_s3_paths_batch(
+        async fn get_document_checksums_batch(
             &self,
             _: Option<&str>,
             _: i64,
-        ) -> Result<Vec<String>, WorkdayError> {
+        ) -> Result<Vec<DocumentChecksum>, WorkdayError> {
             unreachable!()
         }
         async fn delete_document_by_s3_path(&self, _: &str) -> Result<(), WorkdayError> {
             unreachable!()
         }
+        async fn get_yearly_document_record(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Option<YearlyDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_pending_document_years(
+            &self,
+            _: i32,
+        ) -> Result<Vec<(Uuid, i32)>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_yearly_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: String,
+            _: String,
+            _: String,
+        ) -> Result<YearlyDocument, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share(
+            &self,
+            _: DocumentShareRow,
+        ) -> Result<DocumentShareRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_shares(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_by_token_hash(
+            &self,
+            _: &str,
+        ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn revoke_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share_access(
+            &self,
+            _: DocumentShareAccessRow,
+        ) -> Result<(), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_accesses(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn count_document_shares_since(
+            &self,
+            _: Uuid,
+            _: DateTime<Utc>,
+        ) -> Result<i64, WorkdayError> {
+            unreachable!()
+        }
+        async fn count_wrong_password_document_share_accesses_since(
+            &self,
+            _: Uuid,
+            _: DateTime<Utc>,
+        ) -> Result<i64, WorkdayError> {
+            unreachable!()
+        }
     }
 
     #[tokio::test]
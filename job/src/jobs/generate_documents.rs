@@ -1,12 +1,25 @@
+use std::collections::BTreeMap;
+
 use chrono::{Datelike, Months, NaiveDate, Utc};
 use plannify_driver_api_core::{
     application::{DriverRepositories, DriverService},
     domain::{
+        common::constants::EnumDriverMailType,
+        driver::port::DriverDatabaseRepository,
+        mail::{
+            entities::{MailStatus, monthly_report_file_name},
+            port::MailDatabaseRepository,
+        },
+        notification::{
+            entities::Notification, port::NotificationChannelRepository,
+            services::NotificationDispatcher,
+        },
         storage::port::StorageRepository,
         workday::port::{WorkdayCacheRepository, WorkdayDatabaseRepository, WorkdayService},
     },
 };
 use tracing::{error, info, warn};
+use uuid::Uuid;
 
 pub async fn run(repos: &DriverRepositories, months_ago: u32) -> i32 {
     let service: DriverService = repos.clone().into();
@@ -15,16 +28,25 @@ pub async fn run(repos: &DriverRepositories, months_ago: u32) -> i32 {
         &repos.workday_cache_repository,
         &repos.storage_repository,
         &service,
+        &repos.driver_database_repository,
+        &repos.mail_database_repository,
+        &repos.mail_smtp_repository,
+        &repos.web_push_repository,
         months_ago,
     )
     .await
 }
 
-async fn run_inner<WDB, WC, SR, WS>(
+#[allow(clippy::too_many_arguments)]
+async fn run_inner<WDB, WC, SR, WS, DDB, MDB, EC, PC>(
     workday_db: &WDB,
     cache: &WC,
     storage: &SR,
     service: &WS,
+    driver_db: &DDB,
+    mail_db: &MDB,
+    email_channel: &EC,
+    push_channel: &PC,
     months_ago: u32,
 ) -> i32
 where
@@ -32,6 +54,10 @@ where
     WC: WorkdayCacheRepository,
     SR: StorageRepository,
     WS: WorkdayService,
+    DDB: DriverDatabaseRepository,
+    MDB: MailDatabaseRepository,
+    EC: NotificationChannelRepository,
+    PC: NotificationChannelRepository,
 {
     info!("Starting generate_documents job");
 
@@ -53,20 +79,20 @@ where
     let total = pending.len();
     if total == 0 {
         info!("No pending documents to generate");
-        return 0;
+    } else {
+        info!("Found {} month(s) pending document generation", total);
     }
 
-    info!("Found {} month(s) pending document generation", total);
-
     let mut generated = 0u32;
     let mut failed = 0u32;
+    let mut generated_by_driver: BTreeMap<Uuid, Vec<(u32, i32)>> = BTreeMap::new();
 
     for (driver_id, month, year) in &pending {
-        let pdf = match service
-            .get_workday_document_by_month(*driver_id, *month, *year)
+        let document = match service
+            .generate_verifiable_workday_document(*driver_id, *month, *year)
             .await
         {
-            Ok(Some(bytes)) => bytes,
+            Ok(Some(document)) => document,
             Ok(None) => {
                 warn!(
                     driver_id = %driver_id,
@@ -89,13 +115,13 @@ where
             }
         };
 
-        let s3_key = format!(
-            "drivers/{}/workdays/monthly-reports/workdays-{}-{:02}.pdf",
-            driver_id, year, month
-        );
-        let file_name = format!("workdays-{}-{:02}.pdf", year, month);
+        let s3_key = monthly_document_key(*driver_id, *month, *year);
+        let file_name = monthly_report_file_name(*month as u32, *year);
 
-        if let Err(e) = storage.upload(&s3_key, pdf, "application/pdf").await {
+        if let Err(e) = storage
+            .upload(&s3_key, document.pdf, "application/pdf")
+            .await
+        {
             failed += 1;
             error!(
                 driver_id = %driver_id,
@@ -108,7 +134,15 @@ where
         }
 
         let doc = match workday_db
-            .create_workday_document(*driver_id, *month, *year, s3_key, file_name)
+            .create_workday_document(
+                *driver_id,
+                *month,
+                *year,
+                s3_key,
+                file_name,
+                document.integrity,
+                document.is_fallback,
+            )
             .await
         {
             Ok(doc) => doc,
@@ -135,6 +169,10 @@ where
         let _ = cache.delete_documents_by_year(*driver_id, *year).await;
 
         generated += 1;
+        generated_by_driver
+            .entry(*driver_id)
+            .or_default()
+            .push((*month as u32, *year));
         info!(
             driver_id = %driver_id,
             month = %month,
@@ -143,11 +181,239 @@ where
         );
     }
 
-    info!(total, generated, failed, "generate_documents job completed");
+    // The documents are stored whatever happens next, a driver left unnotified does not
+    // fail the job.
+    let dispatcher = NotificationDispatcher::new(mail_db, email_channel, push_channel);
+    for (driver_id, documents) in generated_by_driver {
+        notify_documents_ready(driver_db, mail_db, &dispatcher, driver_id, documents).await;
+    }
+
+    let (regenerated, regeneration_failed) =
+        regenerate_fallback_documents(workday_db, cache, storage, service).await;
+    failed += regeneration_failed;
+
+    info!(
+        total,
+        generated, regenerated, failed, "generate_documents job completed"
+    );
 
     if failed > 0 { 1 } else { 0 }
 }
 
+fn monthly_document_key(driver_id: Uuid, month: i32, year: i32) -> String {
+    format!(
+        "drivers/{}/workdays/monthly-reports/workdays-{}-{:02}.pdf",
+        driver_id, year, month
+    )
+}
+
+/// Renders again, in place, the documents stored while the PDF service was unavailable.
+/// Stops at the first one still rendered by the fallback renderer, the service being down.
+/// Returns how many were regenerated and how many failed.
+async fn regenerate_fallback_documents<WDB, WC, SR, WS>(
+    workday_db: &WDB,
+    cache: &WC,
+    storage: &SR,
+    service: &WS,
+) -> (u32, u32)
+where
+    WDB: WorkdayDatabaseRepository,
+    WC: WorkdayCacheRepository,
+    SR: StorageRepository,
+    WS: WorkdayService,
+{
+    let fallbacks = match workday_db.get_fallback_document_months().await {
+        Ok(f) => f,
+        Err(e) => {
+            error!("Failed to fetch fallback documents: {}", e);
+            return (0, 1);
+        }
+    };
+    if fallbacks.is_empty() {
+        return (0, 0);
+    }
+
+    info!(
+        "Found {} fallback document(s) to regenerate",
+        fallbacks.len()
+    );
+
+    let mut regenerated = 0u32;
+    let mut failed = 0u32;
+
+    for (driver_id, month, year) in fallbacks {
+        let document = match service
+            .generate_verifiable_workday_document(driver_id, month, year)
+            .await
+        {
+            Ok(Some(document)) if !document.is_fallback => document,
+            Ok(Some(_)) => {
+                warn!("PDF service still unavailable, fallback documents kept for now");
+                break;
+            }
+            Ok(None) => {
+                warn!(
+                    driver_id = %driver_id,
+                    month = %month,
+                    year = %year,
+                    "No workdays found for this month"
+                );
+                continue;
+            }
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    month = %month,
+                    year = %year,
+                    error = ?e,
+                    "Failed to regenerate document"
+                );
+                continue;
+            }
+        };
+
+        let s3_key = monthly_document_key(driver_id, month, year);
+        if let Err(e) = storage
+            .upload(&s3_key, document.pdf, "application/pdf")
+            .await
+        {
+            failed += 1;
+            error!(
+                driver_id = %driver_id,
+                month = %month,
+                year = %year,
+                error = ?e,
+                "Failed to upload regenerated document to S3"
+            );
+            continue;
+        }
+
+        let doc = match workday_db
+            .update_regenerated_workday_document(driver_id, month, year, document.integrity)
+            .await
+        {
+            Ok(doc) => doc,
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    month = %month,
+                    year = %year,
+                    error = ?e,
+                    "Failed to update regenerated document record"
+                );
+                continue;
+            }
+        };
+
+        let _ = cache
+            .set_workday_document_record(driver_id, month, year, Some(doc))
+            .await;
+        let _ = cache
+            .delete_generated_documents_by_year(driver_id, year)
+            .await;
+        let _ = cache.delete_documents_by_year(driver_id, year).await;
+
+        regenerated += 1;
+        info!(
+            driver_id = %driver_id,
+            month = %month,
+            year = %year,
+            "Fallback document regenerated"
+        );
+    }
+
+    (regenerated, failed)
+}
+
+async fn notify_documents_ready<DDB, MDB, EC, PC>(
+    driver_db: &DDB,
+    mail_db: &MDB,
+    dispatcher: &NotificationDispatcher<'_, MDB, EC, PC>,
+    driver_id: Uuid,
+    documents: Vec<(u32, i32)>,
+) where
+    DDB: DriverDatabaseRepository,
+    MDB: MailDatabaseRepository,
+    EC: NotificationChannelRepository,
+    PC: NotificationChannelRepository,
+{
+    let driver = match driver_db.get_driver_by_id(driver_id).await {
+        Ok(Some(driver)) if driver.deactivated_at.is_none() => driver,
+        Ok(_) => return,
+        Err(e) => {
+            warn!(
+                driver_id = %driver_id,
+                error = ?e,
+                "Failed to get driver to notify about its documents"
+            );
+            return;
+        }
+    };
+
+    let description = format!(
+        "Documents disponibles : {}",
+        documents
+            .iter()
+            .map(|(month, year)| format!("{}/{}", month, year))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let mail = match mail_db
+        .create_mail(
+            driver.clone(),
+            EnumDriverMailType::DocumentsReady,
+            description,
+            None,
+        )
+        .await
+    {
+        Ok(m) => m,
+        Err(e) => {
+            warn!(
+                driver_id = %driver_id,
+                error = ?e,
+                "Failed to create mail record"
+            );
+            return;
+        }
+    };
+
+    let notification = Notification::DocumentsReady { documents };
+    match dispatcher.dispatch(&driver, &notification).await {
+        Ok(channels) if !channels.is_empty() => {
+            let _ = mail_db
+                .update_mail_status(
+                    mail.pk_driver_mail_id,
+                    MailStatus::SUCCESS,
+                    Some(Utc::now()),
+                )
+                .await;
+            info!(
+                driver_id = %driver_id,
+                channels = ?channels,
+                "Driver notified about its documents"
+            );
+        }
+        Ok(_) => {
+            let _ = mail_db
+                .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                .await;
+        }
+        Err(e) => {
+            let _ = mail_db
+                .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                .await;
+            warn!(
+                driver_id = %driver_id,
+                error = ?e,
+                "Failed to notify driver about its documents"
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::run_inner;
@@ -155,11 +421,31 @@ mod tests {
     use chrono::{DateTime, NaiveDate, Utc};
     use plannify_driver_api_core::{
         domain::{
-            storage::port::StorageRepository,
+            common::constants::EnumDriverMailType,
+            document::entities::RenderedDocument,
+            driver::{
+                entities::{CreateDriverRequest, DriverLanguage},
+                port::{DriverDatabaseRepository, MockDriverDatabaseRepository},
+            },
+            mail::{
+                entities::{MailStatus, NotificationChannel},
+                port::{
+                    MailDatabaseRepository, MockMailDatabaseRepository, MockMailSmtpRepository,
+                },
+            },
+            notification::port::MockNotificationChannelRepository,
+            storage::{
+                entities::{ByteRange, DocumentDownload, StorageObject},
+                port::StorageRepository,
+            },
             workday::{
                 entities::{
-                    CreateWorkdayRequest, UpdateWorkdayRequest, Workday, WorkdayDocument,
-                    WorkdayDocumentInformation, WorkdayGarbageRow, WorkdayRow,
+                    CreateDocumentShareRequest, CreateWorkdayRequest, DocumentChecksum,
+                    DocumentIntegrity, DocumentShareAccessRow, DocumentShareRow,
+                    GeneratedWorkdayDocument, SupersededWorkdayDocument, UpdateWorkdayRequest,
+                    Workday, WorkdayDocument, WorkdayDocumentInformation,
+                    WorkdayDocumentVerification, WorkdayGarbageRow, WorkdayRow, YearlyDocument,
+                    document_sha256,
                 },
                 port::{MockWorkdayCacheRepository, WorkdayDatabaseRepository, WorkdayService},
             },
@@ -177,36 +463,60 @@ mod tests {
 
     // --- Stubs ---
 
-    type UploadLog = Arc<Mutex<Vec<(Uuid, i32, i32, String)>>>;
+    type UploadLog = Arc<Mutex<Vec<(Uuid, i32, i32, String, DocumentIntegrity, bool)>>>;
+    type RegenerationLog = Arc<Mutex<Vec<(Uuid, i32, i32, DocumentIntegrity)>>>;
 
     struct StubWorkdayDb {
         pending: Vec<(Uuid, i32, i32)>,
+        fallbacks: Vec<(Uuid, i32, i32)>,
         create_doc_fail: bool,
         uploads: UploadLog,
+        regenerations: RegenerationLog,
     }
 
     impl StubWorkdayDb {
         fn empty() -> Self {
             Self {
                 pending: vec![],
+                fallbacks: vec![],
                 create_doc_fail: false,
                 uploads: Default::default(),
+                regenerations: Default::default(),
             }
         }
         fn with_pending(driver_id: Uuid, month: i32, year: i32) -> Self {
             Self {
                 pending: vec![(driver_id, month, year)],
-                create_doc_fail: false,
-                uploads: Default::default(),
+                ..Self::empty()
             }
         }
         fn with_pending_and_failing_persist(driver_id: Uuid, month: i32, year: i32) -> Self {
             Self {
                 pending: vec![(driver_id, month, year)],
                 create_doc_fail: true,
-                uploads: Default::default(),
+                ..Self::empty()
             }
         }
+        fn with_fallbacks(fallbacks: Vec<(Uuid, i32, i32)>) -> Self {
+            Self {
+                fallbacks,
+                ..Self::empty()
+            }
+        }
+    }
+
+    fn stub_document(driver_id: Uuid, month: i32, year: i32, is_fallback: bool) -> WorkdayDocument {
+        WorkdayDocument {
+            fk_driver_id: driver_id,
+            month,
+            year,
+            s3_file_path: String::new(),
+            file_name: String::new(),
+            created_at: Utc::now(),
+            sha256: None,
+            verification_code: None,
+            is_fallback,
+        }
     }
 
     impl WorkdayDatabaseRepository for StubWorkdayDb {
@@ -223,22 +533,51 @@ mod tests {
             year: i32,
             s3_key: String,
             _: String,
+            integrity: DocumentIntegrity,
+            is_fallback: bool,
         ) -> Result<WorkdayDocument, WorkdayError> {
             if self.create_doc_fail {
                 return Err(WorkdayError::DatabaseError);
             }
-            self.uploads
-                .lock()
-                .unwrap()
-                .push((driver_id, month, year, s3_key));
-            Ok(WorkdayDocument {
-                fk_driver_id: driver_id,
+            self.uploads.lock().unwrap().push((
+                driver_id,
                 month,
                 year,
-                s3_file_path: String::new(),
-                file_name: String::new(),
-                created_at: Utc::now(),
-            })
+                s3_key,
+                integrity,
+                is_fallback,
+            ));
+            Ok(stub_document(driver_id, month, year, is_fallback))
+        }
+        async fn get_fallback_document_months(
+            &self,
+        ) -> Result<Vec<(Uuid, i32, i32)>, WorkdayError> {
+            Ok(self.fallbacks.clone())
+        }
+        async fn update_regenerated_workday_document(
+            &self,
+            driver_id: Uuid,
+            month: i32,
+            year: i32,
+            integrity: DocumentIntegrity,
+        ) -> Result<WorkdayDocument, WorkdayError> {
+            self.regenerations
+                .lock()
+                .unwrap()
+                .push((driver_id, month, year, integrity));
+            Ok(stub_document(driver_id, month, year, false))
+        }
+        async fn get_workday_document_by_verification_code(
+            &self,
+            _: &str,
+        ) -> Result<Option<WorkdayDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_superseded_workday_document_by_verification_code(
+            &self,
+            _: &str,
+        ) -> Result<Option<SupersededWorkdayDocument>, WorkdayError> {
+            unreachable!()
         }
         async fn get_workday_by_date(
             &self,
@@ -265,6 +604,14 @@ mod tests {
         ) -> Result<(Vec<WorkdayRow>, u32), WorkdayError> {
             unreachable!()
         }
+        async fn get_recorded_workday_dates(
+            &self,
+            _: Uuid,
+            _: NaiveDate,
+            _: NaiveDate,
+        ) -> Result<Vec<NaiveDate>, WorkdayError> {
+            unreachable!()
+        }
         async fn get_workday_years(&self, _: Uuid) -> Result<Vec<i32>, WorkdayError> {
             unreachable!()
         }
@@ -334,16 +681,97 @@ mod tests {
         async fn get_all_document_s3_paths(&self) -> Result<Vec<String>, WorkdayError> {
             unreachable!()
         }
-        async fn get_document_s3_paths_batch(
+        async fn get_document_checksums_batch(
             &self,
             _: Option<&str>,
             _: i64,
-        ) -> Result<Vec<String>, WorkdayError> {
+        ) -> Result<Vec<DocumentChecksum>, WorkdayError> {
             unreachable!()
         }
         async fn delete_document_by_s3_path(&self, _: &str) -> Result<(), WorkdayError> {
             unreachable!()
         }
+        async fn get_yearly_document_record(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Option<YearlyDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_pending_document_years(
+            &self,
+            _: i32,
+        ) -> Result<Vec<(Uuid, i32)>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_yearly_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: String,
+            _: String,
+            _: String,
+        ) -> Result<YearlyDocument, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share(
+            &self,
+            _: DocumentShareRow,
+        ) -> Result<DocumentShareRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_shares(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_by_token_hash(
+            &self,
+            _: &str,
+        ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn revoke_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Option<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share_access(
+            &self,
+            _: DocumentShareAccessRow,
+        ) -> Result<(), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_accesses(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn count_document_shares_since(
+            &self,
+            _: Uuid,
+            _: DateTime<Utc>,
+        ) -> Result<i64, WorkdayError> {
+            unreachable!()
+        }
+        async fn count_wrong_password_document_share_accesses_since(
+            &self,
+            _: Uuid,
+            _: DateTime<Utc>,
+        ) -> Result<i64, WorkdayError> {
+            unreachable!()
+        }
     }
 
     struct StubStorage {
@@ -382,6 +810,13 @@ mod tests {
                 .cloned()
                 .ok_or(StorageError::ObjectNotFound)
         }
+        async fn stream(
+            &self,
+            _: &str,
+            _: Option<ByteRange>,
+        ) -> Result<StorageObject, StorageError> {
+            unreachable!()
+        }
         async fn delete(&self, _: &str) -> Result<(), StorageError> {
             unreachable!()
         }
@@ -403,6 +838,7 @@ mod tests {
 
     struct StubWorkdayService {
         pdf: Option<Bytes>,
+        is_fallback: bool,
         fail: bool,
     }
 
@@ -410,29 +846,120 @@ mod tests {
         fn returns_pdf(bytes: Bytes) -> Self {
             Self {
                 pdf: Some(bytes),
+                is_fallback: false,
+                fail: false,
+            }
+        }
+        fn returns_fallback_pdf(bytes: Bytes) -> Self {
+            Self {
+                pdf: Some(bytes),
+                is_fallback: true,
                 fail: false,
             }
         }
         fn returns_none() -> Self {
             Self {
                 pdf: None,
+                is_fallback: false,
                 fail: false,
             }
         }
     }
 
     impl WorkdayService for StubWorkdayService {
-        async fn get_workday_document_by_month(
+        async fn generate_verifiable_workday_document(
             &self,
             _: Uuid,
             _: i32,
             _: i32,
-        ) -> Result<Option<Bytes>, WorkdayError> {
+        ) -> Result<Option<GeneratedWorkdayDocument>, WorkdayError> {
             if self.fail {
-                Err(WorkdayError::Internal)
-            } else {
-                Ok(self.pdf.clone())
+                return Err(WorkdayError::Internal);
             }
+            Ok(self.pdf.clone().map(|pdf| GeneratedWorkdayDocument {
+                integrity: DocumentIntegrity {
+                    sha256: document_sha256(&pdf),
+                    verification_code: "ABCD-EFGH-JKMN".to_string(),
+                },
+                pdf,
+                is_fallback: self.is_fallback,
+            }))
+        }
+        async fn verify_workday_document(
+            &self,
+            _: &str,
+        ) -> Result<WorkdayDocumentVerification, WorkdayError> {
+            unreachable!()
+        }
+        async fn generate_yearly_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Option<RenderedDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn download_yearly_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: Option<ByteRange>,
+        ) -> Result<Option<DocumentDownload>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: CreateDocumentShareRequest,
+        ) -> Result<(String, DocumentShareRow), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_shares(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn revoke_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<DocumentShareRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_accesses(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_shared_document(
+            &self,
+            _: &str,
+            _: Option<String>,
+            _: Option<String>,
+            _: Option<ByteRange>,
+        ) -> Result<(DocumentDownload, String), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workday_document_by_month(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+        ) -> Result<Option<Bytes>, WorkdayError> {
+            unreachable!()
+        }
+        async fn download_workday_document_by_month(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: Option<ByteRange>,
+        ) -> Result<Option<DocumentDownload>, WorkdayError> {
+            unreachable!()
         }
         async fn get_workday_by_date(
             &self,
@@ -520,6 +1047,10 @@ mod tests {
             &MockWorkdayCacheRepository::new(),
             &StubStorage::ok(),
             &StubWorkdayService::returns_none(),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             3,
         )
         .await;
@@ -534,6 +1065,10 @@ mod tests {
             &MockWorkdayCacheRepository::new(),
             &StubStorage::ok(),
             &StubWorkdayService::returns_none(),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             3,
         )
         .await;
@@ -552,18 +1087,108 @@ mod tests {
             &MockWorkdayCacheRepository::new(),
             &storage,
             &StubWorkdayService::returns_pdf(pdf_bytes.clone()),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
+            3,
+        )
+        .await;
+
+        assert_eq!(result, 0);
+
+        let expected_key = format!(
+            "drivers/{}/workdays/monthly-reports/workdays-2020-03.pdf",
+            driver_id
+        );
+        assert_eq!(storage.download(&expected_key).await.unwrap(), pdf_bytes);
+        let uploads = db.uploads.lock().unwrap();
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].4.sha256, document_sha256(&pdf_bytes));
+        assert_eq!(uploads[0].4.verification_code, "ABCD-EFGH-JKMN");
+        assert!(!uploads[0].5);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_document_is_stored_as_fallback() {
+        let driver_id = Uuid::new_v4();
+        let db = StubWorkdayDb::with_pending(driver_id, 3, 2020);
+
+        let result = run_inner(
+            &db,
+            &MockWorkdayCacheRepository::new(),
+            &StubStorage::ok(),
+            &StubWorkdayService::returns_fallback_pdf(Bytes::from("fallback-pdf")),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             3,
         )
         .await;
 
         assert_eq!(result, 0);
+        let uploads = db.uploads.lock().unwrap();
+        assert_eq!(uploads.len(), 1);
+        assert!(uploads[0].5);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_documents_regenerated_in_place() {
+        let driver_id = Uuid::new_v4();
+        let pdf_bytes = Bytes::from("regenerated-pdf");
+        let storage = StubStorage::ok();
+        let db = StubWorkdayDb::with_fallbacks(vec![(driver_id, 3, 2020)]);
 
+        let result = run_inner(
+            &db,
+            &MockWorkdayCacheRepository::new(),
+            &storage,
+            &StubWorkdayService::returns_pdf(pdf_bytes.clone()),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
+            3,
+        )
+        .await;
+
+        assert_eq!(result, 0);
         let expected_key = format!(
             "drivers/{}/workdays/monthly-reports/workdays-2020-03.pdf",
             driver_id
         );
         assert_eq!(storage.download(&expected_key).await.unwrap(), pdf_bytes);
-        assert_eq!(db.uploads.lock().unwrap().len(), 1);
+        let regenerations = db.regenerations.lock().unwrap();
+        assert_eq!(regenerations.len(), 1);
+        assert_eq!(regenerations[0].3.sha256, document_sha256(&pdf_bytes));
+        assert!(db.uploads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_documents_kept_while_service_unavailable() {
+        let storage = StubStorage::ok();
+        let db = StubWorkdayDb::with_fallbacks(vec![
+            (Uuid::new_v4(), 3, 2020),
+            (Uuid::new_v4(), 4, 2020),
+        ]);
+
+        let result = run_inner(
+            &db,
+            &MockWorkdayCacheRepository::new(),
+            &storage,
+            &StubWorkdayService::returns_fallback_pdf(Bytes::from("fallback-pdf")),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
+            3,
+        )
+        .await;
+
+        assert_eq!(result, 0);
+        assert!(db.regenerations.lock().unwrap().is_empty());
+        assert!(storage.store.lock().unwrap().is_empty());
     }
 
     #[tokio::test]
@@ -574,6 +1199,10 @@ mod tests {
             &MockWorkdayCacheRepository::new(),
             &StubStorage::failing(),
             &StubWorkdayService::returns_pdf(Bytes::from("pdf")),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             3,
         )
         .await;
@@ -588,9 +1217,72 @@ mod tests {
             &MockWorkdayCacheRepository::new(),
             &StubStorage::ok(),
             &StubWorkdayService::returns_pdf(Bytes::from("pdf")),
+            &MockDriverDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             3,
         )
         .await;
         assert_eq!(result, 1);
     }
+
+    #[tokio::test]
+    async fn test_driver_notified_once_for_generated_documents() {
+        let driver_db = MockDriverDatabaseRepository::new();
+        let driver = driver_db
+            .create_driver(CreateDriverRequest {
+                firstname: "Test".into(),
+                lastname: "Driver".into(),
+                gender: None,
+                email: "documents@example.com".into(),
+                password: "hashed".into(),
+                language: DriverLanguage::FR,
+            })
+            .await
+            .unwrap();
+        let mail_db = MockMailDatabaseRepository::new();
+        mail_db
+            .upsert_driver_notification_preference(
+                driver.pk_driver_id,
+                EnumDriverMailType::DocumentsReady.as_id(),
+                NotificationChannel::PUSH,
+                true,
+            )
+            .await
+            .unwrap();
+        let push = MockNotificationChannelRepository::new(NotificationChannel::PUSH);
+        let db = StubWorkdayDb {
+            pending: vec![
+                (driver.pk_driver_id, 1, 2020),
+                (driver.pk_driver_id, 2, 2020),
+            ],
+            ..StubWorkdayDb::empty()
+        };
+
+        let result = run_inner(
+            &db,
+            &MockWorkdayCacheRepository::new(),
+            &StubStorage::ok(),
+            &StubWorkdayService::returns_pdf(Bytes::from("pdf")),
+            &driver_db,
+            &mail_db,
+            &MockMailSmtpRepository::new(),
+            &push,
+            3,
+        )
+        .await;
+
+        assert_eq!(result, 0);
+        assert_eq!(
+            push.sent.lock().unwrap().as_slice(),
+            &[(
+                driver.pk_driver_id,
+                EnumDriverMailType::DocumentsReady.as_id()
+            )]
+        );
+        let (mails, _) = mail_db.get_mails(driver.pk_driver_id, 1, 10).await.unwrap();
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].status, MailStatus::SUCCESS);
+    }
 }
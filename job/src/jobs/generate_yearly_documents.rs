@@ -0,0 +1,454 @@
+use chrono::{Datelike, Months, Utc};
+use plannify_driver_api_core::{
+    application::{DriverRepositories, DriverService},
+    domain::{
+        storage::port::StorageRepository,
+        workday::{
+            entities::{document_sha256, yearly_report_file_name},
+            port::{WorkdayDatabaseRepository, WorkdayService},
+        },
+    },
+};
+use tracing::{error, info, warn};
+
+pub async fn run(repos: &DriverRepositories, months_ago: u32) -> i32 {
+    let service: DriverService = repos.clone().into();
+    run_inner(
+        &repos.workday_database_repository,
+        &repos.storage_repository,
+        &service,
+        months_ago,
+    )
+    .await
+}
+
+async fn run_inner<WDB, SR, WS>(
+    workday_db: &WDB,
+    storage: &SR,
+    service: &WS,
+    months_ago: u32,
+) -> i32
+where
+    WDB: WorkdayDatabaseRepository,
+    SR: StorageRepository,
+    WS: WorkdayService,
+{
+    info!("Starting generate_yearly_documents job");
+
+    // A year is closed once its last month is past the cutoff, leaving drivers the same time
+    // to fill in late workdays as for the monthly documents.
+    let cutoff = Utc::now().date_naive() - Months::new(months_ago);
+    let before_year = cutoff.year();
+
+    info!("Looking for years with workdays before {}", before_year);
+
+    let pending = match workday_db.get_pending_document_years(before_year).await {
+        Ok(p) => p,
+        Err(e) => {
+            error!("Failed to fetch pending document years: {}", e);
+            return 1;
+        }
+    };
+
+    let total = pending.len();
+    if total == 0 {
+        info!("No pending yearly documents to generate");
+        return 0;
+    }
+
+    info!("Found {} year(s) pending document generation", total);
+
+    let mut generated = 0u32;
+    let mut failed = 0u32;
+
+    for (driver_id, year) in &pending {
+        let pdf = match service
+            .generate_yearly_workday_document(*driver_id, *year)
+            .await
+        {
+            Ok(Some(document)) if !document.is_fallback => document.pdf,
+            // Recaps are only stored once, they wait for the PDF service to be back
+            Ok(Some(_)) => {
+                warn!(
+                    driver_id = %driver_id,
+                    year = %year,
+                    "PDF service unavailable, yearly document left for a next run"
+                );
+                continue;
+            }
+            Ok(None) => {
+                warn!(
+                    driver_id = %driver_id,
+                    year = %year,
+                    "No workdays found for this year"
+                );
+                continue;
+            }
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    year = %year,
+                    error = ?e,
+                    "Failed to generate yearly document"
+                );
+                continue;
+            }
+        };
+
+        let s3_key = format!(
+            "drivers/{}/workdays/yearly-reports/workdays-{}.pdf",
+            driver_id, year
+        );
+        let sha256 = document_sha256(&pdf);
+
+        if let Err(e) = storage.upload(&s3_key, pdf, "application/pdf").await {
+            failed += 1;
+            error!(
+                driver_id = %driver_id,
+                year = %year,
+                error = ?e,
+                "Failed to upload yearly document to S3"
+            );
+            continue;
+        }
+
+        if let Err(e) = workday_db
+            .create_yearly_document(
+                *driver_id,
+                *year,
+                s3_key,
+                yearly_report_file_name(*year),
+                sha256,
+            )
+            .await
+        {
+            failed += 1;
+            error!(
+                driver_id = %driver_id,
+                year = %year,
+                error = ?e,
+                "Failed to persist yearly document record"
+            );
+            continue;
+        }
+
+        generated += 1;
+        info!(
+            driver_id = %driver_id,
+            year = %year,
+            "Yearly document generated and stored"
+        );
+    }
+
+    info!(
+        total,
+        generated, failed, "generate_yearly_documents job completed"
+    );
+
+    if failed > 0 { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_inner;
+    use bytes::Bytes;
+    use chrono::{Datelike, NaiveDate, NaiveTime, Utc};
+    use plannify_driver_api_core::{
+        domain::{
+            document::entities::RenderedDocument,
+            storage::{
+                entities::{ByteRange, DocumentDownload},
+                port::{MockStorageRepository, StorageRepository},
+            },
+            workday::{
+                entities::{
+                    CreateDocumentShareRequest, CreateWorkdayRequest, DocumentShareAccessRow,
+                    DocumentShareRow, GeneratedWorkdayDocument, UpdateWorkdayRequest, Workday,
+                    WorkdayDocumentInformation, WorkdayDocumentVerification, WorkdayGarbageRow,
+                    WorkdayRow,
+                },
+                port::{MockWorkdayDatabaseRepository, WorkdayDatabaseRepository, WorkdayService},
+            },
+        },
+        infrastructure::workday::repositories::error::WorkdayError,
+    };
+    use uuid::Uuid;
+
+    struct StubWorkdayService {
+        fail: bool,
+        is_fallback: bool,
+    }
+
+    impl WorkdayService for StubWorkdayService {
+        async fn generate_yearly_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Option<RenderedDocument>, WorkdayError> {
+            if self.fail {
+                return Err(WorkdayError::Internal);
+            }
+            Ok(Some(RenderedDocument {
+                pdf: Bytes::from("pdf"),
+                is_fallback: self.is_fallback,
+            }))
+        }
+        async fn download_yearly_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: Option<ByteRange>,
+        ) -> Result<Option<DocumentDownload>, WorkdayError> {
+            unreachable!()
+        }
+        async fn generate_verifiable_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+        ) -> Result<Option<GeneratedWorkdayDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn verify_workday_document(
+            &self,
+            _: &str,
+        ) -> Result<WorkdayDocumentVerification, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: CreateDocumentShareRequest,
+        ) -> Result<(String, DocumentShareRow), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_shares(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn revoke_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<DocumentShareRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_accesses(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_shared_document(
+            &self,
+            _: &str,
+            _: Option<String>,
+            _: Option<String>,
+            _: Option<ByteRange>,
+        ) -> Result<(DocumentDownload, String), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workday_document_by_month(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+        ) -> Result<Option<Bytes>, WorkdayError> {
+            unreachable!()
+        }
+        async fn download_workday_document_by_month(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: Option<ByteRange>,
+        ) -> Result<Option<DocumentDownload>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workday_by_date(
+            &self,
+            _: Uuid,
+            _: NaiveDate,
+        ) -> Result<Workday, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workdays_by_month(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+        ) -> Result<Vec<Workday>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workdays_by_period(
+            &self,
+            _: Uuid,
+            _: NaiveDate,
+            _: NaiveDate,
+            _: u32,
+            _: u32,
+        ) -> Result<(Vec<Workday>, u32), WorkdayError> {
+            unreachable!()
+        }
+        async fn create_workday(
+            &self,
+            _: Uuid,
+            _: CreateWorkdayRequest,
+        ) -> Result<WorkdayRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn update_workday(
+            &self,
+            _: Uuid,
+            _: UpdateWorkdayRequest,
+        ) -> Result<WorkdayRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn delete_workday(&self, _: Uuid, _: NaiveDate) -> Result<(), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workdays_garbage(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<WorkdayGarbageRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_workday_garbage(
+            &self,
+            _: Uuid,
+            _: NaiveDate,
+        ) -> Result<WorkdayGarbageRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn delete_workday_garbage(&self, _: Uuid, _: NaiveDate) -> Result<(), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workday_documents(&self, _: Uuid) -> Result<Vec<i32>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_generated_document_by_year(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Vec<WorkdayDocumentInformation>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_workday_documents_by_year(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Vec<WorkdayDocumentInformation>, WorkdayError> {
+            unreachable!()
+        }
+    }
+
+    fn workday(date: NaiveDate) -> CreateWorkdayRequest {
+        CreateWorkdayRequest {
+            date,
+            start_time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            end_time: Some(NaiveTime::from_hms_opt(17, 0, 0).unwrap()),
+            rest_time: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+            overnight_rest: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_closed_years_are_stored() {
+        let db = MockWorkdayDatabaseRepository::new();
+        let storage = MockStorageRepository::new();
+        let driver_id = Uuid::new_v4();
+        let current_year = Utc::now().year();
+        let closed = NaiveDate::from_ymd_opt(current_year - 2, 6, 3).unwrap();
+        let open = NaiveDate::from_ymd_opt(current_year, 1, 5).unwrap();
+        db.create_workday(driver_id, workday(closed)).await.unwrap();
+        db.create_workday(driver_id, workday(open)).await.unwrap();
+
+        let service = StubWorkdayService {
+            fail: false,
+            is_fallback: false,
+        };
+        assert_eq!(run_inner(&db, &storage, &service, 0).await, 0);
+
+        let stored = db
+            .get_yearly_document_record(driver_id, current_year - 2)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.file_name,
+            format!("workdays-{}.pdf", current_year - 2)
+        );
+        assert!(storage.download(&stored.s3_file_path).await.is_ok());
+        assert!(
+            db.get_yearly_document_record(driver_id, current_year)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Stored years are not generated twice.
+        assert_eq!(
+            db.get_pending_document_years(current_year).await.unwrap(),
+            vec![]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_pending_year_returns_success() {
+        let db = MockWorkdayDatabaseRepository::new();
+        let storage = MockStorageRepository::new();
+        let service = StubWorkdayService {
+            fail: true,
+            is_fallback: false,
+        };
+        assert_eq!(run_inner(&db, &storage, &service, 3).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_generation_failure_returns_error() {
+        let db = MockWorkdayDatabaseRepository::new();
+        let storage = MockStorageRepository::new();
+        let driver_id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(Utc::now().year() - 2, 6, 3).unwrap();
+        db.create_workday(driver_id, workday(date)).await.unwrap();
+
+        let service = StubWorkdayService {
+            fail: true,
+            is_fallback: false,
+        };
+        assert_eq!(run_inner(&db, &storage, &service, 0).await, 1);
+        assert!(
+            db.get_yearly_document_record(driver_id, date.year())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_recap_is_not_stored() {
+        let db = MockWorkdayDatabaseRepository::new();
+        let storage = MockStorageRepository::new();
+        let driver_id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(Utc::now().year() - 2, 6, 3).unwrap();
+        db.create_workday(driver_id, workday(date)).await.unwrap();
+
+        let service = StubWorkdayService {
+            fail: false,
+            is_fallback: true,
+        };
+        assert_eq!(run_inner(&db, &storage, &service, 0).await, 0);
+        assert_eq!(
+            db.get_pending_document_years(Utc::now().year())
+                .await
+                .unwrap(),
+            vec![(driver_id, date.year())]
+        );
+    }
+}
@@ -1,5 +1,9 @@
 pub mod delete_garbage;
 pub mod generate_documents;
+pub mod generate_yearly_documents;
 pub mod purge_deactivated_accounts;
 pub mod reconcile_documents;
+pub mod render_mail_templates;
+pub mod rotate_storage_keys;
 pub mod send_monthly_reports;
+pub mod send_workday_reminders;
@@ -2,17 +2,27 @@ use tracing::{error, info, warn};
 
 use plannify_driver_api_core::{
     application::DriverRepositories,
-    domain::{driver::port::DriverDatabaseRepository, storage::port::StorageRepository},
+    domain::{
+        driver::port::DriverDatabaseRepository,
+        storage::port::{DataKeyRepository, StorageRepository},
+    },
+    infrastructure::storage::repositories::error::StorageError,
 };
 
 pub async fn run(repos: &DriverRepositories) -> i32 {
-    run_inner(&repos.driver_database_repository, &repos.storage_repository).await
+    run_inner(
+        &repos.driver_database_repository,
+        &repos.storage_repository,
+        &repos.data_key_repository,
+    )
+    .await
 }
 
-async fn run_inner<DDB, SR>(driver_db: &DDB, storage: &SR) -> i32
+async fn run_inner<DDB, SR, DK>(driver_db: &DDB, storage: &SR, data_keys: &DK) -> i32
 where
     DDB: DriverDatabaseRepository,
     SR: StorageRepository,
+    DK: DataKeyRepository,
 {
     info!("Starting purge_deactivated_accounts job");
 
@@ -55,6 +65,35 @@ where
             }
         };
 
+        // Delete S3 files right away: their paths are no longer in the DB for a later run
+        let mut undeleted_paths = Vec::new();
+        for path in &s3_paths {
+            if let Err(e) = storage.delete(path).await {
+                warn!(
+                    driver_id = %driver_id,
+                    path = %path,
+                    error = ?e,
+                    "Failed to delete S3 object"
+                );
+                undeleted_paths.push(path);
+            }
+        }
+
+        // Delete the data key: the encrypted S3 files of the driver can no longer be read.
+        // The driver is kept on failure, so the next run tries again.
+        let shredded = match data_keys.delete_data_key(driver_id).await {
+            Ok(shredded) => shredded,
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    error = ?e,
+                    "Failed to delete driver data key — account kept for the next run"
+                );
+                continue;
+            }
+        };
+
         // Delete driver from DB (cascades to workday_documents, driver_mails, driver_mail_attachments)
         if let Err(e) = driver_db.delete_driver(driver_id).await {
             failed += 1;
@@ -66,32 +105,28 @@ where
             continue;
         }
 
-        // Delete S3 files — log warnings on failure but do not abort the loop
-        let mut s3_ok = true;
-        for path in &s3_paths {
-            if let Err(e) = storage.delete(path).await {
-                s3_ok = false;
-                warn!(
-                    driver_id = %driver_id,
-                    path = %path,
-                    error = ?e,
-                    "Failed to delete S3 object — manual cleanup may be required"
-                );
+        // Files stored before the encryption stay readable without the data key
+        let mut readable_paths = Vec::new();
+        for path in undeleted_paths {
+            if is_readable(storage, path).await {
+                readable_paths.push(path);
             }
         }
 
-        if s3_ok {
+        if readable_paths.is_empty() {
             deleted += 1;
             info!(
                 driver_id = %driver_id,
                 files = s3_paths.len(),
+                shredded,
                 "Account purged successfully"
             );
         } else {
             failed += 1;
             error!(
                 driver_id = %driver_id,
-                "Account removed from DB but some S3 files could not be deleted"
+                paths = ?readable_paths,
+                "Account removed from DB but some readable S3 files could not be deleted — manual cleanup required"
             );
         }
     }
@@ -104,6 +139,17 @@ where
     if failed > 0 { 1 } else { 0 }
 }
 
+/// Whether an object that could not be deleted can still be read. Encrypted objects cannot
+/// once the data key of their driver is gone.
+async fn is_readable<SR: StorageRepository>(storage: &SR, path: &str) -> bool {
+    !matches!(
+        storage.download(path).await,
+        Err(StorageError::ObjectNotFound
+            | StorageError::DataKeyNotFound
+            | StorageError::EncryptionError)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::run_inner;
@@ -115,7 +161,10 @@ mod tests {
                 entities::{CreateDriverRequest, DriverLanguage, DriverRow},
                 port::{DriverDatabaseRepository, MockDriverDatabaseRepository},
             },
-            storage::port::StorageRepository,
+            storage::{
+                entities::{ByteRange, StorageObject, WrappedDataKey},
+                port::{DataKeyRepository, MockDataKeyRepository, StorageRepository},
+            },
         },
         infrastructure::storage::repositories::error::StorageError,
     };
@@ -123,12 +172,15 @@ mod tests {
         sync::{Arc, Mutex},
         time::Duration as StdDuration,
     };
+    use uuid::Uuid;
 
     // ── Stubs ──────────────────────────────────────────────────────────────────
 
     #[derive(Clone)]
     struct StubStorage {
         fail: bool,
+        /// Whether the objects are stored unencrypted, readable without a data key.
+        plaintext: bool,
         deleted: Arc<Mutex<Vec<String>>>,
     }
 
@@ -136,13 +188,21 @@ mod tests {
         fn ok() -> Self {
             Self {
                 fail: false,
+                plaintext: false,
                 deleted: Default::default(),
             }
         }
-        #[allow(dead_code)]
         fn failing() -> Self {
             Self {
                 fail: true,
+                plaintext: false,
+                deleted: Default::default(),
+            }
+        }
+        fn failing_plaintext() -> Self {
+            Self {
+                fail: true,
+                plaintext: true,
                 deleted: Default::default(),
             }
         }
@@ -153,6 +213,16 @@ mod tests {
             unreachable!()
         }
         async fn download(&self, _: &str) -> Result<Bytes, StorageError> {
+            if self.plaintext {
+                return Ok(Bytes::from_static(b"%PDF"));
+            }
+            Err(StorageError::DataKeyNotFound)
+        }
+        async fn stream(
+            &self,
+            _: &str,
+            _: Option<ByteRange>,
+        ) -> Result<StorageObject, StorageError> {
             unreachable!()
         }
         async fn delete(&self, key: &str) -> Result<(), StorageError> {
@@ -178,8 +248,40 @@ mod tests {
         }
     }
 
+    /// Data keys that cannot be deleted.
+    struct FailingDataKeys;
+
+    impl DataKeyRepository for FailingDataKeys {
+        async fn get_data_key(&self, _: Uuid) -> Result<Option<WrappedDataKey>, StorageError> {
+            unreachable!()
+        }
+        async fn create_data_key(
+            &self,
+            _: Uuid,
+            _: WrappedDataKey,
+        ) -> Result<WrappedDataKey, StorageError> {
+            unreachable!()
+        }
+        async fn update_data_key(&self, _: Uuid, _: WrappedDataKey) -> Result<(), StorageError> {
+            unreachable!()
+        }
+        async fn delete_data_key(&self, _: Uuid) -> Result<bool, StorageError> {
+            Err(StorageError::Internal)
+        }
+        async fn get_data_keys_to_rewrap(
+            &self,
+            _: &str,
+        ) -> Result<Vec<(Uuid, WrappedDataKey)>, StorageError> {
+            unreachable!()
+        }
+    }
+
     // ── Helpers ────────────────────────────────────────────────────────────────
 
+    fn document_path(driver: &DriverRow) -> String {
+        format!("drivers/{}/workdays/2025-01.pdf", driver.pk_driver_id)
+    }
+
     async fn driver_deactivated_in_past(
         db: &MockDriverDatabaseRepository,
         email: &str,
@@ -246,7 +348,7 @@ mod tests {
         let db = MockDriverDatabaseRepository::new();
         driver_active(&db, "active@example.com").await;
 
-        let result = run_inner(&db, &StubStorage::ok()).await;
+        let result = run_inner(&db, &StubStorage::ok(), &MockDataKeyRepository::new()).await;
         assert_eq!(result, 0);
     }
 
@@ -255,7 +357,7 @@ mod tests {
         let db = MockDriverDatabaseRepository::new();
         let driver = driver_deactivated_in_future(&db, "future@example.com").await;
 
-        let result = run_inner(&db, &StubStorage::ok()).await;
+        let result = run_inner(&db, &StubStorage::ok(), &MockDataKeyRepository::new()).await;
         assert_eq!(result, 0);
 
         // Driver should still exist
@@ -268,7 +370,7 @@ mod tests {
         let db = MockDriverDatabaseRepository::new();
         let driver = driver_deactivated_in_past(&db, "past@example.com").await;
 
-        let result = run_inner(&db, &StubStorage::ok()).await;
+        let result = run_inner(&db, &StubStorage::ok(), &MockDataKeyRepository::new()).await;
         assert_eq!(result, 0);
 
         // Driver should be gone from DB
@@ -282,7 +384,7 @@ mod tests {
         let active = driver_active(&db, "active2@example.com").await;
         driver_deactivated_in_past(&db, "past2@example.com").await;
 
-        let result = run_inner(&db, &StubStorage::ok()).await;
+        let result = run_inner(&db, &StubStorage::ok(), &MockDataKeyRepository::new()).await;
         assert_eq!(result, 0);
 
         // Active driver still present
@@ -298,8 +400,93 @@ mod tests {
         // The mock returns no S3 paths so S3 delete is never called, but
         // if it were, this tests the job reports failure
         // We test the actual S3 failure path via a custom stub below
-        let result = run_inner(&db, &StubStorage::ok()).await;
+        let result = run_inner(&db, &StubStorage::ok(), &MockDataKeyRepository::new()).await;
         // Mock has no documents → no S3 calls → success
         assert_eq!(result, 0);
     }
+
+    #[tokio::test]
+    async fn test_data_key_deleted_with_account() {
+        let db = MockDriverDatabaseRepository::new();
+        let data_keys = MockDataKeyRepository::new();
+        let purged = driver_deactivated_in_past(&db, "shred@example.com").await;
+        let active = driver_active(&db, "kept@example.com").await;
+        for driver in [&purged, &active] {
+            data_keys
+                .create_data_key(
+                    driver.pk_driver_id,
+                    WrappedDataKey {
+                        master_key_id: "2026".into(),
+                        wrapped_key: vec![0; 60],
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let result = run_inner(&db, &StubStorage::ok(), &data_keys).await;
+        assert_eq!(result, 0);
+
+        assert!(
+            data_keys
+                .get_data_key(purged.pk_driver_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            data_keys
+                .get_data_key(active.pk_driver_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_data_key_failure_keeps_account_for_next_run() {
+        let db = MockDriverDatabaseRepository::new();
+        let driver = driver_deactivated_in_past(&db, "keyfail@example.com").await;
+        db.add_document(driver.pk_driver_id, &document_path(&driver));
+        let storage = StubStorage::ok();
+
+        let result = run_inner(&db, &storage, &FailingDataKeys).await;
+        assert_eq!(result, 1);
+
+        // The files are deleted anyway, the driver is left for the next run
+        assert_eq!(
+            *storage.deleted.lock().unwrap(),
+            vec![document_path(&driver)]
+        );
+        let found = db.get_driver_by_id(driver.pk_driver_id).await.unwrap();
+        assert!(found.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_undeleted_encrypted_files_purged_by_shredding() {
+        let db = MockDriverDatabaseRepository::new();
+        let driver = driver_deactivated_in_past(&db, "encrypted@example.com").await;
+        db.add_document(driver.pk_driver_id, &document_path(&driver));
+
+        let result = run_inner(&db, &StubStorage::failing(), &MockDataKeyRepository::new()).await;
+        assert_eq!(result, 0);
+
+        let found = db.get_driver_by_id(driver.pk_driver_id).await.unwrap();
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_undeleted_plaintext_files_reported() {
+        let db = MockDriverDatabaseRepository::new();
+        let driver = driver_deactivated_in_past(&db, "plaintext@example.com").await;
+        db.add_document(driver.pk_driver_id, &document_path(&driver));
+
+        let result = run_inner(
+            &db,
+            &StubStorage::failing_plaintext(),
+            &MockDataKeyRepository::new(),
+        )
+        .await;
+        assert_eq!(result, 1);
+    }
 }
@@ -33,6 +33,9 @@ fn filter_directory_markers(keys: Vec<String>) -> Vec<String> {
 /// their keys in ascending order.  We advance both streams simultaneously — just
 /// like the merge step in merge-sort — and act on divergences immediately.
 ///
+/// The objects present on both sides are read back and hashed: one whose SHA-256 digest no
+/// longer matches the one recorded at generation is flagged, and left in place for review.
+///
 /// Memory footprint: O(BATCH_SIZE), regardless of the total number of documents.
 async fn run_inner<WDB, SR>(workday_db: &WDB, storage: &SR) -> i32
 where
@@ -44,7 +47,7 @@ where
     // ── Initial load ─────────────────────────────────────────────────────────
 
     let mut db_buf = match workday_db
-        .get_document_s3_paths_batch(None, BATCH_SIZE)
+        .get_document_checksums_batch(None, BATCH_SIZE)
         .await
     {
         Ok(b) => b,
@@ -66,6 +69,7 @@ where
 
     let mut orphaned_db = 0u32;
     let mut orphaned_s3 = 0u32;
+    let mut tampered = 0u32;
     let mut failed = 0u32;
 
     // ── Streaming merge ───────────────────────────────────────────────────────
@@ -74,9 +78,9 @@ where
         // Refill DB buffer: only when we've consumed the current batch AND it was
         // full (meaning there may be more rows).
         if db_idx >= db_buf.len() && db_buf.len() == BATCH_SIZE as usize {
-            let after = db_buf.last().cloned();
+            let after = db_buf.last().map(|c| c.s3_file_path.clone());
             match workday_db
-                .get_document_s3_paths_batch(after.as_deref(), BATCH_SIZE)
+                .get_document_checksums_batch(after.as_deref(), BATCH_SIZE)
                 .await
             {
                 Ok(batch) => {
@@ -116,7 +120,7 @@ where
 
             // DB record exists but the corresponding S3 file is gone.
             (false, true) => {
-                let path = &db_buf[db_idx];
+                let path = &db_buf[db_idx].s3_file_path;
                 match workday_db.delete_document_by_s3_path(path).await {
                     Ok(()) => {
                         orphaned_db += 1;
@@ -148,11 +152,34 @@ where
 
             // Both streams have data — compare the current positions.
             (false, false) => {
-                let db_path = db_buf[db_idx].as_str();
+                let db_path = db_buf[db_idx].s3_file_path.as_str();
                 let s3_key = s3_buf[s3_idx].as_str();
 
                 match db_path.cmp(s3_key) {
                     Ordering::Equal => {
+                        // Documents generated before the checksums were recorded have none.
+                        if let Some(expected) = db_buf[db_idx].sha256.as_deref() {
+                            let actual = match storage.stream(s3_key, None).await {
+                                Ok(object) => object.sha256().await,
+                                Err(e) => Err(e),
+                            };
+                            match actual {
+                                Ok(actual) if actual == expected => {}
+                                Ok(actual) => {
+                                    tampered += 1;
+                                    error!(
+                                        key = s3_key,
+                                        expected,
+                                        actual,
+                                        "Stored document no longer matches its checksum"
+                                    );
+                                }
+                                Err(e) => {
+                                    failed += 1;
+                                    warn!(key = s3_key, error = ?e, "Failed to checksum stored document");
+                                }
+                            }
+                        }
                         db_idx += 1;
                         s3_idx += 1;
                     }
@@ -189,10 +216,10 @@ where
 
     info!(
         orphaned_db,
-        orphaned_s3, failed, "reconcile_documents job completed"
+        orphaned_s3, tampered, failed, "reconcile_documents job completed"
     );
 
-    if failed > 0 { 1 } else { 0 }
+    if failed > 0 || tampered > 0 { 1 } else { 0 }
 }
 
 #[cfg(test)]
@@ -204,7 +231,10 @@ mod tests {
     };
 
     async fn seed_db(db: &MockWorkdayDatabaseRepository, paths: &[&str]) {
-        use plannify_driver_api_core::domain::workday::port::WorkdayDatabaseRepository;
+        use plannify_driver_api_core::domain::workday::{
+            entities::{DocumentIntegrity, document_sha256},
+            port::WorkdayDatabaseRepository,
+        };
         use uuid::Uuid;
         for (i, path) in paths.iter().enumerate() {
             db.create_workday_document(
@@ -213,6 +243,11 @@ mod tests {
                 2026 + i as i32,
                 path.to_string(),
                 "report.pdf".to_string(),
+                DocumentIntegrity {
+                    sha256: document_sha256(b"pdf"),
+                    verification_code: format!("ABCD-EFGH-{:04}", i),
+                },
+                false,
             )
             .await
             .unwrap();
@@ -269,6 +304,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tampered_object_is_flagged_and_kept() {
+        let db = MockWorkdayDatabaseRepository::new();
+        let storage = MockStorageRepository::new();
+        seed_db(&db, &["drivers/1/report.pdf"]).await;
+
+        use plannify_driver_api_core::domain::{
+            storage::port::StorageRepository, workday::port::WorkdayDatabaseRepository,
+        };
+        storage
+            .upload(
+                "drivers/1/report.pdf",
+                Bytes::from("edited pdf"),
+                "application/pdf",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(run_inner(&db, &storage).await, 1);
+
+        assert!(storage.download("drivers/1/report.pdf").await.is_ok());
+        let remaining = db.get_all_document_s3_paths().await.unwrap();
+        assert_eq!(remaining, vec!["drivers/1/report.pdf".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_empty_both_returns_success() {
         let db = MockWorkdayDatabaseRepository::new();
@@ -0,0 +1,105 @@
+use std::{fs, path::Path};
+
+use plannify_driver_api_core::infrastructure::mail::repositories::renderer::{
+    MAIL_LANGUAGES, MailRenderer, MailTemplate,
+};
+use tracing::{error, info};
+
+pub fn run(templates_dir: &str, output_dir: &Path, frontend_url: &str) -> i32 {
+    info!("Starting render_mail_templates job");
+
+    let renderer = match MailRenderer::load(templates_dir) {
+        Ok(renderer) => renderer,
+        Err(e) => {
+            error!(error = ?e, "Failed to parse mail templates");
+            return 1;
+        }
+    };
+
+    let mut failures = 0;
+    let mut rendered = 0;
+
+    for language in MAIL_LANGUAGES {
+        let language_dir = output_dir.join(language);
+        if let Err(e) = fs::create_dir_all(&language_dir) {
+            error!(error = ?e, "Failed to create {}", language_dir.display());
+            return 1;
+        }
+
+        for template in MailTemplate::fixtures(frontend_url) {
+            let mail = match renderer.render(language, &template) {
+                Ok(mail) => mail,
+                Err(e) => {
+                    error!(error = ?e, "Failed to render {}/{}", language, template.name());
+                    failures += 1;
+                    continue;
+                }
+            };
+
+            let html_path = language_dir.join(format!("{}.html", template.name()));
+            let text_path = language_dir.join(format!("{}.txt", template.name()));
+            let written = fs::write(&html_path, &mail.html_body).and_then(|_| {
+                fs::write(
+                    &text_path,
+                    format!("Subject: {}\n\n{}\n", mail.subject, mail.text_body),
+                )
+            });
+
+            if let Err(e) = written {
+                error!(error = ?e, "Failed to write {}", html_path.display());
+                failures += 1;
+                continue;
+            }
+            rendered += 1;
+        }
+    }
+
+    info!(
+        "{} mails rendered to {}, {} failed",
+        rendered,
+        output_dir.display(),
+        failures
+    );
+
+    if failures > 0 { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::run;
+    use uuid::Uuid;
+
+    const TEMPLATES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../core/templates/mails");
+
+    #[test]
+    fn test_renders_every_template_to_output_dir() {
+        let output_dir = std::env::temp_dir().join(format!("mail-previews-{}", Uuid::new_v4()));
+
+        assert_eq!(
+            run(TEMPLATES_DIR, &output_dir, "https://app.plannify.be"),
+            0
+        );
+
+        assert!(output_dir.join("fr/magic_link.html").exists());
+        let text = fs::read_to_string(output_dir.join("fr/magic_link.txt")).unwrap();
+        assert!(text.contains("https://app.plannify.be/authentication/token/magic-link"));
+        let text = fs::read_to_string(output_dir.join("en/monthly_report.txt")).unwrap();
+        assert!(text.starts_with("Subject: Your Plannify monthly report - 09/2026\n\n"));
+
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_templates_dir_returns_failure() {
+        let output_dir = std::env::temp_dir().join(format!("mail-previews-{}", Uuid::new_v4()));
+
+        assert_eq!(
+            run("/nonexistent", &output_dir, "https://app.plannify.be"),
+            1
+        );
+
+        let _ = fs::remove_dir_all(output_dir);
+    }
+}
@@ -0,0 +1,154 @@
+use plannify_driver_api_core::{
+    application::DriverRepositories,
+    domain::storage::port::{DataKeyRepository, KeyManagementRepository},
+};
+use tracing::{error, info};
+
+pub async fn run(repos: &DriverRepositories) -> i32 {
+    run_inner(&repos.data_key_repository, &repos.key_management_repository).await
+}
+
+/// Rewraps with the current master key every data key wrapped with an older one. The stored
+/// objects are left untouched: only their data keys change of wrapping.
+async fn run_inner<DK, KM>(data_keys: &DK, kms: &KM) -> i32
+where
+    DK: DataKeyRepository,
+    KM: KeyManagementRepository,
+{
+    info!("Starting rotate_storage_keys job");
+
+    let Some(current_key_id) = kms.current_key_id() else {
+        error!("No storage master key configured");
+        return 1;
+    };
+
+    let to_rewrap = match data_keys.get_data_keys_to_rewrap(current_key_id).await {
+        Ok(keys) => keys,
+        Err(e) => {
+            error!(error = ?e, "Failed to fetch the data keys to rewrap");
+            return 1;
+        }
+    };
+
+    let total = to_rewrap.len();
+    if total == 0 {
+        info!(
+            current_key_id,
+            "Every data key is wrapped with the current master key"
+        );
+        return 0;
+    }
+
+    let mut rewrapped = 0u32;
+    let mut failed = 0u32;
+
+    for (driver_id, wrapped) in to_rewrap {
+        let data_key = match kms.unwrap_key(&wrapped).await {
+            Ok(data_key) => data_key,
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    master_key_id = %wrapped.master_key_id,
+                    error = ?e,
+                    "Failed to unwrap data key — is its master key still configured?"
+                );
+                continue;
+            }
+        };
+
+        let result = match kms.wrap_key(&data_key).await {
+            Ok(wrapped) => data_keys.update_data_key(driver_id, wrapped).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => rewrapped += 1,
+            Err(e) => {
+                failed += 1;
+                error!(driver_id = %driver_id, error = ?e, "Failed to rewrap data key");
+            }
+        }
+    }
+
+    info!(
+        total,
+        rewrapped, failed, current_key_id, "rotate_storage_keys job completed"
+    );
+
+    if failed > 0 { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_inner;
+    use plannify_driver_api_core::{
+        domain::{
+            common::config::StorageEncryptionConfig,
+            storage::{
+                entities::{DataKey, WrappedDataKey},
+                port::{DataKeyRepository, KeyManagementRepository, MockDataKeyRepository},
+            },
+        },
+        infrastructure::storage::repositories::local_kms::LocalKeyManagementRepository,
+    };
+    use uuid::Uuid;
+
+    const OLD_KEY: &str = "2025:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+    const NEW_KEY: &str = "2026:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+
+    fn kms(master_keys: &[&str]) -> LocalKeyManagementRepository {
+        LocalKeyManagementRepository::new(&StorageEncryptionConfig {
+            master_keys: master_keys.iter().map(|k| k.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_old_data_keys_rewrapped_with_current_master_key() {
+        let data_keys = MockDataKeyRepository::new();
+        let driver_id = Uuid::new_v4();
+        let data_key = DataKey::generate();
+        data_keys
+            .create_data_key(
+                driver_id,
+                kms(&[OLD_KEY]).wrap_key(&data_key).await.unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let result = run_inner(&data_keys, &kms(&[NEW_KEY, OLD_KEY])).await;
+        assert_eq!(result, 0);
+
+        let wrapped = data_keys.get_data_key(driver_id).await.unwrap().unwrap();
+        assert_eq!(wrapped.master_key_id, "2026");
+        let unwrapped = kms(&[NEW_KEY]).unwrap_key(&wrapped).await.unwrap();
+        assert_eq!(unwrapped.as_bytes(), data_key.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_master_key_reported_as_failure() {
+        let data_keys = MockDataKeyRepository::new();
+        let driver_id = Uuid::new_v4();
+        let wrapped = WrappedDataKey {
+            master_key_id: "2024".into(),
+            wrapped_key: vec![0; 60],
+        };
+        data_keys
+            .create_data_key(driver_id, wrapped.clone())
+            .await
+            .unwrap();
+
+        let result = run_inner(&data_keys, &kms(&[NEW_KEY])).await;
+        assert_eq!(result, 1);
+        assert_eq!(
+            data_keys.get_data_key(driver_id).await.unwrap(),
+            Some(wrapped)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_master_key_returns_error() {
+        let result = run_inner(&MockDataKeyRepository::new(), &kms(&[])).await;
+        assert_eq!(result, 1);
+    }
+}
@@ -7,8 +7,12 @@ use plannify_driver_api_core::{
         common::constants::EnumDriverMailType,
         driver::port::DriverDatabaseRepository,
         mail::{
-            entities::MailStatus,
-            port::{MailDatabaseRepository, MailSmtpRepository},
+            entities::{MailStatus, monthly_report_file_name},
+            port::MailDatabaseRepository,
+        },
+        notification::{
+            entities::Notification, port::NotificationChannelRepository,
+            services::NotificationDispatcher,
         },
         storage::port::StorageRepository,
         workday::port::WorkdayService,
@@ -21,23 +25,26 @@ pub async fn run(repos: &DriverRepositories) -> i32 {
         &repos.driver_database_repository,
         &repos.mail_database_repository,
         &repos.mail_smtp_repository,
+        &repos.web_push_repository,
         &repos.storage_repository,
         &service,
     )
     .await
 }
 
-async fn run_inner<DDB, MDB, MS, SR, WS>(
+async fn run_inner<DDB, MDB, EC, PC, SR, WS>(
     driver_db: &DDB,
     mail_db: &MDB,
-    mail_smtp: &MS,
+    email_channel: &EC,
+    push_channel: &PC,
     storage: &SR,
     workday_service: &WS,
 ) -> i32
 where
     DDB: DriverDatabaseRepository,
     MDB: MailDatabaseRepository,
-    MS: MailSmtpRepository,
+    EC: NotificationChannelRepository,
+    PC: NotificationChannelRepository,
     SR: StorageRepository,
     WS: WorkdayService,
 {
@@ -69,6 +76,8 @@ where
 
     info!("Found {} driver(s) with monthly report preference", total);
 
+    let dispatcher = NotificationDispatcher::new(mail_db, email_channel, push_channel);
+
     let mut sent = 0u32;
     let mut skipped = 0u32;
     let mut failed = 0u32;
@@ -157,7 +166,7 @@ where
             }
         };
 
-        let file_name = format!("workdays-{}-{:02}.pdf", year, month);
+        let file_name = monthly_report_file_name(month as u32, year);
 
         if let Err(e) = storage
             .upload(&s3_key, pdf.clone(), "application/pdf")
@@ -220,12 +229,15 @@ where
             continue;
         }
 
-        let send_result = mail_smtp
-            .send_driver_monthly_report_email(driver.clone(), month as u32, year, pdf, file_name)
-            .await;
+        let notification = Notification::MonthlyReport {
+            month: month as u32,
+            year,
+            pdf_bytes: pdf,
+            file_name,
+        };
 
-        match send_result {
-            Ok(()) => {
+        match dispatcher.dispatch(driver, &notification).await {
+            Ok(channels) if !channels.is_empty() => {
                 let _ = mail_db
                     .update_mail_status(
                         mail.pk_driver_mail_id,
@@ -234,7 +246,23 @@ where
                     )
                     .await;
                 sent += 1;
-                info!(driver_id = %driver_id, "Monthly report sent for {}/{}", month, year);
+                info!(
+                    driver_id = %driver_id,
+                    channels = ?channels,
+                    "Monthly report sent for {}/{}",
+                    month,
+                    year
+                );
+            }
+            Ok(_) => {
+                let _ = mail_db
+                    .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                    .await;
+                skipped += 1;
+                warn!(
+                    driver_id = %driver_id,
+                    "No notification channel reached the driver, skipping"
+                );
             }
             Err(e) => {
                 let _ = mail_db
@@ -244,7 +272,7 @@ where
                 error!(
                     driver_id = %driver_id,
                     error = ?e,
-                    "Failed to send monthly report email"
+                    "Failed to send monthly report"
                 );
             }
         }
@@ -265,16 +293,27 @@ mod tests {
     use chrono::NaiveDate;
     use plannify_driver_api_core::{
         domain::{
+            common::constants::EnumDriverMailType,
+            document::entities::RenderedDocument,
             driver::{
                 entities::{CreateDriverRequest, DriverLanguage, DriverRow},
                 port::{DriverDatabaseRepository, MockDriverDatabaseRepository},
             },
-            mail::port::{MailSmtpRepository, MockMailDatabaseRepository},
-            storage::port::StorageRepository,
+            mail::{
+                entities::{MailStatus, NotificationChannel},
+                port::{MailDatabaseRepository, MailSmtpRepository, MockMailDatabaseRepository},
+            },
+            notification::port::MockNotificationChannelRepository,
+            storage::{
+                entities::{ByteRange, DocumentDownload, StorageObject},
+                port::StorageRepository,
+            },
             workday::{
                 entities::{
-                    CreateWorkdayRequest, UpdateWorkdayRequest, Workday,
-                    WorkdayDocumentInformation, WorkdayGarbageRow, WorkdayRow,
+                    CreateDocumentShareRequest, CreateWorkdayRequest, DocumentShareAccessRow,
+                    DocumentShareRow, GeneratedWorkdayDocument, UpdateWorkdayRequest, Workday,
+                    WorkdayDocumentInformation, WorkdayDocumentVerification, WorkdayGarbageRow,
+                    WorkdayRow,
                 },
                 port::WorkdayService,
             },
@@ -324,6 +363,13 @@ mod tests {
         async fn download(&self, _: &str) -> Result<Bytes, StorageError> {
             unreachable!()
         }
+        async fn stream(
+            &self,
+            _: &str,
+            _: Option<ByteRange>,
+        ) -> Result<StorageObject, StorageError> {
+            unreachable!()
+        }
         async fn delete(&self, _: &str) -> Result<(), StorageError> {
             unreachable!()
         }
@@ -381,6 +427,82 @@ mod tests {
             }
             Ok(self.pdf.clone())
         }
+        async fn download_workday_document_by_month(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: Option<ByteRange>,
+        ) -> Result<Option<DocumentDownload>, WorkdayError> {
+            unreachable!()
+        }
+        async fn generate_verifiable_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+        ) -> Result<Option<GeneratedWorkdayDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn verify_workday_document(
+            &self,
+            _: &str,
+        ) -> Result<WorkdayDocumentVerification, WorkdayError> {
+            unreachable!()
+        }
+        async fn generate_yearly_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+        ) -> Result<Option<RenderedDocument>, WorkdayError> {
+            unreachable!()
+        }
+        async fn download_yearly_workday_document(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: Option<ByteRange>,
+        ) -> Result<Option<DocumentDownload>, WorkdayError> {
+            unreachable!()
+        }
+        async fn create_document_share(
+            &self,
+            _: Uuid,
+            _: i32,
+            _: i32,
+            _: CreateDocumentShareRequest,
+        ) -> Result<(String, DocumentShareRow), WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_shares(
+            &self,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn revoke_document_share(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<DocumentShareRow, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_document_share_accesses(
+            &self,
+            _: Uuid,
+            _: Uuid,
+        ) -> Result<Vec<DocumentShareAccessRow>, WorkdayError> {
+            unreachable!()
+        }
+        async fn get_shared_document(
+            &self,
+            _: &str,
+            _: Option<String>,
+            _: Option<String>,
+            _: Option<ByteRange>,
+        ) -> Result<(DocumentDownload, String), WorkdayError> {
+            unreachable!()
+        }
         async fn get_workday_by_date(
             &self,
             _: Uuid,
@@ -472,7 +594,7 @@ mod tests {
         ) -> Result<(), MailError> {
             Ok(())
         }
-        async fn send_driver_verification_email(
+        async fn send_driver_email_change_confirmation_email(
             &self,
             _: DriverRow,
             _: String,
@@ -480,7 +602,13 @@ mod tests {
         ) -> Result<(), MailError> {
             Ok(())
         }
-        async fn send_driver_email_change_email(&self, _: DriverRow) -> Result<(), MailError> {
+        async fn send_driver_email_change_email(
+            &self,
+            _: DriverRow,
+            _: String,
+            _: String,
+            _: u64,
+        ) -> Result<(), MailError> {
             Ok(())
         }
         async fn send_driver_password_change_email(&self, _: DriverRow) -> Result<(), MailError> {
@@ -500,6 +628,14 @@ mod tests {
         ) -> Result<(), MailError> {
             Ok(())
         }
+        async fn send_driver_magic_link_email(
+            &self,
+            _: DriverRow,
+            _: String,
+            _: u64,
+        ) -> Result<(), MailError> {
+            Ok(())
+        }
         async fn send_driver_monthly_report_email(
             &self,
             _: DriverRow,
@@ -510,30 +646,61 @@ mod tests {
         ) -> Result<(), MailError> {
             Ok(())
         }
+        async fn send_driver_workday_reminder_email(
+            &self,
+            _: DriverRow,
+            _: Vec<NaiveDate>,
+        ) -> Result<(), MailError> {
+            Ok(())
+        }
+        async fn send_driver_documents_ready_email(
+            &self,
+            _: DriverRow,
+            _: Vec<(u32, i32)>,
+        ) -> Result<(), MailError> {
+            Ok(())
+        }
+        async fn send_driver_document_shared_email(
+            &self,
+            _: DriverRow,
+            _: DocumentShareRow,
+            _: String,
+        ) -> Result<(), MailError> {
+            Ok(())
+        }
     }
 
     // ── Helpers ────────────────────────────────────────────────────────────────
 
     async fn driver_with_monthly_pref(db: &MockDriverDatabaseRepository, email: &str) -> DriverRow {
-        let driver = db
-            .create_driver(CreateDriverRequest {
-                firstname: "Test".into(),
-                lastname: "Driver".into(),
-                gender: None,
-                email: email.into(),
-                password: "hashed".into(),
-                language: DriverLanguage::FR,
-            })
-            .await
-            .unwrap();
-        db.update_driver(DriverRow {
-            mail_preferences: 8,
-            ..driver
+        db.create_driver(CreateDriverRequest {
+            firstname: "Test".into(),
+            lastname: "Driver".into(),
+            gender: None,
+            email: email.into(),
+            password: "hashed".into(),
+            language: DriverLanguage::FR,
         })
         .await
         .unwrap()
     }
 
+    async fn enable_channel(
+        mail_db: &MockMailDatabaseRepository,
+        driver: &DriverRow,
+        channel: NotificationChannel,
+    ) {
+        mail_db
+            .upsert_driver_notification_preference(
+                driver.pk_driver_id,
+                EnumDriverMailType::MonthlyReports.as_id(),
+                channel,
+                true,
+            )
+            .await
+            .unwrap();
+    }
+
     // ── Tests ──────────────────────────────────────────────────────────────────
 
     #[tokio::test]
@@ -542,6 +709,7 @@ mod tests {
             &MockDriverDatabaseRepository::new(),
             &MockMailDatabaseRepository::new(),
             &MockMailSmtpRepository,
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             &StubStorage::ok(),
             &StubWorkdayService::returns_none(),
         )
@@ -558,6 +726,7 @@ mod tests {
             &driver_db,
             &MockMailDatabaseRepository::new(),
             &MockMailSmtpRepository,
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             &StubStorage::ok(),
             &StubWorkdayService::returns_none(),
         )
@@ -568,17 +737,53 @@ mod tests {
     #[tokio::test]
     async fn test_happy_path_sends_report() {
         let driver_db = MockDriverDatabaseRepository::new();
-        driver_with_monthly_pref(&driver_db, "bob@example.com").await;
+        let driver = driver_with_monthly_pref(&driver_db, "bob@example.com").await;
+        let mail_db = MockMailDatabaseRepository::new();
+        enable_channel(&mail_db, &driver, NotificationChannel::EMAIL).await;
 
         let result = run_inner(
             &driver_db,
-            &MockMailDatabaseRepository::new(),
+            &mail_db,
             &MockMailSmtpRepository,
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             &StubStorage::ok(),
             &StubWorkdayService::returns_pdf(Bytes::from("fake-pdf")),
         )
         .await;
         assert_eq!(result, 0);
+
+        let (mails, _) = mail_db.get_mails(driver.pk_driver_id, 1, 10).await.unwrap();
+        assert_eq!(mails[0].status, MailStatus::SUCCESS);
+    }
+
+    #[tokio::test]
+    async fn test_report_pushed_when_only_push_enabled() {
+        let driver_db = MockDriverDatabaseRepository::new();
+        let driver = driver_with_monthly_pref(&driver_db, "erin@example.com").await;
+        let mail_db = MockMailDatabaseRepository::new();
+        enable_channel(&mail_db, &driver, NotificationChannel::PUSH).await;
+        let push_channel = MockNotificationChannelRepository::new(NotificationChannel::PUSH);
+
+        let result = run_inner(
+            &driver_db,
+            &mail_db,
+            &MockMailSmtpRepository,
+            &push_channel,
+            &StubStorage::ok(),
+            &StubWorkdayService::returns_pdf(Bytes::from("fake-pdf")),
+        )
+        .await;
+        assert_eq!(result, 0);
+
+        assert_eq!(
+            push_channel.sent.lock().unwrap().as_slice(),
+            &[(
+                driver.pk_driver_id,
+                EnumDriverMailType::MonthlyReports.as_id()
+            )]
+        );
+        let (mails, _) = mail_db.get_mails(driver.pk_driver_id, 1, 10).await.unwrap();
+        assert_eq!(mails[0].status, MailStatus::SUCCESS);
     }
 
     #[tokio::test]
@@ -590,6 +795,7 @@ mod tests {
             &driver_db,
             &MockMailDatabaseRepository::new(),
             &MockMailSmtpRepository,
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             &StubStorage::failing(),
             &StubWorkdayService::returns_pdf(Bytes::from("pdf")),
         )
@@ -606,6 +812,7 @@ mod tests {
             &driver_db,
             &MockMailDatabaseRepository::new(),
             &MockMailSmtpRepository,
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
             &StubStorage::ok(),
             &StubWorkdayService::failing(),
         )
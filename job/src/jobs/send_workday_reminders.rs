@@ -0,0 +1,568 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use tracing::{error, info, warn};
+
+use plannify_driver_api_core::{
+    application::DriverRepositories,
+    domain::{
+        common::constants::EnumDriverMailType,
+        driver::port::DriverDatabaseRepository,
+        mail::{entities::MailStatus, port::MailDatabaseRepository},
+        notification::{
+            entities::Notification, port::NotificationChannelRepository,
+            services::NotificationDispatcher,
+        },
+        workday::port::WorkdayDatabaseRepository,
+    },
+};
+
+/// Number of weeks before the checked period used to learn the driver's usual working weekdays.
+const HISTORY_WEEKS: i64 = 8;
+
+/// A weekday is a usual working weekday when it holds a workday in at least this many
+/// of the `HISTORY_WEEKS` weeks.
+const USUAL_WEEKDAY_MIN_WEEKS: usize = 4;
+
+pub async fn run(repos: &DriverRepositories, days: u32) -> i32 {
+    run_inner(
+        &repos.driver_database_repository,
+        &repos.workday_database_repository,
+        &repos.mail_database_repository,
+        &repos.mail_smtp_repository,
+        &repos.web_push_repository,
+        days,
+        Utc::now(),
+    )
+    .await
+}
+
+async fn run_inner<DDB, WDB, MDB, EC, PC>(
+    driver_db: &DDB,
+    workday_db: &WDB,
+    mail_db: &MDB,
+    email_channel: &EC,
+    push_channel: &PC,
+    days: u32,
+    now: DateTime<Utc>,
+) -> i32
+where
+    DDB: DriverDatabaseRepository,
+    WDB: WorkdayDatabaseRepository,
+    MDB: MailDatabaseRepository,
+    EC: NotificationChannelRepository,
+    PC: NotificationChannelRepository,
+{
+    info!("Starting send_workday_reminders job");
+
+    let today = now.date_naive();
+    let period_start = today - Duration::days(days as i64);
+    let period_end = today - Duration::days(1);
+    let history_start = period_start - Duration::weeks(HISTORY_WEEKS);
+
+    // One reminder per driver per week, whatever the number of runs
+    let week_start = (today - Duration::days(today.weekday().num_days_from_monday() as i64))
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc();
+
+    info!(
+        "Looking for missing workdays between {} and {}",
+        period_start, period_end
+    );
+
+    let drivers = match driver_db
+        .get_drivers_with_workday_reminder_preference()
+        .await
+    {
+        Ok(d) => d,
+        Err(e) => {
+            error!(
+                "Failed to get drivers with workday reminder preference: {}",
+                e
+            );
+            return 1;
+        }
+    };
+
+    let total = drivers.len();
+    if total == 0 {
+        info!("No drivers with workday reminder preference enabled");
+        return 0;
+    }
+
+    info!("Found {} driver(s) with workday reminder preference", total);
+
+    let dispatcher = NotificationDispatcher::new(mail_db, email_channel, push_channel);
+
+    let mut sent = 0u32;
+    let mut skipped = 0u32;
+    let mut failed = 0u32;
+
+    for driver in &drivers {
+        let driver_id = driver.pk_driver_id;
+
+        match mail_db
+            .has_workday_reminder_since(driver_id, week_start)
+            .await
+        {
+            Ok(true) => {
+                warn!(
+                    driver_id = %driver_id,
+                    "Workday reminder already sent this week, skipping"
+                );
+                skipped += 1;
+                continue;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    error = ?e,
+                    "Failed to check workday reminder idempotence"
+                );
+                continue;
+            }
+        }
+
+        let recorded_dates = match workday_db
+            .get_recorded_workday_dates(driver_id, history_start, period_end)
+            .await
+        {
+            Ok(dates) => dates,
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    error = ?e,
+                    "Failed to get recorded workday dates"
+                );
+                continue;
+            }
+        };
+
+        let missing_dates = find_missing_dates(
+            &recorded_dates,
+            history_start,
+            period_start,
+            period_end,
+            driver.created_at.date_naive(),
+        );
+
+        if missing_dates.is_empty() {
+            skipped += 1;
+            continue;
+        }
+
+        let description = format!(
+            "Driver workday reminder for {} missing day(s)",
+            missing_dates.len()
+        );
+        let mail = match mail_db
+            .create_mail(
+                driver.clone(),
+                EnumDriverMailType::WorkdayReminders,
+                description,
+                None,
+            )
+            .await
+        {
+            Ok(m) => m,
+            Err(e) => {
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    error = ?e,
+                    "Failed to create mail record"
+                );
+                continue;
+            }
+        };
+
+        let missing_days = missing_dates.len();
+        let notification = Notification::WorkdayReminder { missing_dates };
+
+        match dispatcher.dispatch(driver, &notification).await {
+            Ok(channels) if !channels.is_empty() => {
+                let _ = mail_db
+                    .update_mail_status(
+                        mail.pk_driver_mail_id,
+                        MailStatus::SUCCESS,
+                        Some(Utc::now()),
+                    )
+                    .await;
+                sent += 1;
+                info!(
+                    driver_id = %driver_id,
+                    channels = ?channels,
+                    "Workday reminder sent for {} missing day(s)",
+                    missing_days
+                );
+            }
+            Ok(_) => {
+                let _ = mail_db
+                    .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                    .await;
+                skipped += 1;
+                warn!(
+                    driver_id = %driver_id,
+                    "No notification channel reached the driver, skipping"
+                );
+            }
+            Err(e) => {
+                let _ = mail_db
+                    .update_mail_status(mail.pk_driver_mail_id, MailStatus::FAILED, None)
+                    .await;
+                failed += 1;
+                error!(
+                    driver_id = %driver_id,
+                    error = ?e,
+                    "Failed to send workday reminder"
+                );
+            }
+        }
+    }
+
+    info!(
+        total,
+        sent, skipped, failed, "send_workday_reminders job completed"
+    );
+
+    if failed > 0 { 1 } else { 0 }
+}
+
+/// Returns the dates between `period_start` and `period_end` falling on one of the driver's usual
+/// working weekdays, learnt from `recorded_dates` between `history_start` and `period_start`,
+/// and holding no workday. Dates before `not_before` (the account creation) are never missing.
+fn find_missing_dates(
+    recorded_dates: &[NaiveDate],
+    history_start: NaiveDate,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    not_before: NaiveDate,
+) -> Vec<NaiveDate> {
+    let mut weeks_by_weekday: HashMap<Weekday, usize> = HashMap::new();
+    for date in recorded_dates
+        .iter()
+        .filter(|date| **date >= history_start && **date < period_start)
+    {
+        *weeks_by_weekday.entry(date.weekday()).or_default() += 1;
+    }
+
+    period_start
+        .iter_days()
+        .take_while(|date| *date <= period_end)
+        .filter(|date| *date >= not_before)
+        .filter(|date| {
+            weeks_by_weekday
+                .get(&date.weekday())
+                .is_some_and(|weeks| *weeks >= USUAL_WEEKDAY_MIN_WEEKS)
+        })
+        .filter(|date| !recorded_dates.contains(date))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_missing_dates, run_inner};
+    use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+    use plannify_driver_api_core::{
+        domain::{
+            common::constants::EnumDriverMailType,
+            driver::{
+                entities::{CreateDriverRequest, DriverLanguage, DriverRow},
+                port::{DriverDatabaseRepository, MockDriverDatabaseRepository},
+            },
+            mail::{
+                entities::{MailStatus, NotificationChannel},
+                port::{
+                    MailDatabaseRepository, MockMailDatabaseRepository, MockMailSmtpRepository,
+                },
+            },
+            notification::port::MockNotificationChannelRepository,
+            workday::{
+                entities::CreateWorkdayRequest,
+                port::{MockWorkdayDatabaseRepository, WorkdayDatabaseRepository},
+            },
+        },
+        infrastructure::notification::repositories::error::NotificationError,
+    };
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    async fn driver_with_reminder_pref(db: &MockDriverDatabaseRepository) -> DriverRow {
+        let driver = db
+            .create_driver(CreateDriverRequest {
+                firstname: "Test".into(),
+                lastname: "Driver".into(),
+                gender: None,
+                email: "reminder@example.com".into(),
+                password: "hashed".into(),
+                language: DriverLanguage::FR,
+            })
+            .await
+            .unwrap();
+        db.update_driver(DriverRow {
+            verified_at: Some(Utc::now()),
+            created_at: Utc::now() - Duration::weeks(52),
+            ..driver
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn enable_channel(
+        mail_db: &MockMailDatabaseRepository,
+        driver: &DriverRow,
+        channel: NotificationChannel,
+    ) {
+        mail_db
+            .upsert_driver_notification_preference(
+                driver.pk_driver_id,
+                EnumDriverMailType::WorkdayReminders.as_id(),
+                channel,
+                true,
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Records a workday every weekday from `weeks` weeks ago until `until_days_ago` days ago.
+    async fn record_weekdays(
+        db: &MockWorkdayDatabaseRepository,
+        driver: &DriverRow,
+        weeks: i64,
+        until_days_ago: i64,
+    ) {
+        let today = Utc::now().date_naive();
+        let mut date = today - Duration::weeks(weeks);
+        while date <= today - Duration::days(until_days_ago) {
+            if chrono::Datelike::weekday(&date).num_days_from_monday() < 5 {
+                db.create_workday(
+                    driver.pk_driver_id,
+                    CreateWorkdayRequest {
+                        date,
+                        start_time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                        end_time: Some(NaiveTime::from_hms_opt(17, 0, 0).unwrap()),
+                        rest_time: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+                        overnight_rest: false,
+                    },
+                )
+                .await
+                .unwrap();
+            }
+            date += Duration::days(1);
+        }
+    }
+
+    #[test]
+    fn test_find_missing_dates_only_usual_weekdays() {
+        // Mondays and Tuesdays worked for 8 weeks, Wednesdays only twice
+        let mut recorded = vec![];
+        let mut monday = date("2026-08-10");
+        for week in 0..8 {
+            recorded.push(monday);
+            recorded.push(monday + Duration::days(1));
+            if week < 2 {
+                recorded.push(monday + Duration::days(2));
+            }
+            monday += Duration::weeks(1);
+        }
+        // The Monday of the checked week is filled in
+        recorded.push(date("2026-10-05"));
+
+        let missing = find_missing_dates(
+            &recorded,
+            date("2026-08-10"),
+            date("2026-10-05"),
+            date("2026-10-11"),
+            date("2025-01-01"),
+        );
+
+        assert_eq!(missing, vec![date("2026-10-06")]);
+    }
+
+    #[test]
+    fn test_find_missing_dates_ignores_dates_before_account_creation() {
+        let recorded: Vec<NaiveDate> = (0..8)
+            .map(|week| date("2026-08-10") + Duration::weeks(week))
+            .collect();
+
+        let missing = find_missing_dates(
+            &recorded,
+            date("2026-08-10"),
+            date("2026-10-05"),
+            date("2026-10-18"),
+            date("2026-10-10"),
+        );
+
+        assert_eq!(missing, vec![date("2026-10-12")]);
+    }
+
+    #[test]
+    fn test_find_missing_dates_without_history() {
+        let missing = find_missing_dates(
+            &[],
+            date("2026-08-10"),
+            date("2026-10-05"),
+            date("2026-10-11"),
+            date("2025-01-01"),
+        );
+
+        assert!(missing.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_no_drivers_returns_success() {
+        let result = run_inner(
+            &MockDriverDatabaseRepository::new(),
+            &MockWorkdayDatabaseRepository::new(),
+            &MockMailDatabaseRepository::new(),
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
+            7,
+            Utc::now(),
+        )
+        .await;
+        assert_eq!(result, 0);
+    }
+
+    #[tokio::test]
+    async fn test_driver_without_gaps_is_not_reminded() {
+        let driver_db = MockDriverDatabaseRepository::new();
+        let workday_db = MockWorkdayDatabaseRepository::new();
+        let mail_db = MockMailDatabaseRepository::new();
+        let driver = driver_with_reminder_pref(&driver_db).await;
+        record_weekdays(&workday_db, &driver, 12, 1).await;
+
+        let result = run_inner(
+            &driver_db,
+            &workday_db,
+            &mail_db,
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
+            7,
+            Utc::now(),
+        )
+        .await;
+
+        assert_eq!(result, 0);
+        let (mails, _) = mail_db.get_mails(driver.pk_driver_id, 1, 10).await.unwrap();
+        assert!(mails.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reminder_sent_once_per_week() {
+        let driver_db = MockDriverDatabaseRepository::new();
+        let workday_db = MockWorkdayDatabaseRepository::new();
+        let mail_db = MockMailDatabaseRepository::new();
+        let driver = driver_with_reminder_pref(&driver_db).await;
+        enable_channel(&mail_db, &driver, NotificationChannel::EMAIL).await;
+        // Nothing filled in during the last 10 days
+        record_weekdays(&workday_db, &driver, 12, 10).await;
+
+        for _ in 0..2 {
+            let result = run_inner(
+                &driver_db,
+                &workday_db,
+                &mail_db,
+                &MockMailSmtpRepository::new(),
+                &MockNotificationChannelRepository::new(NotificationChannel::PUSH),
+                7,
+                Utc::now(),
+            )
+            .await;
+            assert_eq!(result, 0);
+        }
+
+        let (mails, _) = mail_db.get_mails(driver.pk_driver_id, 1, 10).await.unwrap();
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].fk_mail_type_id, 5);
+        assert_eq!(mails[0].status, MailStatus::SUCCESS);
+    }
+
+    #[tokio::test]
+    async fn test_reminder_retried_after_a_failed_attempt() {
+        let driver_db = MockDriverDatabaseRepository::new();
+        let workday_db = MockWorkdayDatabaseRepository::new();
+        let mail_db = MockMailDatabaseRepository::new();
+        let driver = driver_with_reminder_pref(&driver_db).await;
+        enable_channel(&mail_db, &driver, NotificationChannel::PUSH).await;
+        mail_db
+            .upsert_driver_notification_preference(
+                driver.pk_driver_id,
+                EnumDriverMailType::WorkdayReminders.as_id(),
+                NotificationChannel::EMAIL,
+                false,
+            )
+            .await
+            .unwrap();
+        record_weekdays(&workday_db, &driver, 12, 10).await;
+
+        run_inner(
+            &driver_db,
+            &workday_db,
+            &mail_db,
+            &MockMailSmtpRepository::new(),
+            &MockNotificationChannelRepository::failing(
+                NotificationChannel::PUSH,
+                NotificationError::Internal,
+            ),
+            7,
+            Utc::now(),
+        )
+        .await;
+        let push = MockNotificationChannelRepository::new(NotificationChannel::PUSH);
+        let result = run_inner(
+            &driver_db,
+            &workday_db,
+            &mail_db,
+            &MockMailSmtpRepository::new(),
+            &push,
+            7,
+            Utc::now(),
+        )
+        .await;
+
+        assert_eq!(result, 0);
+        assert_eq!(push.sent.lock().unwrap().len(), 1);
+        let (mails, _) = mail_db.get_mails(driver.pk_driver_id, 1, 10).await.unwrap();
+        assert_eq!(mails.len(), 2);
+        assert!(mails.iter().any(|m| m.status == MailStatus::FAILED));
+        assert!(mails.iter().any(|m| m.status == MailStatus::SUCCESS));
+    }
+
+    #[tokio::test]
+    async fn test_reminder_pushed_when_only_push_enabled() {
+        let driver_db = MockDriverDatabaseRepository::new();
+        let workday_db = MockWorkdayDatabaseRepository::new();
+        let mail_db = MockMailDatabaseRepository::new();
+        let driver = driver_with_reminder_pref(&driver_db).await;
+        enable_channel(&mail_db, &driver, NotificationChannel::PUSH).await;
+        record_weekdays(&workday_db, &driver, 12, 10).await;
+        let push = MockNotificationChannelRepository::new(NotificationChannel::PUSH);
+
+        let result = run_inner(
+            &driver_db,
+            &workday_db,
+            &mail_db,
+            &MockMailSmtpRepository::new(),
+            &push,
+            7,
+            Utc::now(),
+        )
+        .await;
+
+        assert_eq!(result, 0);
+        assert_eq!(
+            push.sent.lock().unwrap().as_slice(),
+            &[(
+                driver.pk_driver_id,
+                EnumDriverMailType::WorkdayReminders.as_id()
+            )]
+        );
+    }
+}
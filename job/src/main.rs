@@ -1,6 +1,15 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 use dotenv::dotenv;
-use plannify_driver_api_core::{ServiceConfig, application::create_repositories};
+use plannify_driver_api_core::{
+    ServiceConfig,
+    application::create_repositories,
+    domain::common::config::{
+        DocumentDeliveryConfig, DocumentShareConfig, DocumentVerificationConfig, MailEventsConfig,
+        MailUnsubscribeConfig, PasswordPolicyConfig, SmsConfig, WebPushConfig,
+    },
+};
 use tracing_subscriber::EnvFilter;
 
 mod config;
@@ -29,14 +38,46 @@ enum JobCommand {
         months_ago: u32,
     },
 
-    /// Send monthly workday reports by email to drivers who have enabled the preference
+    /// Generate the yearly workday recap of every year closed N months ago that has none yet
+    GenerateYearlyDocuments {
+        #[arg(long, help = "Number of months in the past to use as cutoff")]
+        months_ago: u32,
+    },
+
+    /// Send monthly workday reports by email or push to drivers who have enabled the preference
     SendMonthlyReports,
 
+    /// Remind drivers by email or push of their usual working days left empty over the past N days
+    SendWorkdayReminders {
+        #[arg(long, default_value_t = 7, help = "Number of past days to check")]
+        days: u32,
+    },
+
     /// Permanently delete accounts whose deactivation date has passed (DB + S3)
     PurgeDeactivatedAccounts,
 
     /// Delete DB document records with no S3 file, and S3 objects with no DB record
     ReconcileDocuments,
+
+    /// Rewrap the data keys of the drivers with the current storage master key
+    RotateStorageKeys,
+
+    /// Render every mail template in every language with sample data, without sending anything
+    RenderMailTemplates {
+        #[arg(
+            long,
+            default_value = "core/templates/mails",
+            help = "Directory holding one template directory per language"
+        )]
+        templates_dir: String,
+
+        #[arg(
+            long,
+            default_value = "mail-previews",
+            help = "Directory the rendered mails are written to"
+        )]
+        output_dir: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -52,6 +93,19 @@ async fn main() {
         )
         .init();
 
+    // Rendering needs neither the database nor any other service.
+    if let JobCommand::RenderMailTemplates {
+        templates_dir,
+        output_dir,
+    } = &cli.command
+    {
+        std::process::exit(jobs::render_mail_templates::run(
+            templates_dir,
+            output_dir,
+            &config.frontend_url,
+        ));
+    }
+
     let repos = match create_repositories(
         &config.database_url,
         &config.redis_url,
@@ -68,6 +122,28 @@ async fn main() {
         ServiceConfig {
             workday_garbage_retention_days: config.workday_garbage_retention_days,
             account_deactivation_days: 30,
+            password_policy: PasswordPolicyConfig::default(),
+            mail_unsubscribe: MailUnsubscribeConfig {
+                api_url: config.public_api_url.clone(),
+                secret: config.mail_unsubscribe_secret.clone(),
+            },
+            web_push: WebPushConfig {
+                vapid_private_key: config.vapid_private_key.clone(),
+                vapid_subject: config.vapid_subject.clone(),
+                allow_insecure_endpoints: false,
+            },
+            sms: SmsConfig::default(),
+            mail_events: MailEventsConfig::default(),
+            mail_dkim: config.smtp.dkim(),
+            document_delivery: DocumentDeliveryConfig::default(),
+            storage: config.s3.storage(&config.public_api_url),
+            document_verification: DocumentVerificationConfig {
+                api_url: config.public_api_url.clone(),
+            },
+            document_share: DocumentShareConfig {
+                api_url: config.public_api_url.clone(),
+            },
+            document_service: config.document_service(),
         },
     )
     .await
@@ -84,9 +160,17 @@ async fn main() {
         JobCommand::GenerateDocuments { months_ago } => {
             jobs::generate_documents::run(&repos, months_ago).await
         }
+        JobCommand::GenerateYearlyDocuments { months_ago } => {
+            jobs::generate_yearly_documents::run(&repos, months_ago).await
+        }
         JobCommand::SendMonthlyReports => jobs::send_monthly_reports::run(&repos).await,
+        JobCommand::SendWorkdayReminders { days } => {
+            jobs::send_workday_reminders::run(&repos, days).await
+        }
         JobCommand::PurgeDeactivatedAccounts => jobs::purge_deactivated_accounts::run(&repos).await,
         JobCommand::ReconcileDocuments => jobs::reconcile_documents::run(&repos).await,
+        JobCommand::RotateStorageKeys => jobs::rotate_storage_keys::run(&repos).await,
+        JobCommand::RenderMailTemplates { .. } => unreachable!(),
     };
 
     repos.shutdown_pool().await;